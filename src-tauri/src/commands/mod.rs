@@ -1,19 +1,29 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 use tauri::{Emitter, State};
+use tokio::sync::Notify;
 
-use crate::artifact_diff::{build_diff_report, render_changelog_markdown};
-use crate::config::save_config;
+use crate::artifact_diff::{build_diff_report, render_changelog_markdown, ArtifactDiffReport};
+use crate::config::{
+    config_path, config_section_sources, expand_save_path, save_config, validate_config_fields,
+};
+use crate::content_search::{self, ContentSearchHit};
 use crate::docgen;
 use crate::error::{AppError, ErrorResponse};
 use crate::importer;
-use crate::lint::{lint_documents, render_lint_report_markdown};
-use crate::llm::ChatMessage;
+use crate::lint::{
+    lint_documents, render_lint_report_markdown, DocumentLintReport, LintMode, LintReport,
+    SessionLintSummary,
+};
+use crate::llm::{ChatMessage, UsageStats};
+use crate::preferences::{self, KnownPreference, PreferenceEntry};
+use crate::ratelimit;
 use crate::search::{self, SearchResult};
 use crate::state::AppState;
 use crate::templates;
+use crate::timestamp;
 use crate::types::*;
 
 const SYSTEM_PROMPT: &str = r##"You are AuraForge, a senior engineering planning partner. You help people transform project ideas into comprehensive plans that AI coding tools (like Claude Code) can execute with minimal guesswork.
@@ -124,6 +134,9 @@ const EXPORT_FILE_ORDER: &[&str] = &[
     "README.md",
     "SPEC.md",
     "CLAUDE.md",
+    "AGENTS.md",
+    "GEMINI.md",
+    ".cursorrules",
     "PROMPTS.md",
     "MODEL_HANDOFF.md",
     "CONVERSATION.md",
@@ -131,6 +144,7 @@ const EXPORT_FILE_ORDER: &[&str] = &[
     "ARTIFACT_CHANGELOG.md",
     "ARTIFACT_DIFF.json",
 ];
+const EXPORT_MANIFEST_FILENAME: &str = "manifest.json";
 const EXPORT_MANIFEST_SCHEMA_VERSION: u32 = 3;
 const MIN_SUPPORTED_EXPORT_MANIFEST_SCHEMA_VERSION: u32 = 2;
 
@@ -140,6 +154,31 @@ const REQUIRED_EXPORT_REPORTS: &[&str] = &[
     "reports/ARTIFACT_DIFF.json",
 ];
 
+const DERIVED_ARTIFACTS: &[&str] = &[
+    "LINT_REPORT.md",
+    "ARTIFACT_CHANGELOG.md",
+    "ARTIFACT_DIFF.json",
+];
+
+const MAX_EXPORT_FOLDER_CHARS: usize = 120;
+
+const MIN_CONTENT_QUERY_CHARS: usize = 2;
+const DEFAULT_CONTENT_SEARCH_LIMIT: usize = 50;
+const MAX_CONTENT_SEARCH_LIMIT: usize = 200;
+
+/// Text limits count characters, not bytes, so non-Latin scripts get the same room.
+const MAX_SESSION_NAME_CHARS: usize = 200;
+const MAX_MESSAGE_CHARS: usize = 100_000;
+const MAX_SCRATCHPAD_CHARS: usize = 100_000;
+const MAX_SNAPSHOT_LABEL_CHARS: usize = 200;
+
+const MAX_DOCUMENT_CHUNK_CHARS: usize = 1_000_000;
+
+const SESSION_STATUSES: &[&str] = &["active", "completed", "archived"];
+const STRICT_COMPLETION_MIN_CONFIDENCE: u8 = 70;
+const DEFAULT_SESSION_PAGE_SIZE: usize = 50;
+const MAX_SESSION_PAGE_SIZE: usize = 200;
+
 fn to_response<E: Into<AppError>>(err: E) -> ErrorResponse {
     err.into().to_response()
 }
@@ -154,7 +193,10 @@ pub async fn check_health(state: State<'_, AppState>) -> Result<HealthStatus, Er
         .map_err(|_| to_response(AppError::Config("Config lock poisoned".to_string())))?
         .clone();
 
-    let (ollama_connected, ollama_model_available) = state.ollama.health_check(&config).await;
+    let ((ollama_connected, ollama_model_available), search_probe) = tokio::join!(
+        state.ollama.health_check(&config),
+        search::probe_provider(&config.search)
+    );
 
     let config_error = state
         .config_error
@@ -210,16 +252,33 @@ pub async fn check_health(state: State<'_, AppState>) -> Result<HealthStatus, Er
     if let Some(err) = db_error {
         errors.push(format!("Database error: {}", err));
     }
+    if let Some(Err(err)) = &search_probe {
+        errors.push(search::probe_remediation(&config.search.provider, err));
+    }
 
     Ok(HealthStatus {
         ollama_connected,
         ollama_model_available,
         database_ok,
         config_valid,
+        search_ok: search_probe.map(|probe| probe.is_ok()),
         errors,
     })
 }
 
+#[tauri::command(rename_all = "snake_case")]
+pub async fn check_all_endpoints_health(
+    state: State<'_, AppState>,
+) -> Result<Vec<EndpointHealth>, ErrorResponse> {
+    let llm = state
+        .config
+        .lock()
+        .map_err(|_| to_response(AppError::Config("Config lock poisoned".to_string())))?
+        .llm
+        .clone();
+    Ok(state.ollama.check_all_endpoints(&llm).await)
+}
+
 #[tauri::command(rename_all = "snake_case")]
 pub async fn get_config(state: State<'_, AppState>) -> Result<AppConfig, ErrorResponse> {
     Ok(state
@@ -229,6 +288,31 @@ pub async fn get_config(state: State<'_, AppState>) -> Result<AppConfig, ErrorRe
         .clone())
 }
 
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_effective_config(
+    state: State<'_, AppState>,
+) -> Result<EffectiveConfig, ErrorResponse> {
+    let config = state
+        .config
+        .lock()
+        .map_err(|_| to_response(AppError::Config("Config lock poisoned".to_string())))?
+        .clone();
+    let fell_back = state
+        .config_error
+        .lock()
+        .map(|err| err.is_some())
+        .unwrap_or(false);
+    let raw = if fell_back {
+        None
+    } else {
+        std::fs::read_to_string(config_path()).ok()
+    };
+    Ok(EffectiveConfig {
+        config,
+        sources: config_section_sources(raw.as_deref()),
+    })
+}
+
 #[tauri::command(rename_all = "snake_case")]
 pub async fn update_search_config(
     state: State<'_, AppState>,
@@ -251,18 +335,142 @@ pub async fn update_config(
     state: State<'_, AppState>,
     config: AppConfig,
 ) -> Result<AppConfig, ErrorResponse> {
+    let field_errors = validate_config_fields(&config);
+    if !field_errors.is_empty() {
+        return Err(to_response(AppError::ConfigInvalid(field_errors)));
+    }
     let mut state_config = state
         .config
         .lock()
         .map_err(|_| to_response(AppError::Config("Config lock poisoned".to_string())))?;
     *state_config = config;
     save_config(&state_config).map_err(|e| to_response(AppError::Config(e)))?;
+    ratelimit::configure(&state_config.rate_limit);
     if let Ok(mut err) = state.config_error.lock() {
         *err = None;
     }
     Ok(state_config.clone())
 }
 
+#[tauri::command(rename_all = "snake_case")]
+pub async fn repair_config(
+    state: State<'_, AppState>,
+) -> Result<ConfigRepairReport, ErrorResponse> {
+    let path = config_path();
+    let backup = path.with_extension("yaml.bak");
+    let current = std::fs::read_to_string(&path).ok();
+    let current_loads = current.as_deref().is_some_and(|raw| {
+        serde_yaml::from_str::<AppConfig>(raw)
+            .map(|config| validate_config_fields(&config).is_empty())
+            .unwrap_or(false)
+    });
+    let was_reset = state
+        .config_error
+        .lock()
+        .map(|err| err.is_some())
+        .unwrap_or(false);
+
+    let (source, raw) = match current {
+        Some(raw) if !current_loads => (path.clone(), raw),
+        _ if was_reset && backup.exists() => {
+            let raw = std::fs::read_to_string(&backup).map_err(|e| {
+                to_response(AppError::FileSystem {
+                    path: backup.display().to_string(),
+                    message: e.to_string(),
+                })
+            })?;
+            (backup.clone(), raw)
+        }
+        _ => {
+            return Err(to_response(AppError::Validation(
+                "The config loads cleanly; there is nothing to repair.".to_string(),
+            )))
+        }
+    };
+
+    let backup_path = if source == path {
+        std::fs::copy(&path, &backup).map_err(|e| {
+            to_response(AppError::FileSystem {
+                path: backup.display().to_string(),
+                message: e.to_string(),
+            })
+        })?;
+        Some(backup.display().to_string())
+    } else {
+        Some(source.display().to_string())
+    };
+
+    let (repaired, recovered, reset) = crate::config::repair_config_yaml(&raw);
+    save_config(&repaired).map_err(|e| to_response(AppError::Config(e)))?;
+    ratelimit::configure(&repaired.rate_limit);
+    {
+        let mut state_config = state
+            .config
+            .lock()
+            .map_err(|_| to_response(AppError::Config("Config lock poisoned".to_string())))?;
+        *state_config = repaired;
+    }
+    if let Ok(mut err) = state.config_error.lock() {
+        *err = None;
+    }
+
+    Ok(ConfigRepairReport {
+        source_path: source.display().to_string(),
+        backup_path,
+        recovered,
+        reset,
+    })
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn suggest_base_url(
+    previous_provider: String,
+    provider: String,
+    base_url: String,
+) -> Result<Option<BaseUrlSuggestion>, ErrorResponse> {
+    Ok(
+        crate::config::suggest_base_url(&previous_provider, &provider, &base_url).map(
+            |suggested| BaseUrlSuggestion {
+                base_url: suggested.to_string(),
+                reason: format!(
+                    "{} is the default for {}; {} usually listens on {}.",
+                    base_url.trim(),
+                    previous_provider,
+                    provider,
+                    suggested
+                ),
+            },
+        ),
+    )
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn resolve_default_save_path(
+    state: State<'_, AppState>,
+) -> Result<String, ErrorResponse> {
+    let raw = state
+        .config
+        .lock()
+        .map_err(|_| to_response(AppError::Config("Config lock poisoned".to_string())))?
+        .output
+        .default_save_path
+        .clone();
+    let path = expand_save_path(&raw, dirs::home_dir().as_deref()).map_err(to_response)?;
+
+    let existing = path
+        .ancestors()
+        .find(|ancestor| ancestor.exists())
+        .unwrap_or(&path);
+    if !existing.is_dir() {
+        return Err(to_response(AppError::FileSystem {
+            path: existing.to_string_lossy().to_string(),
+            message: "Default save path is not a folder.".to_string(),
+        }));
+    }
+
+    Ok(path.to_string_lossy().to_string())
+}
+
 // ============ PREFERENCES ============
 
 #[tauri::command(rename_all = "snake_case")]
@@ -279,9 +487,19 @@ pub async fn set_preference(
     key: String,
     value: String,
 ) -> Result<(), ErrorResponse> {
+    if let Some(known) = KnownPreference::from_key(&key) {
+        known.validate(&value).map_err(to_response)?;
+    }
     state.db.set_preference(&key, &value).map_err(to_response)
 }
 
+#[tauri::command(rename_all = "snake_case")]
+pub async fn list_known_preferences(
+    state: State<'_, AppState>,
+) -> Result<Vec<PreferenceEntry>, ErrorResponse> {
+    preferences::list_known(&state.db).map_err(to_response)
+}
+
 // ============ MODELS ============
 
 #[tauri::command(rename_all = "snake_case")]
@@ -298,6 +516,18 @@ pub async fn list_models(state: State<'_, AppState>) -> Result<Vec<String>, Erro
         .map_err(to_response)
 }
 
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_token_limit(
+    state: State<'_, AppState>,
+) -> Result<TokenLimitStatus, ErrorResponse> {
+    let config = state
+        .config
+        .lock()
+        .map_err(|_| to_response(AppError::Config("Config lock poisoned".to_string())))?
+        .clone();
+    Ok(state.ollama.token_limit(&config.llm).await)
+}
+
 #[tauri::command(rename_all = "snake_case")]
 pub async fn pull_model(
     app: tauri::AppHandle,
@@ -309,11 +539,37 @@ pub async fn pull_model(
         .lock()
         .map_err(|_| to_response(AppError::Config("Config lock poisoned".to_string())))?
         .clone();
-    state
+
+    // Remember the pull until it finishes or is cancelled so an interrupted
+    // download can be offered for resumption on next launch.
+    preferences::set_text(&state.db, KnownPreference::PendingModelPull, &model_name)
+        .map_err(to_response)?;
+
+    let result = state
         .ollama
         .pull_model(&app, &config.llm, &model_name)
-        .await
-        .map_err(to_response)
+        .await;
+    match &result {
+        Ok(()) | Err(AppError::StreamCancelled) => {
+            if let Err(e) = preferences::clear(&state.db, KnownPreference::PendingModelPull) {
+                log::warn!("Failed to clear pending model pull: {}", e);
+            }
+        }
+        Err(e) => log::warn!("Model pull for {} left pending: {}", model_name, e),
+    }
+    result.map_err(to_response)
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_pending_model_pull(
+    state: State<'_, AppState>,
+) -> Result<Option<String>, ErrorResponse> {
+    preferences::get_text(&state.db, KnownPreference::PendingModelPull).map_err(to_response)
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn clear_pending_model_pull(state: State<'_, AppState>) -> Result<(), ErrorResponse> {
+    preferences::clear(&state.db, KnownPreference::PendingModelPull).map_err(to_response)
 }
 
 #[tauri::command(rename_all = "snake_case")]
@@ -398,11 +654,7 @@ pub async fn create_session(
     request: CreateSessionRequest,
 ) -> Result<Session, ErrorResponse> {
     if let Some(ref name) = request.name {
-        if name.len() > 200 {
-            return Err(to_response(AppError::Validation(
-                "Session name too long (max 200 chars).".to_string(),
-            )));
-        }
+        check_char_limit("Session name", name, MAX_SESSION_NAME_CHARS).map_err(to_response)?;
     }
     state
         .db
@@ -542,6 +794,55 @@ pub async fn get_sessions(state: State<'_, AppState>) -> Result<Vec<Session>, Er
     state.db.get_sessions().map_err(to_response)
 }
 
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_sessions_with_status(
+    state: State<'_, AppState>,
+) -> Result<Vec<SessionListEntry>, ErrorResponse> {
+    state.db.get_sessions_with_status().map_err(to_response)
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_sessions_paged(
+    state: State<'_, AppState>,
+    limit: Option<usize>,
+    offset: Option<usize>,
+    status_filter: Option<String>,
+    name_query: Option<String>,
+) -> Result<SessionPage, ErrorResponse> {
+    let limit = limit.unwrap_or(DEFAULT_SESSION_PAGE_SIZE);
+    if limit == 0 || limit > MAX_SESSION_PAGE_SIZE {
+        return Err(to_response(AppError::Validation(format!(
+            "limit must be between 1 and {}",
+            MAX_SESSION_PAGE_SIZE
+        ))));
+    }
+    let status_filter = status_filter
+        .map(|status| status.trim().to_ascii_lowercase())
+        .filter(|status| !status.is_empty());
+    if let Some(status) = status_filter.as_deref() {
+        if !SESSION_STATUSES.contains(&status) {
+            return Err(to_response(AppError::Validation(format!(
+                "Unknown session status '{}'. Expected one of: {}",
+                status,
+                SESSION_STATUSES.join(", ")
+            ))));
+        }
+    }
+    let name_query = name_query
+        .map(|query| query.trim().to_string())
+        .filter(|query| !query.is_empty());
+
+    state
+        .db
+        .get_sessions_paged(
+            limit,
+            offset.unwrap_or(0),
+            status_filter.as_deref(),
+            name_query.as_deref(),
+        )
+        .map_err(to_response)
+}
+
 #[tauri::command(rename_all = "snake_case")]
 pub async fn get_session(
     state: State<'_, AppState>,
@@ -564,11 +865,7 @@ pub async fn update_session(
     status: Option<String>,
 ) -> Result<Session, ErrorResponse> {
     if let Some(ref n) = name {
-        if n.len() > 200 {
-            return Err(to_response(AppError::Validation(
-                "Session name too long (max 200 chars).".to_string(),
-            )));
-        }
+        check_char_limit("Session name", n, MAX_SESSION_NAME_CHARS).map_err(to_response)?;
     }
     match state
         .db
@@ -582,6 +879,66 @@ pub async fn update_session(
     }
 }
 
+#[tauri::command(rename_all = "snake_case")]
+pub async fn complete_session(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<SessionCompletion, ErrorResponse> {
+    let session = match state.db.get_session(&session_id) {
+        Ok(session) => session,
+        Err(rusqlite::Error::QueryReturnedNoRows) => {
+            return Err(to_response(AppError::SessionNotFound(session_id)))
+        }
+        Err(e) => return Err(to_response(e)),
+    };
+    let strict = state
+        .config
+        .lock()
+        .map_err(|_| to_response(AppError::Config("Config lock poisoned".to_string())))?
+        .output
+        .completion_checks
+        .trim()
+        .eq_ignore_ascii_case("strict");
+
+    let docs = state.db.get_documents(&session_id).map_err(to_response)?;
+    let mut unmet_checks = Vec::new();
+    if docs.is_empty() {
+        unmet_checks.push("No documents have been generated yet.".to_string());
+    } else if strict {
+        let lint = lint_documents(&docs);
+        if lint.has_critical() {
+            unmet_checks.push(format!(
+                "{} critical lint finding(s) remain.",
+                lint.summary.critical
+            ));
+        }
+        let confidence = generation_confidence_internal(&state, &session_id, &docs)?;
+        if confidence.score < STRICT_COMPLETION_MIN_CONFIDENCE {
+            unmet_checks.push(format!(
+                "Confidence is {}/100 (needs at least {}).",
+                confidence.score, STRICT_COMPLETION_MIN_CONFIDENCE
+            ));
+        }
+    }
+
+    if !unmet_checks.is_empty() {
+        return Ok(SessionCompletion {
+            session,
+            completed: false,
+            unmet_checks,
+        });
+    }
+    let session = state
+        .db
+        .mark_session_completed(&session_id)
+        .map_err(to_response)?;
+    Ok(SessionCompletion {
+        session,
+        completed: true,
+        unmet_checks,
+    })
+}
+
 #[tauri::command(rename_all = "snake_case")]
 pub async fn delete_session(
     state: State<'_, AppState>,
@@ -598,77 +955,386 @@ pub async fn delete_sessions(
     state.db.delete_sessions(&session_ids).map_err(to_response)
 }
 
-// ============ MESSAGES ============
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_session_scratchpad(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<String, ErrorResponse> {
+    match state.db.get_session_scratchpad(&session_id) {
+        Err(rusqlite::Error::QueryReturnedNoRows) => {
+            Err(to_response(AppError::SessionNotFound(session_id)))
+        }
+        result => result.map_err(to_response),
+    }
+}
 
 #[tauri::command(rename_all = "snake_case")]
-pub async fn get_messages(
+pub async fn set_session_scratchpad(
     state: State<'_, AppState>,
     session_id: String,
-) -> Result<Vec<Message>, ErrorResponse> {
-    state.db.get_messages(&session_id).map_err(to_response)
+    content: String,
+) -> Result<(), ErrorResponse> {
+    check_char_limit("Scratchpad", &content, MAX_SCRATCHPAD_CHARS).map_err(to_response)?;
+    let updated = state
+        .db
+        .set_session_scratchpad(&session_id, &content)
+        .map_err(to_response)?;
+    if updated == 0 {
+        return Err(to_response(AppError::SessionNotFound(session_id)));
+    }
+    Ok(())
 }
 
 #[tauri::command(rename_all = "snake_case")]
-pub async fn import_codebase_context(
+pub async fn create_session_snapshot(
     state: State<'_, AppState>,
-    request: ImportCodebaseRequest,
-) -> Result<CodebaseImportSummary, ErrorResponse> {
-    let root_path = request.root_path.clone();
-    let summary =
-        tauri::async_runtime::spawn_blocking(move || importer::summarize_codebase(&root_path))
-            .await
-            .map_err(|e| {
-                to_response(AppError::FileSystem {
-                    path: request.root_path.clone(),
-                    message: format!("Failed to import codebase: {}", e),
-                })
-            })?
-            .map_err(to_response)?;
+    session_id: String,
+    label: String,
+) -> Result<SessionSnapshot, ErrorResponse> {
+    let label = label.trim().to_string();
+    if label.is_empty() {
+        return Err(to_response(AppError::Validation(
+            "Snapshot label cannot be empty.".to_string(),
+        )));
+    }
+    check_char_limit("Snapshot label", &label, MAX_SNAPSHOT_LABEL_CHARS).map_err(to_response)?;
+    match state.db.get_session(&session_id) {
+        Ok(_) => {}
+        Err(rusqlite::Error::QueryReturnedNoRows) => {
+            return Err(to_response(AppError::SessionNotFound(session_id)))
+        }
+        Err(e) => return Err(to_response(e)),
+    }
 
-    let metadata = serde_json::json!({
-        "import_summary": &summary,
-    })
-    .to_string();
-    let content = format!(
-        "{}\n\n{}\n\n{}\n\n{}\n\n{}\n\nImported automatically from `{}`.",
-        summary.summary_markdown,
-        summary.architecture_summary_markdown,
-        summary.risks_gaps_markdown,
-        summary.phased_plan_markdown,
-        summary.verification_plan_markdown,
-        summary.root_path
-    );
+    match state.db.create_session_snapshot(&session_id, &label) {
+        Err(rusqlite::Error::SqliteFailure(err, _))
+            if err.code == rusqlite::ErrorCode::ConstraintViolation =>
+        {
+            Err(to_response(AppError::Validation(format!(
+                "A snapshot named '{}' already exists for this session.",
+                label
+            ))))
+        }
+        result => result.map_err(to_response),
+    }
+}
 
+#[tauri::command(rename_all = "snake_case")]
+pub async fn list_session_snapshots(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<Vec<SessionSnapshot>, ErrorResponse> {
     state
         .db
-        .save_message(
-            &request.session_id,
-            "assistant",
-            &content,
-            Some(metadata.as_str()),
-        )
-        .map_err(to_response)?;
+        .list_session_snapshots(&session_id)
+        .map_err(to_response)
+}
 
-    Ok(summary)
+#[tauri::command(rename_all = "snake_case")]
+pub async fn restore_session_snapshot(
+    state: State<'_, AppState>,
+    session_id: String,
+    label: String,
+) -> Result<SessionSnapshot, ErrorResponse> {
+    match state.db.restore_session_snapshot(&session_id, &label) {
+        Err(rusqlite::Error::QueryReturnedNoRows) => Err(to_response(AppError::Validation(
+            format!("No snapshot named '{}' exists for this session.", label),
+        ))),
+        result => result.map_err(to_response),
+    }
 }
 
 #[tauri::command(rename_all = "snake_case")]
-pub async fn send_message(
+pub async fn can_save_to_folder(
     app: tauri::AppHandle,
     state: State<'_, AppState>,
-    request: SendMessageRequest,
-) -> Result<Message, ErrorResponse> {
-    let session_id = request.session_id;
-    let content = request.content;
-    let is_retry = request.retry.unwrap_or(false);
+    session_id: Option<String>,
+) -> Result<bool, ErrorResponse> {
+    let can_save = match session_id {
+        Some(session_id) => state
+            .db
+            .latest_document_time(&session_id)
+            .map_err(to_response)?
+            .is_some(),
+        None => false,
+    };
+    if let Err(e) = crate::set_menu_item_enabled(&app, crate::SAVE_TO_FOLDER_MENU_ID, can_save) {
+        log::warn!("Failed to update the Save to Folder menu item: {}", e);
+    }
+    Ok(can_save)
+}
 
-    if content.len() > 102_400 {
-        return Err(to_response(AppError::Validation(
-            "Message too long (max 100 KB).".to_string(),
-        )));
+#[tauri::command(rename_all = "snake_case")]
+pub async fn cleanup_orphans(
+    state: State<'_, AppState>,
+) -> Result<OrphanCleanupReport, ErrorResponse> {
+    let report = state.db.cleanup_orphans().map_err(to_response)?;
+    if report.total > 0 {
+        log::info!("Removed {} orphaned rows", report.total);
     }
+    Ok(report)
+}
 
-    // Save user message (skip on retry — message already exists in DB)
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_housekeeping_report(
+    state: State<'_, AppState>,
+) -> Result<Option<HousekeepingReport>, ErrorResponse> {
+    Ok(state
+        .housekeeping_report
+        .lock()
+        .map_err(|_| to_response(AppError::Config("Housekeeping lock poisoned".to_string())))?
+        .clone())
+}
+
+// ============ MESSAGES ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_messages(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<Vec<Message>, ErrorResponse> {
+    state.db.get_messages(&session_id).map_err(to_response)
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_session_token_usage(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<SessionTokenUsage, ErrorResponse> {
+    match state.db.get_session(&session_id) {
+        Ok(_) => {}
+        Err(rusqlite::Error::QueryReturnedNoRows) => {
+            return Err(to_response(AppError::SessionNotFound(session_id)))
+        }
+        Err(e) => return Err(to_response(e)),
+    }
+    let messages = state.db.get_messages(&session_id).map_err(to_response)?;
+    Ok(session_token_usage(session_id, &messages))
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn subscribe_session(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<(), ErrorResponse> {
+    match state.db.get_session(&session_id) {
+        Err(rusqlite::Error::QueryReturnedNoRows) => {
+            return Err(to_response(AppError::SessionNotFound(session_id)));
+        }
+        result => result.map_err(to_response)?,
+    };
+    let messages = state.db.get_messages(&session_id).map_err(to_response)?;
+    let _ = app.emit(
+        "session:messages",
+        SessionMessagesSnapshot {
+            session_id,
+            messages,
+        },
+    );
+    Ok(())
+}
+
+fn emit_session_messages(app: &tauri::AppHandle, state: &AppState, session_id: &str) {
+    match state.db.get_messages(session_id) {
+        Ok(messages) => {
+            let _ = app.emit(
+                "session:messages",
+                SessionMessagesSnapshot {
+                    session_id: session_id.to_string(),
+                    messages,
+                },
+            );
+        }
+        Err(e) => log::warn!("Failed to load messages for {}: {}", session_id, e),
+    }
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn suggest_forge_target(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<ForgeTargetSuggestion, ErrorResponse> {
+    let config = state
+        .config
+        .lock()
+        .map_err(|_| to_response(AppError::Config("Config lock poisoned".to_string())))?
+        .clone();
+    let default_target = resolve_forge_target(None, &config)?;
+    let import_summary = state
+        .db
+        .get_messages(&session_id)
+        .map_err(to_response)?
+        .into_iter()
+        .rev()
+        .find_map(|message| {
+            message
+                .metadata
+                .as_deref()
+                .and_then(extract_import_summary_from_metadata)
+        });
+
+    Ok(match import_summary {
+        Some(summary) => importer::suggest_forge_target(&summary.detected_stacks, default_target),
+        None => ForgeTargetSuggestion {
+            agent_config_file: default_target.agent_config_filename().to_string(),
+            target: default_target,
+            dominant_stack: None,
+            rationale: "No codebase has been imported into this session; keeping the configured default target.".to_string(),
+        },
+    })
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn import_codebase_context(
+    state: State<'_, AppState>,
+    request: ImportCodebaseRequest,
+) -> Result<CodebaseImportSummary, ErrorResponse> {
+    let root_path = request.root_path.clone();
+    let import_config = state
+        .config
+        .lock()
+        .map_err(|_| to_response(AppError::Config("Config lock poisoned".to_string())))?
+        .import
+        .clone();
+    let summary = tauri::async_runtime::spawn_blocking(move || {
+        importer::summarize_codebase(&root_path, &import_config)
+    })
+    .await
+    .map_err(|e| {
+        to_response(AppError::FileSystem {
+            path: request.root_path.clone(),
+            message: format!("Failed to import codebase: {}", e),
+        })
+    })?
+    .map_err(to_response)?;
+
+    let metadata = serde_json::json!({
+        "import_summary": &summary,
+    })
+    .to_string();
+    let content = format!(
+        "{}\n\n{}\n\n{}\n\n{}\n\n{}\n\nImported automatically from `{}`.",
+        summary.summary_markdown,
+        summary.architecture_summary_markdown,
+        summary.risks_gaps_markdown,
+        summary.phased_plan_markdown,
+        summary.verification_plan_markdown,
+        summary.root_path
+    );
+
+    let existing_import_id = if request.update_existing.unwrap_or(false) {
+        let messages = state
+            .db
+            .get_messages(&request.session_id)
+            .map_err(to_response)?;
+        latest_import_message_id(&messages)
+    } else {
+        None
+    };
+
+    match existing_import_id {
+        Some(message_id) => {
+            state
+                .db
+                .update_message(
+                    &request.session_id,
+                    &message_id,
+                    &content,
+                    Some(metadata.as_str()),
+                )
+                .map_err(to_response)?;
+        }
+        None => {
+            state
+                .db
+                .save_message(
+                    &request.session_id,
+                    "assistant",
+                    &content,
+                    Some(metadata.as_str()),
+                )
+                .map_err(to_response)?;
+        }
+    }
+
+    Ok(summary)
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn redetect_stacks(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<CodebaseImportSummary, ErrorResponse> {
+    let messages = state.db.get_messages(&session_id).map_err(to_response)?;
+    let import_message = latest_import_message_id(&messages)
+        .and_then(|id| messages.into_iter().find(|message| message.id == id))
+        .ok_or_else(|| {
+            to_response(AppError::Validation(
+                "No codebase has been imported into this session.".to_string(),
+            ))
+        })?;
+    let metadata = import_message.metadata.as_deref().unwrap_or_default();
+    let (Some(mut summary), Ok(mut metadata)) = (
+        extract_import_summary_from_metadata(metadata),
+        serde_json::from_str::<serde_json::Value>(metadata),
+    ) else {
+        return Err(to_response(AppError::Validation(
+            "The stored import summary could not be read.".to_string(),
+        )));
+    };
+
+    let root_path = summary.root_path.clone();
+    summary.detected_stacks =
+        tauri::async_runtime::spawn_blocking(move || importer::redetect_stacks(&root_path))
+            .await
+            .map_err(|e| {
+                to_response(AppError::FileSystem {
+                    path: summary.root_path.clone(),
+                    message: format!("Failed to probe codebase: {}", e),
+                })
+            })?
+            .map_err(to_response)?;
+
+    metadata["import_summary"] = serde_json::json!(&summary);
+    state
+        .db
+        .update_message(
+            &session_id,
+            &import_message.id,
+            &import_message.content,
+            Some(metadata.to_string().as_str()),
+        )
+        .map_err(to_response)?;
+
+    Ok(summary)
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn send_message(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    request: SendMessageRequest,
+) -> Result<Message, ErrorResponse> {
+    let session_id = request.session_id;
+    let content = request.content;
+    let is_retry = request.retry.unwrap_or(false);
+
+    check_char_limit("Message", &content, MAX_MESSAGE_CHARS).map_err(to_response)?;
+
+    // Reject before persisting anything so the user can simply resend
+    let max_streams = state
+        .config
+        .lock()
+        .map_err(|_| to_response(AppError::Config("Config lock poisoned".to_string())))?
+        .llm
+        .max_concurrent_streams;
+    let _stream_slot = state
+        .stream_slots
+        .try_acquire(max_streams)
+        .ok_or_else(|| to_response(AppError::TooManyStreams(max_streams)))?;
+
+    // Save user message (skip on retry — message already exists in DB)
     let user_msg = if is_retry {
         // Find the last user message from DB
         let messages = state.db.get_messages(&session_id).map_err(to_response)?;
@@ -693,18 +1359,15 @@ pub async fn send_message(
             .save_message(&session_id, "user", &content, None)
             .map_err(to_response)?
     };
-
-    // Auto-name session on first user message
-    let user_count = state.db.message_count(&session_id).unwrap_or(0);
-    if user_count == 1 && !is_retry {
-        let auto_name: String = content.chars().take(60).collect();
-        let auto_name = auto_name.trim().to_string();
-        let auto_name = if content.chars().count() > 60 {
-            format!("{}...", auto_name.trim_end())
-        } else {
-            auto_name
-        };
-        let _ = state.db.update_session(&session_id, Some(&auto_name), None);
+    emit_session_messages(&app, &state, &session_id);
+
+    // Auto-name session on its first user turn, even when seeded messages precede it
+    if !is_retry {
+        let first_user_id = state.db.first_user_message_id(&session_id).unwrap_or(None);
+        if first_user_id.as_deref() == Some(user_msg.id.as_str()) {
+            let auto_name = auto_session_name(&content);
+            let _ = state.db.update_session(&session_id, Some(&auto_name), None);
+        }
     }
 
     // Get config
@@ -719,7 +1382,7 @@ pub async fn send_message(
     let mut search_results: Option<Vec<SearchResult>> = None;
 
     if config.search.enabled && config.search.proactive {
-        if let Some(query) = search::should_search(&content) {
+        if let Some(query) = search::should_search(&content, &config.search.trigger_overrides) {
             search_query = Some(query.clone());
 
             // Emit search_start event
@@ -733,9 +1396,22 @@ pub async fn send_message(
                 },
             );
 
-            // Execute search
-            match search::execute_search(&config.search, &query).await {
-                Ok(results) => {
+            // Execute search; `cancel_search` skips it and the turn continues
+            // without search context.
+            let search_cancel = Arc::new(Notify::new());
+            if let Ok(mut map) = state.search_cancel.lock() {
+                map.insert(session_id.clone(), search_cancel.clone());
+            }
+            let outcome = tokio::select! {
+                result = search::execute_search(&config.search, &query) => Some(result),
+                _ = search_cancel.notified() => None,
+            };
+            if let Ok(mut map) = state.search_cancel.lock() {
+                map.remove(&session_id);
+            }
+
+            match outcome {
+                Some(Ok(results)) => {
                     // Emit search_result event
                     let _ = app.emit(
                         "stream:search",
@@ -748,9 +1424,21 @@ pub async fn send_message(
                     );
                     search_results = Some(results);
                 }
-                Err(e) => {
+                Some(Err(e)) => {
                     log::warn!("Search failed (continuing without): {}", e);
                 }
+                None => {
+                    log::info!("Search cancelled for {} (continuing without)", session_id);
+                    let _ = app.emit(
+                        "stream:search_cancelled",
+                        crate::llm::StreamChunk {
+                            r#type: "search_cancelled".to_string(),
+                            search_query: Some(query.clone()),
+                            session_id: Some(session_id.clone()),
+                            ..Default::default()
+                        },
+                    );
+                }
             }
         }
     }
@@ -758,27 +1446,26 @@ pub async fn send_message(
     // Build conversation history for LLM
     let db_messages = state.db.get_messages(&session_id).map_err(to_response)?;
 
-    let mut chat_messages = vec![ChatMessage {
-        role: "system".to_string(),
-        content: SYSTEM_PROMPT.to_string(),
-    }];
-
-    // Inject search context as a system message if we have results
-    if let Some(ref results) = search_results {
-        chat_messages.push(ChatMessage {
-            role: "system".to_string(),
-            content: build_search_context(search_query.as_deref().unwrap_or(""), results),
-        });
-    }
-
-    for msg in &db_messages {
-        if msg.role == "system" {
-            continue;
-        }
-        chat_messages.push(ChatMessage {
-            role: msg.role.clone(),
-            content: msg.content.clone(),
-        });
+    let mut chat_messages = assemble_chat_messages(
+        &db_messages,
+        search_query.as_deref(),
+        search_results.as_deref(),
+    );
+    let dropped = trim_history_to_budget(&mut chat_messages, config.llm.context_tokens);
+    if dropped > 0 {
+        log::info!(
+            "Dropped {} oldest message(s) to fit llm.context_tokens ({})",
+            dropped,
+            config.llm.context_tokens
+        );
+        let _ = app.emit(
+            "stream:truncated",
+            HistoryTruncated {
+                session_id: session_id.clone(),
+                dropped_messages: dropped,
+                context_tokens: config.llm.context_tokens,
+            },
+        );
     }
 
     // Stream the LLM response
@@ -787,6 +1474,17 @@ pub async fn send_message(
         map.insert(session_id.clone(), cancel_flag.clone());
     }
 
+    if let Some(ref results) = search_results {
+        let _ = app.emit(
+            "stream:context_injected",
+            SearchContextInjected {
+                session_id: session_id.clone(),
+                query: search_query.clone().unwrap_or_default(),
+                result_count: results.len(),
+            },
+        );
+    }
+
     let full_response = state
         .ollama
         .stream_chat(
@@ -801,25 +1499,23 @@ pub async fn send_message(
         .await;
 
     match full_response {
-        Ok(response_text) => {
-            // Build metadata with search info
-            let metadata = if search_query.is_some() || search_results.is_some() {
-                let meta = serde_json::json!({
-                    "search_query": search_query,
-                    "search_results": search_results,
-                });
-                Some(meta.to_string())
-            } else {
-                None
-            };
+        Ok(reply) => {
+            let metadata = assistant_message_metadata(
+                &config.llm,
+                search_query.as_deref(),
+                search_results.as_deref(),
+                reply.usage.as_ref(),
+            );
 
             if let Err(e) = state.db.save_message(
                 &session_id,
                 "assistant",
-                &response_text,
-                metadata.as_deref(),
+                &reply.content,
+                Some(&metadata.to_string()),
             ) {
                 log::error!("Failed to save assistant message: {}", e);
+            } else {
+                emit_session_messages(&app, &state, &session_id);
             }
         }
         Err(AppError::StreamCancelled) => {
@@ -865,56 +1561,257 @@ pub async fn cancel_response(
     Ok(())
 }
 
-// ============ DOCUMENTS ============
+#[tauri::command(rename_all = "snake_case")]
+pub async fn cancel_search(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<(), ErrorResponse> {
+    if let Ok(map) = state.search_cancel.lock() {
+        if let Some(notify) = map.get(&session_id) {
+            notify.notify_one();
+        }
+    }
+    Ok(())
+}
 
 #[tauri::command(rename_all = "snake_case")]
-pub async fn generate_documents(
-    app: tauri::AppHandle,
+pub async fn summarize_old_turns(
     state: State<'_, AppState>,
-    request: GenerateDocumentsRequest,
-) -> Result<Vec<GeneratedDocument>, ErrorResponse> {
+    session_id: String,
+) -> Result<Message, ErrorResponse> {
+    if let Err(e) = state.db.get_session(&session_id) {
+        return Err(match e {
+            rusqlite::Error::QueryReturnedNoRows => {
+                to_response(AppError::SessionNotFound(session_id))
+            }
+            other => to_response(other),
+        });
+    }
+
     let config = state
         .config
         .lock()
         .map_err(|_| to_response(AppError::Config("Config lock poisoned".to_string())))?
         .clone();
-    let target = resolve_forge_target(request.target.as_deref(), &config)?;
-    let quality = analyze_plan_readiness_internal(&state, &request.session_id)?;
 
-    if !request.force.unwrap_or(false) && !quality.missing_must_haves.is_empty() {
+    let messages = state.db.get_messages(&session_id).map_err(to_response)?;
+    let keep_recent = config.llm.compact_keep_recent_messages;
+    let (previous, to_summarize) = compaction_candidates(&messages, keep_recent);
+    let Some(last) = to_summarize.last() else {
         return Err(to_response(AppError::Validation(format!(
-            "Readiness check has missing must-haves: {}. Continue with force=true to forge anyway.",
-            quality.missing_must_haves.join(", ")
+            "Nothing to compact yet: the last {} messages are always kept verbatim.",
+            keep_recent
         ))));
-    }
+    };
 
-    let previous_docs = state
-        .db
-        .get_documents(&request.session_id)
-        .map_err(to_response)?;
+    let mut transcript = String::new();
+    if let Some(previous) = previous {
+        transcript.push_str(&format!(
+            "Summary of the conversation so far:\n{}\n\n",
+            previous.content.trim()
+        ));
+    }
+    for message in &to_summarize {
+        let Some(content) = llm_history_content(message) else {
+            continue;
+        };
+        let label = if message.role == "user" {
+            "User"
+        } else {
+            "AuraForge"
+        };
+        transcript.push_str(&format!("{}: {}\n\n", label, content.trim()));
+    }
 
-    let docs = docgen::generate_all_documents(&app, &state, &request.session_id, &target)
+    let summary = state
+        .ollama
+        .generate(
+            &config.llm,
+            vec![
+                ChatMessage {
+                    role: "system".to_string(),
+                    content: COMPACT_PROMPT.to_string(),
+                },
+                ChatMessage {
+                    role: "user".to_string(),
+                    content: transcript,
+                },
+            ],
+            0.3,
+        )
         .await
         .map_err(to_response)?;
-    let lint_report = lint_documents(&docs);
-    let diff_report = build_diff_report(&previous_docs, &docs);
+    let summary = summary.content.trim();
+    if summary.is_empty() {
+        return Err(to_response(AppError::Validation(
+            "The model returned an empty summary; the conversation was left as is.".to_string(),
+        )));
+    }
 
-    let mut drafts = docs
-        .iter()
-        .map(|doc| (doc.filename.clone(), doc.content.clone()))
-        .collect::<Vec<_>>();
-    drafts.retain(|(filename, _)| {
-        ![
-            "LINT_REPORT.md",
-            "ARTIFACT_CHANGELOG.md",
-            "ARTIFACT_DIFF.json",
-        ]
-        .contains(&filename.as_str())
+    let metadata = serde_json::json!({
+        CONVERSATION_SUMMARY_KEY: {
+            "covers_through": last.id,
+            "summarized_messages": to_summarize.len(),
+        }
     });
-    drafts.push((
-        "LINT_REPORT.md".to_string(),
-        render_lint_report_markdown(&lint_report),
-    ));
+    state
+        .db
+        .save_message(&session_id, "system", summary, Some(&metadata.to_string()))
+        .map_err(to_response)
+}
+
+// ============ DOCUMENTS ============
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn generate_documents(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    request: GenerateDocumentsRequest,
+) -> Result<Vec<GeneratedDocument>, ErrorResponse> {
+    run_generation(app, state, request, false).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn regenerate_document(
+    state: State<'_, AppState>,
+    session_id: String,
+    filename: String,
+) -> Result<GeneratedDocument, ErrorResponse> {
+    match state.db.get_session(&session_id) {
+        Err(rusqlite::Error::QueryReturnedNoRows) => {
+            return Err(to_response(AppError::SessionNotFound(session_id)));
+        }
+        result => result.map_err(to_response)?,
+    };
+    docgen::regenerate_document(&state, &session_id, filename.trim())
+        .await
+        .map_err(to_response)
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn cancel_generation(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<(), ErrorResponse> {
+    if let Ok(map) = state.generation_cancel.lock() {
+        if let Some(flag) = map.get(&session_id) {
+            flag.store(true, std::sync::atomic::Ordering::SeqCst);
+        }
+    }
+    Ok(())
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn resume_generation(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    request: GenerateDocumentsRequest,
+) -> Result<Vec<GeneratedDocument>, ErrorResponse> {
+    let drafts = state
+        .db
+        .get_generation_drafts(&request.session_id)
+        .map_err(to_response)?;
+    if drafts.is_none() {
+        return Err(to_response(AppError::Validation(
+            "No interrupted generation to resume for this session.".to_string(),
+        )));
+    }
+    run_generation(app, state, request, true).await
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_incomplete_generation(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<Option<IncompleteGeneration>, ErrorResponse> {
+    let drafts = state
+        .db
+        .get_generation_drafts(&session_id)
+        .map_err(to_response)?;
+    Ok(drafts.map(|drafts| IncompleteGeneration {
+        session_id,
+        completed: drafts
+            .documents
+            .into_iter()
+            .map(|(filename, _)| filename)
+            .collect(),
+    }))
+}
+
+async fn run_generation(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    request: GenerateDocumentsRequest,
+    resume: bool,
+) -> Result<Vec<GeneratedDocument>, ErrorResponse> {
+    let config = state
+        .config
+        .lock()
+        .map_err(|_| to_response(AppError::Config("Config lock poisoned".to_string())))?
+        .clone();
+    let target = resolve_forge_target(request.target.as_deref(), &config)?;
+    let quality = analyze_plan_readiness_internal(&state, &request.session_id)?;
+
+    if !request.force.unwrap_or(false) && !quality.missing_must_haves.is_empty() {
+        return Err(to_response(AppError::Validation(format!(
+            "Readiness check has missing must-haves: {}. Continue with force=true to forge anyway.",
+            quality.missing_must_haves.join(", ")
+        ))));
+    }
+
+    let previous_docs = state
+        .db
+        .get_documents(&request.session_id)
+        .map_err(to_response)?;
+
+    let cancel_flag = Arc::new(AtomicBool::new(false));
+    {
+        let mut map = state
+            .generation_cancel
+            .lock()
+            .map_err(|_| to_response(AppError::Config("Generation lock poisoned".to_string())))?;
+        if map.contains_key(&request.session_id) {
+            return Err(to_response(AppError::Validation(
+                "Documents are already being generated for this session.".to_string(),
+            )));
+        }
+        map.insert(request.session_id.clone(), cancel_flag.clone());
+    }
+    let app = (!request.quiet.unwrap_or(false)).then_some(&app);
+    let generation = docgen::generate_all_documents(
+        app,
+        &state,
+        &request.session_id,
+        &target,
+        resume,
+        Some(&cancel_flag),
+    )
+    .await;
+    if let Ok(mut map) = state.generation_cancel.lock() {
+        map.remove(&request.session_id);
+    }
+    let generation = generation.map_err(to_response)?;
+    if generation.cancelled {
+        // The preview went out with `generate:cancelled`; nothing below may
+        // persist a partial set.
+        return Err(to_response(AppError::StreamCancelled));
+    }
+    let docs = generation.documents;
+    let lint_mode = LintMode::from_config(&config.output.lint_mode);
+    let lint_report = (lint_mode != LintMode::Off).then(|| lint_documents(&docs));
+    let diff_report = build_diff_report(&previous_docs, &docs);
+
+    let mut drafts = docs
+        .iter()
+        .map(|doc| (doc.filename.clone(), doc.content.clone()))
+        .collect::<Vec<_>>();
+    drafts.retain(|(filename, _)| !DERIVED_ARTIFACTS.contains(&filename.as_str()));
+    if let Some(lint_report) = &lint_report {
+        drafts.push((
+            "LINT_REPORT.md".to_string(),
+            render_lint_report_markdown(lint_report),
+        ));
+    }
     drafts.push((
         "ARTIFACT_CHANGELOG.md".to_string(),
         render_changelog_markdown(&diff_report),
@@ -929,14 +1826,9 @@ pub async fn generate_documents(
         .replace_documents(&request.session_id, &drafts)
         .map_err(to_response)?;
 
-    let lint_mode = config.output.lint_mode.trim().to_ascii_lowercase();
-    let should_fail_on_critical = lint_mode == "fail_on_critical";
-    if lint_report.has_critical() && should_fail_on_critical && !request.force.unwrap_or(false) {
-        return Err(to_response(AppError::Validation(format!(
-            "SpecLint/PromptLint found {} critical issue(s). Review LINT_REPORT.md or continue with force=true.",
-            lint_report.summary.critical
-        ))));
-    }
+    lint_mode
+        .check(lint_report.as_ref(), request.force.unwrap_or(false))
+        .map_err(to_response)?;
 
     let confidence = docgen::analyze_generation_confidence(&docs, Some(&quality));
     let run_id = uuid::Uuid::new_v4().to_string();
@@ -947,8 +1839,16 @@ pub async fn generate_documents(
     let input_fingerprint = build_input_fingerprint(&messages, &target, &config);
     let quality_json = serde_json::to_string(&quality).ok();
     let confidence_json = serde_json::to_string(&confidence).ok();
-    let lint_summary_json = serde_json::to_string(&lint_report.summary).ok();
+    let lint_summary_json = lint_report
+        .as_ref()
+        .and_then(|report| serde_json::to_string(&report.summary).ok());
     let diff_summary_json = serde_json::to_string(&diff_report).ok();
+    let warnings_json = if generation.warnings.is_empty() {
+        None
+    } else {
+        serde_json::to_string(&generation.warnings).ok()
+    };
+    let timing_json = serde_json::to_string(&generation.timing).ok();
     state
         .db
         .upsert_generation_metadata(
@@ -959,6 +1859,9 @@ pub async fn generate_documents(
             Some(run_id.as_str()),
             quality_json.as_deref(),
             confidence_json.as_deref(),
+            warnings_json.as_deref(),
+            timing_json.as_deref(),
+            config.llm.seed,
         )
         .map_err(to_response)?;
 
@@ -971,7 +1874,7 @@ pub async fn generate_documents(
         input_fingerprint,
         lint_summary_json,
         diff_summary_json,
-        created_at: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+        created_at: timestamp::now(),
     };
     let run_artifacts = build_generation_run_artifacts(&run_id, &docs);
     state
@@ -987,7 +1890,235 @@ pub async fn get_documents(
     state: State<'_, AppState>,
     session_id: String,
 ) -> Result<Vec<GeneratedDocument>, ErrorResponse> {
-    state.db.get_documents(&session_id).map_err(to_response)
+    let mut documents = state.db.get_documents(&session_id).map_err(to_response)?;
+    let order = stored_document_order(&state, &session_id).map_err(to_response)?;
+    sort_documents_by_order(&mut documents, &order);
+    Ok(documents)
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn set_document_order(
+    state: State<'_, AppState>,
+    session_id: String,
+    order: Vec<String>,
+) -> Result<Vec<GeneratedDocument>, ErrorResponse> {
+    let mut documents = state.db.get_documents(&session_id).map_err(to_response)?;
+    let order = order
+        .iter()
+        .map(|name| name.trim().to_string())
+        .collect::<Vec<_>>();
+    validate_document_order(&order, &documents).map_err(to_response)?;
+    let updated = state
+        .db
+        .set_document_order(&session_id, &order)
+        .map_err(to_response)?;
+    if updated == 0 {
+        return Err(to_response(AppError::SessionNotFound(session_id)));
+    }
+    sort_documents_by_order(&mut documents, &order);
+    Ok(documents)
+}
+
+fn stored_document_order(state: &AppState, session_id: &str) -> Result<Vec<String>, AppError> {
+    match state.db.get_document_order(session_id) {
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(Vec::new()),
+        result => result.map_err(AppError::from),
+    }
+}
+
+fn validate_document_order(order: &[String], docs: &[GeneratedDocument]) -> Result<(), AppError> {
+    let mut seen = std::collections::HashSet::new();
+    for name in order {
+        if !docs.iter().any(|doc| doc.filename == *name) {
+            return Err(AppError::Validation(format!(
+                "Unknown document '{}' in document order.",
+                name
+            )));
+        }
+        if !seen.insert(name.as_str()) {
+            return Err(AppError::Validation(format!(
+                "Document '{}' appears more than once in document order.",
+                name
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn sort_documents_by_order(docs: &mut [GeneratedDocument], order: &[String]) {
+    docs.sort_by_key(|doc| export_file_rank(&doc.filename, order));
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn list_document_names(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<Vec<DocumentSummary>, ErrorResponse> {
+    state
+        .db
+        .list_document_names(&session_id)
+        .map_err(to_response)
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn lint_document(
+    state: State<'_, AppState>,
+    session_id: String,
+    filename: String,
+) -> Result<DocumentLintReport, ErrorResponse> {
+    let docs = state.db.get_documents(&session_id).map_err(to_response)?;
+    let doc = docs
+        .iter()
+        .find(|doc| doc.filename == filename)
+        .ok_or_else(|| {
+            to_response(AppError::Validation(format!(
+                "No document named {} in this session.",
+                filename
+            )))
+        })?;
+    Ok(crate::lint::lint_document(doc))
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn lint_conversation(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<LintReport, ErrorResponse> {
+    let messages = state.db.get_messages(&session_id).map_err(to_response)?;
+    Ok(crate::lint::lint_conversation(&session_id, &messages))
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn check_document_consistency(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<LintReport, ErrorResponse> {
+    let docs = state.db.get_documents(&session_id).map_err(to_response)?;
+    Ok(crate::lint::check_document_consistency(&docs))
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn lint_all_sessions(
+    state: State<'_, AppState>,
+) -> Result<Vec<SessionLintSummary>, ErrorResponse> {
+    let sessions = state
+        .db
+        .get_sessions_with_documents()
+        .map_err(to_response)?;
+    Ok(crate::lint::lint_sessions(&sessions))
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_document_chunk(
+    state: State<'_, AppState>,
+    session_id: String,
+    filename: String,
+    offset: usize,
+    length: usize,
+) -> Result<DocumentChunk, ErrorResponse> {
+    if length == 0 || length > MAX_DOCUMENT_CHUNK_CHARS {
+        return Err(to_response(AppError::Validation(format!(
+            "Chunk length must be 1-{} characters.",
+            MAX_DOCUMENT_CHUNK_CHARS
+        ))));
+    }
+    state
+        .db
+        .get_document_chunk(&session_id, &filename, offset, length)
+        .map_err(to_response)?
+        .ok_or_else(|| {
+            to_response(AppError::Validation(format!(
+                "No document named {} in this session.",
+                filename
+            )))
+        })
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn extract_open_questions(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<Vec<OpenQuestion>, ErrorResponse> {
+    let docs = state.db.get_documents(&session_id).map_err(to_response)?;
+    Ok(docgen::extract_open_questions(&docs))
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn extract_requirements(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<Vec<Requirement>, ErrorResponse> {
+    let docs = state.db.get_documents(&session_id).map_err(to_response)?;
+    Ok(docgen::extract_requirements(&docs))
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn extract_tech_stack(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<Vec<TechChoice>, ErrorResponse> {
+    let messages = state.db.get_messages(&session_id).map_err(to_response)?;
+    Ok(docgen::extract_tech_stack(&messages))
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_decision_timeline(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<Vec<DecisionEntry>, ErrorResponse> {
+    let messages = state.db.get_messages(&session_id).map_err(to_response)?;
+    Ok(docgen::extract_decision_timeline(&messages))
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_conversation_language(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<Option<ConversationLanguage>, ErrorResponse> {
+    let messages = state.db.get_messages(&session_id).map_err(to_response)?;
+    Ok(docgen::detect_conversation_language(&messages))
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn clear_documents(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<usize, ErrorResponse> {
+    match state.db.get_session(&session_id) {
+        Ok(_) => {}
+        Err(rusqlite::Error::QueryReturnedNoRows) => {
+            return Err(to_response(AppError::SessionNotFound(session_id)))
+        }
+        Err(e) => return Err(to_response(e)),
+    }
+    let cleared = state
+        .db
+        .archive_and_clear_documents(&session_id, "cleared")
+        .map_err(to_response)?;
+    log::info!("Cleared {} documents for session {}", cleared, session_id);
+    Ok(cleared)
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn diff_sessions(
+    state: State<'_, AppState>,
+    base_session_id: String,
+    compare_session_id: String,
+) -> Result<ArtifactDiffReport, ErrorResponse> {
+    let mut sets = Vec::with_capacity(2);
+    for session_id in [&base_session_id, &compare_session_id] {
+        match state.db.get_session(session_id) {
+            Ok(_) => {}
+            Err(rusqlite::Error::QueryReturnedNoRows) => {
+                return Err(to_response(AppError::SessionNotFound(session_id.clone())))
+            }
+            Err(e) => return Err(to_response(e)),
+        }
+        let mut docs = state.db.get_documents(session_id).map_err(to_response)?;
+        docs.retain(|doc| !DERIVED_ARTIFACTS.contains(&doc.filename.as_str()));
+        sets.push(docs);
+    }
+    Ok(build_diff_report(&sets[0], &sets[1]))
 }
 
 #[tauri::command(rename_all = "snake_case")]
@@ -1006,15 +2137,7 @@ pub async fn check_documents_stale(
         .map_err(to_response)?;
 
     match (doc_time, msg_time) {
-        (Some(dt), Some(mt)) => {
-            let parse = |value: &str| {
-                chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S").ok()
-            };
-            match (parse(&dt), parse(&mt)) {
-                (Some(doc_dt), Some(msg_dt)) => Ok(msg_dt > doc_dt),
-                _ => Ok(true),
-            }
-        }
+        (Some(dt), Some(mt)) => Ok(timestamp::is_after(&mt, &dt).unwrap_or(true)),
         (None, _) => Ok(false), // No docs yet, not "stale"
         _ => Ok(false),
     }
@@ -1036,6 +2159,44 @@ pub async fn get_planning_coverage(
     analyze_planning_coverage_internal(&state, &session_id)
 }
 
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_coverage_evidence(
+    state: State<'_, AppState>,
+    session_id: String,
+    topic: String,
+) -> Result<Vec<Message>, ErrorResponse> {
+    let messages = state.db.get_messages(&session_id).map_err(to_response)?;
+    let coverage = docgen::analyze_planning_coverage(&messages, configured_planning_depth(&state)?);
+    docgen::coverage_evidence_messages(&coverage, &messages, &topic).ok_or_else(|| {
+        to_response(AppError::Validation(format!(
+            "Unknown coverage topic '{}'",
+            topic.trim()
+        )))
+    })
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_forge_readiness_checklist(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<ReadinessChecklist, ErrorResponse> {
+    let messages = state.db.get_messages(&session_id).map_err(to_response)?;
+    Ok(docgen::build_readiness_checklist(
+        &messages,
+        configured_planning_depth(&state)?,
+    ))
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_conversation_fingerprint(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<String, ErrorResponse> {
+    state.db.get_session(&session_id).map_err(to_response)?;
+    let messages = state.db.get_messages(&session_id).map_err(to_response)?;
+    Ok(build_conversation_fingerprint(&messages))
+}
+
 #[tauri::command(rename_all = "snake_case")]
 pub async fn get_generation_metadata(
     state: State<'_, AppState>,
@@ -1056,57 +2217,264 @@ pub async fn get_generation_confidence(
     if docs.is_empty() {
         return Ok(None);
     }
+    generation_confidence_internal(&state, &session_id, &docs).map(Some)
+}
 
-    let metadata = state
-        .db
-        .get_generation_metadata(&session_id)
-        .map_err(to_response)?;
-
-    if let Some(meta) = metadata.as_ref() {
-        if let Some(conf_json) = meta.confidence_json.as_ref() {
-            if let Ok(conf) = serde_json::from_str::<ConfidenceReport>(conf_json) {
-                return Ok(Some(conf));
-            }
+#[tauri::command(rename_all = "snake_case")]
+pub async fn recompute_confidence(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<Option<ConfidenceReport>, ErrorResponse> {
+    let docs = state.db.get_documents(&session_id).map_err(to_response)?;
+    if docs.is_empty() {
+        return Ok(None);
+    }
+
+    let quality = state
+        .db
+        .get_generation_metadata(&session_id)
+        .map_err(to_response)?
+        .and_then(|m| m.quality_json)
+        .and_then(|q| serde_json::from_str::<QualityReport>(&q).ok());
+    let confidence = docgen::analyze_generation_confidence(&docs, quality.as_ref());
+
+    let confidence_json = serde_json::to_string(&confidence)
+        .map_err(|e| to_response(AppError::Database(e.to_string())))?;
+    state
+        .db
+        .update_generation_confidence(&session_id, &confidence_json)
+        .map_err(to_response)?;
+    Ok(Some(confidence))
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn regenerate_handoff(
+    state: State<'_, AppState>,
+    session_id: String,
+    target: String,
+) -> Result<GeneratedDocument, ErrorResponse> {
+    let config = state
+        .config
+        .lock()
+        .map_err(|_| to_response(AppError::Config("Config lock poisoned".to_string())))?
+        .clone();
+    let target = resolve_forge_target(Some(&target), &config)?;
+
+    let session = match state.db.get_session(&session_id) {
+        Ok(session) => session,
+        Err(rusqlite::Error::QueryReturnedNoRows) => {
+            return Err(to_response(AppError::SessionNotFound(session_id)))
         }
+        Err(e) => return Err(to_response(e)),
+    };
+
+    let docs = state.db.get_documents(&session_id).map_err(to_response)?;
+    if !docs.iter().any(|d| d.filename == "MODEL_HANDOFF.md") {
+        return Err(to_response(AppError::Validation(
+            "No MODEL_HANDOFF.md to regenerate. Forge the plan first.".to_string(),
+        )));
     }
 
-    let quality = metadata
-        .as_ref()
-        .and_then(|m| m.quality_json.as_ref())
-        .and_then(|q| serde_json::from_str::<QualityReport>(q).ok());
+    let agent_config_file = agent_config_document(&docs).unwrap_or("CLAUDE.md");
+    let content = render_handoff(&state, &config, &session, &target, agent_config_file)?;
+    let doc = state
+        .db
+        .replace_document(
+            &session_id,
+            "MODEL_HANDOFF.md",
+            &content,
+            "handoff_retarget",
+        )
+        .map_err(to_response)?;
+    state
+        .db
+        .update_generation_target(&session_id, target.as_str())
+        .map_err(to_response)?;
+    Ok(doc)
+}
 
-    Ok(Some(docgen::analyze_generation_confidence(
-        &docs,
-        quality.as_ref(),
-    )))
+fn render_handoff(
+    state: &State<'_, AppState>,
+    config: &AppConfig,
+    session: &Session,
+    target: &ForgeTarget,
+    agent_config_file: &str,
+) -> Result<String, ErrorResponse> {
+    let stored_quality = state
+        .db
+        .get_generation_metadata(&session.id)
+        .map_err(to_response)?
+        .and_then(|m| m.quality_json)
+        .and_then(|q| serde_json::from_str::<QualityReport>(&q).ok());
+    let quality = match stored_quality {
+        Some(quality) => quality,
+        None => analyze_plan_readiness_internal(state, &session.id)?,
+    };
+    Ok(docgen::render_model_handoff(
+        session,
+        target,
+        &quality,
+        agent_config_file,
+        config.output.frontmatter,
+    ))
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn list_document_targets(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<DocumentTargets, ErrorResponse> {
+    let docs = state.db.get_documents(&session_id).map_err(to_response)?;
+    let current_target = state
+        .db
+        .get_generation_metadata(&session_id)
+        .map_err(to_response)?
+        .map(|meta| meta.target);
+
+    Ok(DocumentTargets {
+        current_target,
+        agent_config_file: agent_config_document(&docs).map(str::to_string),
+        targets: ForgeTarget::ALL
+            .iter()
+            .map(|target| DocumentTargetOption {
+                target: target.clone(),
+                agent_config_file: target.agent_config_filename().to_string(),
+            })
+            .collect(),
+    })
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_document_catalog() -> Result<Vec<DocumentCatalogEntry>, ErrorResponse> {
+    Ok(document_catalog())
+}
+
+fn document_catalog() -> Vec<DocumentCatalogEntry> {
+    EXPORT_FILE_ORDER
+        .iter()
+        .map(|filename| {
+            let agent_config = ForgeTarget::AGENT_CONFIG_FILENAMES.contains(filename);
+            let source = if agent_config || docgen::LLM_GENERATED_DOCS.contains(filename) {
+                DocumentSource::Llm
+            } else {
+                DocumentSource::Template
+            };
+            let required = docgen::REQUIRED_DOCS.contains(filename)
+                || (agent_config && docgen::REQUIRED_DOCS.contains(&"CLAUDE.md"));
+            DocumentCatalogEntry {
+                filename: filename.to_string(),
+                purpose: document_purpose(filename).to_string(),
+                source,
+                required,
+                export_rank: export_file_rank(filename, &[]),
+                targets: ForgeTarget::ALL
+                    .iter()
+                    .filter(|target| agent_config && target.agent_config_filename() == *filename)
+                    .cloned()
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+fn document_purpose(filename: &str) -> &'static str {
+    match filename {
+        "START_HERE.md" => "Quick-start guide with your first prompt",
+        "README.md" => "Overview of the planning folder and its documents",
+        "SPEC.md" => "Complete specification of what you're building and why",
+        "CLAUDE.md" => "Project instructions for Claude Code",
+        "AGENTS.md" => "Project instructions for Codex and other agents",
+        "GEMINI.md" => "Project instructions for Gemini CLI",
+        ".cursorrules" => "Project rules for Cursor",
+        "PROMPTS.md" => "Step-by-step implementation phases",
+        "MODEL_HANDOFF.md" => "Target-aware handoff notes for your coding model",
+        "CONVERSATION.md" => "Full planning transcript",
+        "LINT_REPORT.md" => "Lint findings across the generated documents",
+        "ARTIFACT_CHANGELOG.md" => "What changed since the previous forge",
+        "ARTIFACT_DIFF.json" => "Machine-readable diff against the previous forge",
+        _ => "",
+    }
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn retarget_documents(
+    state: State<'_, AppState>,
+    session_id: String,
+    target: String,
+) -> Result<Vec<GeneratedDocument>, ErrorResponse> {
+    let config = state
+        .config
+        .lock()
+        .map_err(|_| to_response(AppError::Config("Config lock poisoned".to_string())))?
+        .clone();
+    let resolved = resolve_forge_target(Some(&target), &config)?;
+
+    let session = match state.db.get_session(&session_id) {
+        Ok(session) => session,
+        Err(rusqlite::Error::QueryReturnedNoRows) => {
+            return Err(to_response(AppError::SessionNotFound(session_id)))
+        }
+        Err(e) => return Err(to_response(e)),
+    };
+    let docs = state.db.get_documents(&session_id).map_err(to_response)?;
+    let current_file = agent_config_document(&docs)
+        .filter(|_| docs.iter().any(|d| d.filename == "MODEL_HANDOFF.md"))
+        .ok_or_else(|| {
+            to_response(AppError::Validation(
+                "No agent instruction document or MODEL_HANDOFF.md to retarget. Forge the plan first."
+                    .to_string(),
+            ))
+        })?;
+    let new_file = resolved.agent_config_filename();
+    let handoff = render_handoff(&state, &config, &session, &resolved, new_file)?;
+    state
+        .db
+        .retarget_documents(
+            &session_id,
+            current_file,
+            new_file,
+            &handoff,
+            resolved.as_str(),
+            "handoff_retarget",
+        )
+        .map_err(to_response)?;
+    state.db.get_documents(&session_id).map_err(to_response)
 }
 
 // ============ EXPORT ============
 
+#[tauri::command(rename_all = "snake_case")]
+pub async fn export_preview(
+    state: State<'_, AppState>,
+    session_id: String,
+    include_provenance: Option<bool>,
+    file_order: Option<Vec<String>>,
+) -> Result<ExportPreview, ErrorResponse> {
+    let documents = state.db.get_documents(&session_id).map_err(to_response)?;
+    if documents.is_empty() {
+        return Err(to_response(AppError::Validation(
+            "No documents to save. Generate documents first.".to_string(),
+        )));
+    }
+
+    let plan = plan_export(
+        &state,
+        &session_id,
+        documents,
+        include_provenance.unwrap_or(false),
+        file_order.as_deref().unwrap_or_default(),
+    )
+    .map_err(to_response)?;
+    build_export_preview(&plan).map_err(to_response)
+}
+
 #[tauri::command(rename_all = "snake_case")]
 pub async fn save_to_folder(
+    app: tauri::AppHandle,
     state: State<'_, AppState>,
     request: SaveToFolderRequest,
 ) -> Result<String, ErrorResponse> {
-    let requested_root = std::path::PathBuf::from(&request.folder_path);
-    let root_metadata = std::fs::metadata(&requested_root).map_err(|e| {
-        to_response(AppError::FileSystem {
-            path: request.folder_path.clone(),
-            message: format!("Cannot access destination folder: {}", e),
-        })
-    })?;
-    if !root_metadata.is_dir() {
-        return Err(to_response(AppError::FileSystem {
-            path: request.folder_path.clone(),
-            message: "Destination must be a folder.".to_string(),
-        }));
-    }
-    if root_metadata.permissions().readonly() {
-        return Err(to_response(AppError::FileSystem {
-            path: request.folder_path.clone(),
-            message: "Destination folder is read-only.".to_string(),
-        }));
-    }
+    let requested_root = validate_export_root(&request.folder_path)?;
 
     let documents = state
         .db
@@ -1120,50 +2488,38 @@ pub async fn save_to_folder(
         }));
     }
 
-    let session = state
-        .db
-        .get_session(&request.session_id)
-        .map_err(to_response)?;
-    let generation_meta = state
-        .db
-        .get_generation_metadata(&request.session_id)
-        .map_err(to_response)?;
-    let import_context = state
-        .db
-        .get_messages(&request.session_id)
-        .map_err(to_response)?
-        .into_iter()
-        .rev()
-        .find_map(|message| {
-            message
-                .metadata
-                .as_deref()
-                .and_then(extract_import_summary_from_metadata)
-        });
-    let export_documents = prepare_export_documents(
-        &documents,
-        generation_meta
-            .as_ref()
-            .map(|meta| meta.target.as_str())
-            .unwrap_or("generic"),
+    let plan = plan_export(
+        &state,
+        &request.session_id,
+        documents,
+        request.include_provenance.unwrap_or(false),
+        request.file_order.as_deref().unwrap_or_default(),
     )
     .map_err(to_response)?;
-
-    // Sanitize session name for folder name
-    let sanitized_name = sanitize_folder_name(&session.name);
-    let output_dir = requested_root.join(format!("{}-plan", sanitized_name));
+    let output_dir = requested_root.join(&plan.folder_name);
+    let export_documents = plan.documents;
 
     let output_path = output_dir.to_string_lossy().to_string();
+    let _export_lock = state
+        .export_locks
+        .try_acquire(&output_dir)
+        .ok_or_else(|| to_response(AppError::ExportInProgress(output_path.clone())))?;
     let output_path_for_thread = output_path.clone();
     let docs_for_thread = export_documents.clone();
     let output_dir_for_thread = output_dir.clone();
-    let meta_for_thread = generation_meta.clone();
-    let import_context_for_thread = import_context.clone();
-    let session_name_for_thread = session.name.clone();
-    let session_id_for_thread = request.session_id.clone();
+    let manifest = plan.manifest;
+    let incremental = request.incremental.unwrap_or(false);
 
-    let write_result = tauri::async_runtime::spawn_blocking(move || -> Result<(), AppError> {
+    let write_result = tauri::async_runtime::spawn_blocking(move || {
         if output_dir_for_thread.exists() {
+            if incremental {
+                return write_incremental_export(
+                    &output_dir_for_thread,
+                    &docs_for_thread,
+                    &manifest,
+                )
+                .map(Some);
+            }
             return Err(AppError::FolderExists(output_path_for_thread));
         }
 
@@ -1194,28 +2550,8 @@ pub async fn save_to_folder(
                         message: format!("Failed to create export subdirectory: {}", e),
                     })?;
                 }
-                std::fs::write(&staging_file_path, &doc.content).map_err(|e| {
-                    if e.raw_os_error() == Some(28) {
-                        AppError::FileSystem {
-                            path: final_file_path.to_string_lossy().to_string(),
-                            message: "Not enough disk space. Free up space and try again."
-                                .to_string(),
-                        }
-                    } else if e.kind() == std::io::ErrorKind::PermissionDenied {
-                        AppError::FileSystem {
-                            path: final_file_path.to_string_lossy().to_string(),
-                            message: format!(
-                                "Permission denied writing {}. Choose another folder.",
-                                doc.filename
-                            ),
-                        }
-                    } else {
-                        AppError::FileSystem {
-                            path: final_file_path.to_string_lossy().to_string(),
-                            message: format!("Failed to write {}: {}", doc.filename, e),
-                        }
-                    }
-                })?;
+                std::fs::write(&staging_file_path, &doc.content)
+                    .map_err(|e| export_write_error(&e, &final_file_path, &doc.filename))?;
             }
             Ok(())
         })();
@@ -1225,66 +2561,25 @@ pub async fn save_to_folder(
             return Err(err);
         }
 
-        if !is_supported_export_manifest_schema_version(EXPORT_MANIFEST_SCHEMA_VERSION) {
+        if let Err(err) = ensure_supported_manifest_schema() {
             let _ = std::fs::remove_dir_all(&staging_dir);
-            return Err(AppError::Validation(format!(
-                "Manifest schema v{} is outside supported compatibility range {}..={}.",
-                EXPORT_MANIFEST_SCHEMA_VERSION,
-                MIN_SUPPORTED_EXPORT_MANIFEST_SCHEMA_VERSION,
-                EXPORT_MANIFEST_SCHEMA_VERSION
-            )));
+            return Err(err);
         }
 
-        let manifest = ExportManifest {
-            schema_version: EXPORT_MANIFEST_SCHEMA_VERSION,
-            session_id: session_id_for_thread.clone(),
-            session_name: session_name_for_thread.clone(),
-            target: meta_for_thread
-                .as_ref()
-                .map(|m| m.target.clone())
-                .unwrap_or_else(|| "generic".to_string()),
-            run_id: meta_for_thread.as_ref().and_then(|m| m.run_id.clone()),
-            export_preset: preset_label(
-                meta_for_thread
-                    .as_ref()
-                    .map(|m| m.target.as_str())
-                    .unwrap_or("generic"),
-            )
-            .to_string(),
-            provider: meta_for_thread
-                .as_ref()
-                .map(|m| m.provider.clone())
-                .unwrap_or_else(|| "ollama".to_string()),
-            model: meta_for_thread
-                .as_ref()
-                .map(|m| m.model.clone())
-                .unwrap_or_else(|| "unknown".to_string()),
-            created_at: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
-            quality: meta_for_thread
-                .as_ref()
-                .and_then(|m| m.quality_json.as_ref())
-                .and_then(|q| serde_json::from_str::<QualityReport>(q).ok()),
-            confidence: meta_for_thread
-                .as_ref()
-                .and_then(|m| m.confidence_json.as_ref())
-                .and_then(|q| serde_json::from_str::<ConfidenceReport>(q).ok()),
-            import_context: import_context_for_thread.clone(),
-            files: build_export_manifest_files(&docs_for_thread),
-        };
-        let manifest_json =
-            serde_json::to_string_pretty(&manifest).map_err(|e| AppError::FileSystem {
-                path: staging_dir.to_string_lossy().to_string(),
-                message: format!("Failed to serialize export manifest: {}", e),
-            })?;
-        std::fs::write(staging_dir.join("manifest.json"), manifest_json).map_err(|e| {
-            AppError::FileSystem {
-                path: staging_dir
-                    .join("manifest.json")
-                    .to_string_lossy()
-                    .to_string(),
-                message: format!("Failed to write export manifest: {}", e),
-            }
-        })?;
+        let write_manifest_result = (|| -> Result<(), AppError> {
+            let manifest_json =
+                serde_json::to_string_pretty(&manifest).map_err(|e| AppError::FileSystem {
+                    path: staging_dir.to_string_lossy().to_string(),
+                    message: format!("Failed to serialize export manifest: {}", e),
+                })?;
+            let final_manifest_path = output_dir_for_thread.join(EXPORT_MANIFEST_FILENAME);
+            std::fs::write(staging_dir.join(EXPORT_MANIFEST_FILENAME), manifest_json)
+                .map_err(|e| export_write_error(&e, &final_manifest_path, EXPORT_MANIFEST_FILENAME))
+        })();
+        if let Err(err) = write_manifest_result {
+            let _ = std::fs::remove_dir_all(&staging_dir);
+            return Err(err);
+        }
 
         std::fs::rename(&staging_dir, &output_dir_for_thread).map_err(|e| {
             let _ = std::fs::remove_dir_all(&staging_dir);
@@ -1304,7 +2599,7 @@ pub async fn save_to_folder(
             }
         })?;
 
-        Ok(())
+        Ok(None)
     })
     .await
     .map_err(|e| {
@@ -1314,7 +2609,31 @@ pub async fn save_to_folder(
         })
     })?;
 
-    write_result.map_err(to_response)?;
+    if let Some(IncrementalWrite {
+        written,
+        skipped,
+        removed,
+    }) = write_result.map_err(to_response)?
+    {
+        log::info!(
+            "Incremental export to {}: {} written, {} unchanged, {} removed",
+            output_path,
+            written.len(),
+            skipped.len(),
+            removed.len()
+        );
+        let _ = app.emit(
+            "export:incremental",
+            IncrementalExportReport {
+                session_id: request.session_id.clone(),
+                output_path: output_path.clone(),
+                written,
+                skipped,
+                removed,
+            },
+        );
+        return Ok(output_path);
+    }
     log::info!(
         "Saved {} documents to {}",
         export_documents.len(),
@@ -1324,8 +2643,220 @@ pub async fn save_to_folder(
     Ok(output_path)
 }
 
+#[tauri::command(rename_all = "snake_case")]
+pub async fn save_to_zip(
+    state: State<'_, AppState>,
+    session_id: String,
+    zip_path: String,
+    force: Option<bool>,
+) -> Result<String, ErrorResponse> {
+    let force = force.unwrap_or(false);
+    let archive_path = validate_zip_destination(&zip_path, force)?;
+
+    let documents = state.db.get_documents(&session_id).map_err(to_response)?;
+    if documents.is_empty() {
+        return Err(to_response(AppError::FileSystem {
+            path: zip_path.clone(),
+            message: "No documents to save. Generate documents first.".to_string(),
+        }));
+    }
+
+    let plan = plan_export(&state, &session_id, documents, false, &[]).map_err(to_response)?;
+    let _export_lock = state
+        .export_locks
+        .try_acquire(&archive_path)
+        .ok_or_else(|| to_response(AppError::ExportInProgress(zip_path.clone())))?;
+    let document_count = plan.documents.len();
+    let archive_path_for_thread = archive_path.clone();
+
+    tauri::async_runtime::spawn_blocking(move || {
+        ensure_supported_manifest_schema()?;
+        let archive_name = archive_path_for_thread
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let tmp_path = archive_path_for_thread
+            .with_extension(format!("zip_tmp_{}", uuid::Uuid::new_v4().simple()));
+
+        let write_result = (|| -> Result<(), AppError> {
+            let file = std::fs::File::create(&tmp_path)
+                .map_err(|e| export_write_error(&e, &archive_path_for_thread, &archive_name))?;
+            write_export_zip(file, &plan.folder_name, &plan.documents, &plan.manifest).map_err(
+                |e| match e {
+                    zip::result::ZipError::Io(e) => {
+                        export_write_error(&e, &archive_path_for_thread, &archive_name)
+                    }
+                    other => AppError::FileSystem {
+                        path: archive_path_for_thread.to_string_lossy().to_string(),
+                        message: format!("Failed to build zip archive: {}", other),
+                    },
+                },
+            )?;
+            if !force && archive_path_for_thread.exists() {
+                return Err(zip_exists_error(&archive_path_for_thread));
+            }
+            std::fs::rename(&tmp_path, &archive_path_for_thread)
+                .map_err(|e| export_write_error(&e, &archive_path_for_thread, &archive_name))
+        })();
+        if write_result.is_err() {
+            let _ = std::fs::remove_file(&tmp_path);
+        }
+        write_result
+    })
+    .await
+    .map_err(|e| {
+        to_response(AppError::FileSystem {
+            path: zip_path.clone(),
+            message: format!("Failed to write zip archive: {}", e),
+        })
+    })?
+    .map_err(to_response)?;
+
+    log::info!("Saved {} documents to {}", document_count, zip_path);
+    Ok(archive_path.to_string_lossy().to_string())
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn import_plan_folder(
+    state: State<'_, AppState>,
+    folder_path: String,
+) -> Result<PlanFolderImport, ErrorResponse> {
+    let ExportedPlan {
+        manifest,
+        documents,
+        warnings,
+    } = read_exported_plan(std::path::Path::new(&folder_path)).map_err(to_response)?;
+    for warning in &warnings {
+        log::warn!("Importing {}: {}", folder_path, warning);
+    }
+
+    let session = state
+        .db
+        .create_session(Some(manifest.session_name.as_str()))
+        .map_err(to_response)?;
+    let imported = (|| -> Result<Vec<GeneratedDocument>, rusqlite::Error> {
+        let inserted = state.db.replace_documents(&session.id, &documents)?;
+        let quality_json = manifest.quality.as_ref().map(|value| value.to_string());
+        let confidence_json = manifest.confidence.as_ref().map(|value| value.to_string());
+        state.db.upsert_generation_metadata(
+            &session.id,
+            &manifest.target,
+            &manifest.provider,
+            &manifest.model,
+            manifest.run_id.as_deref(),
+            quality_json.as_deref(),
+            confidence_json.as_deref(),
+            None,
+            None,
+            None,
+        )?;
+        Ok(inserted)
+    })();
+    let documents = match imported {
+        Ok(documents) => documents,
+        Err(e) => {
+            let _ = state.db.delete_session(&session.id);
+            return Err(to_response(e));
+        }
+    };
+
+    log::info!(
+        "Imported {} documents from {} into session {}",
+        documents.len(),
+        folder_path,
+        session.id
+    );
+    Ok(PlanFolderImport {
+        session,
+        documents,
+        warnings,
+    })
+}
+
+/// Documents are persisted before export is attempted, so an export failure is
+/// reported in the result rather than discarding the freshly generated set.
+#[tauri::command(rename_all = "snake_case")]
+pub async fn forge_and_export(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    request: ForgeAndExportRequest,
+) -> Result<ForgeAndExportResult, ErrorResponse> {
+    // Fail fast on an unusable destination before spending minutes on generation.
+    validate_export_root(&request.folder_path)?;
+
+    let documents = generate_documents(
+        app.clone(),
+        state.clone(),
+        GenerateDocumentsRequest {
+            session_id: request.session_id.clone(),
+            target: request.target.clone(),
+            force: request.force,
+            quiet: None,
+        },
+    )
+    .await?;
+
+    let export = save_to_folder(
+        app,
+        state,
+        SaveToFolderRequest {
+            session_id: request.session_id.clone(),
+            folder_path: request.folder_path.clone(),
+            include_provenance: request.include_provenance,
+            incremental: None,
+            file_order: None,
+        },
+    )
+    .await;
+
+    Ok(match export {
+        Ok(output_path) => ForgeAndExportResult {
+            documents,
+            output_path: Some(output_path),
+            export_error: None,
+        },
+        Err(err) => {
+            log::warn!(
+                "Forged documents for {} but export failed: {}",
+                request.session_id,
+                err.message
+            );
+            ForgeAndExportResult {
+                documents,
+                output_path: None,
+                export_error: Some(err),
+            }
+        }
+    })
+}
+
 // ============ SEARCH ============
 
+#[tauri::command(rename_all = "snake_case")]
+pub async fn search_content(
+    state: State<'_, AppState>,
+    query: String,
+    session_id: Option<String>,
+    limit: Option<usize>,
+    offset: Option<usize>,
+) -> Result<Vec<ContentSearchHit>, ErrorResponse> {
+    let query = query.trim();
+    if query.chars().count() < MIN_CONTENT_QUERY_CHARS {
+        return Err(to_response(AppError::Validation(format!(
+            "Search query must be at least {} characters.",
+            MIN_CONTENT_QUERY_CHARS
+        ))));
+    }
+    let limit = limit
+        .unwrap_or(DEFAULT_CONTENT_SEARCH_LIMIT)
+        .clamp(1, MAX_CONTENT_SEARCH_LIMIT);
+    let rows = state
+        .db
+        .search_content(query, session_id.as_deref(), limit, offset.unwrap_or(0))
+        .map_err(to_response)?;
+    Ok(content_search::build_hits(rows, query))
+}
+
 #[tauri::command(rename_all = "snake_case")]
 pub async fn web_search(
     state: State<'_, AppState>,
@@ -1346,10 +2877,108 @@ pub async fn web_search(
         .map_err(to_response)
 }
 
-fn sanitize_folder_name(name: &str) -> String {
-    let sanitized: String = name
-        .chars()
-        .map(|c| {
+fn validate_export_root(folder_path: &str) -> Result<std::path::PathBuf, ErrorResponse> {
+    let requested_root = std::path::PathBuf::from(folder_path);
+    let root_metadata = std::fs::metadata(&requested_root).map_err(|e| {
+        to_response(AppError::FileSystem {
+            path: folder_path.to_string(),
+            message: format!("Cannot access destination folder: {}", e),
+        })
+    })?;
+    if !root_metadata.is_dir() {
+        return Err(to_response(AppError::FileSystem {
+            path: folder_path.to_string(),
+            message: "Destination must be a folder.".to_string(),
+        }));
+    }
+    if root_metadata.permissions().readonly() {
+        return Err(to_response(AppError::FileSystem {
+            path: folder_path.to_string(),
+            message: "Destination folder is read-only.".to_string(),
+        }));
+    }
+    Ok(requested_root)
+}
+
+fn validate_zip_destination(
+    zip_path: &str,
+    force: bool,
+) -> Result<std::path::PathBuf, ErrorResponse> {
+    let archive_path = std::path::PathBuf::from(zip_path);
+    if archive_path.is_dir() {
+        return Err(to_response(AppError::FileSystem {
+            path: zip_path.to_string(),
+            message: "Destination must be a file, not a folder.".to_string(),
+        }));
+    }
+    let parent = archive_path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .ok_or_else(|| {
+            to_response(AppError::FileSystem {
+                path: zip_path.to_string(),
+                message: "Destination must be a full file path.".to_string(),
+            })
+        })?;
+    validate_export_root(&parent.to_string_lossy())?;
+    if archive_path.exists() && !force {
+        return Err(to_response(zip_exists_error(&archive_path)));
+    }
+    Ok(archive_path)
+}
+
+fn zip_exists_error(path: &std::path::Path) -> AppError {
+    AppError::FileSystem {
+        path: path.to_string_lossy().to_string(),
+        message: "A file already exists here. Save with force to replace it.".to_string(),
+    }
+}
+
+fn write_export_zip<W: std::io::Write + std::io::Seek>(
+    writer: W,
+    folder_name: &str,
+    docs: &[ExportDocument],
+    manifest: &ExportManifest,
+) -> zip::result::ZipResult<W> {
+    use std::io::Write;
+
+    let manifest_json = serde_json::to_string_pretty(manifest)
+        .map_err(|e| zip::result::ZipError::Io(std::io::Error::other(e)))?;
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+    let mut archive = zip::ZipWriter::new(writer);
+    let entries = docs
+        .iter()
+        .map(|doc| (doc.filename.as_str(), doc.content.as_bytes()))
+        .chain(std::iter::once((
+            EXPORT_MANIFEST_FILENAME,
+            manifest_json.as_bytes(),
+        )));
+    for (filename, bytes) in entries {
+        archive.start_file(format!("{}/{}", folder_name, filename), options)?;
+        archive.write_all(bytes)?;
+    }
+    archive.finish()
+}
+
+/// Placeholder values are sanitized first, then the whole result, so any
+/// template yields a single safe path component.
+fn export_folder_name(template: &str, session_name: &str, target: &str, date: &str) -> String {
+    let name = template
+        .replace("{name}", &sanitize_folder_name(session_name))
+        .replace("{date}", date)
+        .replace("{target}", &sanitize_folder_name(target));
+    sanitize_path_component(&name, MAX_EXPORT_FOLDER_CHARS)
+}
+
+fn sanitize_folder_name(name: &str) -> String {
+    sanitize_path_component(name, 60)
+}
+
+fn sanitize_path_component(name: &str, max_chars: usize) -> String {
+    let sanitized: String = name
+        .chars()
+        .map(|c| {
             if c.is_alphanumeric() || c == '-' || c == '_' || c == ' ' {
                 c
             } else {
@@ -1362,7 +2991,7 @@ fn sanitize_folder_name(name: &str) -> String {
         .join("-")
         .to_lowercase()
         .chars()
-        .take(60)
+        .take(max_chars)
         .collect();
 
     if sanitized.is_empty() || sanitized.chars().all(|c| c == '_' || c == '-') {
@@ -1377,12 +3006,50 @@ fn is_supported_export_manifest_schema_version(version: u32) -> bool {
         .contains(&version)
 }
 
+fn generation_confidence_internal(
+    state: &State<'_, AppState>,
+    session_id: &str,
+    docs: &[GeneratedDocument],
+) -> Result<ConfidenceReport, ErrorResponse> {
+    let metadata = state
+        .db
+        .get_generation_metadata(session_id)
+        .map_err(to_response)?;
+
+    if let Some(meta) = metadata.as_ref() {
+        if let Some(conf_json) = meta.confidence_json.as_ref() {
+            if let Ok(conf) = serde_json::from_str::<ConfidenceReport>(conf_json) {
+                return Ok(conf);
+            }
+        }
+    }
+
+    let quality = metadata
+        .as_ref()
+        .and_then(|m| m.quality_json.as_ref())
+        .and_then(|q| serde_json::from_str::<QualityReport>(q).ok());
+
+    Ok(docgen::analyze_generation_confidence(
+        docs,
+        quality.as_ref(),
+    ))
+}
+
+fn agent_config_document(docs: &[GeneratedDocument]) -> Option<&str> {
+    docs.iter()
+        .map(|doc| doc.filename.as_str())
+        .find(|filename| ForgeTarget::AGENT_CONFIG_FILENAMES.contains(filename))
+}
+
 fn analyze_plan_readiness_internal(
     state: &State<'_, AppState>,
     session_id: &str,
 ) -> Result<QualityReport, ErrorResponse> {
     let messages = state.db.get_messages(session_id).map_err(to_response)?;
-    Ok(docgen::analyze_plan_readiness(&messages))
+    Ok(docgen::analyze_plan_readiness(
+        &messages,
+        configured_planning_depth(state)?,
+    ))
 }
 
 fn analyze_planning_coverage_internal(
@@ -1390,7 +3057,21 @@ fn analyze_planning_coverage_internal(
     session_id: &str,
 ) -> Result<CoverageReport, ErrorResponse> {
     let messages = state.db.get_messages(session_id).map_err(to_response)?;
-    Ok(docgen::analyze_planning_coverage(&messages))
+    Ok(docgen::analyze_planning_coverage(
+        &messages,
+        configured_planning_depth(state)?,
+    ))
+}
+
+fn configured_planning_depth(state: &State<'_, AppState>) -> Result<PlanningDepth, ErrorResponse> {
+    Ok(state
+        .config
+        .lock()
+        .map_err(|_| to_response(AppError::Config("Config lock poisoned".to_string())))?
+        .output
+        .planning_depth
+        .parse()
+        .unwrap_or_default())
 }
 
 fn resolve_forge_target(
@@ -1420,15 +3101,23 @@ struct ExportManifest {
     quality: Option<QualityReport>,
     confidence: Option<ConfidenceReport>,
     import_context: Option<CodebaseImportSummary>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    provenance: Option<Vec<ProvenanceEntry>>,
     files: Vec<ExportManifestFile>,
 }
 
 #[derive(Debug, Clone, Serialize)]
-struct ExportManifestFile {
-    filename: String,
-    bytes: usize,
-    lines: usize,
-    sha256: String,
+struct ProvenanceEntry {
+    message_id: String,
+    created_at: String,
+    search_query: Option<String>,
+    sources: Vec<ProvenanceSource>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ProvenanceSource {
+    title: String,
+    url: String,
 }
 
 #[derive(Debug, Clone)]
@@ -1437,11 +3126,404 @@ struct ExportDocument {
     content: String,
 }
 
+fn previous_export_files(previous_manifest: &str) -> std::collections::HashMap<String, String> {
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(previous_manifest) else {
+        return std::collections::HashMap::new();
+    };
+    value
+        .get("files")
+        .and_then(|files| files.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|file| {
+            Some((
+                file.get("filename")?.as_str()?.to_string(),
+                file.get("sha256")?.as_str()?.to_string(),
+            ))
+        })
+        .collect()
+}
+
+fn unchanged_export_files(
+    previous_manifest: &str,
+    docs: &[ExportDocument],
+) -> std::collections::HashSet<String> {
+    let previous = previous_export_files(previous_manifest);
+    docs.iter()
+        .filter(|doc| {
+            previous.get(&doc.filename).map(String::as_str)
+                == Some(sha256_hex(doc.content.as_bytes()).as_str())
+        })
+        .map(|doc| doc.filename.clone())
+        .collect()
+}
+
+/// Files listed in the previous manifest that the current document set no longer
+/// produces, e.g. `docs/CLAUDE.md` after retargeting a plan to `AGENTS.md`.
+fn stale_export_files(previous_manifest: &str, docs: &[ExportDocument]) -> Vec<String> {
+    let current = docs
+        .iter()
+        .map(|doc| doc.filename.as_str())
+        .collect::<std::collections::HashSet<_>>();
+    let mut stale = previous_export_files(previous_manifest)
+        .into_keys()
+        .filter(|filename| {
+            !current.contains(filename.as_str())
+                && filename != EXPORT_MANIFEST_FILENAME
+                && validate_export_path(filename).is_ok()
+        })
+        .collect::<Vec<_>>();
+    stale.sort();
+    stale
+}
+
+#[derive(Debug, Deserialize)]
+struct ImportedManifest {
+    schema_version: u32,
+    session_name: String,
+    target: String,
+    provider: String,
+    model: String,
+    #[serde(default)]
+    run_id: Option<String>,
+    #[serde(default)]
+    quality: Option<serde_json::Value>,
+    #[serde(default)]
+    confidence: Option<serde_json::Value>,
+    files: Vec<ImportedManifestFile>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ImportedManifestFile {
+    filename: String,
+    sha256: String,
+}
+
+#[derive(Debug)]
+struct ExportedPlan {
+    manifest: ImportedManifest,
+    documents: Vec<(String, String)>,
+    warnings: Vec<String>,
+}
+
+fn read_exported_plan(folder: &std::path::Path) -> Result<ExportedPlan, AppError> {
+    let manifest_path = folder.join(EXPORT_MANIFEST_FILENAME);
+    let raw = std::fs::read_to_string(&manifest_path).map_err(|e| AppError::FileSystem {
+        path: manifest_path.to_string_lossy().to_string(),
+        message: format!("Cannot read export manifest: {}", e),
+    })?;
+    let manifest = serde_json::from_str::<ImportedManifest>(&raw).map_err(|e| {
+        AppError::Validation(format!(
+            "{} is not a valid export manifest: {}",
+            EXPORT_MANIFEST_FILENAME, e
+        ))
+    })?;
+    if !is_supported_export_manifest_schema_version(manifest.schema_version) {
+        return Err(AppError::Validation(format!(
+            "Unsupported export manifest schema version {}.",
+            manifest.schema_version
+        )));
+    }
+
+    let mut documents: Vec<(String, String)> = Vec::new();
+    let mut warnings = Vec::new();
+    for file in &manifest.files {
+        // The checklist is rebuilt from the documents on every export.
+        if file.filename == EXECUTION_CHECKLIST_EXPORT_PATH {
+            continue;
+        }
+        validate_export_path(&file.filename)?;
+        let basename = export_basename(&file.filename).to_string();
+        if !basename.ends_with(".md")
+            && !ForgeTarget::AGENT_CONFIG_FILENAMES.contains(&basename.as_str())
+        {
+            warnings.push(format!(
+                "{} is not a planning document; skipped",
+                file.filename
+            ));
+            continue;
+        }
+        if documents.iter().any(|(existing, _)| *existing == basename) {
+            warnings.push(format!("{} is listed twice; kept the first copy", basename));
+            continue;
+        }
+        let content = match std::fs::read_to_string(folder.join(&file.filename)) {
+            Ok(content) => content,
+            Err(e) => {
+                warnings.push(format!(
+                    "{} could not be read ({}); skipped",
+                    file.filename, e
+                ));
+                continue;
+            }
+        };
+        if sha256_hex(content.as_bytes()) != file.sha256 {
+            warnings.push(format!("{} was edited after export", file.filename));
+        }
+        documents.push((basename, content));
+    }
+
+    if documents.is_empty() {
+        return Err(AppError::Validation(
+            "The export folder has no Markdown documents to import.".to_string(),
+        ));
+    }
+    Ok(ExportedPlan {
+        manifest,
+        documents,
+        warnings,
+    })
+}
+
+#[derive(Debug, Clone)]
+struct ExportPlan {
+    folder_name: String,
+    documents: Vec<ExportDocument>,
+    manifest: ExportManifest,
+}
+
+#[derive(Debug)]
+struct IncrementalWrite {
+    written: Vec<String>,
+    skipped: Vec<String>,
+    removed: Vec<String>,
+}
+
 // ============ HELPERS ============
 
+fn write_incremental_export(
+    output_dir: &std::path::Path,
+    docs: &[ExportDocument],
+    manifest: &ExportManifest,
+) -> Result<IncrementalWrite, AppError> {
+    ensure_supported_manifest_schema()?;
+    let manifest_path = output_dir.join(EXPORT_MANIFEST_FILENAME);
+    let previous_manifest = std::fs::read_to_string(&manifest_path).ok();
+    let unchanged = previous_manifest
+        .as_deref()
+        .map(|previous| unchanged_export_files(previous, docs))
+        .unwrap_or_default();
+    let stale = previous_manifest
+        .as_deref()
+        .map(|previous| stale_export_files(previous, docs))
+        .unwrap_or_default();
+
+    let mut written = Vec::new();
+    let mut skipped = Vec::new();
+    for doc in docs {
+        let file_path = output_dir.join(&doc.filename);
+        if unchanged.contains(&doc.filename) && file_path.is_file() {
+            skipped.push(doc.filename.clone());
+            continue;
+        }
+        if let Some(parent) = file_path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| AppError::FileSystem {
+                path: parent.to_string_lossy().to_string(),
+                message: format!("Failed to create export subdirectory: {}", e),
+            })?;
+        }
+        write_file_replacing(&file_path, doc.content.as_bytes())
+            .map_err(|e| export_write_error(&e, &file_path, &doc.filename))?;
+        written.push(doc.filename.clone());
+    }
+
+    // Remove stale files before the new manifest lands so a failed removal is
+    // retried on the next export instead of being forgotten.
+    let mut removed = Vec::new();
+    for filename in stale {
+        let file_path = output_dir.join(&filename);
+        match std::fs::remove_file(&file_path) {
+            Ok(()) => removed.push(filename),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => {
+                return Err(AppError::FileSystem {
+                    path: file_path.to_string_lossy().to_string(),
+                    message: format!("Failed to remove stale export file: {}", e),
+                })
+            }
+        }
+    }
+
+    let manifest_json =
+        serde_json::to_string_pretty(manifest).map_err(|e| AppError::FileSystem {
+            path: manifest_path.to_string_lossy().to_string(),
+            message: format!("Failed to serialize export manifest: {}", e),
+        })?;
+    write_file_replacing(&manifest_path, manifest_json.as_bytes())
+        .map_err(|e| export_write_error(&e, &manifest_path, EXPORT_MANIFEST_FILENAME))?;
+
+    Ok(IncrementalWrite {
+        written,
+        skipped,
+        removed,
+    })
+}
+
+/// Writes via a sibling temp file and rename so readers never see a half-written file.
+fn write_file_replacing(path: &std::path::Path, bytes: &[u8]) -> std::io::Result<()> {
+    let tmp_path = path.with_extension(format!("tmp_{}", uuid::Uuid::new_v4().simple()));
+    std::fs::write(&tmp_path, bytes)?;
+    std::fs::rename(&tmp_path, path).inspect_err(|_| {
+        let _ = std::fs::remove_file(&tmp_path);
+    })
+}
+
+fn export_write_error(e: &std::io::Error, path: &std::path::Path, filename: &str) -> AppError {
+    let path = path.to_string_lossy().to_string();
+    if e.raw_os_error() == Some(28) {
+        AppError::FileSystem {
+            path,
+            message: "Not enough disk space. Free up space and try again.".to_string(),
+        }
+    } else if e.kind() == std::io::ErrorKind::PermissionDenied {
+        AppError::FileSystem {
+            path,
+            message: format!(
+                "Permission denied writing {}. Choose another folder.",
+                filename
+            ),
+        }
+    } else {
+        AppError::FileSystem {
+            path,
+            message: format!("Failed to write {}: {}", filename, e),
+        }
+    }
+}
+
+fn ensure_supported_manifest_schema() -> Result<(), AppError> {
+    if is_supported_export_manifest_schema_version(EXPORT_MANIFEST_SCHEMA_VERSION) {
+        return Ok(());
+    }
+    Err(AppError::Validation(format!(
+        "Manifest schema v{} is outside supported compatibility range {}..={}.",
+        EXPORT_MANIFEST_SCHEMA_VERSION,
+        MIN_SUPPORTED_EXPORT_MANIFEST_SCHEMA_VERSION,
+        EXPORT_MANIFEST_SCHEMA_VERSION
+    )))
+}
+
+fn plan_export(
+    state: &AppState,
+    session_id: &str,
+    documents: Vec<GeneratedDocument>,
+    include_provenance: bool,
+    file_order: &[String],
+) -> Result<ExportPlan, AppError> {
+    let session = state.db.get_session(session_id)?;
+    let generation_meta = state.db.get_generation_metadata(session_id)?;
+    let messages = state.db.get_messages(session_id)?;
+    let import_context = messages.iter().rev().find_map(|message| {
+        message
+            .metadata
+            .as_deref()
+            .and_then(extract_import_summary_from_metadata)
+    });
+    let provenance = include_provenance.then(|| build_search_provenance(&messages));
+    let target = generation_meta
+        .as_ref()
+        .map(|meta| meta.target.as_str())
+        .unwrap_or("generic");
+    let lint_mode = LintMode::from_config(
+        &state
+            .config
+            .lock()
+            .map_err(|_| AppError::Config("Config lock poisoned".to_string()))?
+            .output
+            .lint_mode,
+    );
+    let export_documents = prepare_export_documents(&documents, target, lint_mode)?;
+    validate_export_file_order(file_order, &export_documents)?;
+    // Without an explicit order, fall back to the session's saved one, minus
+    // documents that have since been removed or renamed.
+    let stored_order;
+    let file_order = if file_order.is_empty() {
+        stored_order = stored_document_order(state, session_id)?
+            .into_iter()
+            .filter(|name| {
+                export_documents
+                    .iter()
+                    .any(|doc| export_basename(&doc.filename) == name.as_str())
+            })
+            .collect::<Vec<_>>();
+        stored_order.as_slice()
+    } else {
+        file_order
+    };
+
+    let folder_template = state
+        .config
+        .lock()
+        .map_err(|_| AppError::Config("Config lock poisoned".to_string()))?
+        .output
+        .export_folder_template
+        .clone();
+    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+    let folder_name = export_folder_name(&folder_template, &session.name, target, &today);
+
+    let manifest = ExportManifest {
+        schema_version: EXPORT_MANIFEST_SCHEMA_VERSION,
+        session_id: session_id.to_string(),
+        session_name: session.name.clone(),
+        target: target.to_string(),
+        run_id: generation_meta.as_ref().and_then(|m| m.run_id.clone()),
+        export_preset: preset_label(target).to_string(),
+        provider: generation_meta
+            .as_ref()
+            .map(|m| m.provider.clone())
+            .unwrap_or_else(|| "ollama".to_string()),
+        model: generation_meta
+            .as_ref()
+            .map(|m| m.model.clone())
+            .unwrap_or_else(|| "unknown".to_string()),
+        created_at: timestamp::now(),
+        quality: generation_meta
+            .as_ref()
+            .and_then(|m| m.quality_json.as_ref())
+            .and_then(|q| serde_json::from_str::<QualityReport>(q).ok()),
+        confidence: generation_meta
+            .as_ref()
+            .and_then(|m| m.confidence_json.as_ref())
+            .and_then(|q| serde_json::from_str::<ConfidenceReport>(q).ok()),
+        import_context,
+        provenance,
+        files: build_export_manifest_files(&export_documents, file_order),
+    };
+
+    Ok(ExportPlan {
+        folder_name,
+        documents: export_documents,
+        manifest,
+    })
+}
+
+fn build_export_preview(plan: &ExportPlan) -> Result<ExportPreview, AppError> {
+    let manifest_json = serde_json::to_string_pretty(&plan.manifest)
+        .map_err(|e| AppError::Validation(format!("Failed to serialize export manifest: {}", e)))?;
+    let mut files = plan.manifest.files.clone();
+    files.extend(build_export_manifest_files(
+        &[ExportDocument {
+            filename: EXPORT_MANIFEST_FILENAME.to_string(),
+            content: manifest_json,
+        }],
+        &[],
+    ));
+    let total_bytes = files.iter().map(|file| file.bytes).sum();
+
+    Ok(ExportPreview {
+        folder_name: plan.folder_name.clone(),
+        files,
+        total_bytes,
+    })
+}
+
+/// Written by every export from the target alone, so never stored as a document.
+const EXECUTION_CHECKLIST_EXPORT_PATH: &str = "handoff/EXECUTION_CHECKLIST.md";
+
 fn prepare_export_documents(
     docs: &[GeneratedDocument],
     target: &str,
+    lint_mode: LintMode,
 ) -> Result<Vec<ExportDocument>, AppError> {
     let mut exports = docs
         .iter()
@@ -1456,18 +3538,21 @@ fn prepare_export_documents(
         })
         .collect::<Result<Vec<_>, AppError>>()?;
 
-    let checklist_path = "handoff/EXECUTION_CHECKLIST.md";
+    let checklist_path = EXECUTION_CHECKLIST_EXPORT_PATH;
     validate_export_path(checklist_path)?;
     exports.push(ExportDocument {
         filename: checklist_path.to_string(),
         content: build_execution_checklist_doc(target),
     });
-    ensure_required_export_reports(&mut exports)?;
+    ensure_required_export_reports(&mut exports, lint_mode)?;
 
     Ok(exports)
 }
 
-fn ensure_required_export_reports(exports: &mut Vec<ExportDocument>) -> Result<(), AppError> {
+fn ensure_required_export_reports(
+    exports: &mut Vec<ExportDocument>,
+    lint_mode: LintMode,
+) -> Result<(), AppError> {
     let existing = exports
         .iter()
         .map(|doc| doc.filename.clone())
@@ -1480,15 +3565,18 @@ fn ensure_required_export_reports(exports: &mut Vec<ExportDocument>) -> Result<(
         validate_export_path(required_path)?;
         exports.push(ExportDocument {
             filename: (*required_path).to_string(),
-            content: build_missing_report_placeholder(required_path),
+            content: build_missing_report_placeholder(required_path, lint_mode),
         });
     }
 
     Ok(())
 }
 
-fn build_missing_report_placeholder(path: &str) -> String {
+fn build_missing_report_placeholder(path: &str, lint_mode: LintMode) -> String {
     match path {
+        "reports/LINT_REPORT.md" if lint_mode == LintMode::Off => String::from(
+            "# Lint Report (Backfilled)\n\nLinting is disabled (`output.lint_mode: off`), so no SpecLint/PromptLint report was produced for this run.\n",
+        ),
         "reports/LINT_REPORT.md" => String::from(
             "# Lint Report (Backfilled)\n\nNo lint report was stored for this run. Regenerate the plan to produce a full SpecLint/PromptLint report.\n",
         ),
@@ -1596,7 +3684,10 @@ fn validate_source_filename(filename: &str) -> Result<(), AppError> {
     Ok(())
 }
 
-fn build_export_manifest_files(docs: &[ExportDocument]) -> Vec<ExportManifestFile> {
+fn build_export_manifest_files(
+    docs: &[ExportDocument],
+    file_order: &[String],
+) -> Vec<ExportManifestFile> {
     let mut files: Vec<ExportManifestFile> = docs
         .iter()
         .map(|doc| ExportManifestFile {
@@ -1612,8 +3703,8 @@ fn build_export_manifest_files(docs: &[ExportDocument]) -> Vec<ExportManifestFil
         .collect();
 
     files.sort_by(|a, b| {
-        let rank_a = export_file_rank(&a.filename);
-        let rank_b = export_file_rank(&b.filename);
+        let rank_a = export_file_rank(&a.filename, file_order);
+        let rank_b = export_file_rank(&b.filename, file_order);
         rank_a
             .cmp(&rank_b)
             .then_with(|| a.filename.cmp(&b.filename))
@@ -1622,15 +3713,52 @@ fn build_export_manifest_files(docs: &[ExportDocument]) -> Vec<ExportManifestFil
     files
 }
 
-fn export_file_rank(filename: &str) -> usize {
-    let basename = std::path::Path::new(filename)
+fn export_file_rank(filename: &str, file_order: &[String]) -> usize {
+    let basename = export_basename(filename);
+    if let Some(position) = file_order
+        .iter()
+        .position(|wanted| export_basename(wanted) == basename)
+    {
+        return position;
+    }
+    file_order.len()
+        + EXPORT_FILE_ORDER
+            .iter()
+            .position(|known| known == &basename)
+            .unwrap_or(EXPORT_FILE_ORDER.len())
+}
+
+fn export_basename(filename: &str) -> &str {
+    std::path::Path::new(filename.trim())
         .file_name()
         .and_then(|value| value.to_str())
-        .unwrap_or(filename);
-    EXPORT_FILE_ORDER
-        .iter()
-        .position(|known| known == &basename)
-        .unwrap_or(EXPORT_FILE_ORDER.len())
+        .unwrap_or(filename)
+}
+
+fn validate_export_file_order(
+    file_order: &[String],
+    docs: &[ExportDocument],
+) -> Result<(), AppError> {
+    let mut seen = std::collections::HashSet::new();
+    for wanted in file_order {
+        let basename = export_basename(wanted);
+        if !docs
+            .iter()
+            .any(|doc| export_basename(&doc.filename) == basename)
+        {
+            return Err(AppError::Validation(format!(
+                "Unknown file '{}' in export order.",
+                wanted.trim()
+            )));
+        }
+        if !seen.insert(basename) {
+            return Err(AppError::Validation(format!(
+                "File '{}' appears more than once in export order.",
+                wanted.trim()
+            )));
+        }
+    }
+    Ok(())
 }
 
 fn sha256_hex(bytes: &[u8]) -> String {
@@ -1640,91 +3768,825 @@ fn sha256_hex(bytes: &[u8]) -> String {
     digest.iter().map(|b| format!("{:02x}", b)).collect()
 }
 
-fn build_input_fingerprint(
-    messages: &[Message],
-    target: &ForgeTarget,
-    config: &AppConfig,
-) -> String {
-    let mut material = format!(
-        "target={};provider={};model={};temperature={};max_tokens={};",
-        target.as_str(),
-        config.llm.provider,
-        config.llm.model,
-        config.llm.temperature,
-        config.llm.max_tokens
-    );
-    for message in messages {
-        if message.role == "system" {
-            continue;
+fn build_input_fingerprint(
+    messages: &[Message],
+    target: &ForgeTarget,
+    config: &AppConfig,
+) -> String {
+    let mut material = format!(
+        "target={};provider={};model={};temperature={};max_tokens={};",
+        target.as_str(),
+        config.llm.provider,
+        config.llm.model,
+        config.llm.temperature,
+        config.llm.max_tokens
+    );
+    if let Some(seed) = config.llm.seed {
+        material.push_str(&format!("seed={};", seed));
+    }
+    material.push_str(&conversation_fingerprint_material(messages));
+    sha256_hex(material.as_bytes())
+}
+
+/// sha256 over the non-system turns only, so it changes exactly when the
+/// conversation a forge would read changes.
+fn build_conversation_fingerprint(messages: &[Message]) -> String {
+    sha256_hex(conversation_fingerprint_material(messages).as_bytes())
+}
+
+fn conversation_fingerprint_material(messages: &[Message]) -> String {
+    messages
+        .iter()
+        .filter(|message| message.role != "system")
+        .map(|message| format!("{}:{}|", message.role, message.content))
+        .collect()
+}
+
+fn build_generation_run_artifacts(
+    run_id: &str,
+    docs: &[GeneratedDocument],
+) -> Vec<GenerationRunArtifact> {
+    let mut artifacts = docs
+        .iter()
+        .map(|doc| GenerationRunArtifact {
+            run_id: run_id.to_string(),
+            filename: doc.filename.clone(),
+            bytes: doc.content.len(),
+            lines: if doc.content.is_empty() {
+                0
+            } else {
+                doc.content.lines().count()
+            },
+            sha256: sha256_hex(doc.content.as_bytes()),
+        })
+        .collect::<Vec<_>>();
+    artifacts.sort_by(|a, b| a.filename.cmp(&b.filename));
+    artifacts
+}
+
+fn check_char_limit(label: &str, value: &str, max_chars: usize) -> Result<(), AppError> {
+    // Byte length is an upper bound on char count; skip counting short values.
+    if value.len() <= max_chars || value.chars().count() <= max_chars {
+        return Ok(());
+    }
+    Err(AppError::Validation(format!(
+        "{} too long (max {} characters).",
+        label, max_chars
+    )))
+}
+
+fn auto_session_name(content: &str) -> String {
+    let auto_name: String = content.chars().take(60).collect();
+    let auto_name = auto_name.trim().to_string();
+    if content.chars().count() > 60 {
+        format!("{}...", auto_name.trim_end())
+    } else {
+        auto_name
+    }
+}
+
+#[tauri::command(rename_all = "snake_case")]
+pub async fn preview_chat_request(
+    state: State<'_, AppState>,
+    session_id: String,
+    content: String,
+    run_search: Option<bool>,
+) -> Result<Vec<ChatMessage>, ErrorResponse> {
+    check_char_limit("Message", &content, MAX_MESSAGE_CHARS).map_err(to_response)?;
+    match state.db.get_session(&session_id) {
+        Ok(_) => {}
+        Err(rusqlite::Error::QueryReturnedNoRows) => {
+            return Err(to_response(AppError::SessionNotFound(session_id)))
+        }
+        Err(e) => return Err(to_response(e)),
+    }
+
+    let config = state
+        .config
+        .lock()
+        .map_err(|_| to_response(AppError::Config("Config lock poisoned".to_string())))?
+        .clone();
+
+    let mut search_query = None;
+    let mut search_results = None;
+    if run_search.unwrap_or(false) && config.search.enabled && config.search.proactive {
+        if let Some(query) = search::should_search(&content, &config.search.trigger_overrides) {
+            match search::execute_search(&config.search, &query).await {
+                Ok(results) => search_results = Some(results),
+                Err(e) => log::warn!("Preview search failed (continuing without): {}", e),
+            }
+            search_query = Some(query);
+        }
+    }
+
+    let mut messages = state.db.get_messages(&session_id).map_err(to_response)?;
+    messages.push(Message {
+        id: "preview".to_string(),
+        session_id,
+        role: "user".to_string(),
+        content,
+        metadata: None,
+        created_at: timestamp::now(),
+    });
+
+    let mut request = assemble_chat_messages(
+        &messages,
+        search_query.as_deref(),
+        search_results.as_deref(),
+    );
+    trim_history_to_budget(&mut request, config.llm.context_tokens);
+    Ok(request)
+}
+
+const MESSAGE_USAGE_KEY: &str = "usage";
+
+fn session_token_usage(session_id: String, messages: &[Message]) -> SessionTokenUsage {
+    let mut totals = SessionTokenUsage {
+        session_id,
+        ..Default::default()
+    };
+    for message in messages {
+        let Some(usage) = message
+            .metadata
+            .as_deref()
+            .and_then(|raw| serde_json::from_str::<serde_json::Value>(raw).ok())
+            .and_then(|meta| {
+                serde_json::from_value::<UsageStats>(meta.get(MESSAGE_USAGE_KEY)?.clone()).ok()
+            })
+        else {
+            continue;
+        };
+        let prompt = usage.prompt_tokens.unwrap_or(0);
+        let completion = usage.completion_tokens.unwrap_or(0);
+        totals.prompt_tokens += prompt;
+        totals.completion_tokens += completion;
+        totals.total_tokens += usage.total_tokens.unwrap_or(prompt + completion);
+        totals.messages_counted += 1;
+    }
+    totals
+}
+
+/// Metadata stored with an assistant reply: the parameters that produced it and
+/// any search context it was grounded on.
+fn assistant_message_metadata(
+    llm: &LLMConfig,
+    search_query: Option<&str>,
+    search_results: Option<&[SearchResult]>,
+    usage: Option<&UsageStats>,
+) -> serde_json::Value {
+    let mut meta = serde_json::json!({
+        "model_used": llm.model,
+        "provider": llm.provider,
+        "temperature": llm.temperature,
+        "search_used": search_results.is_some(),
+    });
+    if let Some(usage) = usage {
+        meta[MESSAGE_USAGE_KEY] = serde_json::json!(usage);
+    }
+    if search_query.is_some() || search_results.is_some() {
+        meta["search_query"] = serde_json::json!(search_query);
+        meta["search_results"] = serde_json::json!(search_results);
+        meta["citations"] =
+            serde_json::json!(search::assign_citations(search_results.unwrap_or_default()));
+    }
+    meta
+}
+
+fn latest_import_message_id(messages: &[Message]) -> Option<String> {
+    messages
+        .iter()
+        .rev()
+        .filter(|message| message.role == "assistant")
+        .find(|message| {
+            message
+                .metadata
+                .as_deref()
+                .and_then(extract_import_summary_from_metadata)
+                .is_some()
+        })
+        .map(|message| message.id.clone())
+}
+
+fn extract_import_summary_from_metadata(metadata: &str) -> Option<CodebaseImportSummary> {
+    let value = serde_json::from_str::<serde_json::Value>(metadata).ok()?;
+    serde_json::from_value::<CodebaseImportSummary>(value.get("import_summary")?.clone()).ok()
+}
+
+fn build_search_provenance(messages: &[Message]) -> Vec<ProvenanceEntry> {
+    messages
+        .iter()
+        .filter_map(|message| {
+            let value =
+                serde_json::from_str::<serde_json::Value>(message.metadata.as_deref()?).ok()?;
+            let search_query = value
+                .get("search_query")
+                .and_then(|query| query.as_str())
+                .map(str::to_string);
+            let sources = value
+                .get("search_results")
+                .cloned()
+                .and_then(|results| serde_json::from_value::<Vec<SearchResult>>(results).ok())
+                .unwrap_or_default()
+                .into_iter()
+                .map(|result| ProvenanceSource {
+                    title: result.title,
+                    url: result.url,
+                })
+                .collect::<Vec<_>>();
+            if search_query.is_none() && sources.is_empty() {
+                return None;
+            }
+            Some(ProvenanceEntry {
+                message_id: message.id.clone(),
+                created_at: message.created_at.clone(),
+                search_query,
+                sources,
+            })
+        })
+        .collect()
+}
+
+/// Branch notes are dropped and codebase-import reports are cut down to their
+/// overview; both remain untouched in the UI and CONVERSATION.md.
+fn llm_history_content(message: &Message) -> Option<String> {
+    let Some(metadata) = message.metadata.as_deref() else {
+        return Some(message.content.clone());
+    };
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(metadata) else {
+        return Some(message.content.clone());
+    };
+
+    if value.get("branch_source_session_id").is_some() {
+        return None;
+    }
+    if value.get("import_summary").is_some() {
+        return Some(match extract_import_summary_from_metadata(metadata) {
+            Some(summary) => format!(
+                "Imported codebase context from `{}` (stacks: {}).\n\n{}",
+                summary.root_path,
+                if summary.detected_stacks.is_empty() {
+                    "unknown".to_string()
+                } else {
+                    summary.detected_stacks.join(", ")
+                },
+                summary.summary_markdown.trim()
+            ),
+            None => message.content.clone(),
+        });
+    }
+
+    Some(message.content.clone())
+}
+
+const CONVERSATION_SUMMARY_KEY: &str = "conversation_summary";
+
+const COMPACT_PROMPT: &str = "You condense software planning conversations. Summarize the \
+transcript below so the planning can continue without it. Keep every decision, requirement, \
+constraint, chosen technology, open question and rejected alternative, with the reasoning \
+where it was given. Drop pleasantries and repetition. Write concise Markdown bullet points \
+grouped by topic; do not add anything that was not discussed.";
+
+fn latest_conversation_summary(messages: &[Message]) -> Option<(usize, &Message)> {
+    messages.iter().rev().find_map(|message| {
+        if message.role != "system" {
+            return None;
+        }
+        let metadata =
+            serde_json::from_str::<serde_json::Value>(message.metadata.as_deref()?).ok()?;
+        let covers_through = metadata
+            .get(CONVERSATION_SUMMARY_KEY)?
+            .get("covers_through")?
+            .as_str()?;
+        let index = messages.iter().position(|m| m.id == covers_through)?;
+        Some((index, message))
+    })
+}
+
+fn compaction_candidates(
+    messages: &[Message],
+    keep_recent: usize,
+) -> (Option<&Message>, Vec<&Message>) {
+    let (start, previous) = match latest_conversation_summary(messages) {
+        Some((index, summary)) => (index + 1, Some(summary)),
+        None => (0, None),
+    };
+    let turns = messages[start..]
+        .iter()
+        .filter(|message| message.role != "system")
+        .collect::<Vec<_>>();
+    let foldable = turns.len().saturating_sub(keep_recent);
+    (previous, turns.into_iter().take(foldable).collect())
+}
+
+fn assemble_chat_messages(
+    messages: &[Message],
+    search_query: Option<&str>,
+    search_results: Option<&[SearchResult]>,
+) -> Vec<ChatMessage> {
+    let mut chat_messages = vec![ChatMessage {
+        role: "system".to_string(),
+        content: SYSTEM_PROMPT.to_string(),
+    }];
+
+    // Inject search context as a system message if we have results
+    if let Some(results) = search_results {
+        chat_messages.push(ChatMessage {
+            role: "system".to_string(),
+            content: build_search_context(search_query.unwrap_or(""), results),
+        });
+    }
+
+    chat_messages.extend(build_chat_history(messages));
+    chat_messages
+}
+
+const CHAT_CHARS_PER_TOKEN: usize = 4;
+
+/// System messages and the latest user message always stay, even if they alone
+/// exceed the budget. A budget of 0 keeps everything.
+fn trim_history_to_budget(messages: &mut Vec<ChatMessage>, context_tokens: u64) -> usize {
+    if context_tokens == 0 {
+        return 0;
+    }
+    let budget = usize::try_from(context_tokens)
+        .unwrap_or(usize::MAX)
+        .saturating_mul(CHAT_CHARS_PER_TOKEN);
+    let mut total = messages
+        .iter()
+        .map(|message| message.content.chars().count())
+        .sum::<usize>();
+    let latest_user = messages.iter().rposition(|message| message.role == "user");
+
+    let mut dropped = 0;
+    let mut index = 0;
+    while total > budget && index < messages.len() {
+        // Everything removed so far sat before `index`.
+        if messages[index].role == "system" || Some(index + dropped) == latest_user {
+            index += 1;
+            continue;
+        }
+        total -= messages.remove(index).content.chars().count();
+        dropped += 1;
+    }
+    dropped
+}
+
+/// Conversation history for the model: the newest summary in place of the
+/// turns it covers, then every later non-system message.
+fn build_chat_history(messages: &[Message]) -> Vec<ChatMessage> {
+    let mut history = Vec::new();
+    let start = match latest_conversation_summary(messages) {
+        Some((index, summary)) => {
+            history.push(ChatMessage {
+                role: "system".to_string(),
+                content: format!(
+                    "Summary of the earlier conversation:\n\n{}",
+                    summary.content
+                ),
+            });
+            index + 1
+        }
+        None => 0,
+    };
+
+    for msg in &messages[start..] {
+        if msg.role == "system" {
+            continue;
+        }
+        let Some(content) = llm_history_content(msg) else {
+            continue;
+        };
+        history.push(ChatMessage {
+            role: msg.role.clone(),
+            content,
+        });
+    }
+    history
+}
+
+/// Marker ids match `search::assign_citations`.
+fn build_search_context(query: &str, results: &[SearchResult]) -> String {
+    let mut context = format!(
+        "## Web Search Results\nThe following search results were found for \"{}\":\n\n",
+        query
+    );
+
+    for (result, citation) in results.iter().zip(search::assign_citations(results)) {
+        context.push_str(&format!(
+            "[^{}] **{}**\n   URL: {}\n   {}\n\n",
+            citation.id, result.title, result.url, result.snippet
+        ));
+    }
+
+    context.push_str(
+        "Use these search results to inform your response where relevant. \
+         When a statement relies on a result, put that result's footnote marker \
+         right after it, e.g. `SQLite handles this well[^1].` Only use the markers \
+         listed above, and do not write footnote definitions; they are added automatically.",
+    );
+
+    context
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn doc(filename: &str, content: &str) -> GeneratedDocument {
+        GeneratedDocument {
+            id: "doc-id".to_string(),
+            session_id: "session-id".to_string(),
+            filename: filename.to_string(),
+            content: content.to_string(),
+            created_at: "2026-01-01 00:00:00".to_string(),
+        }
+    }
+
+    fn message(content: &str, metadata: Option<serde_json::Value>) -> Message {
+        Message {
+            id: "msg-id".to_string(),
+            session_id: "session-id".to_string(),
+            role: "assistant".to_string(),
+            content: content.to_string(),
+            metadata: metadata.map(|value| value.to_string()),
+            created_at: "2026-01-01 00:00:00".to_string(),
+        }
+    }
+
+    #[test]
+    fn conversation_fingerprint_ignores_system_messages_and_tracks_content() {
+        let mut system = message("Seeded template prompt", None);
+        system.role = "system".to_string();
+        let reply = message("Let's use SQLite.", None);
+        let baseline = build_conversation_fingerprint(std::slice::from_ref(&reply));
+
+        assert_eq!(baseline.len(), 64);
+        assert_eq!(
+            build_conversation_fingerprint(&[system, reply.clone()]),
+            baseline
+        );
+        assert_ne!(
+            build_conversation_fingerprint(&[message("Let's use Postgres.", None)]),
+            baseline
+        );
+
+        let config = AppConfig::default();
+        assert_ne!(
+            build_input_fingerprint(&[reply], &ForgeTarget::Generic, &config),
+            baseline
+        );
+    }
+
+    #[test]
+    fn char_limits_count_characters_not_bytes() {
+        let cjk_name = "名".repeat(MAX_SESSION_NAME_CHARS);
+        assert!(cjk_name.len() > MAX_SESSION_NAME_CHARS);
+        assert!(check_char_limit("Session name", &cjk_name, MAX_SESSION_NAME_CHARS).is_ok());
+
+        let over = format!("{}é", cjk_name);
+        let err = check_char_limit("Session name", &over, MAX_SESSION_NAME_CHARS)
+            .expect_err("201 characters should be rejected");
+        assert!(err.to_string().contains("max 200 characters"));
+
+        let emoji_message = "🚀".repeat(MAX_MESSAGE_CHARS);
+        assert!(check_char_limit("Message", &emoji_message, MAX_MESSAGE_CHARS).is_ok());
+        assert!(
+            check_char_limit("Message", &format!("{}x", emoji_message), MAX_MESSAGE_CHARS).is_err()
+        );
+    }
+
+    #[test]
+    fn auto_session_name_truncates_long_messages() {
+        assert_eq!(
+            auto_session_name("  Build a todo app  "),
+            "Build a todo app"
+        );
+        let long = format!("{} tail", "word ".repeat(20));
+        let name = auto_session_name(&long);
+        assert!(name.ends_with("..."));
+        assert_eq!(name.trim_end_matches("...").chars().count(), 59);
+    }
+
+    #[test]
+    fn assistant_metadata_records_generation_parameters() {
+        let llm = AppConfig::default().llm;
+        let meta = assistant_message_metadata(&llm, None, None, None);
+        assert_eq!(meta["model_used"], llm.model.as_str());
+        assert_eq!(meta["provider"], llm.provider.as_str());
+        assert_eq!(meta["search_used"], false);
+        assert!(meta.get("search_query").is_none());
+        assert!(meta.get(MESSAGE_USAGE_KEY).is_none());
+
+        let results = vec![SearchResult {
+            title: "Tauri docs".to_string(),
+            url: "https://tauri.app".to_string(),
+            snippet: String::new(),
+            score: 1.0,
+        }];
+        let meta = assistant_message_metadata(&llm, Some("tauri 2"), Some(&results), None);
+        assert_eq!(meta["search_used"], true);
+        assert_eq!(meta["search_query"], "tauri 2");
+        assert_eq!(meta["search_results"].as_array().map(Vec::len), Some(1));
+        assert_eq!(
+            meta["citations"],
+            serde_json::json!([{ "id": "1", "title": "Tauri docs", "url": "https://tauri.app" }])
+        );
+    }
+
+    #[test]
+    fn session_token_usage_sums_reply_metadata() {
+        let llm = AppConfig::default().llm;
+        let reply = |prompt, completion| {
+            let usage = UsageStats {
+                prompt_tokens: Some(prompt),
+                completion_tokens: Some(completion),
+                total_tokens: Some(prompt + completion),
+            };
+            message(
+                "reply",
+                Some(assistant_message_metadata(&llm, None, None, Some(&usage))),
+            )
+        };
+        let messages = vec![
+            reply(100, 40),
+            message(
+                "no usage reported",
+                Some(serde_json::json!({ "model_used": "x" })),
+            ),
+            message("plain", None),
+            reply(180, 60),
+        ];
+
+        let usage = session_token_usage("session-id".to_string(), &messages);
+        assert_eq!(usage.prompt_tokens, 280);
+        assert_eq!(usage.completion_tokens, 100);
+        assert_eq!(usage.total_tokens, 380);
+        assert_eq!(usage.messages_counted, 2);
+    }
+
+    #[test]
+    fn assembled_request_puts_search_context_between_prompt_and_history() {
+        let mut user = message("Should I use SQLite?", None);
+        user.role = "user".to_string();
+        let history = vec![message("What are you building?", None), user];
+        let results = vec![SearchResult {
+            title: "SQLite docs".to_string(),
+            url: "https://sqlite.org".to_string(),
+            snippet: String::new(),
+            score: 1.0,
+        }];
+
+        let request = assemble_chat_messages(&history, Some("sqlite"), Some(&results));
+        let roles = request
+            .iter()
+            .map(|message| message.role.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(roles, vec!["system", "system", "assistant", "user"]);
+        assert_eq!(request[0].content, SYSTEM_PROMPT);
+        assert!(request[1].content.contains("[^1] **SQLite docs**"));
+        assert_eq!(request[3].content, "Should I use SQLite?");
+
+        let without_search = assemble_chat_messages(&history, None, None);
+        assert_eq!(without_search.len(), 3);
+        assert_eq!(without_search[1].content, "What are you building?");
+    }
+
+    #[test]
+    fn history_trimming_drops_oldest_turns_first() {
+        let turn = |role: &str, chars: usize| ChatMessage {
+            role: role.to_string(),
+            content: "x".repeat(chars),
+        };
+        let mut request = vec![
+            turn("system", 2000),
+            turn("user", 2000),
+            turn("assistant", 2000),
+            turn("user", 2000),
+            turn("assistant", 2000),
+            turn("user", 400),
+        ];
+
+        // 1500 tokens is ~6000 chars: the prompt, the latest exchange and the
+        // new message fit.
+        let dropped = trim_history_to_budget(&mut request, 1500);
+        assert_eq!(dropped, 3);
+        let roles = request
+            .iter()
+            .map(|message| message.role.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(roles, vec!["system", "assistant", "user"]);
+
+        // The system prompt and the latest user message survive any budget.
+        let dropped = trim_history_to_budget(&mut request, 1);
+        assert_eq!(dropped, 1);
+        assert_eq!(request.len(), 2);
+        assert_eq!(request[1].content.len(), 400);
+
+        assert_eq!(trim_history_to_budget(&mut request, 0), 0);
+    }
+
+    #[test]
+    fn search_context_labels_results_with_footnote_markers() {
+        let results = vec![
+            SearchResult {
+                title: "SQLite docs".to_string(),
+                url: "https://sqlite.org".to_string(),
+                snippet: "Small. Fast. Reliable.".to_string(),
+                score: 0.9,
+            },
+            SearchResult {
+                title: "Postgres docs".to_string(),
+                url: "https://postgresql.org".to_string(),
+                snippet: "The world's most advanced open source database.".to_string(),
+                score: 0.8,
+            },
+        ];
+        let context = build_search_context("sqlite vs postgres", &results);
+
+        assert!(context.contains(
+            "[^1] **SQLite docs**\n   URL: https://sqlite.org\n   Small. Fast. Reliable.\n\n"
+        ));
+        assert!(context.contains("[^2] **Postgres docs**\n   URL: https://postgresql.org\n"));
+        assert!(context.contains("SQLite handles this well[^1]."));
+        assert!(!context.contains("[^3]"));
+    }
+
+    #[test]
+    fn search_provenance_lists_queries_and_sources() {
+        let grounded = message(
+            "Use SQLite.",
+            Some(serde_json::json!({
+                "search_query": "sqlite vs postgres",
+                "search_results": [{
+                    "title": "SQLite docs",
+                    "url": "https://sqlite.org",
+                    "snippet": "Small. Fast. Reliable.",
+                    "score": 0.9
+                }],
+            })),
+        );
+        let branch_note = message(
+            "Branch created.",
+            Some(serde_json::json!({ "branch_source_session_id": "parent" })),
+        );
+        let provenance = build_search_provenance(&[message("Plain", None), branch_note, grounded]);
+
+        assert_eq!(provenance.len(), 1);
+        assert_eq!(
+            provenance[0].search_query.as_deref(),
+            Some("sqlite vs postgres")
+        );
+        assert_eq!(provenance[0].sources.len(), 1);
+        assert_eq!(provenance[0].sources[0].url, "https://sqlite.org");
+    }
+
+    fn sample_import_summary() -> CodebaseImportSummary {
+        CodebaseImportSummary {
+            root_path: "/repo".to_string(),
+            files_scanned: 10,
+            files_included: 5,
+            total_bytes_read: 1024,
+            detected_stacks: vec!["Rust crate".to_string()],
+            key_files: vec!["Cargo.toml".to_string()],
+            summary_markdown: "## Overview\nA CLI tool.".to_string(),
+            architecture_summary_markdown: "## Architecture\nLong details".to_string(),
+            risks_gaps_markdown: String::new(),
+            phased_plan_markdown: String::new(),
+            verification_plan_markdown: String::new(),
+            citations: Vec::new(),
         }
-        material.push_str(&format!("{}:{}|", message.role, message.content));
     }
-    sha256_hex(material.as_bytes())
-}
 
-fn build_generation_run_artifacts(
-    run_id: &str,
-    docs: &[GeneratedDocument],
-) -> Vec<GenerationRunArtifact> {
-    let mut artifacts = docs
-        .iter()
-        .map(|doc| GenerationRunArtifact {
-            run_id: run_id.to_string(),
-            filename: doc.filename.clone(),
-            bytes: doc.content.len(),
-            lines: if doc.content.is_empty() {
-                0
-            } else {
-                doc.content.lines().count()
-            },
-            sha256: sha256_hex(doc.content.as_bytes()),
-        })
-        .collect::<Vec<_>>();
-    artifacts.sort_by(|a, b| a.filename.cmp(&b.filename));
-    artifacts
-}
+    #[test]
+    fn latest_import_message_is_found_among_other_messages() {
+        let import_meta = serde_json::json!({ "import_summary": sample_import_summary() });
+        let mut older = message("first import", Some(import_meta.clone()));
+        older.id = "import-1".to_string();
+        let mut newer = message("second import", Some(import_meta));
+        newer.id = "import-2".to_string();
+        let reply = message("Plain reply", None);
 
-fn extract_import_summary_from_metadata(metadata: &str) -> Option<CodebaseImportSummary> {
-    let value = serde_json::from_str::<serde_json::Value>(metadata).ok()?;
-    serde_json::from_value::<CodebaseImportSummary>(value.get("import_summary")?.clone()).ok()
-}
+        assert_eq!(
+            latest_import_message_id(&[older.clone(), newer, reply.clone()]).as_deref(),
+            Some("import-2")
+        );
+        assert_eq!(
+            latest_import_message_id(&[older, reply.clone()]).as_deref(),
+            Some("import-1")
+        );
+        assert!(latest_import_message_id(&[reply]).is_none());
+    }
 
-fn build_search_context(query: &str, results: &[SearchResult]) -> String {
-    let mut context = format!(
-        "## Web Search Results\nThe following search results were found for \"{}\":\n\n",
-        query
-    );
+    #[test]
+    fn llm_history_skips_branch_notes_and_condenses_import_reports() {
+        let branch_note = message(
+            "Branch created.",
+            Some(serde_json::json!({ "branch_source_session_id": "parent" })),
+        );
+        assert!(llm_history_content(&branch_note).is_none());
 
-    for (i, result) in results.iter().enumerate() {
-        context.push_str(&format!(
-            "{}. **{}**\n   URL: {}\n   {}\n\n",
-            i + 1,
-            result.title,
-            result.url,
-            result.snippet
-        ));
+        let summary = sample_import_summary();
+        let import = message(
+            "## Overview\nA CLI tool.\n\n## Architecture\nLong details",
+            Some(serde_json::json!({ "import_summary": summary })),
+        );
+        let condensed = llm_history_content(&import).expect("import kept in condensed form");
+        assert!(condensed.contains("`/repo`"));
+        assert!(condensed.contains("Rust crate"));
+        assert!(condensed.contains("A CLI tool."));
+        assert!(!condensed.contains("Long details"));
+
+        let search = message(
+            "Use SQLite.",
+            Some(serde_json::json!({ "search_query": "sqlite vs postgres" })),
+        );
+        assert_eq!(llm_history_content(&search).as_deref(), Some("Use SQLite."));
+        assert_eq!(
+            llm_history_content(&message("Plain", None)).as_deref(),
+            Some("Plain")
+        );
     }
 
-    context.push_str(
-        "Use these search results to inform your response where relevant. \
-         Cite sources when referencing specific information.",
-    );
+    #[test]
+    fn conversation_summary_replaces_covered_turns_in_history() {
+        let turn = |id: &str, role: &str| Message {
+            id: id.to_string(),
+            role: role.to_string(),
+            ..message(id, None)
+        };
+        let mut messages = (1..=6)
+            .map(|i| {
+                turn(
+                    &format!("m{}", i),
+                    if i % 2 == 1 { "user" } else { "assistant" },
+                )
+            })
+            .collect::<Vec<_>>();
 
-    context
-}
+        let (previous, foldable) = compaction_candidates(&messages, 2);
+        assert!(previous.is_none());
+        assert_eq!(
+            foldable.iter().map(|m| m.id.as_str()).collect::<Vec<_>>(),
+            vec!["m1", "m2", "m3", "m4"]
+        );
+        assert!(compaction_candidates(&messages, 6).1.is_empty());
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        messages.push(Message {
+            id: "summary".to_string(),
+            role: "system".to_string(),
+            ..message(
+                "Decided on SQLite.",
+                Some(serde_json::json!({
+                    CONVERSATION_SUMMARY_KEY: { "covers_through": "m4", "summarized_messages": 4 }
+                })),
+            )
+        });
+        messages.push(turn("m7", "user"));
 
-    fn doc(filename: &str, content: &str) -> GeneratedDocument {
-        GeneratedDocument {
-            id: "doc-id".to_string(),
-            session_id: "session-id".to_string(),
-            filename: filename.to_string(),
-            content: content.to_string(),
-            created_at: "2026-01-01 00:00:00".to_string(),
-        }
+        let history = build_chat_history(&messages);
+        assert_eq!(history.len(), 4);
+        assert_eq!(history[0].role, "system");
+        assert!(history[0].content.ends_with("Decided on SQLite."));
+        assert_eq!(
+            history[1..]
+                .iter()
+                .map(|m| m.content.as_str())
+                .collect::<Vec<_>>(),
+            vec!["m5", "m6", "m7"]
+        );
+
+        let (previous, foldable) = compaction_candidates(&messages, 2);
+        assert_eq!(previous.map(|m| m.id.as_str()), Some("summary"));
+        assert_eq!(foldable.len(), 1);
+        assert_eq!(foldable[0].id, "m5");
+    }
+
+    #[test]
+    fn export_folder_name_applies_template_placeholders() {
+        assert_eq!(
+            export_folder_name("{name}-plan", "My App: v2", "claude", "2026-10-15"),
+            "my-app_-v2-plan"
+        );
+        assert_eq!(
+            export_folder_name("{date}_{name}", "Task Tracker", "codex", "2026-10-15"),
+            "2026-10-15_task-tracker"
+        );
+        assert_eq!(
+            export_folder_name("{name} ({target})", "CLI", "cursor", "2026-10-15"),
+            "cli-_cursor_"
+        );
+    }
+
+    #[test]
+    fn export_folder_name_falls_back_to_untitled() {
+        assert_eq!(
+            export_folder_name("../", "ignored", "generic", "2026-10-15"),
+            "untitled"
+        );
+        assert_eq!(
+            export_folder_name("{name}", "???", "generic", "2026-10-15"),
+            "untitled"
+        );
     }
 
     #[test]
@@ -1737,9 +4599,10 @@ mod tests {
                 doc("A_CUSTOM.md", "custom"),
             ],
             "codex",
+            LintMode::FailOnCritical,
         )
         .expect("export docs should validate");
-        let files = build_export_manifest_files(&export_docs);
+        let files = build_export_manifest_files(&export_docs, &[]);
 
         let ordered_names: Vec<String> = files.into_iter().map(|f| f.filename).collect();
         assert_eq!(
@@ -1757,12 +4620,115 @@ mod tests {
         );
     }
 
+    #[test]
+    fn document_catalog_covers_export_order_with_target_filenames() {
+        let catalog = document_catalog();
+        assert_eq!(catalog.len(), EXPORT_FILE_ORDER.len());
+        assert!(catalog
+            .iter()
+            .enumerate()
+            .all(|(rank, entry)| entry.export_rank == rank && !entry.purpose.is_empty()));
+
+        let entry = |name: &str| catalog.iter().find(|e| e.filename == name).unwrap();
+        assert_eq!(entry("SPEC.md").source, DocumentSource::Llm);
+        assert!(entry("SPEC.md").required);
+        assert!(entry("SPEC.md").targets.is_empty());
+        assert_eq!(entry("CONVERSATION.md").source, DocumentSource::Template);
+        assert!(!entry("CONVERSATION.md").required);
+        assert_eq!(entry("MODEL_HANDOFF.md").source, DocumentSource::Template);
+        assert!(entry("MODEL_HANDOFF.md").required);
+        assert_eq!(
+            entry("AGENTS.md").targets,
+            vec![ForgeTarget::Codex, ForgeTarget::Generic]
+        );
+        assert_eq!(entry("GEMINI.md").source, DocumentSource::Llm);
+        assert!(entry("GEMINI.md").required);
+    }
+
+    #[test]
+    fn document_order_puts_listed_documents_first() {
+        let mut docs = vec![
+            doc("SPEC.md", "spec"),
+            doc("ADR-002.md", "adr two"),
+            doc("ADR-001.md", "adr one"),
+            doc("README.md", "read me"),
+            doc("START_HERE.md", "start"),
+        ];
+        let order = vec!["ADR-001.md".to_string(), "SPEC.md".to_string()];
+        validate_document_order(&order, &docs).expect("order should validate");
+
+        sort_documents_by_order(&mut docs, &order);
+        let names: Vec<&str> = docs.iter().map(|d| d.filename.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "ADR-001.md",
+                "SPEC.md",
+                "START_HERE.md",
+                "README.md",
+                "ADR-002.md"
+            ]
+        );
+
+        let unknown = validate_document_order(&["NOTES.md".to_string()], &docs);
+        assert!(matches!(unknown, Err(AppError::Validation(msg)) if msg.contains("NOTES.md")));
+        let repeated =
+            validate_document_order(&["SPEC.md".to_string(), "SPEC.md".to_string()], &docs);
+        assert!(
+            matches!(repeated, Err(AppError::Validation(msg)) if msg.contains("more than once"))
+        );
+    }
+
+    #[test]
+    fn custom_file_order_comes_first_and_the_rest_keep_default_ranking() {
+        let export_docs = prepare_export_documents(
+            &[
+                doc("README.md", "read me"),
+                doc("START_HERE.md", "start here"),
+                doc("SPEC.md", "spec"),
+                doc("PROMPTS.md", "prompts"),
+            ],
+            "generic",
+            LintMode::FailOnCritical,
+        )
+        .expect("export docs should validate");
+        let order = vec!["SPEC.md".to_string(), "docs/PROMPTS.md".to_string()];
+        validate_export_file_order(&order, &export_docs).expect("order should validate");
+
+        let names = build_export_manifest_files(&export_docs, &order)
+            .into_iter()
+            .map(|f| f.filename)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            &names[..4],
+            [
+                "docs/SPEC.md",
+                "docs/PROMPTS.md",
+                "docs/START_HERE.md",
+                "docs/README.md"
+            ]
+        );
+
+        let unknown = validate_export_file_order(&["NOTES.md".to_string()], &export_docs);
+        assert!(
+            matches!(unknown, Err(AppError::Validation(message)) if message.contains("NOTES.md"))
+        );
+        let repeated = validate_export_file_order(
+            &["SPEC.md".to_string(), "docs/SPEC.md".to_string()],
+            &export_docs,
+        );
+        assert!(repeated.is_err());
+    }
+
     #[test]
     fn build_export_manifest_files_includes_hash_bytes_and_lines() {
-        let export_docs =
-            prepare_export_documents(&[doc("SPEC.md", "abc"), doc("EMPTY.md", "")], "generic")
-                .expect("export docs should validate");
-        let files = build_export_manifest_files(&export_docs);
+        let export_docs = prepare_export_documents(
+            &[doc("SPEC.md", "abc"), doc("EMPTY.md", "")],
+            "generic",
+            LintMode::FailOnCritical,
+        )
+        .expect("export docs should validate");
+        let files = build_export_manifest_files(&export_docs, &[]);
         let spec = files
             .iter()
             .find(|f| f.filename == "docs/SPEC.md")
@@ -1786,25 +4752,196 @@ mod tests {
         );
     }
 
+    fn sample_manifest(documents: &[ExportDocument]) -> ExportManifest {
+        ExportManifest {
+            schema_version: EXPORT_MANIFEST_SCHEMA_VERSION,
+            session_id: "s1".to_string(),
+            session_name: "Demo".to_string(),
+            target: "generic".to_string(),
+            run_id: None,
+            export_preset: preset_label("generic").to_string(),
+            provider: "ollama".to_string(),
+            model: "unknown".to_string(),
+            created_at: "2026-01-01 00:00:00".to_string(),
+            quality: None,
+            confidence: None,
+            import_context: None,
+            provenance: None,
+            files: build_export_manifest_files(documents, &[]),
+        }
+    }
+
+    #[test]
+    fn manifest_write_errors_map_like_document_writes() {
+        let path = std::path::Path::new("/exports/demo-plan/manifest.json");
+
+        let disk_full = std::io::Error::from_raw_os_error(28);
+        match export_write_error(&disk_full, path, EXPORT_MANIFEST_FILENAME) {
+            AppError::FileSystem { path, message } => {
+                assert_eq!(path, "/exports/demo-plan/manifest.json");
+                assert_eq!(
+                    message,
+                    "Not enough disk space. Free up space and try again."
+                );
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+
+        let denied = std::io::Error::from(std::io::ErrorKind::PermissionDenied);
+        assert!(matches!(
+            export_write_error(&denied, path, EXPORT_MANIFEST_FILENAME),
+            AppError::FileSystem { message, .. }
+                if message == "Permission denied writing manifest.json. Choose another folder."
+        ));
+    }
+
+    #[test]
+    fn incremental_export_rewrites_only_changed_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = prepare_export_documents(
+            &[doc("SPEC.md", "# Spec v1"), doc("README.md", "# Readme")],
+            "generic",
+            LintMode::FailOnCritical,
+        )
+        .unwrap();
+        let IncrementalWrite {
+            written,
+            skipped,
+            removed,
+        } = write_incremental_export(dir.path(), &first, &sample_manifest(&first)).unwrap();
+        assert_eq!(written.len(), first.len());
+        assert!(skipped.is_empty());
+        assert!(removed.is_empty());
+
+        let second = prepare_export_documents(
+            &[doc("SPEC.md", "# Spec v2"), doc("README.md", "# Readme")],
+            "generic",
+            LintMode::FailOnCritical,
+        )
+        .unwrap();
+        let IncrementalWrite {
+            written,
+            skipped,
+            removed,
+        } = write_incremental_export(dir.path(), &second, &sample_manifest(&second)).unwrap();
+        assert_eq!(written, vec!["docs/SPEC.md".to_string()]);
+        assert_eq!(skipped.len(), second.len() - 1);
+        assert!(skipped.contains(&"docs/README.md".to_string()));
+        assert!(removed.is_empty());
+
+        let spec = std::fs::read_to_string(dir.path().join("docs/SPEC.md")).unwrap();
+        assert_eq!(spec, "# Spec v2");
+        let manifest = std::fs::read_to_string(dir.path().join("manifest.json")).unwrap();
+        assert!(manifest.contains(&sha256_hex(b"# Spec v2")));
+        assert_eq!(
+            unchanged_export_files(&manifest, &second).len(),
+            second.len()
+        );
+
+        let with_claude = prepare_export_documents(
+            &[doc("SPEC.md", "# Spec v2"), doc("CLAUDE.md", "# Claude")],
+            "generic",
+            LintMode::FailOnCritical,
+        )
+        .unwrap();
+        write_incremental_export(dir.path(), &with_claude, &sample_manifest(&with_claude)).unwrap();
+        let retargeted = prepare_export_documents(
+            &[doc("SPEC.md", "# Spec v2"), doc("AGENTS.md", "# Agents")],
+            "generic",
+            LintMode::FailOnCritical,
+        )
+        .unwrap();
+        let IncrementalWrite {
+            written, removed, ..
+        } = write_incremental_export(dir.path(), &retargeted, &sample_manifest(&retargeted))
+            .unwrap();
+        assert_eq!(written, vec!["docs/AGENTS.md".to_string()]);
+        assert_eq!(removed, vec!["docs/CLAUDE.md".to_string()]);
+        assert!(!dir.path().join("docs/CLAUDE.md").exists());
+        assert!(dir.path().join("docs/AGENTS.md").is_file());
+    }
+
+    #[test]
+    fn unchanged_export_files_ignores_malformed_manifests() {
+        let docs = prepare_export_documents(
+            &[doc("SPEC.md", "abc")],
+            "generic",
+            LintMode::FailOnCritical,
+        )
+        .unwrap();
+        assert!(unchanged_export_files("not json", &docs).is_empty());
+        assert!(unchanged_export_files(r#"{"files": 3}"#, &docs).is_empty());
+    }
+
+    #[test]
+    fn export_preview_lists_documents_then_manifest() {
+        let documents = prepare_export_documents(
+            &[doc("SPEC.md", "abc")],
+            "generic",
+            LintMode::FailOnCritical,
+        )
+        .expect("export docs should validate");
+        let plan = ExportPlan {
+            folder_name: "demo-plan".to_string(),
+            manifest: sample_manifest(&documents),
+            documents,
+        };
+
+        let preview = build_export_preview(&plan).expect("preview should build");
+        assert_eq!(preview.folder_name, "demo-plan");
+        assert_eq!(preview.files.len(), plan.documents.len() + 1);
+        let last = preview.files.last().expect("manifest entry");
+        assert_eq!(last.filename, "manifest.json");
+        assert!(last.bytes > 0);
+        assert_eq!(
+            preview.total_bytes,
+            preview.files.iter().map(|f| f.bytes).sum::<usize>()
+        );
+    }
+
+    #[test]
+    fn export_preview_rejects_unsafe_filenames() {
+        let err = prepare_export_documents(
+            &[doc("../SPEC.md", "abc")],
+            "generic",
+            LintMode::FailOnCritical,
+        )
+        .expect_err("nested filename should be rejected");
+        assert!(matches!(err, AppError::Validation(_)));
+    }
+
     #[test]
     fn prepare_export_documents_rejects_nested_or_absolute_paths() {
-        let nested = prepare_export_documents(&[doc("../escape.md", "bad")], "generic");
+        let nested = prepare_export_documents(
+            &[doc("../escape.md", "bad")],
+            "generic",
+            LintMode::FailOnCritical,
+        );
         assert!(nested.is_err(), "parent traversal should be rejected");
 
-        let absolute = prepare_export_documents(&[doc("/tmp/evil.md", "bad")], "generic");
+        let absolute = prepare_export_documents(
+            &[doc("/tmp/evil.md", "bad")],
+            "generic",
+            LintMode::FailOnCritical,
+        );
         assert!(absolute.is_err(), "absolute paths should be rejected");
     }
 
     #[test]
     fn prepare_export_documents_rejects_empty_filename() {
-        let result = prepare_export_documents(&[doc("   ", "bad")], "generic");
+        let result =
+            prepare_export_documents(&[doc("   ", "bad")], "generic", LintMode::FailOnCritical);
         assert!(result.is_err(), "blank filenames should be rejected");
     }
 
     #[test]
     fn prepare_export_documents_backfills_required_reports() {
-        let export_docs = prepare_export_documents(&[doc("SPEC.md", "abc")], "generic")
-            .expect("export docs should validate");
+        let export_docs = prepare_export_documents(
+            &[doc("SPEC.md", "abc")],
+            "generic",
+            LintMode::FailOnCritical,
+        )
+        .expect("export docs should validate");
         let names = export_docs
             .iter()
             .map(|doc| doc.filename.as_str())
@@ -1828,6 +4965,18 @@ mod tests {
             .expect("backfilled diff report must exist");
         serde_json::from_str::<serde_json::Value>(&diff_doc.content)
             .expect("backfilled diff report should remain valid json");
+
+        let lint_report = |mode| {
+            prepare_export_documents(&[doc("SPEC.md", "abc")], "generic", mode)
+                .unwrap()
+                .into_iter()
+                .find(|doc| doc.filename == "reports/LINT_REPORT.md")
+                .unwrap()
+                .content
+        };
+        assert!(lint_report(LintMode::Off).contains("Linting is disabled"));
+        assert!(!lint_report(LintMode::Off).contains("Regenerate"));
+        assert!(lint_report(LintMode::WarnOnly).contains("Regenerate"));
     }
 
     #[test]
@@ -1835,6 +4984,7 @@ mod tests {
         let export_docs = prepare_export_documents(
             &[doc("LINT_REPORT.md", "already-here"), doc("SPEC.md", "abc")],
             "generic",
+            LintMode::FailOnCritical,
         )
         .expect("export docs should validate");
         let lint_report = export_docs
@@ -1879,4 +5029,156 @@ mod tests {
             "future schema versions should be rejected until explicitly supported"
         );
     }
+
+    #[test]
+    fn export_zip_nests_documents_and_manifest_under_the_folder_name() {
+        let docs = vec![
+            ExportDocument {
+                filename: "README.md".to_string(),
+                content: "# Plan\n".to_string(),
+            },
+            ExportDocument {
+                filename: "handoff/EXECUTION_CHECKLIST.md".to_string(),
+                content: "- [ ] Start\n".to_string(),
+            },
+        ];
+        let manifest = sample_manifest(&docs);
+
+        let cursor = write_export_zip(
+            std::io::Cursor::new(Vec::new()),
+            "demo-plan",
+            &docs,
+            &manifest,
+        )
+        .expect("zip should build");
+        let mut archive = zip::ZipArchive::new(cursor).expect("zip should read back");
+        let mut names = archive.file_names().map(str::to_string).collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(
+            names,
+            vec![
+                "demo-plan/README.md",
+                "demo-plan/handoff/EXECUTION_CHECKLIST.md",
+                "demo-plan/manifest.json",
+            ]
+        );
+
+        let mut readme = String::new();
+        std::io::Read::read_to_string(
+            &mut archive
+                .by_name("demo-plan/README.md")
+                .expect("readme entry"),
+            &mut readme,
+        )
+        .expect("readme should read");
+        assert_eq!(readme, "# Plan\n");
+    }
+
+    #[test]
+    fn exported_plan_reads_back_with_edit_warnings() {
+        let docs = vec![
+            ExportDocument {
+                filename: "docs/SPEC.md".to_string(),
+                content: "# Spec\n".to_string(),
+            },
+            ExportDocument {
+                filename: "handoff/MODEL_HANDOFF.md".to_string(),
+                content: "# Handoff\n".to_string(),
+            },
+            ExportDocument {
+                filename: EXECUTION_CHECKLIST_EXPORT_PATH.to_string(),
+                content: "- [ ] Start\n".to_string(),
+            },
+            ExportDocument {
+                filename: ".cursorrules".to_string(),
+                content: "Use TypeScript.\n".to_string(),
+            },
+            ExportDocument {
+                filename: "ARTIFACT_DIFF.json".to_string(),
+                content: "{}\n".to_string(),
+            },
+        ];
+        let dir = tempfile::tempdir().expect("temp dir");
+        for doc in &docs {
+            let path = dir.path().join(&doc.filename);
+            std::fs::create_dir_all(path.parent().expect("parent")).expect("create dir");
+            std::fs::write(&path, &doc.content).expect("write doc");
+        }
+        std::fs::write(
+            dir.path().join(EXPORT_MANIFEST_FILENAME),
+            serde_json::to_string_pretty(&sample_manifest(&docs)).expect("manifest json"),
+        )
+        .expect("write manifest");
+        std::fs::write(
+            dir.path().join("docs/SPEC.md"),
+            "# Spec\n\nEdited by hand.\n",
+        )
+        .expect("edit spec");
+
+        let plan = read_exported_plan(dir.path()).expect("export should import");
+        assert_eq!(plan.manifest.session_name, "Demo");
+        assert_eq!(plan.manifest.target, "generic");
+        let names = plan
+            .documents
+            .iter()
+            .map(|(filename, _)| filename.as_str())
+            .collect::<Vec<_>>();
+        assert!(names.contains(&"SPEC.md"));
+        assert!(names.contains(&"MODEL_HANDOFF.md"));
+        assert!(names.contains(&".cursorrules"));
+        assert!(!names.contains(&"EXECUTION_CHECKLIST.md"));
+        assert!(!names.contains(&"ARTIFACT_DIFF.json"));
+        assert_eq!(
+            plan.warnings,
+            vec![
+                "docs/SPEC.md was edited after export".to_string(),
+                "ARTIFACT_DIFF.json is not a planning document; skipped".to_string(),
+            ]
+        );
+
+        std::fs::remove_file(dir.path().join(EXPORT_MANIFEST_FILENAME)).expect("remove manifest");
+        assert!(read_exported_plan(dir.path()).is_err());
+    }
+
+    #[test]
+    fn zip_destination_must_be_a_new_file_in_an_existing_folder() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let archive = dir.path().join("plan.zip");
+        let archive_str = archive.to_string_lossy().to_string();
+        assert_eq!(
+            validate_zip_destination(&archive_str, false).expect("new file is accepted"),
+            archive
+        );
+
+        let err = validate_zip_destination(&dir.path().to_string_lossy(), false)
+            .expect_err("a folder is rejected");
+        assert!(err.message.contains("must be a file"));
+
+        std::fs::write(&archive, "old").expect("write existing archive");
+        let err = validate_zip_destination(&archive_str, false).expect_err("no force");
+        assert!(err.message.contains("already exists"));
+        assert!(validate_zip_destination(&archive_str, true).is_ok());
+
+        let missing = dir.path().join("missing").join("plan.zip");
+        assert!(validate_zip_destination(&missing.to_string_lossy(), false).is_err());
+    }
+
+    #[test]
+    fn validate_export_root_rejects_files_and_missing_paths() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let root = dir.path().to_string_lossy().to_string();
+        assert_eq!(
+            validate_export_root(&root).expect("dir should be accepted"),
+            dir.path()
+        );
+
+        let file_path = dir.path().join("notes.txt");
+        std::fs::write(&file_path, "x").expect("write file");
+        let err = validate_export_root(&file_path.to_string_lossy()).expect_err("file");
+        assert_eq!(err.code, "filesystem_error");
+        assert!(err.message.contains("must be a folder"));
+
+        let missing = dir.path().join("missing");
+        assert!(validate_export_root(&missing.to_string_lossy()).is_err());
+    }
 }
@@ -1,20 +1,41 @@
+use std::collections::HashSet;
 use std::fs;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 
-use crate::error::ConfigError;
-use crate::types::AppConfig;
+use reqwest::header::{HeaderName, HeaderValue};
+
+use crate::error::{ConfigError, ConfigFieldError};
+use crate::importer;
+use crate::lint::LintMode;
+use crate::types::{
+    default_export_folder_template, AppConfig, ConfigResetField, ConfigSectionSources,
+    ConfigSource, PlanningDepth,
+};
 
 const DEFAULT_CONFIG_YAML: &str = r#"# AuraForge Configuration
 
 # LLM Provider Settings
 llm:
-  provider: ollama                          # ollama | openai_compatible
+  provider: ollama                          # ollama | openai_compatible | anthropic
   model: qwen3-coder
   base_url: http://localhost:11434          # Ollama default (LM Studio commonly uses :1234)
-  api_key: ""                               # optional for openai_compatible runtimes
+  api_key: ""                               # optional for openai_compatible runtimes, required for anthropic
   temperature: 0.7
   max_tokens: 65536
+  clamp_to_context: true                    # cap max_tokens to half the model context (Ollama only)
+  request_timeout_secs: 300                 # overall limit per chat, generation or model pull request
+  first_token_timeout_secs: 300             # wait for the first streamed token (cold model loads)
+  stream_idle_timeout_secs: 60              # max gap between tokens once streaming
+  context_tokens: 32768                     # chat request token budget; oldest turns dropped to fit (0 = full)
+  compact_keep_recent_messages: 20          # messages kept verbatim when compacting old turns
+  max_concurrent_streams: 2                 # chat responses streamed at once across sessions
+  max_retries: 2                            # retries on connection errors and 5xx replies (0-3)
+  seed: null                                # fixed sampling seed for reproducible output (null = random)
+  headers: {}                               # extra headers on every LLM request, e.g. { X-Org-Id: acme }
+  stream_usage: false                       # request token usage on streamed replies (openai_compatible; vLLM, LiteLLM)
+  profiles: []                              # extra endpoints for check_all_endpoints_health, e.g.
+                                            # [{ name: lmstudio, provider: openai_compatible, base_url: "http://localhost:1234" }]
 
 # Web Search Settings
 search:
@@ -23,6 +44,11 @@ search:
   tavily_api_key: ""                        # Required if using Tavily
   searxng_url: ""                           # Required if using SearXNG
   proactive: true                           # Auto-search during conversation
+  region: ""                                # DuckDuckGo region, e.g. us-en | de-de | wt-wt
+  safe_search: ""                           # DuckDuckGo safe search: strict | moderate | off
+  trigger_overrides:                        # phrases merged with proactive search heuristics
+    always: []                              # e.g. ["latest release of *"]
+    never: []                               # e.g. ["rust"] to stop searching on a common word
 
 # UI Preferences
 ui:
@@ -33,9 +59,76 @@ output:
   include_conversation: true                # Include CONVERSATION.md
   default_save_path: ~/Projects             # Default folder picker location
   default_target: generic                   # claude | codex | cursor | gemini | generic
-  lint_mode: fail_on_critical               # fail_on_critical | warn
+  lint_mode: fail_on_critical               # fail_on_critical | warn_only | off
+  generation_retries: 1                     # extra attempts when a generated doc fails validation (0-5)
+  doc_validation: strict                    # strict | heading | off
+  export_folder_template: "{name}-plan"     # placeholders: {name} {date} {target}
+  generation_context_limit: 0               # conversation token budget per doc prompt (0 = full)
+  conversation_generation_details: false    # note model/temperature/sources per reply in CONVERSATION.md
+  frontmatter: false                        # prepend YAML frontmatter to generated Markdown docs
+  completion_checks: lenient                # lenient | strict (lint + confidence) for complete_session
+  planning_depth: standard                  # quick | standard | thorough (must-have planning topics)
+
+# Outbound Request Limits (requests per minute, 0 = unlimited)
+rate_limit:
+  default_per_minute: 0                     # applies to every host without an override
+  per_host: {}                              # e.g. { localhost: 120, html.duckduckgo.com: 20 }
+
+# Startup Session Housekeeping
+housekeeping:
+  enabled: false                            # prune idle sessions when the app starts
+  max_idle_days: 90                         # sessions untouched for longer than this
+  action: archive                           # archive | delete
+  include_with_documents: false             # also prune sessions that have generated documents
+  include_inactive: false                   # also prune completed/archived sessions
+
+# Codebase Import
+import:
+  min_snippet_relevance: 0.5                # share of a cited snippet that must be code, not comments/imports (0 = keep all)
+  read_workers: 0                           # threads reading file contents (0 = match CPU cores, max 8)
+  read_timeout_secs: 60                     # stop reading further files after this long (0 = no limit)
 "#;
 
+const MAX_GENERATION_RETRIES: u32 = 5;
+/// One backoff step each: 250ms, 500ms, 1s.
+const MAX_LLM_RETRIES: u32 = 3;
+const MIN_GENERATION_CONTEXT_LIMIT: u64 = 1000;
+const MIN_CHAT_CONTEXT_TOKENS: u64 = 1000;
+const MIN_COMPACT_KEEP_RECENT_MESSAGES: usize = 2;
+
+pub const EXPORT_FOLDER_PLACEHOLDERS: &[&str] = &["{name}", "{date}", "{target}"];
+
+const LLM_PROVIDERS: &[&str] = &["ollama", "openai_compatible", "anthropic"];
+
+const PROVIDER_DEFAULT_BASE_URLS: &[(&str, &str)] = &[
+    ("ollama", "http://localhost:11434"),
+    ("openai_compatible", "http://localhost:1234"),
+    ("anthropic", "https://api.anthropic.com"),
+];
+
+pub fn expand_save_path(raw: &str, home: Option<&Path>) -> Result<PathBuf, ConfigError> {
+    resolve_save_path(raw, home).map_err(|reason| {
+        ConfigError::InvalidValue(format!("output.default_save_path={} ({})", raw, reason))
+    })
+}
+
+fn resolve_save_path(raw: &str, home: Option<&Path>) -> Result<PathBuf, &'static str> {
+    let trimmed = raw.trim();
+    let expanded = match trimmed.strip_prefix('~') {
+        Some(rest) if rest.is_empty() || rest.starts_with(['/', '\\']) => {
+            let home = home.ok_or("home directory not found")?;
+            home.join(rest.trim_start_matches(['/', '\\']))
+        }
+        Some(_) => return Err("only '~' or '~/' prefixes are supported"),
+        None => PathBuf::from(trimmed),
+    };
+
+    if !expanded.is_absolute() {
+        return Err("must be absolute or start with '~/'");
+    }
+    Ok(expanded)
+}
+
 pub fn auraforge_dir() -> PathBuf {
     if let Some(home) = dirs::home_dir() {
         home.join(".auraforge")
@@ -185,106 +278,577 @@ fn sync_directory(path: &Path) -> Result<(), String> {
 }
 
 fn validate_config(config: &AppConfig) -> Result<(), ConfigError> {
+    match validate_config_fields(config).into_iter().next() {
+        Some(first) => Err(first.into()),
+        None => Ok(()),
+    }
+}
+
+pub fn validate_config_fields(config: &AppConfig) -> Vec<ConfigFieldError> {
+    let mut errors = Vec::new();
+
     let llm_provider = config.llm.provider.as_str();
-    if !["ollama", "openai_compatible"].contains(&llm_provider) {
-        return Err(ConfigError::InvalidValue(format!(
-            "llm.provider={} (expected 'ollama' or 'openai_compatible')",
-            config.llm.provider
-        )));
+    if !LLM_PROVIDERS.contains(&llm_provider) {
+        errors.push(ConfigFieldError::invalid(
+            "llm.provider",
+            format!(
+                "'{}' is not supported (expected 'ollama', 'openai_compatible' or 'anthropic')",
+                config.llm.provider
+            ),
+        ));
     }
 
     if config.llm.model.trim().is_empty() {
-        return Err(ConfigError::MissingField("llm.model".to_string()));
+        errors.push(ConfigFieldError::missing("llm.model"));
     }
 
     if !(0.0..=2.0).contains(&config.llm.temperature) {
-        return Err(ConfigError::InvalidValue(format!(
-            "llm.temperature={} (must be 0.0-2.0)",
-            config.llm.temperature
-        )));
+        errors.push(ConfigFieldError::invalid(
+            "llm.temperature",
+            format!("must be 0.0-2.0 (got {})", config.llm.temperature),
+        ));
     }
 
     if config.llm.base_url.trim().is_empty() {
-        return Err(ConfigError::MissingField("llm.base_url".to_string()));
-    }
-    match url::Url::parse(&config.llm.base_url) {
-        Ok(parsed) => {
-            if parsed.scheme() != "http" && parsed.scheme() != "https" {
-                return Err(ConfigError::InvalidValue(format!(
-                    "llm.base_url: scheme '{}' is not allowed (only http/https)",
-                    parsed.scheme()
-                )));
+        errors.push(ConfigFieldError::missing("llm.base_url"));
+    } else if let Err(message) = check_http_url(&config.llm.base_url) {
+        errors.push(ConfigFieldError::invalid("llm.base_url", message));
+    }
+
+    if llm_provider == "anthropic"
+        && config
+            .llm
+            .api_key
+            .as_deref()
+            .is_none_or(|key| key.trim().is_empty())
+    {
+        errors.push(ConfigFieldError::missing("llm.api_key"));
+    }
+
+    for (field, secs) in [
+        ("llm.request_timeout_secs", config.llm.request_timeout_secs),
+        (
+            "llm.first_token_timeout_secs",
+            config.llm.first_token_timeout_secs,
+        ),
+        (
+            "llm.stream_idle_timeout_secs",
+            config.llm.stream_idle_timeout_secs,
+        ),
+    ] {
+        if secs == 0 {
+            errors.push(ConfigFieldError::invalid(
+                field,
+                "must be at least 1".to_string(),
+            ));
+        }
+    }
+    if config.llm.stream_idle_timeout_secs > 0
+        && config.llm.first_token_timeout_secs < config.llm.stream_idle_timeout_secs
+    {
+        errors.push(ConfigFieldError::invalid(
+            "llm.first_token_timeout_secs",
+            format!(
+                "must be >= stream_idle_timeout_secs={} (got {})",
+                config.llm.stream_idle_timeout_secs, config.llm.first_token_timeout_secs
+            ),
+        ));
+    }
+    // The whole-request deadline would cut the first-token wait short.
+    if config.llm.request_timeout_secs > 0
+        && config.llm.request_timeout_secs < config.llm.first_token_timeout_secs
+    {
+        errors.push(ConfigFieldError::invalid(
+            "llm.request_timeout_secs",
+            format!(
+                "must be >= first_token_timeout_secs={} (got {})",
+                config.llm.first_token_timeout_secs, config.llm.request_timeout_secs
+            ),
+        ));
+    }
+    if config.llm.context_tokens != 0 && config.llm.context_tokens < MIN_CHAT_CONTEXT_TOKENS {
+        errors.push(ConfigFieldError::invalid(
+            "llm.context_tokens",
+            format!(
+                "must be 0 or at least {} (got {})",
+                MIN_CHAT_CONTEXT_TOKENS, config.llm.context_tokens
+            ),
+        ));
+    }
+    if config.llm.compact_keep_recent_messages < MIN_COMPACT_KEEP_RECENT_MESSAGES {
+        errors.push(ConfigFieldError::invalid(
+            "llm.compact_keep_recent_messages",
+            format!(
+                "must be at least {} (got {})",
+                MIN_COMPACT_KEEP_RECENT_MESSAGES, config.llm.compact_keep_recent_messages
+            ),
+        ));
+    }
+    if config.llm.max_concurrent_streams == 0 {
+        errors.push(ConfigFieldError::invalid(
+            "llm.max_concurrent_streams",
+            "must be at least 1".to_string(),
+        ));
+    }
+    if config.llm.max_retries > MAX_LLM_RETRIES {
+        errors.push(ConfigFieldError::invalid(
+            "llm.max_retries",
+            format!(
+                "must be 0-{} (got {})",
+                MAX_LLM_RETRIES, config.llm.max_retries
+            ),
+        ));
+    }
+    let mut headers = config.llm.headers.iter().collect::<Vec<_>>();
+    headers.sort();
+    for (name, value) in headers {
+        let field = format!("llm.headers.{}", name);
+        if HeaderName::from_bytes(name.trim().as_bytes()).is_err() {
+            errors.push(ConfigFieldError::invalid(
+                &field,
+                format!("'{}' is not a valid HTTP header name", name),
+            ));
+        } else if HeaderValue::from_str(value.trim()).is_err() {
+            errors.push(ConfigFieldError::invalid(
+                &field,
+                "value must be visible ASCII without line breaks".to_string(),
+            ));
+        }
+    }
+    let mut profile_names = HashSet::new();
+    for (index, profile) in config.llm.profiles.iter().enumerate() {
+        let field = |name: &str| format!("llm.profiles[{}].{}", index, name);
+        let name = profile.name.trim();
+        if name.is_empty() {
+            errors.push(ConfigFieldError::missing(&field("name")));
+        } else if name == "active" || !profile_names.insert(name.to_ascii_lowercase()) {
+            errors.push(ConfigFieldError::invalid(
+                &field("name"),
+                format!("'{}' is reserved or used by another profile", name),
+            ));
+        }
+        if let Some(provider) = profile.provider.as_deref().map(str::trim) {
+            if !provider.is_empty() && !LLM_PROVIDERS.contains(&provider) {
+                errors.push(ConfigFieldError::invalid(
+                    &field("provider"),
+                    format!(
+                        "'{}' is not supported (expected 'ollama', 'openai_compatible' or 'anthropic')",
+                        provider
+                    ),
+                ));
             }
         }
-        Err(e) => {
-            return Err(ConfigError::InvalidValue(format!("llm.base_url: {}", e)));
+        if let Some(base_url) = profile.base_url.as_deref().map(str::trim) {
+            if !base_url.is_empty() {
+                if let Err(message) = check_http_url(base_url) {
+                    errors.push(ConfigFieldError::invalid(&field("base_url"), message));
+                }
+            }
         }
     }
 
     let search_provider = config.search.provider.as_str();
     if !["tavily", "duckduckgo", "searxng", "none"].contains(&search_provider) {
-        return Err(ConfigError::InvalidValue(format!(
-            "search.provider={}",
-            config.search.provider
-        )));
+        errors.push(ConfigFieldError::invalid(
+            "search.provider",
+            format!(
+                "'{}' is not supported (expected 'tavily', 'duckduckgo', 'searxng' or 'none')",
+                config.search.provider
+            ),
+        ));
     }
 
     if config.search.enabled
         && search_provider == "tavily"
         && config.search.tavily_api_key.trim().is_empty()
     {
-        return Err(ConfigError::MissingField(
-            "search.tavily_api_key".to_string(),
-        ));
+        errors.push(ConfigFieldError::missing("search.tavily_api_key"));
     }
 
-    if config.search.enabled && search_provider == "searxng" && config.search.searxng_url.is_empty()
+    if config.search.enabled && search_provider == "searxng" {
+        if config.search.searxng_url.is_empty() {
+            errors.push(ConfigFieldError::missing("search.searxng_url"));
+        } else if let Err(message) = check_http_url(&config.search.searxng_url) {
+            errors.push(ConfigFieldError::invalid("search.searxng_url", message));
+        }
+    }
+
+    // Empty strings mean "unset"; normalization turns them into `None`.
+    if let Some(region) = config
+        .search
+        .region
+        .as_deref()
+        .filter(|value| !value.trim().is_empty())
     {
-        return Err(ConfigError::MissingField("search.searxng_url".to_string()));
+        if !is_valid_search_region(region) {
+            errors.push(ConfigFieldError::invalid(
+                "search.region",
+                format!("'{}' is not a region code like 'us-en' or 'wt-wt'", region),
+            ));
+        }
     }
-    if config.search.enabled
-        && search_provider == "searxng"
-        && !config.search.searxng_url.is_empty()
+    if let Some(safe_search) = config
+        .search
+        .safe_search
+        .as_deref()
+        .filter(|value| !value.trim().is_empty())
     {
-        match url::Url::parse(&config.search.searxng_url) {
-            Ok(parsed) => {
-                if parsed.scheme() != "http" && parsed.scheme() != "https" {
-                    return Err(ConfigError::InvalidValue(format!(
-                        "search.searxng_url: scheme '{}' is not allowed (only http/https)",
-                        parsed.scheme()
-                    )));
-                }
-            }
-            Err(e) => {
-                return Err(ConfigError::InvalidValue(format!(
-                    "search.searxng_url: {}",
-                    e
-                )));
-            }
+        if !["strict", "moderate", "off"].contains(&safe_search) {
+            errors.push(ConfigFieldError::invalid(
+                "search.safe_search",
+                format!(
+                    "'{}' is not one of 'strict', 'moderate' or 'off'",
+                    safe_search
+                ),
+            ));
         }
     }
 
+    let overrides = &config.search.trigger_overrides;
+    for (list, patterns) in [("always", &overrides.always), ("never", &overrides.never)] {
+        if let Some(pattern) = patterns
+            .iter()
+            .find(|pattern| pattern.matches('*').count() > 1 || pattern.trim() == "*")
+        {
+            errors.push(ConfigFieldError::invalid(
+                &format!("search.trigger_overrides.{}", list),
+                format!(
+                    "'{}' may contain at most one '*' wildcard and some text",
+                    pattern
+                ),
+            ));
+        }
+    }
+
+    if let Some(host) = config
+        .rate_limit
+        .per_host
+        .keys()
+        .find(|host| host.trim().is_empty() || host.contains('/'))
+    {
+        errors.push(ConfigFieldError::invalid(
+            "rate_limit.per_host",
+            format!("'{}' is not a host name", host),
+        ));
+    }
+
+    if config.housekeeping.max_idle_days == 0 {
+        errors.push(ConfigFieldError::invalid(
+            "housekeeping.max_idle_days",
+            "must be at least 1".to_string(),
+        ));
+    }
+    if !["archive", "delete"].contains(&config.housekeeping.action.as_str()) {
+        errors.push(ConfigFieldError::invalid(
+            "housekeeping.action",
+            format!(
+                "'{}' is not 'archive' or 'delete'",
+                config.housekeeping.action
+            ),
+        ));
+    }
+
+    let relevance = config.import.min_snippet_relevance;
+    if !(0.0..=1.0).contains(&relevance) {
+        errors.push(ConfigFieldError::invalid(
+            "import.min_snippet_relevance",
+            format!("{} is not between 0.0 and 1.0", relevance),
+        ));
+    }
+    if config.import.read_workers > importer::MAX_READ_WORKERS {
+        errors.push(ConfigFieldError::invalid(
+            "import.read_workers",
+            format!(
+                "must be 0-{} (got {})",
+                importer::MAX_READ_WORKERS,
+                config.import.read_workers
+            ),
+        ));
+    }
+
     if config.output.default_save_path.trim().is_empty() {
-        return Err(ConfigError::MissingField(
-            "output.default_save_path".to_string(),
+        errors.push(ConfigFieldError::missing("output.default_save_path"));
+    } else if let Err(reason) = resolve_save_path(
+        &config.output.default_save_path,
+        dirs::home_dir().as_deref(),
+    ) {
+        errors.push(ConfigFieldError::invalid(
+            "output.default_save_path",
+            reason.to_string(),
         ));
     }
     let target = config.output.default_target.as_str();
     if !["claude", "codex", "cursor", "gemini", "generic"].contains(&target) {
-        return Err(ConfigError::InvalidValue(format!(
-            "output.default_target={}",
-            config.output.default_target
-        )));
+        errors.push(ConfigFieldError::invalid(
+            "output.default_target",
+            format!(
+                "'{}' is not one of 'claude', 'codex', 'cursor', 'gemini' or 'generic'",
+                config.output.default_target
+            ),
+        ));
     }
-    let lint_mode = config.output.lint_mode.trim().to_ascii_lowercase();
-    if !["fail_on_critical", "warn"].contains(&lint_mode.as_str()) {
-        return Err(ConfigError::InvalidValue(format!(
-            "output.lint_mode={} (expected 'fail_on_critical' or 'warn')",
-            config.output.lint_mode
-        )));
+    if LintMode::parse(&config.output.lint_mode).is_none() {
+        errors.push(ConfigFieldError::invalid(
+            "output.lint_mode",
+            format!(
+                "'{}' is not 'fail_on_critical', 'warn_only' or 'off'",
+                config.output.lint_mode
+            ),
+        ));
+    }
+    if config.output.generation_retries > MAX_GENERATION_RETRIES {
+        errors.push(ConfigFieldError::invalid(
+            "output.generation_retries",
+            format!(
+                "must be 0-{} (got {})",
+                MAX_GENERATION_RETRIES, config.output.generation_retries
+            ),
+        ));
+    }
+    let doc_validation = config.output.doc_validation.trim().to_ascii_lowercase();
+    if !["strict", "heading", "off"].contains(&doc_validation.as_str()) {
+        errors.push(ConfigFieldError::invalid(
+            "output.doc_validation",
+            format!(
+                "'{}' is not one of 'strict', 'heading' or 'off'",
+                config.output.doc_validation
+            ),
+        ));
+    }
+    let template = config.output.export_folder_template.trim();
+    if template.is_empty() {
+        errors.push(ConfigFieldError::missing("output.export_folder_template"));
+    } else {
+        let unknown = EXPORT_FOLDER_PLACEHOLDERS
+            .iter()
+            .fold(template.to_string(), |rest, placeholder| {
+                rest.replace(placeholder, "")
+            });
+        if unknown.contains(['{', '}']) {
+            errors.push(ConfigFieldError::invalid(
+                "output.export_folder_template",
+                format!(
+                    "'{}' uses an unknown placeholder (supported: {})",
+                    template,
+                    EXPORT_FOLDER_PLACEHOLDERS.join(", ")
+                ),
+            ));
+        }
+    }
+    let completion_checks = config.output.completion_checks.trim().to_ascii_lowercase();
+    if !["lenient", "strict"].contains(&completion_checks.as_str()) {
+        errors.push(ConfigFieldError::invalid(
+            "output.completion_checks",
+            format!(
+                "'{}' is not 'lenient' or 'strict'",
+                config.output.completion_checks
+            ),
+        ));
+    }
+    if let Err(message) = config.output.planning_depth.parse::<PlanningDepth>() {
+        errors.push(ConfigFieldError::invalid("output.planning_depth", message));
+    }
+    let context_limit = config.output.generation_context_limit;
+    if context_limit != 0 && context_limit < MIN_GENERATION_CONTEXT_LIMIT {
+        errors.push(ConfigFieldError::invalid(
+            "output.generation_context_limit",
+            format!(
+                "must be 0 or at least {} (got {})",
+                MIN_GENERATION_CONTEXT_LIMIT, context_limit
+            ),
+        ));
     }
 
-    Ok(())
+    errors
+}
+
+fn check_http_url(url: &str) -> Result<(), String> {
+    match url::Url::parse(url) {
+        Ok(parsed) if parsed.scheme() == "http" || parsed.scheme() == "https" => Ok(()),
+        Ok(parsed) => Err(format!(
+            "scheme '{}' is not allowed (only http/https)",
+            parsed.scheme()
+        )),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+fn is_valid_search_region(region: &str) -> bool {
+    let mut parts = region.split('-');
+    matches!(
+        (parts.next(), parts.next(), parts.next()),
+        (Some(country), Some(language), None)
+            if country.len() == 2
+                && language.len() == 2
+                && country.chars().chain(language.chars()).all(|c| c.is_ascii_lowercase())
+    )
+}
+
+pub fn provider_default_base_url(provider: &str) -> Option<&'static str> {
+    let provider = provider.trim().to_ascii_lowercase();
+    PROVIDER_DEFAULT_BASE_URLS
+        .iter()
+        .find(|(name, _)| *name == provider)
+        .map(|(_, url)| *url)
+}
+
+pub fn suggest_base_url(
+    previous_provider: &str,
+    provider: &str,
+    base_url: &str,
+) -> Option<&'static str> {
+    let previous_default = provider_default_base_url(previous_provider)?;
+    let new_default = provider_default_base_url(provider)?;
+    if previous_default == new_default {
+        return None;
+    }
+    same_local_url(base_url, previous_default).then_some(new_default)
+}
+
+pub fn config_section_sources(raw: Option<&str>) -> ConfigSectionSources {
+    let mapping = raw
+        .and_then(|content| serde_yaml::from_str::<serde_yaml::Value>(content).ok())
+        .and_then(|value| value.as_mapping().cloned());
+    let source = |key: &str| match &mapping {
+        Some(map) if map.contains_key(key) => ConfigSource::File,
+        _ => ConfigSource::Default,
+    };
+    ConfigSectionSources {
+        llm: source("llm"),
+        search: source("search"),
+        ui: source("ui"),
+        output: source("output"),
+        rate_limit: source("rate_limit"),
+        housekeeping: source("housekeeping"),
+        import: source("import"),
+    }
+}
+
+fn same_local_url(a: &str, b: &str) -> bool {
+    let normalize = |url: &str| {
+        url.trim()
+            .trim_end_matches('/')
+            .to_ascii_lowercase()
+            .replace("127.0.0.1", "localhost")
+    };
+    normalize(a) == normalize(b)
+}
+
+const CONFIG_SECTIONS: &[&str] = &[
+    "llm",
+    "search",
+    "ui",
+    "output",
+    "rate_limit",
+    "housekeeping",
+    "import",
+];
+
+pub fn repair_config_yaml(raw: &str) -> (AppConfig, Vec<String>, Vec<ConfigResetField>) {
+    let defaults: serde_yaml::Value =
+        serde_yaml::from_str(DEFAULT_CONFIG_YAML).expect("default config should parse");
+    let mut merged = defaults.clone();
+    let mut recovered = Vec::new();
+    let mut reset = Vec::new();
+
+    let parsed = match serde_yaml::from_str::<serde_yaml::Value>(raw) {
+        Ok(value) => value,
+        Err(e) => {
+            reset.push(ConfigResetField {
+                field_path: "*".to_string(),
+                reason: format!("YAML syntax error: {}", e),
+            });
+            return (parse_config_value(&defaults), recovered, reset);
+        }
+    };
+
+    for section in CONFIG_SECTIONS {
+        let Some(raw_section) = parsed.get(section) else {
+            continue;
+        };
+        let Some(fields) = raw_section.as_mapping() else {
+            reset.push(ConfigResetField {
+                field_path: section.to_string(),
+                reason: "expected a mapping of settings".to_string(),
+            });
+            continue;
+        };
+        for (key, value) in fields {
+            let Some(key) = key.as_str() else {
+                continue;
+            };
+            let field_path = format!("{}.{}", section, key);
+            let mut candidate = merged.clone();
+            set_config_field(&mut candidate, section, key, Some(value.clone()));
+            match serde_yaml::from_value::<AppConfig>(candidate.clone()) {
+                Ok(_) => {
+                    merged = candidate;
+                    recovered.push(field_path);
+                }
+                Err(e) => reset.push(ConfigResetField {
+                    field_path,
+                    reason: e.to_string(),
+                }),
+            }
+        }
+    }
+
+    // Well-typed but invalid values (a file:// base_url, say) go back to
+    // their defaults one field at a time until the whole config validates.
+    for _ in 0..=recovered.len() {
+        let mut config = parse_config_value(&merged);
+        normalize_local_model_config(&mut config);
+        let Some(error) = validate_config_fields(&config).into_iter().next() else {
+            break;
+        };
+        let mut parts = error.field_path.splitn(3, '.');
+        let (Some(section), Some(key)) = (parts.next(), parts.next()) else {
+            break;
+        };
+        // List entries (`llm.profiles[1].name`) reset the whole list.
+        let key = key.split('[').next().unwrap_or(key);
+        let field_path = format!("{}.{}", section, key);
+        let default = defaults.get(section).and_then(|s| s.get(key)).cloned();
+        set_config_field(&mut merged, section, key, default);
+        recovered.retain(|path| path != &field_path);
+        reset.push(ConfigResetField {
+            field_path,
+            reason: error.message,
+        });
+    }
+
+    let mut config = parse_config_value(&merged);
+    normalize_local_model_config(&mut config);
+    (config, recovered, reset)
+}
+
+fn parse_config_value(value: &serde_yaml::Value) -> AppConfig {
+    serde_yaml::from_value(value.clone()).unwrap_or_default()
+}
+
+fn set_config_field(
+    root: &mut serde_yaml::Value,
+    section: &str,
+    key: &str,
+    value: Option<serde_yaml::Value>,
+) {
+    let Some(root) = root.as_mapping_mut() else {
+        return;
+    };
+    let section = root
+        .entry(section.into())
+        .or_insert_with(|| serde_yaml::Value::Mapping(Default::default()));
+    let Some(fields) = section.as_mapping_mut() else {
+        return;
+    };
+    match value {
+        Some(value) => {
+            fields.insert(key.into(), value);
+        }
+        None => {
+            fields.remove(key);
+        }
+    }
 }
 
 fn normalize_local_model_config(config: &mut AppConfig) -> bool {
@@ -294,6 +858,7 @@ fn normalize_local_model_config(config: &mut AppConfig) -> bool {
     let normalized_provider = match provider.as_str() {
         "ollama" => "ollama",
         "openai_compatible" | "openai-compatible" | "lmstudio" => "openai_compatible",
+        "anthropic" => "anthropic",
         _ => "ollama",
     };
     if config.llm.provider != normalized_provider {
@@ -311,22 +876,61 @@ fn normalize_local_model_config(config: &mut AppConfig) -> bool {
         changed = true;
     }
 
-    let lint_mode = config.output.lint_mode.trim().to_ascii_lowercase();
-    let normalized_lint_mode = match lint_mode.as_str() {
-        "fail_on_critical" | "warn" => lint_mode,
-        _ => "fail_on_critical".to_string(),
-    };
+    for value in [&mut config.search.region, &mut config.search.safe_search] {
+        let normalized = value
+            .as_deref()
+            .map(|raw| raw.trim().to_ascii_lowercase())
+            .filter(|raw| !raw.is_empty());
+        if *value != normalized {
+            *value = normalized;
+            changed = true;
+        }
+    }
+
+    let overrides = &mut config.search.trigger_overrides;
+    for patterns in [&mut overrides.always, &mut overrides.never] {
+        let normalized = patterns
+            .iter()
+            .map(|pattern| pattern.trim().to_lowercase())
+            .filter(|pattern| !pattern.is_empty())
+            .collect::<Vec<_>>();
+        if *patterns != normalized {
+            *patterns = normalized;
+            changed = true;
+        }
+    }
+
+    let normalized_lint_mode = LintMode::from_config(&config.output.lint_mode)
+        .as_str()
+        .to_string();
     if config.output.lint_mode != normalized_lint_mode {
         config.output.lint_mode = normalized_lint_mode;
         changed = true;
     }
 
+    let doc_validation = config.output.doc_validation.trim().to_ascii_lowercase();
+    let normalized_doc_validation = match doc_validation.as_str() {
+        "strict" | "heading" | "off" => doc_validation,
+        _ => "strict".to_string(),
+    };
+    if config.output.doc_validation != normalized_doc_validation {
+        config.output.doc_validation = normalized_doc_validation;
+        changed = true;
+    }
+
+    if config.output.export_folder_template.trim().is_empty() {
+        config.output.export_folder_template = default_export_folder_template();
+        changed = true;
+    }
+
     changed
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::ConfigFieldErrorKind;
+    use crate::types::LlmProfile;
     use tempfile::tempdir;
 
     fn default_config() -> AppConfig {
@@ -348,6 +952,358 @@ mod tests {
         assert!(err.unwrap_err().to_string().contains("not allowed"));
     }
 
+    #[test]
+    fn expand_save_path_resolves_tilde_against_home() {
+        let home = Path::new("/home/tester");
+        assert_eq!(
+            expand_save_path("~/Projects", Some(home)).unwrap(),
+            home.join("Projects")
+        );
+        assert_eq!(expand_save_path(" ~ ", Some(home)).unwrap(), home);
+        assert_eq!(
+            expand_save_path("/srv/plans", Some(home)).unwrap(),
+            PathBuf::from("/srv/plans")
+        );
+    }
+
+    #[test]
+    fn expand_save_path_rejects_unexpandable_values() {
+        let home = Path::new("/home/tester");
+        assert!(expand_save_path("~other/Projects", Some(home)).is_err());
+        assert!(expand_save_path("Projects", Some(home)).is_err());
+        assert!(expand_save_path("~/Projects", None).is_err());
+    }
+
+    #[test]
+    fn validate_config_fields_reports_every_invalid_field() {
+        let mut config = default_config();
+        config.llm.temperature = 3.0;
+        config.llm.base_url = "file:///etc/passwd".to_string();
+        config.search.enabled = true;
+        config.search.provider = "tavily".to_string();
+        config.search.tavily_api_key = " ".to_string();
+
+        let errors = validate_config_fields(&config);
+        let paths = errors
+            .iter()
+            .map(|e| e.field_path.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            paths,
+            vec!["llm.temperature", "llm.base_url", "search.tavily_api_key"]
+        );
+        assert_eq!(errors[2].kind, ConfigFieldErrorKind::Missing);
+
+        // The single-error form reduces to the first field.
+        let first = validate_config(&config).unwrap_err().to_string();
+        assert!(first.contains("llm.temperature: must be 0.0-2.0"));
+        assert!(validate_config_fields(&default_config()).is_empty());
+    }
+
+    #[test]
+    fn validate_config_fields_requires_an_anthropic_api_key() {
+        let mut config = default_config();
+        config.llm.provider = "anthropic".to_string();
+        config.llm.base_url = "https://api.anthropic.com".to_string();
+
+        let errors = validate_config_fields(&config);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].field_path, "llm.api_key");
+        assert_eq!(errors[0].kind, ConfigFieldErrorKind::Missing);
+
+        config.llm.api_key = Some("sk-ant-test".to_string());
+        assert!(validate_config_fields(&config).is_empty());
+    }
+
+    #[test]
+    fn repair_config_yaml_keeps_valid_fields_and_resets_broken_ones() {
+        let raw = "llm:\n  provider: ollama\n  model: llama3\n  temperature: hot\n  \
+                   base_url: file:///etc\nsearch: disabled\noutput:\n  frontmatter: true\n";
+        let (config, recovered, reset) = repair_config_yaml(raw);
+
+        assert_eq!(config.llm.model, "llama3");
+        assert!(config.output.frontmatter);
+        assert_eq!(config.llm.temperature, default_config().llm.temperature);
+        assert_eq!(config.llm.base_url, "http://localhost:11434");
+        assert!(validate_config(&config).is_ok());
+
+        assert!(recovered.contains(&"llm.model".to_string()));
+        assert!(recovered.contains(&"output.frontmatter".to_string()));
+        assert!(!recovered.contains(&"llm.base_url".to_string()));
+        let reset_paths = reset
+            .iter()
+            .map(|field| field.field_path.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            reset_paths,
+            vec!["llm.temperature", "search", "llm.base_url"]
+        );
+    }
+
+    #[test]
+    fn validate_config_fields_rejects_malformed_llm_headers() {
+        let mut config = default_config();
+        config.llm.headers = [
+            ("X-Org-Id", "acme"),
+            ("Bad Header", "value"),
+            ("X-Route", "a\nb"),
+        ]
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+
+        let paths = validate_config_fields(&config)
+            .into_iter()
+            .map(|e| e.field_path)
+            .collect::<Vec<_>>();
+        assert_eq!(paths, vec!["llm.headers.Bad Header", "llm.headers.X-Route"]);
+    }
+
+    #[test]
+    fn validate_config_fields_reports_indexed_profile_paths() {
+        let mut config = default_config();
+        config.llm.profiles = vec![
+            LlmProfile {
+                name: "lmstudio".to_string(),
+                provider: Some("openai_compatible".to_string()),
+                base_url: Some("http://localhost:1234".to_string()),
+                model: None,
+                api_key: None,
+            },
+            LlmProfile {
+                name: "LMStudio".to_string(),
+                provider: Some("remote_cloud".to_string()),
+                base_url: Some("file:///tmp".to_string()),
+                model: None,
+                api_key: None,
+            },
+        ];
+
+        let paths = validate_config_fields(&config)
+            .into_iter()
+            .map(|e| e.field_path)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            paths,
+            vec![
+                "llm.profiles[1].name",
+                "llm.profiles[1].provider",
+                "llm.profiles[1].base_url"
+            ]
+        );
+
+        let raw = "llm:\n  model: llama3\n  profiles:\n    - name: active\n";
+        let (config, recovered, reset) = repair_config_yaml(raw);
+        assert!(config.llm.profiles.is_empty());
+        assert_eq!(config.llm.model, "llama3");
+        assert!(!recovered.contains(&"llm.profiles".to_string()));
+        assert_eq!(reset[0].field_path, "llm.profiles");
+    }
+
+    #[test]
+    fn repair_config_yaml_resets_everything_on_syntax_errors() {
+        let (config, recovered, reset) = repair_config_yaml("llm: [unclosed");
+        assert!(recovered.is_empty());
+        assert_eq!(reset.len(), 1);
+        assert_eq!(reset[0].field_path, "*");
+        assert_eq!(config.llm.model, default_config().llm.model);
+    }
+
+    #[test]
+    fn validate_config_rejects_first_token_timeout_shorter_than_idle() {
+        let mut config = default_config();
+        config.llm.first_token_timeout_secs = 30;
+        config.llm.stream_idle_timeout_secs = 60;
+        assert!(matches!(
+            validate_config(&config),
+            Err(ConfigError::InvalidValue(_))
+        ));
+        config.llm.stream_idle_timeout_secs = 0;
+        assert!(matches!(
+            validate_config(&config),
+            Err(ConfigError::InvalidValue(_))
+        ));
+    }
+
+    #[test]
+    fn validate_config_checks_request_timeout() {
+        let mut config = default_config();
+        config.llm.request_timeout_secs = 60;
+        let errors = validate_config_fields(&config);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].field_path, "llm.request_timeout_secs");
+
+        config.llm.first_token_timeout_secs = 60;
+        assert!(validate_config(&config).is_ok());
+
+        config.llm.request_timeout_secs = 0;
+        assert!(validate_config(&config).is_err());
+    }
+
+    #[test]
+    fn validate_config_rejects_out_of_range_llm_retries() {
+        let mut config = default_config();
+        config.llm.max_retries = MAX_LLM_RETRIES + 1;
+        let err = validate_config(&config).expect_err("too many retries should be rejected");
+        assert!(err.to_string().contains("llm.max_retries"));
+
+        config.llm.max_retries = 0;
+        assert!(validate_config(&config).is_ok());
+    }
+
+    #[test]
+    fn validate_config_rejects_out_of_range_import_read_workers() {
+        let mut config = default_config();
+        config.import.read_workers = importer::MAX_READ_WORKERS + 1;
+        let err = validate_config(&config).expect_err("too many workers should be rejected");
+        assert!(err.to_string().contains("import.read_workers"));
+
+        config.import.read_workers = 0;
+        assert!(validate_config(&config).is_ok());
+    }
+
+    #[test]
+    fn validate_config_rejects_out_of_range_generation_retries() {
+        let mut config = default_config();
+        config.output.generation_retries = MAX_GENERATION_RETRIES + 1;
+        let err = validate_config(&config).expect_err("too many retries should be rejected");
+        assert!(err.to_string().contains("output.generation_retries"));
+
+        config.output.generation_retries = 0;
+        assert!(validate_config(&config).is_ok());
+    }
+
+    #[test]
+    fn generation_context_limit_must_be_zero_or_large_enough() {
+        let mut config = default_config();
+        assert_eq!(config.output.generation_context_limit, 0);
+        config.output.generation_context_limit = 500;
+        assert!(matches!(
+            validate_config(&config),
+            Err(ConfigError::InvalidValue(_))
+        ));
+        config.output.generation_context_limit = 8000;
+        assert!(validate_config(&config).is_ok());
+    }
+
+    #[test]
+    fn chat_context_tokens_must_be_zero_or_large_enough() {
+        let mut config = default_config();
+        config.llm.context_tokens = 200;
+        let errors = validate_config_fields(&config);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].field_path, "llm.context_tokens");
+
+        config.llm.context_tokens = 0;
+        assert!(validate_config(&config).is_ok());
+    }
+
+    #[test]
+    fn export_folder_template_rejects_unknown_placeholders() {
+        let mut config = default_config();
+        assert_eq!(config.output.export_folder_template, "{name}-plan");
+        config.output.export_folder_template = "{date}-{name}".to_string();
+        assert!(validate_config(&config).is_ok());
+
+        config.output.export_folder_template = "{project}-plan".to_string();
+        let err = validate_config(&config).expect_err("unknown placeholder should be rejected");
+        assert!(err.to_string().contains("output.export_folder_template"));
+
+        config.output.export_folder_template = "  ".to_string();
+        assert!(normalize_local_model_config(&mut config));
+        assert_eq!(config.output.export_folder_template, "{name}-plan");
+    }
+
+    #[test]
+    fn config_section_sources_tracks_keys_present_in_file() {
+        let all_file = config_section_sources(Some(DEFAULT_CONFIG_YAML));
+        assert_eq!(all_file.llm, ConfigSource::File);
+        assert_eq!(all_file.rate_limit, ConfigSource::File);
+
+        let partial = "llm:\n  provider: ollama\noutput:\n  frontmatter: true\n";
+        let sources = config_section_sources(Some(partial));
+        assert_eq!(sources.llm, ConfigSource::File);
+        assert_eq!(sources.output, ConfigSource::File);
+        assert_eq!(sources.search, ConfigSource::Default);
+        assert_eq!(sources.rate_limit, ConfigSource::Default);
+
+        let fallback = config_section_sources(None);
+        assert_eq!(fallback.llm, ConfigSource::Default);
+        assert_eq!(
+            config_section_sources(Some(": not yaml [")).ui,
+            ConfigSource::Default
+        );
+    }
+
+    #[test]
+    fn suggest_base_url_when_switching_from_ollama_default() {
+        assert_eq!(
+            suggest_base_url("ollama", "openai_compatible", "http://localhost:11434"),
+            Some("http://localhost:1234")
+        );
+        assert_eq!(
+            suggest_base_url("openai_compatible", "ollama", "http://127.0.0.1:1234/"),
+            Some("http://localhost:11434")
+        );
+    }
+
+    #[test]
+    fn suggest_base_url_leaves_custom_urls_alone() {
+        assert_eq!(
+            suggest_base_url("ollama", "openai_compatible", "http://gpu-box:8080"),
+            None
+        );
+        assert_eq!(
+            suggest_base_url("ollama", "ollama", "http://localhost:11434"),
+            None
+        );
+        assert_eq!(
+            suggest_base_url("mystery", "ollama", "http://localhost:1234"),
+            None
+        );
+    }
+
+    #[test]
+    fn normalize_local_model_config_resets_unknown_doc_validation() {
+        let mut config = default_config();
+        config.output.doc_validation = "paranoid".to_string();
+        assert!(normalize_local_model_config(&mut config));
+        assert_eq!(config.output.doc_validation, "strict");
+    }
+
+    #[test]
+    fn trigger_overrides_are_normalized_and_validated() {
+        let mut config = default_config();
+        config.search.trigger_overrides.never = vec![" Rust ".to_string(), "  ".to_string()];
+        assert!(normalize_local_model_config(&mut config));
+        assert_eq!(config.search.trigger_overrides.never, vec!["rust"]);
+        assert!(validate_config(&config).is_ok());
+
+        config.search.trigger_overrides.always = vec!["a * b * c".to_string()];
+        let err = validate_config(&config).expect_err("two wildcards should be rejected");
+        assert!(err.to_string().contains("search.trigger_overrides.always"));
+    }
+
+    #[test]
+    fn search_region_and_safe_search_are_normalized_and_validated() {
+        let mut config = default_config();
+        config.search.region = Some(" DE-de ".to_string());
+        config.search.safe_search = Some("".to_string());
+        assert!(normalize_local_model_config(&mut config));
+        assert_eq!(config.search.region.as_deref(), Some("de-de"));
+        assert!(config.search.safe_search.is_none());
+        assert!(validate_config(&config).is_ok());
+
+        config.search.region = Some("germany".to_string());
+        let err = validate_config(&config).expect_err("malformed region should be rejected");
+        assert!(err.to_string().contains("search.region"));
+
+        config.search.region = None;
+        config.search.safe_search = Some("sometimes".to_string());
+        let err = validate_config(&config).expect_err("unknown safe_search should be rejected");
+        assert!(err.to_string().contains("search.safe_search"));
+    }
+
     #[cfg(unix)]
     #[test]
     fn write_config_atomically_sets_0600_permissions() {
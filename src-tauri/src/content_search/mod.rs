@@ -0,0 +1,254 @@
+use serde::{Deserialize, Serialize};
+
+use crate::types::ContentSearchRow;
+
+const CONTEXT_WORDS: usize = 8;
+const CONTEXT_LINES: usize = 1;
+const MAX_SNIPPET_CHARS: usize = 240;
+const ELLIPSIS: char = '…';
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ContentSource {
+    Message,
+    Document,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContentSearchHit {
+    pub session_id: String,
+    pub session_name: String,
+    pub source: ContentSource,
+    pub source_id: String,
+    pub label: String,
+    pub snippet: String,
+    pub match_offset: usize,
+    pub match_len: usize,
+    pub line: Option<usize>,
+}
+
+pub fn build_hits(rows: Vec<ContentSearchRow>, query: &str) -> Vec<ContentSearchHit> {
+    rows.into_iter()
+        .filter_map(|row| {
+            let content = row.content.chars().collect::<Vec<_>>();
+            let (start, end) = find_match(&content, query)?;
+            let source = if row.is_document {
+                ContentSource::Document
+            } else {
+                ContentSource::Message
+            };
+            let (snippet, match_offset, line) = match source {
+                ContentSource::Message => {
+                    let (snippet, offset) = message_window(&content, start, end);
+                    (snippet, offset, None)
+                }
+                ContentSource::Document => {
+                    let (snippet, offset, line) = document_window(&content, start, end);
+                    (snippet, offset, Some(line))
+                }
+            };
+            Some(ContentSearchHit {
+                session_id: row.session_id,
+                session_name: row.session_name,
+                source,
+                source_id: row.source_id,
+                label: row.label,
+                snippet,
+                match_offset,
+                match_len: end - start,
+                line,
+            })
+        })
+        .collect()
+}
+
+/// Case-insensitive search over chars, so offsets stay valid for any script.
+fn find_match(content: &[char], query: &str) -> Option<(usize, usize)> {
+    let needle = query.chars().map(fold_char).collect::<Vec<_>>();
+    if needle.is_empty() || needle.len() > content.len() {
+        return None;
+    }
+    let haystack = content.iter().copied().map(fold_char).collect::<Vec<_>>();
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle.as_slice())
+        .map(|start| (start, start + needle.len()))
+}
+
+fn fold_char(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+fn message_window(content: &[char], start: usize, end: usize) -> (String, usize) {
+    let mut from = start;
+    for _ in 0..CONTEXT_WORDS {
+        while from > 0 && content[from - 1].is_whitespace() {
+            from -= 1;
+        }
+        while from > 0 && !content[from - 1].is_whitespace() {
+            from -= 1;
+        }
+    }
+    let mut to = end;
+    for _ in 0..CONTEXT_WORDS {
+        while to < content.len() && content[to].is_whitespace() {
+            to += 1;
+        }
+        while to < content.len() && !content[to].is_whitespace() {
+            to += 1;
+        }
+    }
+
+    let (snippet, offset) = render_window(content, from, to, start, end);
+    let flattened = snippet
+        .chars()
+        .map(|c| if c == '\n' || c == '\r' { ' ' } else { c })
+        .collect();
+    (flattened, offset)
+}
+
+fn document_window(content: &[char], start: usize, end: usize) -> (String, usize, usize) {
+    let line = content[..start].iter().filter(|c| **c == '\n').count() + 1;
+
+    let mut from = start;
+    let mut newlines = 0;
+    while from > 0 {
+        if content[from - 1] == '\n' {
+            if newlines == CONTEXT_LINES {
+                break;
+            }
+            newlines += 1;
+        }
+        from -= 1;
+    }
+    let mut to = end;
+    let mut newlines = 0;
+    while to < content.len() {
+        if content[to] == '\n' {
+            if newlines == CONTEXT_LINES {
+                break;
+            }
+            newlines += 1;
+        }
+        to += 1;
+    }
+
+    let (snippet, offset) = render_window(content, from, to, start, end);
+    (snippet, offset, line)
+}
+
+fn render_window(
+    content: &[char],
+    mut from: usize,
+    mut to: usize,
+    start: usize,
+    end: usize,
+) -> (String, usize) {
+    if to - from > MAX_SNIPPET_CHARS {
+        let match_len = end - start;
+        let budget = MAX_SNIPPET_CHARS.saturating_sub(match_len);
+        let before = (budget / 2).min(start - from);
+        let after = budget.saturating_sub(before).min(to - end);
+        let before = budget.saturating_sub(after).min(start - from);
+        from = start - before;
+        to = (end + after).min(content.len());
+    }
+    while from < start && content[from].is_whitespace() {
+        from += 1;
+    }
+    while to > end && content[to - 1].is_whitespace() {
+        to -= 1;
+    }
+
+    let mut snippet = String::new();
+    let mut offset = start - from;
+    if from > 0 {
+        snippet.push(ELLIPSIS);
+        offset += 1;
+    }
+    snippet.extend(&content[from..to]);
+    if to < content.len() {
+        snippet.push(ELLIPSIS);
+    }
+    (snippet, offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(is_document: bool, content: &str) -> ContentSearchRow {
+        ContentSearchRow {
+            session_id: "s1".to_string(),
+            session_name: "Session".to_string(),
+            is_document,
+            source_id: "id".to_string(),
+            label: if is_document { "SPEC.md" } else { "user" }.to_string(),
+            content: content.to_string(),
+        }
+    }
+
+    fn highlighted(hit: &ContentSearchHit) -> String {
+        hit.snippet
+            .chars()
+            .skip(hit.match_offset)
+            .take(hit.match_len)
+            .collect()
+    }
+
+    #[test]
+    fn message_hit_includes_word_context_and_offset() {
+        let content = "one two three four five six seven eight nine ten \
+                       the Database layer uses sqlite with WAL mode enabled for all writes \
+                       and then some more trailing words follow here";
+        let hits = build_hits(vec![row(false, content)], "database");
+        assert_eq!(hits.len(), 1);
+        let hit = &hits[0];
+        assert_eq!(highlighted(hit), "Database");
+        assert!(hit.snippet.starts_with('…'));
+        assert!(hit.snippet.ends_with('…'));
+        assert!(hit.snippet.contains("ten the Database layer"));
+        assert!(hit.line.is_none());
+    }
+
+    #[test]
+    fn match_at_start_and_end_has_no_leading_or_trailing_ellipsis() {
+        let hits = build_hits(vec![row(false, "Tauri app\nwith sqlite")], "tauri");
+        assert_eq!(hits[0].match_offset, 0);
+        assert_eq!(hits[0].snippet, "Tauri app with sqlite");
+
+        let hits = build_hits(vec![row(false, "we store data in sqlite")], "SQLITE");
+        assert_eq!(highlighted(&hits[0]), "sqlite");
+        assert!(!hits[0].snippet.ends_with('…'));
+    }
+
+    #[test]
+    fn document_hit_uses_surrounding_lines() {
+        let content =
+            "# Spec\n\n## Data\nSessions live in sqlite.\nMessages too.\n\n## Other\nText";
+        let hits = build_hits(vec![row(true, content)], "sqlite");
+        let hit = &hits[0];
+        assert_eq!(hit.line, Some(4));
+        assert_eq!(
+            hit.snippet,
+            "…## Data\nSessions live in sqlite.\nMessages too.…"
+        );
+        assert_eq!(highlighted(hit), "sqlite");
+    }
+
+    #[test]
+    fn long_windows_are_capped_around_the_match() {
+        let line = format!("{}needle{}", "a".repeat(500), "b".repeat(500));
+        let hits = build_hits(vec![row(true, &line)], "needle");
+        let hit = &hits[0];
+        assert!(hit.snippet.chars().count() <= MAX_SNIPPET_CHARS + 2);
+        assert_eq!(highlighted(hit), "needle");
+    }
+
+    #[test]
+    fn offsets_are_char_based_for_multibyte_content() {
+        let hits = build_hits(vec![row(false, "Ünïcode façade naïve")], "NAÏVE");
+        assert_eq!(highlighted(&hits[0]), "naïve");
+        assert!(build_hits(vec![row(false, "nothing here")], "absent").is_empty());
+    }
+}
@@ -2,8 +2,14 @@ use rusqlite::{params, Connection};
 use std::path::Path;
 use std::sync::Mutex;
 
+use crate::timestamp;
 use crate::types::*;
 
+/// Schema changes on top of the version-1 baseline that `initialize` creates,
+/// as `(version, sql)` in ascending order. Append new steps with the next
+/// version; never edit or reorder a step that has shipped.
+const MIGRATIONS: &[(u32, &str)] = &[];
+
 /// Validates that a string is a safe SQL identifier (table name, column name, etc.).
 /// Only allows `[a-zA-Z_][a-zA-Z0-9_]*`. Panics on invalid input because callers
 /// always pass hardcoded strings.
@@ -19,6 +25,86 @@ fn validate_identifier(name: &str) {
     assert!(valid, "Invalid SQL identifier: {:?}", name);
 }
 
+fn escape_like(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Runs are swept before their artifacts so artifacts of just-removed runs are
+/// caught too.
+const ORPHAN_SWEEPS: &[(&str, &str)] = &[
+    ("messages", "session_id NOT IN (SELECT id FROM sessions)"),
+    ("documents", "session_id NOT IN (SELECT id FROM sessions)"),
+    (
+        "document_versions",
+        "session_id NOT IN (SELECT id FROM sessions)",
+    ),
+    (
+        "generation_metadata",
+        "session_id NOT IN (SELECT id FROM sessions)",
+    ),
+    (
+        "generation_drafts",
+        "session_id NOT IN (SELECT id FROM sessions)",
+    ),
+    (
+        "generation_runs",
+        "session_id NOT IN (SELECT id FROM sessions)",
+    ),
+    (
+        "generation_run_artifacts",
+        "run_id NOT IN (SELECT run_id FROM generation_runs)",
+    ),
+    (
+        "session_branches",
+        "branch_session_id NOT IN (SELECT id FROM sessions)",
+    ),
+    (
+        "session_snapshots",
+        "session_id NOT IN (SELECT id FROM sessions)",
+    ),
+    (
+        "session_snapshot_messages",
+        "snapshot_id NOT IN (SELECT id FROM session_snapshots)",
+    ),
+    (
+        "session_snapshot_documents",
+        "snapshot_id NOT IN (SELECT id FROM session_snapshots)",
+    ),
+];
+
+const SNAPSHOT_SELECT: &str = "
+    SELECT sn.id, sn.session_id, sn.label,
+           (SELECT COUNT(*) FROM session_snapshot_messages m WHERE m.snapshot_id = sn.id),
+           (SELECT COUNT(*) FROM session_snapshot_documents d WHERE d.snapshot_id = sn.id),
+           sn.created_at
+    FROM session_snapshots sn";
+
+const CONTENT_SEARCH_SQL: &str = "
+    SELECT hit.session_id, s.name, hit.is_document, hit.source_id, hit.label, hit.content
+    FROM (
+        SELECT m.session_id, 0 AS is_document, m.id AS source_id, m.role AS label,
+               m.content, m.created_at, m.rowid AS seq
+        FROM messages m
+        WHERE (?1 IS NULL OR m.session_id = ?1) AND m.role != 'system'
+          AND m.content LIKE ?2 ESCAPE '\\'
+        UNION ALL
+        SELECT d.session_id, 1, d.id, d.filename, d.content, d.created_at, d.rowid
+        FROM documents d
+        WHERE (?1 IS NULL OR d.session_id = ?1)
+          AND d.content LIKE ?2 ESCAPE '\\'
+    ) hit
+    JOIN sessions s ON s.id = hit.session_id
+    ORDER BY s.updated_at DESC, s.rowid DESC, hit.is_document ASC,
+             hit.created_at DESC, hit.seq DESC
+    LIMIT ?3 OFFSET ?4";
+
 pub struct Database {
     conn: Mutex<Connection>,
 }
@@ -51,7 +137,7 @@ impl Database {
     }
 
     fn initialize(&self) -> Result<(), rusqlite::Error> {
-        let conn = self.conn();
+        let mut conn = self.conn();
         conn.execute_batch(
             "
             CREATE TABLE IF NOT EXISTS sessions (
@@ -123,6 +209,56 @@ impl Database {
                 created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
                 FOREIGN KEY (branch_session_id) REFERENCES sessions(id) ON DELETE CASCADE
             );
+            CREATE TABLE IF NOT EXISTS document_versions (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                document_id TEXT NOT NULL,
+                session_id TEXT NOT NULL,
+                filename TEXT NOT NULL,
+                content TEXT NOT NULL,
+                created_at TIMESTAMP,
+                archived_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+                reason TEXT NOT NULL,
+                FOREIGN KEY (session_id) REFERENCES sessions(id) ON DELETE CASCADE
+            );
+            CREATE TABLE IF NOT EXISTS generation_drafts (
+                run_id TEXT NOT NULL,
+                session_id TEXT NOT NULL,
+                filename TEXT NOT NULL,
+                position INTEGER NOT NULL,
+                content TEXT NOT NULL,
+                created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+                PRIMARY KEY (run_id, filename),
+                FOREIGN KEY (session_id) REFERENCES sessions(id) ON DELETE CASCADE
+            );
+            CREATE TABLE IF NOT EXISTS session_snapshots (
+                id TEXT PRIMARY KEY,
+                session_id TEXT NOT NULL,
+                label TEXT NOT NULL,
+                created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+                UNIQUE (session_id, label),
+                FOREIGN KEY (session_id) REFERENCES sessions(id) ON DELETE CASCADE
+            );
+            CREATE TABLE IF NOT EXISTS session_snapshot_messages (
+                snapshot_id TEXT NOT NULL,
+                position INTEGER NOT NULL,
+                message_id TEXT NOT NULL,
+                role TEXT NOT NULL,
+                content TEXT NOT NULL,
+                metadata TEXT,
+                created_at TIMESTAMP,
+                PRIMARY KEY (snapshot_id, position),
+                FOREIGN KEY (snapshot_id) REFERENCES session_snapshots(id) ON DELETE CASCADE
+            );
+            CREATE TABLE IF NOT EXISTS session_snapshot_documents (
+                snapshot_id TEXT NOT NULL,
+                position INTEGER NOT NULL,
+                document_id TEXT NOT NULL,
+                filename TEXT NOT NULL,
+                content TEXT NOT NULL,
+                created_at TIMESTAMP,
+                PRIMARY KEY (snapshot_id, position),
+                FOREIGN KEY (snapshot_id) REFERENCES session_snapshots(id) ON DELETE CASCADE
+            );
             CREATE TABLE IF NOT EXISTS schema_migrations (
                 version INTEGER PRIMARY KEY
             );
@@ -133,13 +269,50 @@ impl Database {
             CREATE INDEX IF NOT EXISTS idx_generation_metadata_created ON generation_metadata(created_at DESC);
             CREATE INDEX IF NOT EXISTS idx_generation_runs_session_created ON generation_runs(session_id, created_at DESC);
             CREATE INDEX IF NOT EXISTS idx_branch_root ON session_branches(root_session_id);
+            CREATE INDEX IF NOT EXISTS idx_document_versions_session ON document_versions(session_id, archived_at DESC);
+            CREATE INDEX IF NOT EXISTS idx_generation_drafts_session ON generation_drafts(session_id);
             ",
         )?;
         Self::ensure_column_exists(&conn, "generation_metadata", "confidence_json", "TEXT")?;
         Self::ensure_column_exists(&conn, "generation_metadata", "run_id", "TEXT")?;
+        Self::ensure_column_exists(&conn, "generation_metadata", "warnings_json", "TEXT")?;
+        Self::ensure_column_exists(&conn, "generation_metadata", "timing_json", "TEXT")?;
+        Self::ensure_column_exists(&conn, "generation_metadata", "seed", "INTEGER")?;
+        Self::ensure_column_exists(&conn, "sessions", "scratchpad", "TEXT")?;
+        Self::ensure_column_exists(&conn, "sessions", "completed_at", "TEXT")?;
+        Self::ensure_column_exists(&conn, "sessions", "document_order", "TEXT")?;
+        Self::run_migrations(&mut conn, MIGRATIONS)?;
         Ok(())
     }
 
+    /// Each step runs in its own transaction together with its version row, so a
+    /// failing step leaves the database at the last version that fully applied.
+    fn run_migrations(
+        conn: &mut Connection,
+        migrations: &[(u32, &str)],
+    ) -> Result<u32, rusqlite::Error> {
+        let mut current: u32 = conn.query_row(
+            "SELECT COALESCE(MAX(version), 0) FROM schema_migrations",
+            [],
+            |row| row.get(0),
+        )?;
+        for (version, sql) in migrations {
+            if *version <= current {
+                continue;
+            }
+            let tx = conn.transaction()?;
+            tx.execute_batch(sql)?;
+            tx.execute(
+                "INSERT INTO schema_migrations (version) VALUES (?1)",
+                params![version],
+            )?;
+            tx.commit()?;
+            log::info!("Applied schema migration {}", version);
+            current = *version;
+        }
+        Ok(current)
+    }
+
     pub fn is_ok(&self) -> bool {
         let conn = self.conn();
         conn.execute_batch("SELECT 1").is_ok()
@@ -163,7 +336,7 @@ impl Database {
     pub fn get_sessions(&self) -> Result<Vec<Session>, rusqlite::Error> {
         let conn = self.conn();
         let mut stmt = conn.prepare(
-            "SELECT id, name, description, status, created_at, updated_at FROM sessions ORDER BY updated_at DESC",
+            "SELECT id, name, description, status, created_at, updated_at, completed_at FROM sessions ORDER BY updated_at DESC",
         )?;
 
         let rows = stmt.query_map([], |row| {
@@ -172,19 +345,148 @@ impl Database {
                 name: row.get(1)?,
                 description: row.get(2)?,
                 status: row.get(3)?,
-                created_at: row.get(4)?,
-                updated_at: row.get(5)?,
+                created_at: timestamp::normalize(row.get(4)?),
+                updated_at: timestamp::normalize(row.get(5)?),
+                completed_at: row.get::<_, Option<String>>(6)?.map(timestamp::normalize),
+            })
+        })?;
+
+        rows.collect()
+    }
+
+    pub fn get_sessions_with_status(&self) -> Result<Vec<SessionListEntry>, rusqlite::Error> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT s.id, s.name, s.description, s.status, s.created_at, s.updated_at, s.completed_at,
+                    (SELECT MAX(d.created_at) FROM documents d WHERE d.session_id = s.id),
+                    (SELECT MAX(m.created_at) FROM messages m WHERE m.session_id = s.id)
+             FROM sessions s
+             ORDER BY s.updated_at DESC",
+        )?;
+
+        let rows = stmt.query_map([], |row| {
+            let latest_doc: Option<String> = row.get(7)?;
+            let latest_msg: Option<String> = row.get(8)?;
+            Ok(SessionListEntry {
+                session: Session {
+                    id: row.get(0)?,
+                    name: row.get(1)?,
+                    description: row.get(2)?,
+                    status: row.get(3)?,
+                    created_at: timestamp::normalize(row.get(4)?),
+                    updated_at: timestamp::normalize(row.get(5)?),
+                    completed_at: row.get::<_, Option<String>>(6)?.map(timestamp::normalize),
+                },
+                has_documents: latest_doc.is_some(),
+                docs_stale: match (&latest_doc, &latest_msg) {
+                    (Some(doc), Some(msg)) => timestamp::is_after(msg, doc).unwrap_or(true),
+                    _ => false,
+                },
             })
         })?;
 
         rows.collect()
     }
 
+    /// LIKE wildcards in `name_query` are treated literally.
+    pub fn get_sessions_paged(
+        &self,
+        limit: usize,
+        offset: usize,
+        status_filter: Option<&str>,
+        name_query: Option<&str>,
+    ) -> Result<SessionPage, rusqlite::Error> {
+        let conn = self.conn();
+        let name_pattern = name_query.map(|query| format!("%{}%", escape_like(query)));
+        let filter = "(?1 IS NULL OR status = ?1) AND (?2 IS NULL OR name LIKE ?2 ESCAPE '\\')";
+
+        let total: i64 = conn.query_row(
+            &format!("SELECT COUNT(*) FROM sessions WHERE {}", filter),
+            params![status_filter, name_pattern],
+            |row| row.get(0),
+        )?;
+
+        let mut stmt = conn.prepare(&format!(
+            "SELECT id, name, description, status, created_at, updated_at, completed_at FROM sessions \
+             WHERE {} ORDER BY updated_at DESC, rowid DESC LIMIT ?3 OFFSET ?4",
+            filter
+        ))?;
+        let rows = stmt.query_map(
+            params![status_filter, name_pattern, limit as i64, offset as i64],
+            |row| {
+                Ok(Session {
+                    id: row.get(0)?,
+                    name: row.get(1)?,
+                    description: row.get(2)?,
+                    status: row.get(3)?,
+                    created_at: timestamp::normalize(row.get(4)?),
+                    updated_at: timestamp::normalize(row.get(5)?),
+                    completed_at: row.get::<_, Option<String>>(6)?.map(timestamp::normalize),
+                })
+            },
+        )?;
+
+        Ok(SessionPage {
+            sessions: rows.collect::<Result<Vec<_>, _>>()?,
+            total: total.max(0) as usize,
+        })
+    }
+
     pub fn get_session(&self, session_id: &str) -> Result<Session, rusqlite::Error> {
         let conn = self.conn();
         Self::read_session_row(&conn, session_id)
     }
 
+    pub fn get_session_scratchpad(&self, session_id: &str) -> Result<String, rusqlite::Error> {
+        let conn = self.conn();
+        conn.query_row(
+            "SELECT scratchpad FROM sessions WHERE id = ?1",
+            params![session_id],
+            |row| row.get::<_, Option<String>>(0),
+        )
+        .map(Option::unwrap_or_default)
+    }
+
+    pub fn set_session_scratchpad(
+        &self,
+        session_id: &str,
+        content: &str,
+    ) -> Result<usize, rusqlite::Error> {
+        let conn = self.conn();
+        conn.execute(
+            "UPDATE sessions SET scratchpad = ?1 WHERE id = ?2",
+            params![content, session_id],
+        )
+    }
+
+    pub fn get_document_order(&self, session_id: &str) -> Result<Vec<String>, rusqlite::Error> {
+        let conn = self.conn();
+        let stored: Option<String> = conn.query_row(
+            "SELECT document_order FROM sessions WHERE id = ?1",
+            params![session_id],
+            |row| row.get(0),
+        )?;
+        Ok(stored
+            .unwrap_or_default()
+            .lines()
+            .filter(|name| !name.is_empty())
+            .map(str::to_string)
+            .collect())
+    }
+
+    pub fn set_document_order(
+        &self,
+        session_id: &str,
+        order: &[String],
+    ) -> Result<usize, rusqlite::Error> {
+        let conn = self.conn();
+        let stored = (!order.is_empty()).then(|| order.join("\n"));
+        conn.execute(
+            "UPDATE sessions SET document_order = ?1 WHERE id = ?2",
+            params![stored, session_id],
+        )
+    }
+
     pub fn update_session(
         &self,
         session_id: &str,
@@ -202,7 +504,9 @@ impl Database {
         }
         if let Some(s) = status {
             tx.execute(
-                "UPDATE sessions SET status = ?1, updated_at = CURRENT_TIMESTAMP WHERE id = ?2",
+                "UPDATE sessions SET status = ?1, updated_at = CURRENT_TIMESTAMP,
+                    completed_at = CASE WHEN ?1 = 'completed' THEN completed_at END
+                 WHERE id = ?2",
                 params![s, session_id],
             )?;
         }
@@ -211,6 +515,20 @@ impl Database {
         Self::read_session_row(&conn, session_id)
     }
 
+    pub fn mark_session_completed(&self, session_id: &str) -> Result<Session, rusqlite::Error> {
+        let conn = self.conn();
+        let updated = conn.execute(
+            "UPDATE sessions SET status = 'completed', completed_at = CURRENT_TIMESTAMP,
+                updated_at = CURRENT_TIMESTAMP
+             WHERE id = ?1",
+            params![session_id],
+        )?;
+        if updated == 0 {
+            return Err(rusqlite::Error::QueryReturnedNoRows);
+        }
+        Self::read_session_row(&conn, session_id)
+    }
+
     pub fn delete_session(&self, session_id: &str) -> Result<(), rusqlite::Error> {
         let conn = self.conn();
         conn.execute("DELETE FROM sessions WHERE id = ?1", params![session_id])?;
@@ -228,6 +546,82 @@ impl Database {
         Ok(deleted)
     }
 
+    pub fn cleanup_orphans(&self) -> Result<OrphanCleanupReport, rusqlite::Error> {
+        let mut conn = self.conn();
+        let tx = conn.transaction()?;
+        let mut tables = Vec::with_capacity(ORPHAN_SWEEPS.len());
+        for (table, condition) in ORPHAN_SWEEPS {
+            let deleted = tx.execute(&format!("DELETE FROM {} WHERE {}", table, condition), [])?;
+            tables.push(OrphanTableCount {
+                table: table.to_string(),
+                deleted,
+            });
+        }
+        tx.commit()?;
+
+        let total = tables.iter().map(|entry| entry.deleted).sum();
+        Ok(OrphanCleanupReport { tables, total })
+    }
+
+    pub fn housekeep_sessions(
+        &self,
+        policy: &HousekeepingConfig,
+    ) -> Result<HousekeepingReport, rusqlite::Error> {
+        let delete = policy.action == "delete";
+        let mut conn = self.conn();
+        let tx = conn.transaction()?;
+        let sessions = {
+            let mut stmt = tx.prepare(
+                "SELECT id, name, description, status, created_at, updated_at, completed_at
+                 FROM sessions s
+                 WHERE datetime(s.updated_at) < datetime('now', ?1)
+                   AND (?2 OR NOT EXISTS (SELECT 1 FROM documents d WHERE d.session_id = s.id))
+                   AND (?3 OR s.status = 'active')
+                   AND (?4 OR s.status != 'archived')
+                 ORDER BY s.updated_at ASC",
+            )?;
+            let rows = stmt.query_map(
+                params![
+                    format!("-{} days", policy.max_idle_days),
+                    policy.include_with_documents,
+                    policy.include_inactive,
+                    delete,
+                ],
+                |row| {
+                    Ok(Session {
+                        id: row.get(0)?,
+                        name: row.get(1)?,
+                        description: row.get(2)?,
+                        status: row.get(3)?,
+                        created_at: timestamp::normalize(row.get(4)?),
+                        updated_at: timestamp::normalize(row.get(5)?),
+                        completed_at: row.get::<_, Option<String>>(6)?.map(timestamp::normalize),
+                    })
+                },
+            )?;
+            rows.collect::<Result<Vec<_>, _>>()?
+        };
+
+        for session in &sessions {
+            if delete {
+                tx.execute("DELETE FROM sessions WHERE id = ?1", params![session.id])?;
+            } else {
+                tx.execute(
+                    "UPDATE sessions SET status = 'archived', completed_at = NULL WHERE id = ?1",
+                    params![session.id],
+                )?;
+            }
+        }
+        tx.commit()?;
+
+        Ok(HousekeepingReport {
+            action: policy.action.clone(),
+            max_idle_days: policy.max_idle_days,
+            sessions,
+            ran_at: timestamp::now(),
+        })
+    }
+
     pub fn get_branch_root_session_id(&self, session_id: &str) -> Result<String, rusqlite::Error> {
         let conn = self.conn();
         match conn.query_row(
@@ -264,7 +658,7 @@ impl Database {
 
     fn read_session_row(conn: &Connection, id: &str) -> Result<Session, rusqlite::Error> {
         conn.query_row(
-            "SELECT id, name, description, status, created_at, updated_at FROM sessions WHERE id = ?1",
+            "SELECT id, name, description, status, created_at, updated_at, completed_at FROM sessions WHERE id = ?1",
             params![id],
             |row| {
                 Ok(Session {
@@ -272,8 +666,11 @@ impl Database {
                     name: row.get(1)?,
                     description: row.get(2)?,
                     status: row.get(3)?,
-                    created_at: row.get(4)?,
-                    updated_at: row.get(5)?,
+                    created_at: timestamp::normalize(row.get(4)?),
+                    updated_at: timestamp::normalize(row.get(5)?),
+                    completed_at: row
+                        .get::<_, Option<String>>(6)?
+                        .map(timestamp::normalize),
                 })
             },
         )
@@ -309,7 +706,7 @@ impl Database {
                     role: row.get(2)?,
                     content: row.get(3)?,
                     metadata: row.get(4)?,
-                    created_at: row.get(5)?,
+                    created_at: timestamp::normalize(row.get(5)?),
                 })
             },
         )?;
@@ -317,6 +714,29 @@ impl Database {
         Ok(msg)
     }
 
+    pub fn update_message(
+        &self,
+        session_id: &str,
+        message_id: &str,
+        content: &str,
+        metadata: Option<&str>,
+    ) -> Result<usize, rusqlite::Error> {
+        let mut conn = self.conn();
+        let tx = conn.transaction()?;
+        let updated = tx.execute(
+            "UPDATE messages SET content = ?3, metadata = ?4 WHERE id = ?2 AND session_id = ?1",
+            params![session_id, message_id, content, metadata],
+        )?;
+        if updated > 0 {
+            tx.execute(
+                "UPDATE sessions SET updated_at = CURRENT_TIMESTAMP WHERE id = ?1",
+                params![session_id],
+            )?;
+        }
+        tx.commit()?;
+        Ok(updated)
+    }
+
     pub fn get_messages(&self, session_id: &str) -> Result<Vec<Message>, rusqlite::Error> {
         let conn = self.conn();
         let mut stmt = conn.prepare(
@@ -333,7 +753,7 @@ impl Database {
                 role: row.get(2)?,
                 content: row.get(3)?,
                 metadata: row.get(4)?,
-                created_at: row.get(5)?,
+                created_at: timestamp::normalize(row.get(5)?),
             })
         })?;
 
@@ -361,13 +781,21 @@ impl Database {
         Ok(rows > 0)
     }
 
-    pub fn message_count(&self, session_id: &str) -> Result<i64, rusqlite::Error> {
+    pub fn first_user_message_id(
+        &self,
+        session_id: &str,
+    ) -> Result<Option<String>, rusqlite::Error> {
         let conn = self.conn();
-        conn.query_row(
-            "SELECT COUNT(*) FROM messages WHERE session_id = ?1 AND role = 'user'",
+        match conn.query_row(
+            "SELECT id FROM messages WHERE session_id = ?1 AND role = 'user'
+             ORDER BY rowid ASC LIMIT 1",
             params![session_id],
             |row| row.get(0),
-        )
+        ) {
+            Ok(id) => Ok(Some(id)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
     }
 
     // ---- Documents ----
@@ -396,7 +824,7 @@ impl Database {
                     session_id: row.get(1)?,
                     filename: row.get(2)?,
                     content: row.get(3)?,
-                    created_at: row.get(4)?,
+                    created_at: timestamp::normalize(row.get(4)?),
                 })
             },
         )
@@ -417,13 +845,132 @@ impl Database {
                 session_id: row.get(1)?,
                 filename: row.get(2)?,
                 content: row.get(3)?,
-                created_at: row.get(4)?,
+                created_at: timestamp::normalize(row.get(4)?),
+            })
+        })?;
+
+        rows.collect()
+    }
+
+    pub fn get_sessions_with_documents(
+        &self,
+    ) -> Result<Vec<(Session, Vec<GeneratedDocument>)>, rusqlite::Error> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT s.id, s.name, s.description, s.status, s.created_at, s.updated_at, s.completed_at,
+                    d.id, d.filename, d.content, d.created_at
+             FROM sessions s
+             JOIN documents d ON d.session_id = s.id
+             ORDER BY s.rowid, d.created_at ASC, d.rowid ASC",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            let session = Session {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                description: row.get(2)?,
+                status: row.get(3)?,
+                created_at: timestamp::normalize(row.get(4)?),
+                updated_at: timestamp::normalize(row.get(5)?),
+                completed_at: row.get::<_, Option<String>>(6)?.map(timestamp::normalize),
+            };
+            let doc = GeneratedDocument {
+                id: row.get(7)?,
+                session_id: session.id.clone(),
+                filename: row.get(8)?,
+                content: row.get(9)?,
+                created_at: timestamp::normalize(row.get(10)?),
+            };
+            Ok((session, doc))
+        })?;
+
+        let mut sessions: Vec<(Session, Vec<GeneratedDocument>)> = Vec::new();
+        for row in rows {
+            let (session, doc) = row?;
+            match sessions.last_mut() {
+                Some((last, docs)) if last.id == session.id => docs.push(doc),
+                _ => sessions.push((session, vec![doc])),
+            }
+        }
+        Ok(sessions)
+    }
+
+    pub fn list_document_names(
+        &self,
+        session_id: &str,
+    ) -> Result<Vec<DocumentSummary>, rusqlite::Error> {
+        let conn = self.conn();
+        // length() on TEXT counts characters; cast to BLOB to report bytes.
+        let mut stmt = conn.prepare(
+            "SELECT filename, length(CAST(content AS BLOB)), created_at FROM documents WHERE session_id = ?1 ORDER BY created_at ASC, rowid ASC",
+        )?;
+
+        let rows = stmt.query_map(params![session_id], |row| {
+            Ok(DocumentSummary {
+                filename: row.get(0)?,
+                bytes: row.get::<_, i64>(1)? as usize,
+                created_at: timestamp::normalize(row.get(2)?),
             })
         })?;
 
         rows.collect()
     }
 
+    pub fn get_document_chunk(
+        &self,
+        session_id: &str,
+        filename: &str,
+        offset: usize,
+        length: usize,
+    ) -> Result<Option<DocumentChunk>, rusqlite::Error> {
+        let conn = self.conn();
+        match conn.query_row(
+            "SELECT substr(content, ?3 + 1, ?4), length(content) FROM documents
+             WHERE session_id = ?1 AND filename = ?2
+             ORDER BY created_at DESC, rowid DESC LIMIT 1",
+            params![session_id, filename, offset as i64, length as i64],
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)? as usize)),
+        ) {
+            Ok((content, total_chars)) => {
+                let end = offset + content.chars().count();
+                Ok(Some(DocumentChunk {
+                    filename: filename.to_string(),
+                    offset,
+                    content,
+                    total_chars,
+                    next_offset: (end < total_chars).then_some(end),
+                }))
+            }
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn search_content(
+        &self,
+        query: &str,
+        session_id: Option<&str>,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<ContentSearchRow>, rusqlite::Error> {
+        let conn = self.conn();
+        let pattern = format!("%{}%", escape_like(query));
+        let mut stmt = conn.prepare(CONTENT_SEARCH_SQL)?;
+        let rows = stmt.query_map(
+            params![session_id, pattern, limit as i64, offset as i64],
+            |row| {
+                Ok(ContentSearchRow {
+                    session_id: row.get(0)?,
+                    session_name: row.get(1)?,
+                    is_document: row.get(2)?,
+                    source_id: row.get(3)?,
+                    label: row.get(4)?,
+                    content: row.get(5)?,
+                })
+            },
+        )?;
+        rows.collect()
+    }
+
     #[allow(dead_code)]
     pub fn delete_documents(&self, session_id: &str) -> Result<(), rusqlite::Error> {
         let conn = self.conn();
@@ -434,6 +981,26 @@ impl Database {
         Ok(())
     }
 
+    pub fn archive_and_clear_documents(
+        &self,
+        session_id: &str,
+        reason: &str,
+    ) -> Result<usize, rusqlite::Error> {
+        let mut conn = self.conn();
+        let tx = conn.transaction()?;
+        tx.execute(
+            "INSERT INTO document_versions (document_id, session_id, filename, content, created_at, reason)
+             SELECT id, session_id, filename, content, created_at, ?2 FROM documents WHERE session_id = ?1",
+            params![session_id, reason],
+        )?;
+        let cleared = tx.execute(
+            "DELETE FROM documents WHERE session_id = ?1",
+            params![session_id],
+        )?;
+        tx.commit()?;
+        Ok(cleared)
+    }
+
     pub fn replace_documents(
         &self,
         session_id: &str,
@@ -463,7 +1030,7 @@ impl Database {
                         session_id: row.get(1)?,
                         filename: row.get(2)?,
                         content: row.get(3)?,
-                        created_at: row.get(4)?,
+                        created_at: timestamp::normalize(row.get(4)?),
                     })
                 },
             )?;
@@ -473,42 +1040,190 @@ impl Database {
         Ok(inserted)
     }
 
-    pub fn latest_document_time(
+    pub fn replace_document(
         &self,
         session_id: &str,
-    ) -> Result<Option<String>, rusqlite::Error> {
-        let conn = self.conn();
-        conn.query_row(
-            "SELECT MAX(created_at) FROM documents WHERE session_id = ?1",
-            params![session_id],
-            |row| row.get(0),
-        )
-    }
-
-    pub fn latest_message_time(&self, session_id: &str) -> Result<Option<String>, rusqlite::Error> {
-        let conn = self.conn();
-        conn.query_row(
-            "SELECT MAX(created_at) FROM messages WHERE session_id = ?1",
-            params![session_id],
-            |row| row.get(0),
-        )
+        filename: &str,
+        content: &str,
+        reason: &str,
+    ) -> Result<GeneratedDocument, rusqlite::Error> {
+        let mut conn = self.conn();
+        let tx = conn.transaction()?;
+        let doc = Self::replace_document_row(&tx, session_id, filename, content, reason)?;
+        tx.commit()?;
+        Ok(doc)
     }
 
-    #[allow(clippy::too_many_arguments)]
-    pub fn upsert_generation_metadata(
-        &self,
+    fn replace_document_row(
+        conn: &Connection,
         session_id: &str,
-        target: &str,
-        provider: &str,
-        model: &str,
-        run_id: Option<&str>,
-        quality_json: Option<&str>,
+        filename: &str,
+        content: &str,
+        reason: &str,
+    ) -> Result<GeneratedDocument, rusqlite::Error> {
+        conn.execute(
+            "INSERT INTO document_versions (document_id, session_id, filename, content, created_at, reason)
+             SELECT id, session_id, filename, content, created_at, ?3 FROM documents
+             WHERE session_id = ?1 AND filename = ?2",
+            params![session_id, filename, reason],
+        )?;
+        conn.execute(
+            "DELETE FROM documents WHERE session_id = ?1 AND filename = ?2",
+            params![session_id, filename],
+        )?;
+
+        let id = uuid::Uuid::new_v4().to_string();
+        conn.execute(
+            "INSERT INTO documents (id, session_id, filename, content) VALUES (?1, ?2, ?3, ?4)",
+            params![id, session_id, filename, content],
+        )?;
+        conn.query_row(
+            "SELECT id, session_id, filename, content, created_at FROM documents WHERE id = ?1",
+            params![id],
+            |row| {
+                Ok(GeneratedDocument {
+                    id: row.get(0)?,
+                    session_id: row.get(1)?,
+                    filename: row.get(2)?,
+                    content: row.get(3)?,
+                    created_at: timestamp::normalize(row.get(4)?),
+                })
+            },
+        )
+    }
+
+    pub fn retarget_documents(
+        &self,
+        session_id: &str,
+        from: &str,
+        to: &str,
+        handoff: &str,
+        target: &str,
+        reason: &str,
+    ) -> Result<(), rusqlite::Error> {
+        let mut conn = self.conn();
+        let tx = conn.transaction()?;
+        if from != to {
+            tx.execute(
+                "INSERT INTO document_versions (document_id, session_id, filename, content, created_at, reason)
+                 SELECT id, session_id, filename, content, created_at, ?3 FROM documents
+                 WHERE session_id = ?1 AND filename = ?2",
+                params![session_id, from, reason],
+            )?;
+            tx.execute(
+                "UPDATE documents SET filename = ?3 WHERE session_id = ?1 AND filename = ?2",
+                params![session_id, from, to],
+            )?;
+        }
+        Self::replace_document_row(&tx, session_id, "MODEL_HANDOFF.md", handoff, reason)?;
+        tx.execute(
+            "UPDATE generation_metadata SET target = ?2 WHERE session_id = ?1",
+            params![session_id, target],
+        )?;
+        tx.commit()
+    }
+
+    // ---- Generation drafts ----
+
+    pub fn save_generation_draft(
+        &self,
+        session_id: &str,
+        run_id: &str,
+        position: usize,
+        filename: &str,
+        content: &str,
+    ) -> Result<(), rusqlite::Error> {
+        let conn = self.conn();
+        conn.execute(
+            "INSERT INTO generation_drafts (run_id, session_id, filename, position, content)
+             VALUES (?1, ?2, ?3, ?4, ?5)
+             ON CONFLICT(run_id, filename) DO UPDATE SET
+                position=excluded.position,
+                content=excluded.content,
+                created_at=CURRENT_TIMESTAMP",
+            params![run_id, session_id, filename, position as i64, content],
+        )?;
+        Ok(())
+    }
+
+    pub fn get_generation_drafts(
+        &self,
+        session_id: &str,
+    ) -> Result<Option<GenerationDrafts>, rusqlite::Error> {
+        let conn = self.conn();
+        let run_id: Option<String> = match conn.query_row(
+            "SELECT run_id FROM generation_drafts WHERE session_id = ?1
+             ORDER BY created_at DESC, rowid DESC LIMIT 1",
+            params![session_id],
+            |row| row.get(0),
+        ) {
+            Ok(run_id) => Some(run_id),
+            Err(rusqlite::Error::QueryReturnedNoRows) => None,
+            Err(err) => return Err(err),
+        };
+        let Some(run_id) = run_id else {
+            return Ok(None);
+        };
+
+        let mut stmt = conn.prepare(
+            "SELECT filename, content FROM generation_drafts
+             WHERE run_id = ?1 ORDER BY position ASC",
+        )?;
+        let documents = stmt
+            .query_map(params![run_id], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<Vec<(String, String)>, _>>()?;
+        Ok(Some(GenerationDrafts { run_id, documents }))
+    }
+
+    pub fn clear_generation_drafts(&self, session_id: &str) -> Result<usize, rusqlite::Error> {
+        let conn = self.conn();
+        conn.execute(
+            "DELETE FROM generation_drafts WHERE session_id = ?1",
+            params![session_id],
+        )
+    }
+
+    pub fn latest_document_time(
+        &self,
+        session_id: &str,
+    ) -> Result<Option<String>, rusqlite::Error> {
+        let conn = self.conn();
+        conn.query_row(
+            "SELECT MAX(created_at) FROM documents WHERE session_id = ?1",
+            params![session_id],
+            |row| row.get::<_, Option<String>>(0),
+        )
+        .map(|time| time.map(timestamp::normalize))
+    }
+
+    pub fn latest_message_time(&self, session_id: &str) -> Result<Option<String>, rusqlite::Error> {
+        let conn = self.conn();
+        conn.query_row(
+            "SELECT MAX(created_at) FROM messages WHERE session_id = ?1",
+            params![session_id],
+            |row| row.get::<_, Option<String>>(0),
+        )
+        .map(|time| time.map(timestamp::normalize))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn upsert_generation_metadata(
+        &self,
+        session_id: &str,
+        target: &str,
+        provider: &str,
+        model: &str,
+        run_id: Option<&str>,
+        quality_json: Option<&str>,
         confidence_json: Option<&str>,
+        warnings_json: Option<&str>,
+        timing_json: Option<&str>,
+        seed: Option<i64>,
     ) -> Result<(), rusqlite::Error> {
         let conn = self.conn();
         conn.execute(
-            "INSERT INTO generation_metadata (session_id, target, provider, model, run_id, quality_json, confidence_json, created_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, CURRENT_TIMESTAMP)
+            "INSERT INTO generation_metadata (session_id, target, provider, model, run_id, quality_json, confidence_json, warnings_json, timing_json, seed, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, CURRENT_TIMESTAMP)
              ON CONFLICT(session_id) DO UPDATE SET
                 target=excluded.target,
                 provider=excluded.provider,
@@ -516,6 +1231,9 @@ impl Database {
                 run_id=excluded.run_id,
                 quality_json=excluded.quality_json,
                 confidence_json=excluded.confidence_json,
+                warnings_json=excluded.warnings_json,
+                timing_json=excluded.timing_json,
+                seed=excluded.seed,
                 created_at=CURRENT_TIMESTAMP",
             params![
                 session_id,
@@ -524,19 +1242,46 @@ impl Database {
                 model,
                 run_id,
                 quality_json,
-                confidence_json
+                confidence_json,
+                warnings_json,
+                timing_json,
+                seed
             ],
         )?;
         Ok(())
     }
 
+    pub fn update_generation_target(
+        &self,
+        session_id: &str,
+        target: &str,
+    ) -> Result<usize, rusqlite::Error> {
+        let conn = self.conn();
+        conn.execute(
+            "UPDATE generation_metadata SET target = ?2 WHERE session_id = ?1",
+            params![session_id, target],
+        )
+    }
+
+    pub fn update_generation_confidence(
+        &self,
+        session_id: &str,
+        confidence_json: &str,
+    ) -> Result<usize, rusqlite::Error> {
+        let conn = self.conn();
+        conn.execute(
+            "UPDATE generation_metadata SET confidence_json = ?2 WHERE session_id = ?1",
+            params![session_id, confidence_json],
+        )
+    }
+
     pub fn get_generation_metadata(
         &self,
         session_id: &str,
     ) -> Result<Option<GenerationMetadata>, rusqlite::Error> {
         let conn = self.conn();
         match conn.query_row(
-            "SELECT session_id, target, provider, model, run_id, quality_json, confidence_json, created_at
+            "SELECT session_id, target, provider, model, run_id, quality_json, confidence_json, warnings_json, timing_json, seed, created_at
              FROM generation_metadata WHERE session_id = ?1",
             params![session_id],
             |row| {
@@ -548,7 +1293,10 @@ impl Database {
                     run_id: row.get(4)?,
                     quality_json: row.get(5)?,
                     confidence_json: row.get(6)?,
-                    created_at: row.get(7)?,
+                    warnings_json: row.get(7)?,
+                    timing_json: row.get(8)?,
+                    seed: row.get(9)?,
+                    created_at: timestamp::normalize(row.get(10)?),
                 })
             },
         ) {
@@ -624,6 +1372,147 @@ impl Database {
         rows.collect()
     }
 
+    // ---- Snapshots ----
+
+    pub fn create_session_snapshot(
+        &self,
+        session_id: &str,
+        label: &str,
+    ) -> Result<SessionSnapshot, rusqlite::Error> {
+        let mut conn = self.conn();
+        let tx = conn.transaction()?;
+        let id = Self::write_snapshot(&tx, session_id, label)?;
+        let snapshot = Self::read_snapshot_row(&tx, &id)?;
+        tx.commit()?;
+        Ok(snapshot)
+    }
+
+    pub fn list_session_snapshots(
+        &self,
+        session_id: &str,
+    ) -> Result<Vec<SessionSnapshot>, rusqlite::Error> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(&format!(
+            "{} WHERE sn.session_id = ?1 ORDER BY sn.created_at DESC, sn.rowid DESC",
+            SNAPSHOT_SELECT
+        ))?;
+        let rows = stmt.query_map(params![session_id], Self::map_snapshot_row)?;
+        rows.collect()
+    }
+
+    pub fn restore_session_snapshot(
+        &self,
+        session_id: &str,
+        label: &str,
+    ) -> Result<SessionSnapshot, rusqlite::Error> {
+        let mut conn = self.conn();
+        let tx = conn.transaction()?;
+        let snapshot_id: String = tx.query_row(
+            "SELECT id FROM session_snapshots WHERE session_id = ?1 AND label = ?2",
+            params![session_id, label],
+            |row| row.get(0),
+        )?;
+
+        let base_label = format!("Before restoring \"{}\" ({})", label, timestamp::now());
+        let mut backup_label = base_label.clone();
+        let mut attempt = 1;
+        while tx.query_row(
+            "SELECT EXISTS (SELECT 1 FROM session_snapshots WHERE session_id = ?1 AND label = ?2)",
+            params![session_id, backup_label],
+            |row| row.get::<_, bool>(0),
+        )? {
+            attempt += 1;
+            backup_label = format!("{} #{}", base_label, attempt);
+        }
+        let backup_id = Self::write_snapshot(&tx, session_id, &backup_label)?;
+
+        tx.execute(
+            "DELETE FROM messages WHERE session_id = ?1",
+            params![session_id],
+        )?;
+        tx.execute(
+            "INSERT INTO document_versions (document_id, session_id, filename, content, created_at, reason)
+             SELECT id, session_id, filename, content, created_at, 'snapshot_restore' FROM documents
+             WHERE session_id = ?1",
+            params![session_id],
+        )?;
+        tx.execute(
+            "DELETE FROM documents WHERE session_id = ?1",
+            params![session_id],
+        )?;
+        // The metadata described the generation that produced the replaced set.
+        tx.execute(
+            "DELETE FROM generation_metadata WHERE session_id = ?1",
+            params![session_id],
+        )?;
+        tx.execute(
+            "INSERT INTO messages (id, session_id, role, content, metadata, created_at)
+             SELECT message_id, ?2, role, content, metadata, created_at
+             FROM session_snapshot_messages WHERE snapshot_id = ?1 ORDER BY position",
+            params![snapshot_id, session_id],
+        )?;
+        tx.execute(
+            "INSERT INTO documents (id, session_id, filename, content, created_at)
+             SELECT document_id, ?2, filename, content, created_at
+             FROM session_snapshot_documents WHERE snapshot_id = ?1 ORDER BY position",
+            params![snapshot_id, session_id],
+        )?;
+        tx.execute(
+            "UPDATE sessions SET updated_at = CURRENT_TIMESTAMP WHERE id = ?1",
+            params![session_id],
+        )?;
+
+        let backup = Self::read_snapshot_row(&tx, &backup_id)?;
+        tx.commit()?;
+        Ok(backup)
+    }
+
+    fn write_snapshot(
+        conn: &Connection,
+        session_id: &str,
+        label: &str,
+    ) -> Result<String, rusqlite::Error> {
+        let id = uuid::Uuid::new_v4().to_string();
+        conn.execute(
+            "INSERT INTO session_snapshots (id, session_id, label) VALUES (?1, ?2, ?3)",
+            params![id, session_id, label],
+        )?;
+        conn.execute(
+            "INSERT INTO session_snapshot_messages
+                 (snapshot_id, position, message_id, role, content, metadata, created_at)
+             SELECT ?1, ROW_NUMBER() OVER (ORDER BY rowid), id, role, content, metadata, created_at
+             FROM messages WHERE session_id = ?2",
+            params![id, session_id],
+        )?;
+        conn.execute(
+            "INSERT INTO session_snapshot_documents
+                 (snapshot_id, position, document_id, filename, content, created_at)
+             SELECT ?1, ROW_NUMBER() OVER (ORDER BY created_at, rowid), id, filename, content, created_at
+             FROM documents WHERE session_id = ?2",
+            params![id, session_id],
+        )?;
+        Ok(id)
+    }
+
+    fn read_snapshot_row(conn: &Connection, id: &str) -> Result<SessionSnapshot, rusqlite::Error> {
+        conn.query_row(
+            &format!("{} WHERE sn.id = ?1", SNAPSHOT_SELECT),
+            params![id],
+            Self::map_snapshot_row,
+        )
+    }
+
+    fn map_snapshot_row(row: &rusqlite::Row<'_>) -> Result<SessionSnapshot, rusqlite::Error> {
+        Ok(SessionSnapshot {
+            id: row.get(0)?,
+            session_id: row.get(1)?,
+            label: row.get(2)?,
+            message_count: row.get::<_, i64>(3)?.max(0) as usize,
+            document_count: row.get::<_, i64>(4)?.max(0) as usize,
+            created_at: timestamp::normalize(row.get(5)?),
+        })
+    }
+
     // ---- Preferences ----
 
     pub fn get_preference(&self, key: &str) -> Result<Option<String>, rusqlite::Error> {
@@ -648,6 +1537,12 @@ impl Database {
         Ok(())
     }
 
+    pub fn delete_preference(&self, key: &str) -> Result<(), rusqlite::Error> {
+        let conn = self.conn();
+        conn.execute("DELETE FROM preferences WHERE key = ?1", params![key])?;
+        Ok(())
+    }
+
     fn conn(&self) -> std::sync::MutexGuard<'_, Connection> {
         self.conn.lock().unwrap_or_else(|e| e.into_inner())
     }
@@ -679,12 +1574,173 @@ impl Database {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
 
     fn test_db() -> Database {
         let dir = tempfile::tempdir().unwrap();
         Database::new(&dir.path().join("test.db")).unwrap()
     }
 
+    #[test]
+    fn migrations_are_listed_in_ascending_order_after_the_baseline() {
+        let versions = MIGRATIONS.iter().map(|(v, _)| *v).collect::<Vec<_>>();
+        assert!(versions.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(versions.first().is_none_or(|first| *first > 1));
+    }
+
+    #[test]
+    fn run_migrations_applies_pending_steps_once() {
+        let db = test_db();
+        let mut conn = db.conn();
+        let steps: &[(u32, &str)] = &[
+            (
+                2,
+                "CREATE TABLE tags (session_id TEXT NOT NULL, tag TEXT NOT NULL)",
+            ),
+            (3, "ALTER TABLE tags ADD COLUMN color TEXT"),
+        ];
+
+        assert_eq!(Database::run_migrations(&mut conn, steps).unwrap(), 3);
+        // A second run finds nothing pending; re-running step 2 would fail.
+        assert_eq!(Database::run_migrations(&mut conn, steps).unwrap(), 3);
+        conn.execute(
+            "INSERT INTO tags (session_id, tag, color) VALUES ('s', 'mvp', 'red')",
+            [],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn failed_migration_rolls_back_and_keeps_the_version() {
+        let db = test_db();
+        let mut conn = db.conn();
+        let steps: &[(u32, &str)] = &[
+            (2, "CREATE TABLE tags (tag TEXT)"),
+            (
+                3,
+                "CREATE TABLE labels (label TEXT); ALTER TABLE missing ADD COLUMN x TEXT",
+            ),
+        ];
+
+        assert!(Database::run_migrations(&mut conn, steps).is_err());
+        let version: u32 = conn
+            .query_row("SELECT MAX(version) FROM schema_migrations", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(version, 2);
+        let labels: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE name = 'labels'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(labels, 0);
+    }
+
+    #[test]
+    fn housekeeping_respects_age_documents_and_status_guards() {
+        let db = test_db();
+        let idle = db.create_session(Some("Idle")).unwrap();
+        let recent = db.create_session(Some("Recent")).unwrap();
+        let with_docs = db.create_session(Some("With docs")).unwrap();
+        let completed = db.create_session(Some("Completed")).unwrap();
+        db.save_document(&with_docs.id, "SPEC.md", "# Spec")
+            .unwrap();
+        db.mark_session_completed(&completed.id).unwrap();
+        {
+            let conn = db.conn();
+            for id in [&idle.id, &with_docs.id, &completed.id] {
+                conn.execute(
+                    "UPDATE sessions SET updated_at = '2020-01-01 00:00:00' WHERE id = ?1",
+                    params![id],
+                )
+                .unwrap();
+            }
+        }
+
+        let policy = HousekeepingConfig {
+            enabled: true,
+            ..Default::default()
+        };
+        let report = db.housekeep_sessions(&policy).unwrap();
+        let ids = report
+            .sessions
+            .iter()
+            .map(|session| session.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec![idle.id.as_str()]);
+        let archived = db.get_session(&idle.id).unwrap();
+        assert_eq!(archived.status, "archived");
+        assert_eq!(archived.updated_at, report.sessions[0].updated_at);
+        assert_eq!(db.get_session(&recent.id).unwrap().status, "active");
+        assert_eq!(db.get_session(&completed.id).unwrap().status, "completed");
+        assert!(db.housekeep_sessions(&policy).unwrap().sessions.is_empty());
+
+        let delete_everything_idle = HousekeepingConfig {
+            action: "delete".to_string(),
+            include_with_documents: true,
+            include_inactive: true,
+            ..policy
+        };
+        let mut deleted = db
+            .housekeep_sessions(&delete_everything_idle)
+            .unwrap()
+            .sessions
+            .into_iter()
+            .map(|session| session.id)
+            .collect::<Vec<_>>();
+        deleted.sort();
+        let mut expected = vec![idle.id, with_docs.id, completed.id];
+        expected.sort();
+        assert_eq!(deleted, expected);
+        let remaining = db.get_sessions().unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].id, recent.id);
+    }
+
+    #[test]
+    fn cleanup_orphans_removes_rows_for_missing_sessions() {
+        let db = test_db();
+        let kept = db.create_session(Some("Kept")).unwrap();
+        db.save_message(&kept.id, "user", "hello", None).unwrap();
+        db.replace_documents(&kept.id, &[("SPEC.md".to_string(), "# Spec".to_string())])
+            .unwrap();
+        {
+            let conn = db.conn();
+            conn.execute_batch(
+                "PRAGMA foreign_keys=OFF;
+                 INSERT INTO messages (id, session_id, role, content) VALUES ('m-gone', 'gone', 'user', 'x');
+                 INSERT INTO document_versions (document_id, session_id, filename, content, reason)
+                     VALUES ('d-gone', 'gone', 'SPEC.md', 'x', 'regenerate');
+                 INSERT INTO document_versions (document_id, session_id, filename, content, reason)
+                     VALUES ('d-gone-2', 'gone', 'README.md', 'x', 'regenerate');
+                 INSERT INTO session_branches (branch_session_id, root_session_id, source_session_id)
+                     VALUES ('gone', 'gone-root', 'gone-root');
+                 PRAGMA foreign_keys=ON;",
+            )
+            .unwrap();
+        }
+
+        let report = db.cleanup_orphans().unwrap();
+        let count = |table: &str| {
+            report
+                .tables
+                .iter()
+                .find(|entry| entry.table == table)
+                .map(|entry| entry.deleted)
+        };
+        assert_eq!(count("messages"), Some(1));
+        assert_eq!(count("document_versions"), Some(2));
+        assert_eq!(count("session_branches"), Some(1));
+        assert_eq!(count("documents"), Some(0));
+        assert_eq!(report.total, 4);
+        assert_eq!(db.get_messages(&kept.id).unwrap().len(), 1);
+        assert_eq!(db.get_documents(&kept.id).unwrap().len(), 1);
+        assert_eq!(db.cleanup_orphans().unwrap().total, 0);
+    }
+
     // ---- Session Tests ----
 
     #[test]
@@ -703,6 +1759,109 @@ mod tests {
         assert_eq!(session.name, "My App");
     }
 
+    #[test]
+    fn get_sessions_paged_filters_by_status_and_name() {
+        let db = test_db();
+        let alpha = db.create_session(Some("Alpha Planner")).unwrap();
+        db.create_session(Some("Beta")).unwrap();
+        db.create_session(Some("alphabet soup")).unwrap();
+        db.create_session(Some("100%_done")).unwrap();
+        db.update_session(&alpha.id, None, Some("archived"))
+            .unwrap();
+
+        let page = db.get_sessions_paged(10, 0, None, Some("ALPHA")).unwrap();
+        assert_eq!(page.total, 2);
+        assert_eq!(page.sessions.len(), 2);
+
+        let page = db
+            .get_sessions_paged(10, 0, Some("archived"), Some("alpha"))
+            .unwrap();
+        assert_eq!(page.total, 1);
+        assert_eq!(page.sessions[0].id, alpha.id);
+
+        // Wildcards in the query are matched literally.
+        let page = db.get_sessions_paged(10, 0, None, Some("%_")).unwrap();
+        assert_eq!(page.total, 1);
+        assert_eq!(page.sessions[0].name, "100%_done");
+    }
+
+    #[test]
+    fn get_sessions_paged_respects_offset_boundaries() {
+        let db = test_db();
+        for i in 0..5 {
+            db.create_session(Some(&format!("Session {}", i))).unwrap();
+        }
+
+        let first = db.get_sessions_paged(2, 0, None, None).unwrap();
+        assert_eq!(first.total, 5);
+        assert_eq!(first.sessions.len(), 2);
+
+        let last = db.get_sessions_paged(2, 4, None, None).unwrap();
+        assert_eq!(last.sessions.len(), 1);
+
+        let beyond = db.get_sessions_paged(2, 5, None, None).unwrap();
+        assert!(beyond.sessions.is_empty());
+        assert_eq!(beyond.total, 5);
+
+        let mut seen = first
+            .sessions
+            .iter()
+            .chain(
+                db.get_sessions_paged(2, 2, None, None)
+                    .unwrap()
+                    .sessions
+                    .iter(),
+            )
+            .chain(last.sessions.iter())
+            .map(|session| session.id.clone())
+            .collect::<Vec<_>>();
+        seen.sort();
+        seen.dedup();
+        assert_eq!(seen.len(), 5);
+    }
+
+    #[test]
+    fn get_sessions_with_status_flags_documents_and_staleness() {
+        let db = test_db();
+        let empty = db.create_session(Some("Empty")).unwrap();
+        let fresh = db.create_session(Some("Fresh")).unwrap();
+        let stale = db.create_session(Some("Stale")).unwrap();
+
+        for session in [&fresh, &stale] {
+            db.save_message(&session.id, "user", "hello", None).unwrap();
+            db.save_document(&session.id, "SPEC.md", "# Spec").unwrap();
+        }
+        db.save_message(&stale.id, "user", "one more thing", None)
+            .unwrap();
+        {
+            let conn = db.conn();
+            conn.execute(
+                "UPDATE documents SET created_at = '2026-01-01 00:00:00' WHERE session_id = ?1",
+                params![stale.id],
+            )
+            .unwrap();
+            conn.execute(
+                "UPDATE messages SET created_at = '2026-01-02 00:00:00' WHERE session_id = ?1 AND content = 'one more thing'",
+                params![stale.id],
+            )
+            .unwrap();
+        }
+
+        let entries = db.get_sessions_with_status().unwrap();
+        let find = |id: &str| {
+            entries
+                .iter()
+                .find(|entry| entry.session.id == id)
+                .expect("session should be listed")
+        };
+        assert!(!find(&empty.id).has_documents);
+        assert!(!find(&empty.id).docs_stale);
+        assert!(find(&fresh.id).has_documents);
+        assert!(!find(&fresh.id).docs_stale);
+        assert!(find(&stale.id).has_documents);
+        assert!(find(&stale.id).docs_stale);
+    }
+
     #[test]
     fn get_sessions_returns_all() {
         let db = test_db();
@@ -751,6 +1910,26 @@ mod tests {
         assert_eq!(updated.status, "completed");
     }
 
+    #[test]
+    fn mark_session_completed_stamps_and_status_change_clears() {
+        let db = test_db();
+        let session = db.create_session(None).unwrap();
+        assert!(session.completed_at.is_none());
+
+        let completed = db.mark_session_completed(&session.id).unwrap();
+        assert_eq!(completed.status, "completed");
+        assert!(completed.completed_at.is_some());
+
+        let reopened = db
+            .update_session(&session.id, None, Some("active"))
+            .unwrap();
+        assert!(reopened.completed_at.is_none());
+        assert!(matches!(
+            db.mark_session_completed("missing"),
+            Err(rusqlite::Error::QueryReturnedNoRows)
+        ));
+    }
+
     #[test]
     fn update_session_name_and_status_together() {
         let db = test_db();
@@ -783,44 +1962,107 @@ mod tests {
     // ---- Message Tests ----
 
     #[test]
-    fn save_and_get_messages() {
+    fn save_and_get_messages() {
+        let db = test_db();
+        let session = db.create_session(None).unwrap();
+
+        db.save_message(&session.id, "user", "Hello", None).unwrap();
+        db.save_message(&session.id, "assistant", "Hi there!", None)
+            .unwrap();
+
+        let messages = db.get_messages(&session.id).unwrap();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].role, "user");
+        assert_eq!(messages[0].content, "Hello");
+        assert_eq!(messages[1].role, "assistant");
+    }
+
+    #[test]
+    fn save_message_with_metadata() {
+        let db = test_db();
+        let session = db.create_session(None).unwrap();
+        let meta = r#"{"search_query":"react vs vue"}"#;
+        let msg = db
+            .save_message(&session.id, "assistant", "content", Some(meta))
+            .unwrap();
+        assert_eq!(msg.metadata.as_deref(), Some(meta));
+    }
+
+    #[test]
+    fn update_message_replaces_content_in_place() {
+        let db = test_db();
+        let session = db.create_session(None).unwrap();
+        let msg = db
+            .save_message(&session.id, "assistant", "old", Some(r#"{"a":1}"#))
+            .unwrap();
+        db.save_message(&session.id, "user", "later", None).unwrap();
+
+        let updated = db
+            .update_message(&session.id, &msg.id, "new", Some(r#"{"a":2}"#))
+            .unwrap();
+        assert_eq!(updated, 1);
+        let messages = db.get_messages(&session.id).unwrap();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].id, msg.id);
+        assert_eq!(messages[0].content, "new");
+        assert_eq!(messages[0].metadata.as_deref(), Some(r#"{"a":2}"#));
+
+        assert_eq!(
+            db.update_message("other-session", &msg.id, "x", None)
+                .unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn document_order_round_trips_and_clears() {
         let db = test_db();
         let session = db.create_session(None).unwrap();
+        assert!(db.get_document_order(&session.id).unwrap().is_empty());
 
-        db.save_message(&session.id, "user", "Hello", None).unwrap();
-        db.save_message(&session.id, "assistant", "Hi there!", None)
-            .unwrap();
+        let order = vec!["ADR-001.md".to_string(), "SPEC.md".to_string()];
+        assert_eq!(db.set_document_order(&session.id, &order).unwrap(), 1);
+        assert_eq!(db.get_document_order(&session.id).unwrap(), order);
 
-        let messages = db.get_messages(&session.id).unwrap();
-        assert_eq!(messages.len(), 2);
-        assert_eq!(messages[0].role, "user");
-        assert_eq!(messages[0].content, "Hello");
-        assert_eq!(messages[1].role, "assistant");
+        assert_eq!(db.set_document_order(&session.id, &[]).unwrap(), 1);
+        assert!(db.get_document_order(&session.id).unwrap().is_empty());
+        assert_eq!(db.set_document_order("missing", &order).unwrap(), 0);
     }
 
     #[test]
-    fn save_message_with_metadata() {
+    fn scratchpad_round_trips_and_starts_empty() {
         let db = test_db();
         let session = db.create_session(None).unwrap();
-        let meta = r#"{"search_query":"react vs vue"}"#;
-        let msg = db
-            .save_message(&session.id, "assistant", "content", Some(meta))
-            .unwrap();
-        assert_eq!(msg.metadata.as_deref(), Some(meta));
+        assert_eq!(db.get_session_scratchpad(&session.id).unwrap(), "");
+
+        let notes = "- maybe offline mode?\n- https://tauri.app";
+        assert_eq!(db.set_session_scratchpad(&session.id, notes).unwrap(), 1);
+        assert_eq!(db.get_session_scratchpad(&session.id).unwrap(), notes);
+        assert!(db.get_messages(&session.id).unwrap().is_empty());
+
+        assert_eq!(db.set_session_scratchpad("missing", notes).unwrap(), 0);
+        assert!(matches!(
+            db.get_session_scratchpad("missing"),
+            Err(rusqlite::Error::QueryReturnedNoRows)
+        ));
     }
 
     #[test]
-    fn message_count_only_user() {
+    fn first_user_message_skips_seed_messages() {
         let db = test_db();
-        let session = db.create_session(None).unwrap();
+        let session = db.create_session(Some("Template Name")).unwrap();
+        assert_eq!(db.first_user_message_id(&session.id).unwrap(), None);
 
-        db.save_message(&session.id, "user", "q1", None).unwrap();
+        db.save_message(&session.id, "assistant", "seed prompt", None)
+            .unwrap();
+        let q1 = db.save_message(&session.id, "user", "q1", None).unwrap();
         db.save_message(&session.id, "assistant", "a1", None)
             .unwrap();
-        db.save_message(&session.id, "user", "q2", None).unwrap();
+        let q2 = db.save_message(&session.id, "user", "q2", None).unwrap();
 
-        let count = db.message_count(&session.id).unwrap();
-        assert_eq!(count, 2);
+        let first = db.first_user_message_id(&session.id).unwrap();
+        assert_eq!(first.as_deref(), Some(q1.id.as_str()));
+        assert_ne!(first.as_deref(), Some(q2.id.as_str()));
     }
 
     #[test]
@@ -934,6 +2176,59 @@ mod tests {
         assert_eq!(docs[0].content, "# Hello");
     }
 
+    #[test]
+    fn list_document_names_reports_byte_sizes_without_content() {
+        let db = test_db();
+        let session = db.create_session(None).unwrap();
+        let other = db.create_session(None).unwrap();
+
+        db.save_document(&session.id, "README.md", "# Hello")
+            .unwrap();
+        db.save_document(&session.id, "SPEC.md", "## Spëc").unwrap();
+        db.save_document(&other.id, "OTHER.md", "other").unwrap();
+
+        let names = db.list_document_names(&session.id).unwrap();
+        assert_eq!(names.len(), 2);
+        assert_eq!(names[0].filename, "README.md");
+        assert_eq!(names[0].bytes, 7);
+        // Multi-byte characters are counted in bytes, not chars.
+        assert_eq!(names[1].bytes, "## Spëc".len());
+        assert!(db.list_document_names("missing").unwrap().is_empty());
+    }
+
+    #[test]
+    fn get_document_chunk_slices_by_character() {
+        let db = test_db();
+        let session = db.create_session(None).unwrap();
+        db.save_document(&session.id, "SPEC.md", "# Spëc\n日本語 body")
+            .unwrap();
+
+        let first = db
+            .get_document_chunk(&session.id, "SPEC.md", 0, 6)
+            .unwrap()
+            .unwrap();
+        assert_eq!(first.content, "# Spëc");
+        assert_eq!(first.total_chars, 15);
+        assert_eq!(first.next_offset, Some(6));
+
+        let rest = db
+            .get_document_chunk(&session.id, "SPEC.md", 7, 100)
+            .unwrap()
+            .unwrap();
+        assert_eq!(rest.content, "日本語 body");
+        assert_eq!(rest.next_offset, None);
+
+        let past_end = db
+            .get_document_chunk(&session.id, "SPEC.md", 50, 10)
+            .unwrap()
+            .unwrap();
+        assert!(past_end.content.is_empty());
+        assert!(db
+            .get_document_chunk(&session.id, "README.md", 0, 10)
+            .unwrap()
+            .is_none());
+    }
+
     #[test]
     fn delete_documents() {
         let db = test_db();
@@ -944,6 +2239,350 @@ mod tests {
         assert!(db.get_documents(&session.id).unwrap().is_empty());
     }
 
+    #[test]
+    fn replace_document_archives_only_that_file() {
+        let db = test_db();
+        let session = db.create_session(None).unwrap();
+        db.save_document(&session.id, "SPEC.md", "# Spec").unwrap();
+        db.save_document(&session.id, "MODEL_HANDOFF.md", "# Handoff (claude)")
+            .unwrap();
+
+        let doc = db
+            .replace_document(
+                &session.id,
+                "MODEL_HANDOFF.md",
+                "# Handoff (codex)",
+                "handoff_retarget",
+            )
+            .unwrap();
+        assert_eq!(doc.content, "# Handoff (codex)");
+
+        let docs = db.get_documents(&session.id).unwrap();
+        assert_eq!(docs.len(), 2);
+        assert!(docs
+            .iter()
+            .any(|d| d.filename == "SPEC.md" && d.content == "# Spec"));
+
+        let conn = db.conn();
+        let archived: Vec<(String, String, String)> = conn
+            .prepare(
+                "SELECT filename, content, reason FROM document_versions WHERE session_id = ?1",
+            )
+            .unwrap()
+            .query_map(params![session.id], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+            })
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            archived,
+            vec![(
+                "MODEL_HANDOFF.md".to_string(),
+                "# Handoff (claude)".to_string(),
+                "handoff_retarget".to_string(),
+            )]
+        );
+    }
+
+    #[test]
+    fn update_generation_confidence_replaces_stored_report() {
+        let db = test_db();
+        let session = db.create_session(None).unwrap();
+        assert_eq!(
+            db.update_generation_confidence(&session.id, "{\"score\":50}")
+                .unwrap(),
+            0
+        );
+
+        db.upsert_generation_metadata(
+            &session.id,
+            "claude",
+            "ollama",
+            "model",
+            Some("run-1"),
+            Some("{\"score\":80}"),
+            Some("{\"score\":82}"),
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            db.update_generation_confidence(&session.id, "{\"score\":64}")
+                .unwrap(),
+            1
+        );
+
+        let meta = db.get_generation_metadata(&session.id).unwrap().unwrap();
+        assert_eq!(meta.confidence_json.as_deref(), Some("{\"score\":64}"));
+        assert_eq!(meta.quality_json.as_deref(), Some("{\"score\":80}"));
+        assert_eq!(meta.target, "claude");
+    }
+
+    #[test]
+    fn retarget_documents_renames_and_archives_in_one_step() {
+        let db = test_db();
+        let session = db.create_session(None).unwrap();
+        db.replace_documents(
+            &session.id,
+            &[
+                ("CLAUDE.md".to_string(), "# Conventions".to_string()),
+                (
+                    "MODEL_HANDOFF.md".to_string(),
+                    "# Handoff (claude)".to_string(),
+                ),
+                ("SPEC.md".to_string(), "# Spec".to_string()),
+            ],
+        )
+        .unwrap();
+        db.upsert_generation_metadata(
+            &session.id,
+            "claude",
+            "ollama",
+            "model",
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+
+        db.retarget_documents(
+            &session.id,
+            "CLAUDE.md",
+            "GEMINI.md",
+            "# Handoff (gemini)",
+            "gemini",
+            "retarget",
+        )
+        .unwrap();
+
+        let after = db.get_documents(&session.id).unwrap();
+        let renamed = after.iter().find(|d| d.filename == "GEMINI.md").unwrap();
+        assert_eq!(renamed.content, "# Conventions");
+        assert!(after.iter().all(|d| d.filename != "CLAUDE.md"));
+        let handoff = after
+            .iter()
+            .find(|d| d.filename == "MODEL_HANDOFF.md")
+            .unwrap();
+        assert_eq!(handoff.content, "# Handoff (gemini)");
+        let meta = db.get_generation_metadata(&session.id).unwrap().unwrap();
+        assert_eq!(meta.target, "gemini");
+
+        let conn = db.conn();
+        let archived: Vec<(String, String)> = conn
+            .prepare(
+                "SELECT filename, reason FROM document_versions WHERE session_id = ?1 ORDER BY filename",
+            )
+            .unwrap()
+            .query_map(params![session.id], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            archived,
+            vec![
+                ("CLAUDE.md".to_string(), "retarget".to_string()),
+                ("MODEL_HANDOFF.md".to_string(), "retarget".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn update_generation_target_keeps_other_metadata() {
+        let db = test_db();
+        let session = db.create_session(None).unwrap();
+        assert_eq!(
+            db.update_generation_target(&session.id, "codex").unwrap(),
+            0
+        );
+
+        db.upsert_generation_metadata(
+            &session.id,
+            "claude",
+            "ollama",
+            "model",
+            Some("run-1"),
+            Some("{\"score\":80}"),
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            db.update_generation_target(&session.id, "codex").unwrap(),
+            1
+        );
+
+        let meta = db.get_generation_metadata(&session.id).unwrap().unwrap();
+        assert_eq!(meta.target, "codex");
+        assert_eq!(meta.run_id.as_deref(), Some("run-1"));
+        assert_eq!(meta.quality_json.as_deref(), Some("{\"score\":80}"));
+    }
+
+    #[test]
+    fn archive_and_clear_documents_keeps_versions() {
+        let db = test_db();
+        let session = db.create_session(None).unwrap();
+        let other = db.create_session(None).unwrap();
+        db.save_document(&session.id, "SPEC.md", "# Spec").unwrap();
+        db.save_document(&session.id, "README.md", "# Readme")
+            .unwrap();
+        db.save_document(&other.id, "SPEC.md", "# Other").unwrap();
+
+        let cleared = db
+            .archive_and_clear_documents(&session.id, "cleared")
+            .unwrap();
+        assert_eq!(cleared, 2);
+        assert!(db.get_documents(&session.id).unwrap().is_empty());
+        assert_eq!(db.get_documents(&other.id).unwrap().len(), 1);
+
+        let conn = db.conn();
+        let archived: Vec<(String, String)> = conn
+            .prepare(
+                "SELECT filename, content FROM document_versions WHERE session_id = ?1 ORDER BY filename",
+            )
+            .unwrap()
+            .query_map(params![session.id], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            archived,
+            vec![
+                ("README.md".to_string(), "# Readme".to_string()),
+                ("SPEC.md".to_string(), "# Spec".to_string()),
+            ]
+        );
+        drop(conn);
+
+        assert_eq!(
+            db.archive_and_clear_documents(&session.id, "cleared")
+                .unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn search_content_scopes_to_session_and_respects_limit() {
+        let db = test_db();
+        let session = db.create_session(Some("Alpha")).unwrap();
+        let other = db.create_session(Some("Beta")).unwrap();
+        db.save_message(&session.id, "user", "We need SQLite storage", None)
+            .unwrap();
+        db.save_message(&session.id, "system", "sqlite system prompt", None)
+            .unwrap();
+        db.save_document(&session.id, "SPEC.md", "# Spec\nUse sqlite.")
+            .unwrap();
+        db.save_message(&other.id, "user", "sqlite elsewhere", None)
+            .unwrap();
+        db.save_message(&session.id, "user", "100% done", None)
+            .unwrap();
+
+        let rows = db
+            .search_content("sqlite", Some(&session.id), 10, 0)
+            .unwrap();
+        assert_eq!(rows.len(), 2);
+        assert!(!rows[0].is_document);
+        assert_eq!(rows[0].label, "user");
+        assert!(rows[1].is_document);
+        assert_eq!(rows[1].label, "SPEC.md");
+        assert_eq!(rows[1].session_name, "Alpha");
+
+        assert_eq!(db.search_content("sqlite", None, 10, 0).unwrap().len(), 3);
+        assert_eq!(db.search_content("sqlite", None, 1, 0).unwrap().len(), 1);
+        assert_eq!(db.search_content("0%", None, 10, 0).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn search_content_runs_as_one_query_ordered_by_session_activity() {
+        let db = test_db();
+        let mut sessions = Vec::new();
+        for i in 0..30 {
+            let session = db.create_session(Some(&format!("S{}", i))).unwrap();
+            for j in 0..10 {
+                let content = if j % 3 == 0 {
+                    format!("note {} mentions postgres", j)
+                } else {
+                    format!("note {} is unrelated", j)
+                };
+                db.save_message(&session.id, "user", &content, None)
+                    .unwrap();
+            }
+            db.save_document(&session.id, "SPEC.md", "# Spec\nUse Postgres.")
+                .unwrap();
+            sessions.push(session);
+        }
+        {
+            let conn = db.conn();
+            for (i, session) in sessions.iter().enumerate() {
+                conn.execute(
+                    "UPDATE sessions SET updated_at = datetime('2026-01-01', ?2) WHERE id = ?1",
+                    params![session.id, format!("+{} minutes", i)],
+                )
+                .unwrap();
+            }
+        }
+
+        static STATEMENTS: AtomicUsize = AtomicUsize::new(0);
+        fn count_statement(_: &str) {
+            STATEMENTS.fetch_add(1, Ordering::SeqCst);
+        }
+        db.conn().trace(Some(count_statement));
+        let rows = db.search_content("postgres", None, 200, 0).unwrap();
+        db.conn().trace(None);
+        // Both tables are searched by one statement, never per session.
+        assert_eq!(STATEMENTS.load(Ordering::SeqCst), 1);
+
+        assert_eq!(rows.len(), 30 * 5);
+        // Most recently updated session first, its messages before its document.
+        assert_eq!(rows[0].session_name, "S29");
+        assert!(rows[..4].iter().all(|row| !row.is_document));
+        assert!(rows[4].is_document);
+        assert_eq!(rows[5].session_name, "S28");
+        assert_eq!(rows.last().unwrap().session_name, "S0");
+
+        let page = db.search_content("postgres", None, 5, 5).unwrap();
+        assert_eq!(
+            page.iter().map(|row| &row.source_id).collect::<Vec<_>>(),
+            rows[5..10]
+                .iter()
+                .map(|row| &row.source_id)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn generation_drafts_track_latest_run_in_order() {
+        let db = test_db();
+        let session = db.create_session(Some("Drafts")).unwrap();
+        assert!(db.get_generation_drafts(&session.id).unwrap().is_none());
+
+        db.save_generation_draft(&session.id, "run-1", 1, "CLAUDE.md", "# Claude")
+            .unwrap();
+        db.save_generation_draft(&session.id, "run-1", 0, "SPEC.md", "# Spec v1")
+            .unwrap();
+        db.save_generation_draft(&session.id, "run-1", 0, "SPEC.md", "# Spec v2")
+            .unwrap();
+
+        let drafts = db.get_generation_drafts(&session.id).unwrap().unwrap();
+        assert_eq!(drafts.run_id, "run-1");
+        assert_eq!(
+            drafts.documents,
+            vec![
+                ("SPEC.md".to_string(), "# Spec v2".to_string()),
+                ("CLAUDE.md".to_string(), "# Claude".to_string()),
+            ]
+        );
+
+        assert_eq!(db.clear_generation_drafts(&session.id).unwrap(), 2);
+        assert!(db.get_generation_drafts(&session.id).unwrap().is_none());
+    }
+
     #[test]
     fn latest_times_for_staleness() {
         let db = test_db();
@@ -967,6 +2606,199 @@ mod tests {
         assert!(db.is_ok());
     }
 
+    #[test]
+    fn lint_sessions_covers_only_sessions_with_documents_critical_first() {
+        let db = test_db();
+        let clean = db.create_session(Some("Clean plan")).unwrap();
+        db.replace_documents(
+            &clean.id,
+            &[
+                (
+                    "SPEC.md".to_string(),
+                    "# Project\n## Features\nAcceptance Criteria".to_string(),
+                ),
+                (
+                    "PROMPTS.md".to_string(),
+                    "# Project\n## Verification Checklist\n- [ ] run tests".to_string(),
+                ),
+                (
+                    "START_HERE.md".to_string(),
+                    "# Project\n## Verification\n- [ ] verify setup".to_string(),
+                ),
+                (
+                    "MODEL_HANDOFF.md".to_string(),
+                    "# Project\n## Verification\n- [ ] phase checks".to_string(),
+                ),
+            ],
+        )
+        .unwrap();
+        let failing = db.create_session(Some("Failing plan")).unwrap();
+        db.replace_documents(
+            &failing.id,
+            &[(
+                "SPEC.md".to_string(),
+                "# Project\n## Features\n- Login".to_string(),
+            )],
+        )
+        .unwrap();
+        db.create_session(Some("No documents")).unwrap();
+
+        let sessions = db.get_sessions_with_documents().unwrap();
+        assert_eq!(sessions.len(), 2);
+        assert_eq!(sessions[0].1.len(), 4);
+
+        let summaries = crate::lint::lint_sessions(&sessions);
+        let order = summaries
+            .iter()
+            .map(|entry| (entry.session_name.as_str(), entry.summary.critical))
+            .collect::<Vec<_>>();
+        assert_eq!(order[0].0, "Failing plan");
+        assert!(order[0].1 > 0);
+        assert_eq!(order[1], ("Clean plan", 0));
+        assert_eq!(summaries[0].session_id, failing.id);
+    }
+
+    // ---- Snapshot Tests ----
+
+    #[test]
+    fn snapshot_restore_round_trips_messages_and_documents() {
+        let db = test_db();
+        let session = db.create_session(Some("Snapshots")).unwrap();
+        let first = db
+            .save_message(&session.id, "user", "Build a habit tracker", None)
+            .unwrap();
+        let reply = db
+            .save_message(
+                &session.id,
+                "assistant",
+                "Which platforms?",
+                Some(r#"{"k":1}"#),
+            )
+            .unwrap();
+        db.replace_documents(
+            &session.id,
+            &[
+                ("SPEC.md".to_string(), "# Spec v1".to_string()),
+                ("README.md".to_string(), "# Readme v1".to_string()),
+            ],
+        )
+        .unwrap();
+        let docs_before = db.get_documents(&session.id).unwrap();
+
+        let snapshot = db
+            .create_session_snapshot(&session.id, "before scope change")
+            .unwrap();
+        assert_eq!(snapshot.message_count, 2);
+        assert_eq!(snapshot.document_count, 2);
+        assert!(db
+            .create_session_snapshot(&session.id, "before scope change")
+            .is_err());
+
+        db.save_message(&session.id, "user", "Add a web version too", None)
+            .unwrap();
+        db.replace_documents(
+            &session.id,
+            &[("SPEC.md".to_string(), "# Spec v2".to_string())],
+        )
+        .unwrap();
+        db.upsert_generation_metadata(
+            &session.id,
+            "generic",
+            "ollama",
+            "qwen3-coder",
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+
+        let backup = db
+            .restore_session_snapshot(&session.id, "before scope change")
+            .unwrap();
+        assert!(backup
+            .label
+            .starts_with("Before restoring \"before scope change\""));
+        assert_eq!(backup.message_count, 3);
+        assert_eq!(backup.document_count, 1);
+
+        let messages = db.get_messages(&session.id).unwrap();
+        let restored = messages
+            .iter()
+            .map(|m| {
+                (
+                    m.id.as_str(),
+                    m.role.as_str(),
+                    m.content.as_str(),
+                    m.metadata.as_deref(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            restored,
+            vec![
+                (first.id.as_str(), "user", "Build a habit tracker", None),
+                (
+                    reply.id.as_str(),
+                    "assistant",
+                    "Which platforms?",
+                    Some(r#"{"k":1}"#)
+                ),
+            ]
+        );
+        let docs = db.get_documents(&session.id).unwrap();
+        let pairs = |docs: &[GeneratedDocument]| {
+            docs.iter()
+                .map(|d| (d.id.clone(), d.filename.clone(), d.content.clone()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(pairs(&docs), pairs(&docs_before));
+
+        let conn = db.conn();
+        let archived: Vec<(String, String, String)> = conn
+            .prepare(
+                "SELECT filename, content, reason FROM document_versions WHERE session_id = ?1",
+            )
+            .unwrap()
+            .query_map(params![session.id], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+            })
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        drop(conn);
+        assert!(archived.contains(&(
+            "SPEC.md".to_string(),
+            "# Spec v2".to_string(),
+            "snapshot_restore".to_string()
+        )));
+        assert!(db.get_generation_metadata(&session.id).unwrap().is_none());
+
+        // Restoring the backup undoes the restore.
+        db.restore_session_snapshot(&session.id, &backup.label)
+            .unwrap();
+        assert_eq!(db.get_messages(&session.id).unwrap().len(), 3);
+        assert_eq!(
+            db.get_documents(&session.id).unwrap()[0].content,
+            "# Spec v2"
+        );
+
+        let labels = db
+            .list_session_snapshots(&session.id)
+            .unwrap()
+            .into_iter()
+            .map(|snapshot| snapshot.label)
+            .collect::<Vec<_>>();
+        assert_eq!(labels.len(), 3);
+        assert!(labels.contains(&"before scope change".to_string()));
+        assert!(matches!(
+            db.restore_session_snapshot(&session.id, "missing"),
+            Err(rusqlite::Error::QueryReturnedNoRows)
+        ));
+    }
+
     // ---- Preference Tests ----
 
     #[test]
@@ -996,6 +2828,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn delete_preference_removes_key() {
+        let db = test_db();
+        db.set_preference("pending_model_pull", "qwen3-coder")
+            .unwrap();
+        db.delete_preference("pending_model_pull").unwrap();
+        assert_eq!(db.get_preference("pending_model_pull").unwrap(), None);
+        // Deleting a missing key is a no-op.
+        db.delete_preference("pending_model_pull").unwrap();
+    }
+
     #[test]
     fn upsert_and_get_generation_metadata() {
         let db = test_db();
@@ -1008,15 +2851,27 @@ mod tests {
             Some("run-1"),
             Some(r#"{"score":75}"#),
             Some(r#"{"score":82}"#),
+            Some(r#"[{"filename":"SPEC.md","reason":"short","attempts":2}]"#),
+            Some(r#"{"total_ms":1200,"documents":[]}"#),
+            Some(42),
         )
         .unwrap();
 
         let meta = db.get_generation_metadata(&session.id).unwrap().unwrap();
         assert_eq!(meta.target, "generic");
+        assert_eq!(meta.seed, Some(42));
         assert_eq!(meta.provider, "ollama");
         assert_eq!(meta.model, "qwen3-coder");
         assert_eq!(meta.run_id.as_deref(), Some("run-1"));
         assert_eq!(meta.confidence_json.as_deref(), Some(r#"{"score":82}"#));
+        assert!(meta
+            .warnings_json
+            .as_deref()
+            .is_some_and(|json| json.contains("SPEC.md")));
+        assert_eq!(
+            meta.timing_json.as_deref(),
+            Some(r#"{"total_ms":1200,"documents":[]}"#)
+        );
 
         db.upsert_generation_metadata(
             &session.id,
@@ -1026,15 +2881,21 @@ mod tests {
             Some("run-2"),
             None,
             None,
+            None,
+            None,
+            None,
         )
         .unwrap();
         let updated = db.get_generation_metadata(&session.id).unwrap().unwrap();
         assert_eq!(updated.target, "codex");
         assert_eq!(updated.provider, "openai");
+        assert_eq!(updated.seed, None);
         assert_eq!(updated.model, "gpt-5");
         assert_eq!(updated.run_id.as_deref(), Some("run-2"));
         assert!(updated.quality_json.is_none());
         assert!(updated.confidence_json.is_none());
+        assert!(updated.warnings_json.is_none());
+        assert!(updated.timing_json.is_none());
     }
 
     #[test]
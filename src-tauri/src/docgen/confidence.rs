@@ -1,8 +1,11 @@
 use std::collections::HashMap;
 
-use crate::types::{ConfidenceFactor, ConfidenceReport, GeneratedDocument, QualityReport};
+use crate::types::{
+    ConfidenceFactor, ConfidenceReport, ForgeTarget, GeneratedDocument, QualityReport,
+};
 
-const REQUIRED_DOCS: &[&str] = &[
+/// The agent instructions count under `CLAUDE.md` whatever the target calls them.
+pub const REQUIRED_DOCS: &[&str] = &[
     "START_HERE.md",
     "SPEC.md",
     "CLAUDE.md",
@@ -15,9 +18,17 @@ pub fn analyze_generation_confidence(
     docs: &[GeneratedDocument],
     readiness: Option<&QualityReport>,
 ) -> ConfidenceReport {
+    // A retargeted set carries its agent instructions under another name.
     let by_name: HashMap<&str, &GeneratedDocument> = docs
         .iter()
-        .map(|doc| (doc.filename.as_str(), doc))
+        .map(|doc| {
+            let name = doc.filename.as_str();
+            if ForgeTarget::AGENT_CONFIG_FILENAMES.contains(&name) {
+                ("CLAUDE.md", doc)
+            } else {
+                (name, doc)
+            }
+        })
         .collect();
 
     let mut factors = Vec::new();
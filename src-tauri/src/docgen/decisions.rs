@@ -0,0 +1,174 @@
+use crate::types::{DecisionConfidence, DecisionEntry, Message};
+
+const DECISION_PHRASES: &[(&str, DecisionConfidence)] = &[
+    ("we decided", DecisionConfidence::High),
+    ("we've decided", DecisionConfidence::High),
+    ("we have decided", DecisionConfidence::High),
+    ("i've decided", DecisionConfidence::High),
+    ("decided to", DecisionConfidence::High),
+    ("decided on", DecisionConfidence::High),
+    ("locked in", DecisionConfidence::High),
+    ("settled on", DecisionConfidence::High),
+    ("final decision", DecisionConfidence::High),
+    ("let's use", DecisionConfidence::Medium),
+    ("let's go with", DecisionConfidence::Medium),
+    ("let us use", DecisionConfidence::Medium),
+    ("going with", DecisionConfidence::Medium),
+    ("go with", DecisionConfidence::Medium),
+    ("we'll use", DecisionConfidence::Medium),
+    ("we will use", DecisionConfidence::Medium),
+    ("i'll use", DecisionConfidence::Medium),
+    ("sticking with", DecisionConfidence::Medium),
+];
+
+pub fn extract_decision_timeline(messages: &[Message]) -> Vec<DecisionEntry> {
+    let mut decisions = Vec::new();
+
+    for message in messages {
+        let from_user = match message.role.as_str() {
+            "user" => true,
+            "assistant" => false,
+            _ => continue,
+        };
+
+        for sentence in sentences(&message.content) {
+            if sentence.ends_with('?') {
+                continue;
+            }
+            let lower = sentence.to_lowercase().replace('’', "'");
+            let Some(confidence) = DECISION_PHRASES
+                .iter()
+                .filter(|(phrase, _)| contains_phrase(&lower, phrase))
+                .map(|(_, confidence)| *confidence)
+                .max()
+            else {
+                continue;
+            };
+
+            decisions.push(DecisionEntry {
+                message_id: message.id.clone(),
+                created_at: message.created_at.clone(),
+                decision_text: sentence.replace("**", ""),
+                confidence: if from_user {
+                    confidence
+                } else {
+                    confidence.downgraded()
+                },
+            });
+        }
+    }
+
+    decisions
+}
+
+/// A terminator only ends a sentence when followed by whitespace, so `Next.js`
+/// stays intact.
+fn sentences(text: &str) -> Vec<&str> {
+    let mut sentences = Vec::new();
+
+    for line in text.lines() {
+        let line = line.trim_start_matches(['-', '*', '+', '>', '#', ' ', '\t']);
+        let mut start = 0;
+        let mut chars = line.char_indices().peekable();
+        while let Some((index, c)) = chars.next() {
+            let at_boundary = chars.peek().is_none_or(|(_, next)| next.is_whitespace());
+            if matches!(c, '.' | '!' | '?') && at_boundary {
+                let end = index + c.len_utf8();
+                sentences.push(line[start..end].trim());
+                start = end;
+            }
+        }
+        sentences.push(line[start..].trim());
+    }
+
+    sentences.retain(|sentence| !sentence.is_empty());
+    sentences
+}
+
+fn contains_phrase(lower: &str, phrase: &str) -> bool {
+    lower.match_indices(phrase).any(|(start, _)| {
+        lower[..start]
+            .chars()
+            .next_back()
+            .is_none_or(|c| !c.is_alphanumeric())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(id: &str, role: &str, content: &str) -> Message {
+        Message {
+            id: id.to_string(),
+            session_id: "s1".to_string(),
+            role: role.to_string(),
+            content: content.to_string(),
+            metadata: None,
+            created_at: format!("2026-01-01 00:00:0{}", id),
+        }
+    }
+
+    #[test]
+    fn finds_common_decision_phrasings_in_order() {
+        let messages = vec![
+            message(
+                "1",
+                "user",
+                "I want a desktop app. Let's use Tauri for the shell.",
+            ),
+            message("2", "assistant", "Great. Should we go with SQLite?"),
+            message(
+                "3",
+                "user",
+                "Yes, we decided on SQLite. Auth is locked in as OAuth!",
+            ),
+            message("4", "user", "Going with Next.js for the marketing site"),
+        ];
+
+        let timeline = extract_decision_timeline(&messages);
+        let texts = timeline
+            .iter()
+            .map(|entry| entry.decision_text.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            texts,
+            vec![
+                "Let's use Tauri for the shell.",
+                "Yes, we decided on SQLite.",
+                "Auth is locked in as OAuth!",
+                "Going with Next.js for the marketing site",
+            ]
+        );
+        assert_eq!(timeline[0].message_id, "1");
+        assert_eq!(timeline[0].confidence, DecisionConfidence::Medium);
+        assert_eq!(timeline[1].confidence, DecisionConfidence::High);
+        assert_eq!(timeline[3].created_at, "2026-01-01 00:00:04");
+    }
+
+    #[test]
+    fn assistant_decisions_are_less_certain() {
+        let messages = vec![
+            message("1", "assistant", "So far we've decided: **Rust** backend."),
+            message("2", "assistant", "I'd suggest we go with Postgres."),
+            message("3", "system", "We decided to import the repo."),
+        ];
+
+        let timeline = extract_decision_timeline(&messages);
+
+        assert_eq!(timeline.len(), 2);
+        assert_eq!(
+            timeline[0].decision_text,
+            "So far we've decided: Rust backend."
+        );
+        assert_eq!(timeline[0].confidence, DecisionConfidence::Medium);
+        assert_eq!(timeline[1].confidence, DecisionConfidence::Low);
+    }
+
+    #[test]
+    fn ignores_phrases_inside_other_words() {
+        let messages = vec![message("1", "user", "The ongoing with-block is fine.")];
+        assert!(extract_decision_timeline(&messages).is_empty());
+    }
+}
@@ -0,0 +1,107 @@
+use whatlang::Lang;
+
+use crate::types::{ConversationLanguage, Message};
+
+const MIN_DETECTION_CHARS: usize = 40;
+
+/// Only user messages are considered: assistant replies follow the English system
+/// prompt and would pull the result towards English.
+pub fn detect_conversation_language(messages: &[Message]) -> Option<ConversationLanguage> {
+    let text = messages
+        .iter()
+        .filter(|message| message.role == "user")
+        .map(|message| message.content.as_str())
+        .collect::<Vec<_>>()
+        .join("\n");
+    if text.trim().chars().count() < MIN_DETECTION_CHARS {
+        return None;
+    }
+
+    let info = whatlang::detect(&text)?;
+    Some(ConversationLanguage {
+        code: info.lang().code().to_string(),
+        name: info.lang().eng_name().to_string(),
+        is_english: info.lang() == Lang::Eng,
+        reliable: info.is_reliable(),
+    })
+}
+
+pub fn language_instruction(language: Option<&ConversationLanguage>) -> Option<String> {
+    let language = language.filter(|language| language.reliable && !language.is_english)?;
+    Some(format!(
+        "\n\n## Output Language\nThe planning conversation was held in {name}. Write the \
+         document prose in {name}. Keep Markdown structure, file names, code, commands and \
+         the [TBD] markers exactly as these instructions specify.",
+        name = language.name
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(role: &str, content: &str) -> Message {
+        Message {
+            id: "m1".to_string(),
+            session_id: "s1".to_string(),
+            role: role.to_string(),
+            content: content.to_string(),
+            metadata: None,
+            created_at: "2026-01-01 00:00:00".to_string(),
+        }
+    }
+
+    #[test]
+    fn detects_a_non_english_conversation_from_user_messages() {
+        let messages = [
+            message(
+                "user",
+                "Quiero construir una aplicación de escritorio para gestionar las facturas \
+                 de mi pequeña empresa y enviar recordatorios a los clientes.",
+            ),
+            message(
+                "assistant",
+                "Great, let's talk about the data model you need for invoices first.",
+            ),
+            message(
+                "user",
+                "Los datos se guardan en una base de datos local y no necesitamos servidor.",
+            ),
+        ];
+
+        let language = detect_conversation_language(&messages).expect("language detected");
+        assert_eq!(language.code, "spa");
+        assert_eq!(language.name, "Spanish");
+        assert!(!language.is_english);
+        assert!(language.reliable);
+
+        let instruction = language_instruction(Some(&language)).expect("instruction added");
+        assert!(instruction.contains("Spanish"));
+    }
+
+    #[test]
+    fn english_conversations_keep_the_prompt_unchanged() {
+        let messages = [message(
+            "user",
+            "I want to build a desktop app that tracks invoices for my small business \
+             and sends reminders to customers when payments are late.",
+        )];
+
+        let language = detect_conversation_language(&messages).expect("language detected");
+        assert!(language.is_english);
+        assert!(language_instruction(Some(&language)).is_none());
+        assert!(language_instruction(None).is_none());
+    }
+
+    #[test]
+    fn too_little_user_text_is_not_detected() {
+        let messages = [
+            message("user", "Hola"),
+            message(
+                "assistant",
+                "Hello! What would you like to build today? Tell me about the idea.",
+            ),
+        ];
+        assert!(detect_conversation_language(&messages).is_none());
+    }
+}
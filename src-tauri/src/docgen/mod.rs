@@ -1,27 +1,114 @@
 mod confidence;
+mod decisions;
+mod language;
+mod open_questions;
 mod prompts;
 mod quality;
+mod requirements;
+mod tech_stack;
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
 
 use tauri::Emitter;
 
+use crate::db::Database;
 use crate::error::AppError;
 use crate::llm::ChatMessage;
+use crate::search::Citation;
 use crate::state::AppState;
+use crate::timestamp;
 use crate::types::{
-    ForgeTarget, GenerateComplete, GenerateProgress, GeneratedDocument, Message, QualityReport,
-    Session,
+    AppConfig, DocumentTiming, ForgeTarget, GenerateCancelled, GenerateComplete, GenerateProgress,
+    GeneratedDocument, GenerationTiming, GenerationWarning, Message, QualityReport, Session,
 };
 
-pub use confidence::analyze_generation_confidence;
+pub use confidence::{analyze_generation_confidence, REQUIRED_DOCS};
+pub use decisions::extract_decision_timeline;
+pub use language::detect_conversation_language;
+pub use open_questions::extract_open_questions;
 use prompts::*;
-pub use quality::{analyze_plan_readiness, analyze_planning_coverage};
+pub use quality::{
+    analyze_plan_readiness, analyze_planning_coverage, build_readiness_checklist,
+    coverage_evidence_messages,
+};
+pub use requirements::extract_requirements;
+pub use tech_stack::{extract_tech_stack, technologies_in};
+
+/// The agent instructions are generated as `CLAUDE.md` and renamed per target.
+pub const LLM_GENERATED_DOCS: &[&str] = &[
+    "SPEC.md",
+    "CLAUDE.md",
+    "PROMPTS.md",
+    "README.md",
+    "START_HERE.md",
+];
+
+/// Order: SPEC → CLAUDE → PROMPTS → README → START_HERE (cross-referencing order)
+const DOC_PROMPTS: [(&str, &str); 5] = [
+    ("SPEC.md", SPEC_PROMPT),
+    ("CLAUDE.md", CLAUDE_PROMPT),
+    ("PROMPTS.md", PROMPTS_PROMPT),
+    ("README.md", README_PROMPT),
+    ("START_HERE.md", START_HERE_PROMPT),
+];
+
+const DATA_DERIVED_DOCS: &[&str] = &["CONVERSATION.md", "MODEL_HANDOFF.md"];
+
+const LENGTH_CHECKED_DOCS: &[&str] = &["SPEC.md", "PROMPTS.md"];
+const MIN_CORE_DOC_CHARS: usize = 400;
+
+/// Refusals show up at the very start of the output, so only the head is scanned
+/// to avoid flagging documents that legitimately quote these phrases.
+const REFUSAL_SCAN_CHARS: usize = 400;
+const REFUSAL_PHRASES: &[&str] = &[
+    "i'm sorry, but",
+    "i am sorry, but",
+    "i cannot help",
+    "i can't help",
+    "i cannot assist",
+    "i can't assist",
+    "i'm unable to",
+    "i am unable to",
+    "as an ai language model",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DocValidation {
+    Off,
+    Heading,
+    Strict,
+}
+
+impl DocValidation {
+    fn from_config(value: &str) -> Self {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "off" => Self::Off,
+            "heading" => Self::Heading,
+            _ => Self::Strict,
+        }
+    }
+}
+
+pub struct GenerationOutput {
+    pub documents: Vec<GeneratedDocument>,
+    pub warnings: Vec<GenerationWarning>,
+    pub timing: GenerationTiming,
+    pub cancelled: bool,
+}
 
+/// Each LLM-written document is saved to `generation_drafts` as it completes, so
+/// `resume` can continue an interrupted run. A cancelled run leaves the stored set
+/// untouched.
 pub async fn generate_all_documents(
-    app: &tauri::AppHandle,
+    app: Option<&tauri::AppHandle>,
     state: &AppState,
     session_id: &str,
     target: &ForgeTarget,
-) -> Result<Vec<GeneratedDocument>, AppError> {
+    resume: bool,
+    cancel: Option<&AtomicBool>,
+) -> Result<GenerationOutput, AppError> {
+    let started = Instant::now();
     let messages = state.db.get_messages(session_id).map_err(AppError::from)?;
 
     let user_msgs = messages.iter().any(|m| m.role == "user");
@@ -33,169 +120,665 @@ pub async fn generate_all_documents(
 
     let session = state.db.get_session(session_id).map_err(AppError::from)?;
 
-    let conversation = format_conversation_for_prompt(&messages);
     let config = state
         .config
         .lock()
         .map_err(|_| AppError::Config("Config lock poisoned".to_string()))?
         .clone();
+    let conversation =
+        format_conversation_for_prompt(&messages, config.output.generation_context_limit);
+    let language = detect_conversation_language(&messages);
+    let language_instruction = language::language_instruction(language.as_ref());
+    if let (Some(language), Some(_)) = (&language, &language_instruction) {
+        log::info!(
+            "Conversation for {} is in {}; asking for documents in that language",
+            session_id,
+            language.name
+        );
+    }
 
-    let mut drafts: Vec<(String, String)> = Vec::new();
+    let resumed = if resume {
+        state
+            .db
+            .get_generation_drafts(session_id)
+            .map_err(AppError::from)?
+    } else {
+        state
+            .db
+            .clear_generation_drafts(session_id)
+            .map_err(AppError::from)?;
+        None
+    };
+    let (run_id, mut drafts) = match resumed {
+        Some(resumed) => {
+            log::info!(
+                "Resuming generation for {} with {} completed document(s)",
+                session_id,
+                resumed.documents.len()
+            );
+            (resumed.run_id, resumed.documents)
+        }
+        None => (uuid::Uuid::new_v4().to_string(), Vec::new()),
+    };
+    let mut warnings: Vec<GenerationWarning> = Vec::new();
+    let mut timings: Vec<DocumentTiming> = Vec::new();
     let include_conversation = config.output.include_conversation;
 
-    // Order: SPEC → CLAUDE → PROMPTS → README → START_HERE (cross-referencing order)
-    let doc_configs = [
-        ("SPEC.md", SPEC_PROMPT),
-        ("CLAUDE.md", CLAUDE_PROMPT),
-        ("PROMPTS.md", PROMPTS_PROMPT),
-        ("README.md", README_PROMPT),
-        ("START_HERE.md", START_HERE_PROMPT),
-    ];
+    let doc_configs = DOC_PROMPTS;
 
     let total = doc_configs.len() + if include_conversation { 2 } else { 1 };
 
     for (i, (filename, prompt_template)) in doc_configs.iter().enumerate() {
+        if drafts.iter().any(|(name, _)| name == filename) {
+            continue;
+        }
+        if cancel.is_some_and(|flag| flag.load(Ordering::SeqCst)) {
+            return cancelled_output(
+                app, &state.db, session_id, &drafts, warnings, timings, started,
+            );
+        }
+
         // Emit progress
-        let _ = app.emit(
-            "generate:progress",
-            GenerateProgress {
-                current: i + 1,
-                total,
-                filename: filename.to_string(),
-                session_id: session_id.to_string(),
-            },
-        );
+        if let Some(app) = app {
+            let _ = app.emit(
+                "generate:progress",
+                GenerateProgress {
+                    current: i + 1,
+                    total,
+                    filename: filename.to_string(),
+                    session_id: session_id.to_string(),
+                    elapsed_ms: millis(started.elapsed()),
+                    previous: timings.last().cloned(),
+                },
+            );
+        }
+        let doc_started = Instant::now();
 
         let today = chrono::Local::now().format("%Y-%m-%d").to_string();
-        let previously_generated = if drafts.is_empty() {
-            "No documents generated yet.".to_string()
-        } else {
-            drafts
-                .iter()
-                .map(|(name, content)| format!("## {}\n\n{}", name, content))
-                .collect::<Vec<_>>()
-                .join("\n\n---\n\n")
-        };
+        let previously_generated = previously_generated_context(&drafts);
 
         let prompt = prompt_template
             .replace("{conversation_history}", &conversation)
             .replace("{current_date}", &today)
             .replace("{previously_generated_docs}", &previously_generated);
 
-        let system_prompt = DOCGEN_SYSTEM_PROMPT.replace("{current_date}", &today);
+        let mut system_prompt = DOCGEN_SYSTEM_PROMPT.replace("{current_date}", &today);
+        if let Some(instruction) = &language_instruction {
+            system_prompt.push_str(instruction);
+        }
 
-        let llm_messages = vec![
-            ChatMessage {
-                role: "system".to_string(),
-                content: system_prompt.clone(),
-            },
-            ChatMessage {
-                role: "user".to_string(),
-                content: prompt.clone(),
-            },
-        ];
+        let (content, attempts, failure) =
+            generate_validated_doc(state, &config, filename, &system_prompt, &prompt).await?;
 
-        let mut content = state
-            .ollama
-            .generate(&config.llm, llm_messages, 0.4) // Lower temperature for structured output
-            .await?;
-
-        // Validate output starts with # heading — retry once if not
-        if !content.trim_start().starts_with('#') {
-            let retry_messages = vec![
-                ChatMessage {
-                    role: "system".to_string(),
-                    content: system_prompt.clone(),
-                },
-                ChatMessage {
-                    role: "user".to_string(),
-                    content: format!(
-                        "{}\n\nIMPORTANT: Start with a # heading. Output only valid Markdown.",
-                        prompt
-                    ),
-                },
-            ];
-
-            content = state
-                .ollama
-                .generate(&config.llm, retry_messages, 0.3)
-                .await?;
+        if let Some(reason) = failure {
+            log::warn!(
+                "Storing {} despite failed validation after {} attempt(s): {}",
+                filename,
+                attempts,
+                reason
+            );
+            warnings.push(GenerationWarning {
+                filename: filename.to_string(),
+                reason,
+                attempts,
+            });
         }
 
+        let duration_ms = millis(doc_started.elapsed());
+        log::info!("Generated {} in {} ms", filename, duration_ms);
+        timings.push(DocumentTiming {
+            filename: filename.to_string(),
+            duration_ms,
+            attempts,
+        });
+        state
+            .db
+            .save_generation_draft(session_id, &run_id, i, filename, &content)
+            .map_err(AppError::from)?;
         drafts.push((filename.to_string(), content));
     }
+    // A cancel that lands while the last document is generating.
+    if cancel.is_some_and(|flag| flag.load(Ordering::SeqCst)) {
+        return cancelled_output(
+            app, &state.db, session_id, &drafts, warnings, timings, started,
+        );
+    }
 
     // CONVERSATION.md — generated from data, not LLM (optional)
     if include_conversation {
         let conversation_step = total - 1;
+        if let Some(app) = app {
+            let _ = app.emit(
+                "generate:progress",
+                GenerateProgress {
+                    current: conversation_step,
+                    total,
+                    filename: "CONVERSATION.md".to_string(),
+                    session_id: session_id.to_string(),
+                    elapsed_ms: millis(started.elapsed()),
+                    previous: timings.last().cloned(),
+                },
+            );
+        }
+
+        let conversation_md = generate_conversation_md(
+            &session,
+            &messages,
+            config.output.conversation_generation_details,
+        );
+        drafts.push(("CONVERSATION.md".to_string(), conversation_md));
+    }
+
+    // MODEL_HANDOFF.md — target-aware handoff instructions.
+    let handoff_step = total;
+    if let Some(app) = app {
         let _ = app.emit(
             "generate:progress",
             GenerateProgress {
-                current: conversation_step,
+                current: handoff_step,
                 total,
-                filename: "CONVERSATION.md".to_string(),
+                filename: "MODEL_HANDOFF.md".to_string(),
                 session_id: session_id.to_string(),
+                elapsed_ms: millis(started.elapsed()),
+                previous: timings.last().cloned(),
             },
         );
-
-        let conversation_md = generate_conversation_md(&session, &messages);
-        drafts.push(("CONVERSATION.md".to_string(), conversation_md));
     }
-
-    // MODEL_HANDOFF.md — target-aware handoff instructions.
-    let handoff_step = total;
-    let _ = app.emit(
-        "generate:progress",
-        GenerateProgress {
-            current: handoff_step,
-            total,
-            filename: "MODEL_HANDOFF.md".to_string(),
-            session_id: session_id.to_string(),
-        },
+    let quality = analyze_plan_readiness(
+        &messages,
+        config.output.planning_depth.parse().unwrap_or_default(),
     );
-    let quality = analyze_plan_readiness(&messages);
+    // Drafts keep the `CLAUDE.md` prompt name so a resumed run finds them.
+    let agent_config_file = target.agent_config_filename();
+    for (filename, _) in drafts.iter_mut() {
+        if filename == "CLAUDE.md" {
+            *filename = agent_config_file.to_string();
+        }
+    }
     drafts.push((
         "MODEL_HANDOFF.md".to_string(),
-        generate_model_handoff_doc(&session, target, &quality),
+        generate_model_handoff_doc(&session, target, &quality, agent_config_file),
     ));
 
-    let documents = state
+    if config.output.frontmatter {
+        let frontmatter = build_frontmatter(
+            &session.name,
+            &chrono::Local::now().format("%Y-%m-%d").to_string(),
+            target.as_str(),
+            session_id,
+        );
+        for (filename, content) in drafts.iter_mut() {
+            if filename.ends_with(".md") {
+                *content = format!("{}{}", frontmatter, content);
+            }
+        }
+    }
+
+    let documents = finalize_documents(&state.db, session_id, &drafts, false)?;
+
+    let timing = GenerationTiming {
+        total_ms: millis(started.elapsed()),
+        documents: timings,
+    };
+    log::info!(
+        "Generated {} documents in {} ms",
+        documents.len(),
+        timing.total_ms
+    );
+
+    if let Some(app) = app {
+        let _ = app.emit(
+            "generate:complete",
+            GenerateComplete {
+                session_id: session_id.to_string(),
+                count: documents.len(),
+                timing: timing.clone(),
+            },
+        );
+    }
+
+    Ok(GenerationOutput {
+        documents,
+        warnings,
+        timing,
+        cancelled: false,
+    })
+}
+
+/// A retargeted agent instruction file (e.g. `GEMINI.md`) uses the `CLAUDE.md`
+/// prompt and keeps its name.
+pub async fn regenerate_document(
+    state: &AppState,
+    session_id: &str,
+    filename: &str,
+) -> Result<GeneratedDocument, AppError> {
+    let (prompt_name, prompt_template) = regeneration_prompt(filename)?;
+
+    let session = state.db.get_session(session_id).map_err(AppError::from)?;
+    let docs = state.db.get_documents(session_id).map_err(AppError::from)?;
+    if !docs.iter().any(|doc| doc.filename == filename) {
+        return Err(AppError::Validation(format!(
+            "No {} in this session to regenerate. Forge the plan first.",
+            filename
+        )));
+    }
+    let messages = state.db.get_messages(session_id).map_err(AppError::from)?;
+    let config = state
+        .config
+        .lock()
+        .map_err(|_| AppError::Config("Config lock poisoned".to_string()))?
+        .clone();
+
+    let context_docs = DOC_PROMPTS
+        .iter()
+        .filter(|(name, _)| *name != prompt_name)
+        .filter_map(|(name, _)| {
+            docs.iter().find(|doc| {
+                doc.filename == *name
+                    || (*name == "CLAUDE.md"
+                        && ForgeTarget::AGENT_CONFIG_FILENAMES.contains(&doc.filename.as_str()))
+            })
+        })
+        .map(|doc| {
+            (
+                doc.filename.clone(),
+                strip_frontmatter(&doc.content).to_string(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+    let conversation =
+        format_conversation_for_prompt(&messages, config.output.generation_context_limit);
+    let prompt = prompt_template
+        .replace("{conversation_history}", &conversation)
+        .replace("{current_date}", &today)
+        .replace(
+            "{previously_generated_docs}",
+            &previously_generated_context(&context_docs),
+        );
+    let mut system_prompt = DOCGEN_SYSTEM_PROMPT.replace("{current_date}", &today);
+    let language = detect_conversation_language(&messages);
+    if let Some(instruction) = language::language_instruction(language.as_ref()) {
+        system_prompt.push_str(&instruction);
+    }
+
+    let started = Instant::now();
+    let (mut content, attempts, failure) =
+        generate_validated_doc(state, &config, prompt_name, &system_prompt, &prompt).await?;
+    if let Some(reason) = failure {
+        log::warn!(
+            "Storing regenerated {} despite failed validation after {} attempt(s): {}",
+            filename,
+            attempts,
+            reason
+        );
+    }
+    log::info!(
+        "Regenerated {} in {} ms",
+        filename,
+        millis(started.elapsed())
+    );
+
+    if config.output.frontmatter {
+        let target = state
+            .db
+            .get_generation_metadata(session_id)
+            .map_err(AppError::from)?
+            .map(|meta| meta.target)
+            .unwrap_or_else(|| config.output.default_target.clone());
+        let frontmatter = build_frontmatter(&session.name, &today, &target, session_id);
+        content = format!("{}{}", frontmatter, content);
+    }
+
+    state
         .db
-        .replace_documents(session_id, &drafts)
-        .map_err(AppError::from)?;
-
-    let _ = app.emit(
-        "generate:complete",
-        GenerateComplete {
-            session_id: session_id.to_string(),
-            count: documents.len(),
+        .replace_document(session_id, filename, &content, "regenerate")
+        .map_err(AppError::from)
+}
+
+fn regeneration_prompt(filename: &str) -> Result<(&'static str, &'static str), AppError> {
+    if DATA_DERIVED_DOCS.contains(&filename) {
+        return Err(AppError::Validation(format!(
+            "{} is built from session data, not generated. Re-forge the plan to refresh it.",
+            filename
+        )));
+    }
+    let prompt_name = if ForgeTarget::AGENT_CONFIG_FILENAMES.contains(&filename) {
+        "CLAUDE.md"
+    } else {
+        filename
+    };
+    DOC_PROMPTS
+        .iter()
+        .find(|(name, _)| *name == prompt_name)
+        .copied()
+        .ok_or_else(|| {
+            AppError::Validation(format!(
+                "Unknown document '{}'. Only {} can be regenerated.",
+                filename,
+                LLM_GENERATED_DOCS.join(", ")
+            ))
+        })
+}
+
+fn previously_generated_context(docs: &[(String, String)]) -> String {
+    if docs.is_empty() {
+        return "No documents generated yet.".to_string();
+    }
+    docs.iter()
+        .map(|(name, content)| format!("## {}\n\n{}", name, content))
+        .collect::<Vec<_>>()
+        .join("\n\n---\n\n")
+}
+
+async fn generate_validated_doc(
+    state: &AppState,
+    config: &AppConfig,
+    filename: &str,
+    system_prompt: &str,
+    prompt: &str,
+) -> Result<(String, u32, Option<String>), AppError> {
+    let validation = DocValidation::from_config(&config.output.doc_validation);
+    let llm_messages = vec![
+        ChatMessage {
+            role: "system".to_string(),
+            content: system_prompt.to_string(),
         },
+        ChatMessage {
+            role: "user".to_string(),
+            content: prompt.to_string(),
+        },
+    ];
+
+    let mut content = state
+        .ollama
+        .generate(&config.llm, llm_messages, 0.4) // Lower temperature for structured output
+        .await?
+        .content;
+
+    let mut attempts = 1u32;
+    let mut failure = validate_generated_doc(filename, &content, validation).err();
+    while let Some(reason) = failure.clone() {
+        if attempts > config.output.generation_retries {
+            break;
+        }
+        log::warn!(
+            "{} failed validation on attempt {} ({}), retrying",
+            filename,
+            attempts,
+            reason
+        );
+
+        let retry_messages = vec![
+            ChatMessage {
+                role: "system".to_string(),
+                content: system_prompt.to_string(),
+            },
+            ChatMessage {
+                role: "user".to_string(),
+                content: format!(
+                    "{}\n\nIMPORTANT: Your previous attempt was rejected ({}). Start with a # heading and output the complete document as valid Markdown.",
+                    prompt, reason
+                ),
+            },
+        ];
+
+        content = state
+            .ollama
+            .generate(&config.llm, retry_messages, 0.3)
+            .await?
+            .content;
+        attempts += 1;
+        failure = validate_generated_doc(filename, &content, validation).err();
+    }
+    Ok((content, attempts, failure))
+}
+
+fn cancelled_output(
+    app: Option<&tauri::AppHandle>,
+    db: &Database,
+    session_id: &str,
+    drafts: &[(String, String)],
+    warnings: Vec<GenerationWarning>,
+    timings: Vec<DocumentTiming>,
+    started: Instant,
+) -> Result<GenerationOutput, AppError> {
+    log::info!(
+        "Generation cancelled for {} after {} document(s)",
+        session_id,
+        drafts.len()
     );
+    let documents = finalize_documents(db, session_id, drafts, true)?;
+    if let Some(app) = app {
+        let _ = app.emit(
+            "generate:cancelled",
+            GenerateCancelled {
+                session_id: session_id.to_string(),
+                documents: documents.clone(),
+            },
+        );
+    }
+    Ok(GenerationOutput {
+        documents,
+        warnings,
+        timing: GenerationTiming {
+            total_ms: millis(started.elapsed()),
+            documents: timings,
+        },
+        cancelled: true,
+    })
+}
 
+fn finalize_documents(
+    db: &Database,
+    session_id: &str,
+    drafts: &[(String, String)],
+    cancelled: bool,
+) -> Result<Vec<GeneratedDocument>, AppError> {
+    if cancelled {
+        let created_at = timestamp::now();
+        return Ok(drafts
+            .iter()
+            .map(|(filename, content)| GeneratedDocument {
+                id: String::new(),
+                session_id: session_id.to_string(),
+                filename: filename.clone(),
+                content: content.clone(),
+                created_at: created_at.clone(),
+            })
+            .collect());
+    }
+
+    let documents = db.replace_documents(session_id, drafts)?;
+    db.clear_generation_drafts(session_id)?;
     Ok(documents)
 }
 
-fn format_conversation_for_prompt(messages: &[Message]) -> String {
-    let mut output = String::new();
+fn millis(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
+
+fn validate_generated_doc(
+    filename: &str,
+    content: &str,
+    validation: DocValidation,
+) -> Result<(), String> {
+    if validation == DocValidation::Off {
+        return Ok(());
+    }
 
-    for msg in messages {
-        if msg.role == "system" {
-            continue;
+    let trimmed = strip_frontmatter(content).trim();
+    if !trimmed.starts_with('#') {
+        return Err("output does not start with a Markdown # heading".to_string());
+    }
+    if validation == DocValidation::Heading {
+        return Ok(());
+    }
+
+    let length = trimmed.chars().count();
+    if LENGTH_CHECKED_DOCS.contains(&filename) && length < MIN_CORE_DOC_CHARS {
+        return Err(format!(
+            "output is suspiciously short ({} chars, expected at least {})",
+            length, MIN_CORE_DOC_CHARS
+        ));
+    }
+
+    let head = trimmed
+        .chars()
+        .take(REFUSAL_SCAN_CHARS)
+        .collect::<String>()
+        .to_lowercase()
+        .replace('\u{2019}', "'");
+    if let Some(phrase) = REFUSAL_PHRASES.iter().find(|phrase| head.contains(*phrase)) {
+        return Err(format!("output looks like a refusal (\"{}\")", phrase));
+    }
+
+    Ok(())
+}
+
+fn build_frontmatter(project: &str, date: &str, target: &str, session_id: &str) -> String {
+    format!(
+        "---\nproject: {}\ngenerated: {}\ntarget: {}\nsource_session_id: {}\n---\n\n",
+        yaml_string(project),
+        yaml_string(date),
+        yaml_string(target),
+        yaml_string(session_id)
+    )
+}
+
+fn yaml_string(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', " ");
+    format!("\"{}\"", escaped)
+}
+
+fn strip_frontmatter(content: &str) -> &str {
+    let rest = content.trim_start();
+    let Some(after_open) = rest
+        .strip_prefix("---\n")
+        .or_else(|| rest.strip_prefix("---\r\n"))
+    else {
+        return content;
+    };
+    let mut offset = 0;
+    for line in after_open.split_inclusive('\n') {
+        offset += line.len();
+        if line.trim_end() == "---" {
+            return &after_open[offset..];
         }
+    }
+    content
+}
 
-        let label = match msg.role.as_str() {
-            "user" => "User",
-            "assistant" => "AuraForge",
-            _ => "Unknown",
-        };
+const CHARS_PER_TOKEN: usize = 4;
+const PREAMBLE_BUDGET_DIVISOR: usize = 5;
+const PREAMBLE_POINT_CHARS: usize = 160;
+
+fn format_conversation_for_prompt(messages: &[Message], token_limit: u64) -> String {
+    let messages = messages
+        .iter()
+        .filter(|msg| msg.role != "system")
+        .collect::<Vec<_>>();
+    let turns = messages
+        .iter()
+        .map(|msg| {
+            let label = match msg.role.as_str() {
+                "user" => "User",
+                "assistant" => "AuraForge",
+                _ => "Unknown",
+            };
+            format!("{}: {}\n\n", label, msg.content)
+        })
+        .collect::<Vec<_>>();
+
+    let budget = usize::try_from(token_limit)
+        .unwrap_or(usize::MAX)
+        .saturating_mul(CHARS_PER_TOKEN);
+    let total = turns.iter().map(|turn| turn.chars().count()).sum::<usize>();
+    if token_limit == 0 || total <= budget {
+        return turns.concat();
+    }
+
+    let preamble_budget = budget / PREAMBLE_BUDGET_DIVISOR;
+    let recent_budget = budget - preamble_budget;
+    let mut used = 0;
+    let mut kept = 0;
+    for turn in turns.iter().rev() {
+        let len = turn.chars().count();
+        if used + len > recent_budget {
+            break;
+        }
+        used += len;
+        kept += 1;
+    }
+
+    let (split, recent) = if kept == 0 {
+        // Even the latest turn is over budget: keep its tail.
+        let last = &turns[turns.len() - 1];
+        let skip = last.chars().count() - recent_budget;
+        (
+            turns.len() - 1,
+            format!("…{}", last.chars().skip(skip).collect::<String>()),
+        )
+    } else {
+        (turns.len() - kept, turns[turns.len() - kept..].concat())
+    };
+    log::info!(
+        "Conversation exceeds generation_context_limit ({} tokens); condensing {} of {} turns",
+        token_limit,
+        split,
+        turns.len()
+    );
 
-        output.push_str(&format!("{}: {}\n\n", label, msg.content));
+    format!(
+        "{}{}",
+        condense_older_turns(&messages[..split], preamble_budget),
+        recent
+    )
+}
+
+fn condense_older_turns(messages: &[&Message], budget: usize) -> String {
+    let mut output = format!(
+        "[Earlier conversation condensed: {} turns omitted. User points in order:]\n",
+        messages.len()
+    );
+    let mut used = output.chars().count();
+
+    for msg in messages.iter().filter(|msg| msg.role == "user") {
+        let first_line = msg.content.lines().find(|line| !line.trim().is_empty());
+        let Some(first_line) = first_line else {
+            continue;
+        };
+        let mut point = first_line
+            .trim()
+            .chars()
+            .take(PREAMBLE_POINT_CHARS)
+            .collect::<String>();
+        if point.chars().count() < first_line.trim().chars().count() {
+            point.push('…');
+        }
+        let line = format!("- {}\n", point);
+        let len = line.chars().count();
+        if used + len > budget {
+            output.push_str("- …\n");
+            break;
+        }
+        output.push_str(&line);
+        used += len;
     }
 
+    output.push('\n');
     output
 }
 
-fn generate_conversation_md(session: &Session, messages: &[Message]) -> String {
+fn generate_conversation_md(
+    session: &Session,
+    messages: &[Message],
+    include_generation_details: bool,
+) -> String {
     let mut output = format!(
         "# {} - Planning Conversation\n\n\
          This is the complete planning conversation that generated these documents.\n\
@@ -206,6 +789,7 @@ fn generate_conversation_md(session: &Session, messages: &[Message]) -> String {
         session.name, session.created_at
     );
 
+    let mut next_footnote = 1;
     for message in messages {
         let role_label = match message.role.as_str() {
             "user" => "**User**",
@@ -214,13 +798,30 @@ fn generate_conversation_md(session: &Session, messages: &[Message]) -> String {
             _ => "**Unknown**",
         };
 
-        output.push_str(&format!("{}: {}\n\n", role_label, message.content));
+        let meta = message
+            .metadata
+            .as_deref()
+            .and_then(|metadata| serde_json::from_str::<serde_json::Value>(metadata).ok());
+        let citations = meta
+            .as_ref()
+            .and_then(|meta| meta.get("citations").cloned())
+            .and_then(|citations| serde_json::from_value::<Vec<Citation>>(citations).ok())
+            .unwrap_or_default();
+        let (content, footnotes) = link_citations(&message.content, &citations, &mut next_footnote);
+
+        output.push_str(&format!("{}: {}\n\n", role_label, content));
+        if !footnotes.is_empty() {
+            output.push_str(&format!("{}\n\n", footnotes.join("\n")));
+        }
 
         // Include search context if present in metadata
-        if let Some(ref metadata_str) = message.metadata {
-            if let Ok(meta) = serde_json::from_str::<serde_json::Value>(metadata_str) {
-                if let Some(query) = meta.get("search_query").and_then(|v| v.as_str()) {
-                    output.push_str(&format!("*[Searched: {}]*\n\n", query));
+        if let Some(meta) = meta {
+            if let Some(query) = meta.get("search_query").and_then(|v| v.as_str()) {
+                output.push_str(&format!("*[Searched: {}]*\n\n", query));
+            }
+            if include_generation_details {
+                if let Some(details) = generation_details_line(&meta) {
+                    output.push_str(&format!("*[{}]*\n\n", details));
                 }
             }
         }
@@ -235,10 +836,105 @@ fn generate_conversation_md(session: &Session, messages: &[Message]) -> String {
     output
 }
 
+fn link_citations(
+    content: &str,
+    citations: &[Citation],
+    next_label: &mut usize,
+) -> (String, Vec<String>) {
+    if citations.is_empty() {
+        return (content.to_string(), Vec::new());
+    }
+
+    let mut labels: Vec<(&str, usize)> = Vec::new();
+    let mut footnotes = Vec::new();
+    let mut linked = String::with_capacity(content.len());
+    let mut rest = content;
+
+    while let Some(start) = rest.find("[^") {
+        linked.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let citation = after.find(']').and_then(|end| {
+            let id = &after[..end];
+            citations
+                .iter()
+                .find(|citation| citation.id == id)
+                .map(|citation| (citation, end))
+        });
+        let Some((citation, end)) = citation else {
+            linked.push_str("[^");
+            rest = after;
+            continue;
+        };
+
+        let label = match labels.iter().find(|(id, _)| *id == citation.id) {
+            Some((_, label)) => *label,
+            None => {
+                let label = *next_label;
+                *next_label += 1;
+                labels.push((&citation.id, label));
+                footnotes.push(format!(
+                    "[^{}]: [{}]({})",
+                    label, citation.title, citation.url
+                ));
+                label
+            }
+        };
+        linked.push_str(&format!("[^{}]", label));
+        rest = &after[end + 1..];
+    }
+    linked.push_str(rest);
+
+    (linked, footnotes)
+}
+
+fn generation_details_line(meta: &serde_json::Value) -> Option<String> {
+    let model = meta.get("model_used").and_then(|v| v.as_str())?;
+    let mut line = format!("Generated by {}", model);
+    if let Some(provider) = meta.get("provider").and_then(|v| v.as_str()) {
+        line.push_str(&format!(" ({})", provider));
+    }
+    if let Some(temperature) = meta.get("temperature").and_then(|v| v.as_f64()) {
+        line.push_str(&format!(" @ {}", temperature));
+    }
+    let sources = meta
+        .get("search_results")
+        .and_then(|v| v.as_array())
+        .map_or(0, Vec::len);
+    if sources > 0 {
+        line.push_str(&format!(
+            ", grounded on {} source{}",
+            sources,
+            if sources == 1 { "" } else { "s" }
+        ));
+    }
+    Some(line)
+}
+
+pub fn render_model_handoff(
+    session: &Session,
+    target: &ForgeTarget,
+    quality: &QualityReport,
+    agent_config_file: &str,
+    frontmatter: bool,
+) -> String {
+    let handoff = generate_model_handoff_doc(session, target, quality, agent_config_file);
+    if !frontmatter {
+        return handoff;
+    }
+    let header = build_frontmatter(
+        &session.name,
+        &chrono::Local::now().format("%Y-%m-%d").to_string(),
+        target.as_str(),
+        &session.id,
+    );
+    format!("{}{}", header, handoff)
+}
+
 fn generate_model_handoff_doc(
     session: &Session,
     target: &ForgeTarget,
     quality: &QualityReport,
+    agent_config_file: &str,
 ) -> String {
     let target_name = match target {
         ForgeTarget::Claude => "Claude Code",
@@ -259,12 +955,18 @@ fn generate_model_handoff_doc(
          1. Read `START_HERE.md`\n\
          2. Read `SPEC.md`\n\
          3. Read `PROMPTS.md`\n\
-         4. Read `CLAUDE.md` for repo conventions (applies broadly even for non-Claude targets)\n\n",
+         4. Read `{}` for repo conventions{}\n\n",
         target.as_str(),
         target_name,
         session.name,
         session.updated_at,
-        quality.score
+        quality.score,
+        agent_config_file,
+        if agent_config_file == "CLAUDE.md" && *target != ForgeTarget::Claude {
+            " (applies broadly even for non-Claude targets)"
+        } else {
+            ""
+        }
     );
 
     if !quality.missing_must_haves.is_empty() {
@@ -319,3 +1021,393 @@ fn generate_model_handoff_doc(
 
     output
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(role: &str, content: &str) -> Message {
+        Message {
+            id: "m".to_string(),
+            session_id: "s".to_string(),
+            role: role.to_string(),
+            content: content.to_string(),
+            metadata: None,
+            created_at: "2026-01-01 00:00:00".to_string(),
+        }
+    }
+
+    fn long_body() -> String {
+        "- Requirement detail line\n".repeat(40)
+    }
+
+    fn test_state(base_url: &str) -> AppState {
+        let mut config = AppConfig::default();
+        config.llm.base_url = base_url.to_string();
+        config.output.doc_validation = "off".to_string();
+        config.output.generation_retries = 0;
+        AppState {
+            db: Database::new_in_memory().unwrap(),
+            ollama: crate::llm::OllamaClient::new(),
+            config: std::sync::Mutex::new(config),
+            config_error: Default::default(),
+            db_error: Default::default(),
+            stream_cancel: Default::default(),
+            search_cancel: Default::default(),
+            generation_cancel: Default::default(),
+            export_locks: Default::default(),
+            stream_slots: Default::default(),
+            housekeeping_report: Default::default(),
+        }
+    }
+
+    fn scripted_llm(
+        replies: usize,
+        cancel_on: usize,
+        cancel: std::sync::Arc<AtomicBool>,
+    ) -> (String, std::thread::JoinHandle<()>) {
+        use std::io::{Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind test port");
+        let url = format!("http://{}", listener.local_addr().expect("local addr"));
+        let handle = std::thread::spawn(move || {
+            for reply in 1..=replies {
+                let (mut socket, _) = listener.accept().expect("accept request");
+                let mut request = Vec::new();
+                let mut buf = [0u8; 4096];
+                let body_len = loop {
+                    let n = socket.read(&mut buf).expect("read request");
+                    request.extend_from_slice(&buf[..n]);
+                    if let Some(end) = request.windows(4).position(|w| w == b"\r\n\r\n") {
+                        let head = String::from_utf8_lossy(&request[..end]).to_ascii_lowercase();
+                        let length = head
+                            .lines()
+                            .find_map(|line| line.strip_prefix("content-length:"))
+                            .and_then(|value| value.trim().parse::<usize>().ok())
+                            .unwrap_or(0);
+                        break end + 4 + length;
+                    }
+                };
+                while request.len() < body_len {
+                    let n = socket.read(&mut buf).expect("read body");
+                    request.extend_from_slice(&buf[..n]);
+                }
+                if reply == cancel_on {
+                    cancel.store(true, Ordering::SeqCst);
+                }
+                let body = serde_json::json!({
+                    "message": { "content": format!("# Document {}\n\nNew content.", reply) },
+                })
+                .to_string();
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                socket
+                    .write_all(response.as_bytes())
+                    .expect("write response");
+            }
+        });
+        (url, handle)
+    }
+
+    fn seed_forged_session(state: &AppState) -> String {
+        let session = state.db.create_session(None).unwrap();
+        state
+            .db
+            .save_message(&session.id, "user", "Build a habit tracker", None)
+            .unwrap();
+        let prior = LLM_GENERATED_DOCS
+            .iter()
+            .map(|name| (name.to_string(), format!("# Old {}", name)))
+            .collect::<Vec<_>>();
+        state.db.replace_documents(&session.id, &prior).unwrap();
+        session.id
+    }
+
+    #[test]
+    fn generation_details_line_summarizes_stored_parameters() {
+        let meta = serde_json::json!({
+            "model_used": "qwen3-coder",
+            "provider": "ollama",
+            "temperature": 0.7,
+            "search_results": [{}, {}, {}],
+        });
+        assert_eq!(
+            generation_details_line(&meta).as_deref(),
+            Some("Generated by qwen3-coder (ollama) @ 0.7, grounded on 3 sources")
+        );
+        assert!(generation_details_line(&serde_json::json!({"search_query": "x"})).is_none());
+    }
+
+    #[test]
+    fn conversation_footnotes_resolve_citations_with_unique_labels() {
+        let citations = serde_json::json!({
+            "citations": [
+                { "id": "1", "title": "SQLite docs", "url": "https://sqlite.org" },
+                { "id": "2", "title": "Tauri docs", "url": "https://tauri.app" },
+            ],
+        })
+        .to_string();
+        let reply = |content: &str| Message {
+            id: "m".to_string(),
+            session_id: "s".to_string(),
+            role: "assistant".to_string(),
+            content: content.to_string(),
+            metadata: Some(citations.clone()),
+            created_at: "2026-01-01 00:00:00".to_string(),
+        };
+        let session = Session {
+            id: "s".to_string(),
+            name: "Recipes".to_string(),
+            description: None,
+            status: "active".to_string(),
+            created_at: "2026-01-01 00:00:00".to_string(),
+            updated_at: "2026-01-01 00:00:00".to_string(),
+            completed_at: None,
+        };
+
+        let md = generate_conversation_md(
+            &session,
+            &[
+                reply("Use SQLite[^1] inside Tauri[^2]; SQLite[^1] is embedded."),
+                reply("Tauri[^2] bundles small binaries, unlike [^9] or [^note]."),
+            ],
+            false,
+        );
+
+        assert!(md.contains(
+            "Use SQLite[^1] inside Tauri[^2]; SQLite[^1] is embedded.\n\n\
+             [^1]: [SQLite docs](https://sqlite.org)\n[^2]: [Tauri docs](https://tauri.app)\n\n"
+        ));
+        assert!(md.contains(
+            "Tauri[^3] bundles small binaries, unlike [^9] or [^note].\n\n\
+             [^3]: [Tauri docs](https://tauri.app)\n\n"
+        ));
+    }
+
+    #[test]
+    fn validation_passes_with_frontmatter_present() {
+        let frontmatter = build_frontmatter("Recipe \"Box\"", "2026-01-01", "claude", "s1");
+        let content = format!("{}# Spec\n\n{}", frontmatter, long_body());
+        assert!(validate_generated_doc("SPEC.md", &content, DocValidation::Strict).is_ok());
+        assert!(validate_generated_doc("SPEC.md", &content, DocValidation::Heading).is_ok());
+        assert!(frontmatter.contains("project: \"Recipe \\\"Box\\\"\"\n"));
+        assert!(frontmatter.contains("source_session_id: \"s1\"\n"));
+    }
+
+    #[test]
+    fn frontmatter_does_not_hide_a_missing_heading() {
+        let frontmatter = build_frontmatter("Demo", "2026-01-01", "generic", "s1");
+        let content = format!("{}Here is your spec:\n\n{}", frontmatter, long_body());
+        assert!(validate_generated_doc("SPEC.md", &content, DocValidation::Heading).is_err());
+        assert_eq!(strip_frontmatter("---\nunterminated"), "---\nunterminated");
+    }
+
+    #[test]
+    fn regeneration_prompt_accepts_llm_docs_only() {
+        assert_eq!(regeneration_prompt("SPEC.md").unwrap().0, "SPEC.md");
+        assert_eq!(regeneration_prompt("GEMINI.md").unwrap().0, "CLAUDE.md");
+        assert_eq!(
+            regeneration_prompt(".cursorrules").unwrap().1,
+            CLAUDE_PROMPT
+        );
+
+        for derived in ["CONVERSATION.md", "MODEL_HANDOFF.md"] {
+            assert!(matches!(
+                regeneration_prompt(derived),
+                Err(AppError::Validation(msg)) if msg.contains("built from session data")
+            ));
+        }
+        assert!(matches!(
+            regeneration_prompt("NOTES.md"),
+            Err(AppError::Validation(msg)) if msg.starts_with("Unknown document 'NOTES.md'")
+        ));
+    }
+
+    #[test]
+    fn previously_generated_context_lists_docs_by_name() {
+        assert_eq!(
+            previously_generated_context(&[]),
+            "No documents generated yet."
+        );
+        let context = previously_generated_context(&[
+            ("SPEC.md".to_string(), "# Spec".to_string()),
+            ("README.md".to_string(), "# Readme".to_string()),
+        ]);
+        assert_eq!(
+            context,
+            "## SPEC.md\n\n# Spec\n\n---\n\n## README.md\n\n# Readme"
+        );
+    }
+
+    #[tokio::test]
+    async fn cancelled_generation_leaves_persisted_documents_intact() {
+        // The cancel arrives while the second of five documents is generating.
+        let cancel = std::sync::Arc::new(AtomicBool::new(false));
+        let (url, server) = scripted_llm(2, 2, cancel.clone());
+        let state = test_state(&url);
+        let session_id = seed_forged_session(&state);
+
+        let output = generate_all_documents(
+            None,
+            &state,
+            &session_id,
+            &ForgeTarget::Claude,
+            false,
+            Some(&cancel),
+        )
+        .await
+        .unwrap();
+        server.join().expect("server thread");
+
+        assert!(output.cancelled);
+        let preview = output
+            .documents
+            .iter()
+            .map(|doc| doc.filename.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(preview, vec!["SPEC.md", "CLAUDE.md"]);
+        assert!(output.documents.iter().all(|doc| doc.id.is_empty()));
+        assert_eq!(output.documents[0].content, "# Document 1\n\nNew content.");
+
+        let stored = state.db.get_documents(&session_id).unwrap();
+        assert_eq!(stored.len(), LLM_GENERATED_DOCS.len());
+        let spec = stored.iter().find(|doc| doc.filename == "SPEC.md").unwrap();
+        assert_eq!(spec.content, "# Old SPEC.md");
+    }
+
+    #[tokio::test]
+    async fn cancel_during_the_last_document_is_not_persisted() {
+        let cancel = std::sync::Arc::new(AtomicBool::new(false));
+        let (url, server) = scripted_llm(
+            LLM_GENERATED_DOCS.len(),
+            LLM_GENERATED_DOCS.len(),
+            cancel.clone(),
+        );
+        let state = test_state(&url);
+        let session_id = seed_forged_session(&state);
+
+        let output = generate_all_documents(
+            None,
+            &state,
+            &session_id,
+            &ForgeTarget::Claude,
+            false,
+            Some(&cancel),
+        )
+        .await
+        .unwrap();
+        server.join().expect("server thread");
+
+        assert!(output.cancelled);
+        assert_eq!(output.documents.len(), LLM_GENERATED_DOCS.len());
+        let stored = state.db.get_documents(&session_id).unwrap();
+        assert_eq!(stored.len(), LLM_GENERATED_DOCS.len());
+        assert!(stored.iter().all(|doc| doc.content.starts_with("# Old ")));
+    }
+
+    #[test]
+    fn conversation_is_unchanged_without_a_limit() {
+        let messages = vec![
+            message("system", "hidden"),
+            message("user", "Build a todo app"),
+            message("assistant", "Which platform?"),
+        ];
+        assert_eq!(
+            format_conversation_for_prompt(&messages, 0),
+            "User: Build a todo app\n\nAuraForge: Which platform?\n\n"
+        );
+    }
+
+    #[test]
+    fn long_conversation_is_truncated_to_recent_turns() {
+        let mut messages = vec![message("user", "Goal: offline-first recipe manager")];
+        for i in 0..200 {
+            messages.push(message(
+                "assistant",
+                &format!("Question {} {}", i, "x".repeat(200)),
+            ));
+            messages.push(message(
+                "user",
+                &format!("Answer {} {}", i, "y".repeat(200)),
+            ));
+        }
+        messages.push(message("user", "Final: ship on Tauri"));
+
+        let limit = 1000;
+        let prompt = format_conversation_for_prompt(&messages, limit);
+        assert!(prompt.chars().count() <= limit as usize * CHARS_PER_TOKEN);
+        assert!(prompt.starts_with("[Earlier conversation condensed:"));
+        assert!(prompt.contains("- Goal: offline-first recipe manager"));
+        assert!(prompt.ends_with("User: Final: ship on Tauri\n\n"));
+        assert!(!prompt.contains("Question 0 "));
+    }
+
+    #[test]
+    fn oversized_latest_turn_keeps_its_tail() {
+        let messages = vec![
+            message("user", "start"),
+            message("user", &format!("{}END", "z".repeat(10_000))),
+        ];
+        let prompt = format_conversation_for_prompt(&messages, 1000);
+        assert!(prompt.chars().count() <= 4000);
+        assert!(prompt.ends_with("END\n\n"));
+        assert!(prompt.contains("- start"));
+    }
+
+    #[test]
+    fn validation_accepts_well_formed_core_doc() {
+        let content = format!("# Spec\n\n{}", long_body());
+        assert!(validate_generated_doc("SPEC.md", &content, DocValidation::Strict).is_ok());
+    }
+
+    #[test]
+    fn validation_rejects_missing_heading_in_every_enabled_mode() {
+        let content = format!("Here is your spec:\n\n{}", long_body());
+        let strict = validate_generated_doc("SPEC.md", &content, DocValidation::Strict)
+            .expect_err("non-heading output should fail strict validation");
+        assert!(strict.contains("heading"));
+        assert!(validate_generated_doc("SPEC.md", &content, DocValidation::Heading).is_err());
+        assert!(validate_generated_doc("SPEC.md", &content, DocValidation::Off).is_ok());
+    }
+
+    #[test]
+    fn validation_rejects_short_core_docs_only_in_strict_mode() {
+        let short = "# Prompts\n\n## Phase 1\nDo it.";
+        let err = validate_generated_doc("PROMPTS.md", short, DocValidation::Strict)
+            .expect_err("short PROMPTS.md should fail strict validation");
+        assert!(err.contains("suspiciously short"));
+        assert!(validate_generated_doc("PROMPTS.md", short, DocValidation::Heading).is_ok());
+        // README is not length-checked; short orientation docs are fine.
+        assert!(validate_generated_doc("README.md", short, DocValidation::Strict).is_ok());
+    }
+
+    #[test]
+    fn validation_rejects_refusals_near_the_top() {
+        let refusal = format!(
+            "# Sorry\n\nI\u{2019}m sorry, but I can't generate this document.\n{}",
+            long_body()
+        );
+        let err = validate_generated_doc("SPEC.md", &refusal, DocValidation::Strict)
+            .expect_err("refusal should fail strict validation");
+        assert!(err.contains("refusal"));
+
+        let quoted_late = format!(
+            "# Spec\n\n{}\nError copy: \"I'm sorry, but that failed.\"",
+            long_body()
+        );
+        assert!(validate_generated_doc("SPEC.md", &quoted_late, DocValidation::Strict).is_ok());
+    }
+
+    #[test]
+    fn doc_validation_parses_config_values() {
+        assert_eq!(DocValidation::from_config("off"), DocValidation::Off);
+        assert_eq!(
+            DocValidation::from_config(" Heading "),
+            DocValidation::Heading
+        );
+        assert_eq!(DocValidation::from_config("unknown"), DocValidation::Strict);
+    }
+}
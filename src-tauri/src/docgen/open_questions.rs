@@ -0,0 +1,307 @@
+use std::collections::HashSet;
+
+use crate::types::{GeneratedDocument, OpenQuestion};
+
+const SKIPPED_DOCS: &[&str] = &[
+    "CONVERSATION.md",
+    "LINT_REPORT.md",
+    "ARTIFACT_CHANGELOG.md",
+    "ARTIFACT_DIFF.json",
+];
+
+const SEPARATORS: &[char] = &[' ', '—', '–', '-', ':', ';', ',', '.'];
+
+pub fn extract_open_questions(docs: &[GeneratedDocument]) -> Vec<OpenQuestion> {
+    let mut questions = Vec::new();
+    let mut seen = HashSet::new();
+
+    for doc in docs {
+        if SKIPPED_DOCS.contains(&doc.filename.as_str()) {
+            continue;
+        }
+
+        let lines = doc.content.lines().collect::<Vec<_>>();
+        let section = if doc.filename == "SPEC.md" {
+            open_questions_section(&lines)
+        } else {
+            None
+        };
+
+        let mut push = |question: String, recommendation: Option<String>| {
+            if seen.insert((doc.filename.clone(), question.to_lowercase())) {
+                questions.push(OpenQuestion {
+                    filename: doc.filename.clone(),
+                    question,
+                    recommendation,
+                });
+            }
+        };
+
+        if let Some((start, end)) = section {
+            for line in &lines[start..end] {
+                let Some(item) = list_item_text(line) else {
+                    continue;
+                };
+                let text = unwrap_markers(item);
+                let (question, recommendation) = split_recommendation(&text);
+                if let Some(question) = question {
+                    push(question, recommendation);
+                }
+            }
+        }
+
+        let mut current_heading = "";
+        for (index, line) in lines.iter().enumerate() {
+            if let Some(heading) = heading_text(line) {
+                current_heading = heading;
+            }
+            if section.is_some_and(|(start, end)| index >= start && index < end) {
+                continue;
+            }
+
+            for (inner, remainder) in find_markers(line) {
+                let (question, recommendation) = split_recommendation(&inner);
+                let question = question
+                    .or_else(|| context_label(&remainder))
+                    .or_else(|| clean_label(current_heading))
+                    .unwrap_or_else(|| "Unspecified item".to_string());
+                push(question, recommendation);
+            }
+        }
+    }
+
+    questions
+}
+
+fn open_questions_section(lines: &[&str]) -> Option<(usize, usize)> {
+    let (heading_index, level) = lines.iter().enumerate().find_map(|(index, line)| {
+        let level = heading_level(line)?;
+        heading_text(line)?
+            .to_ascii_lowercase()
+            .contains("open questions")
+            .then_some((index, level))
+    })?;
+
+    let end = lines
+        .iter()
+        .enumerate()
+        .skip(heading_index + 1)
+        .find(|(_, line)| heading_level(line).is_some_and(|next| next <= level))
+        .map(|(index, _)| index)
+        .unwrap_or(lines.len());
+
+    Some((heading_index + 1, end))
+}
+
+pub(super) fn heading_level(line: &str) -> Option<usize> {
+    let trimmed = line.trim_start();
+    let level = trimmed.chars().take_while(|c| *c == '#').count();
+    (level > 0 && trimmed[level..].starts_with(' ')).then_some(level)
+}
+
+pub(super) fn heading_text(line: &str) -> Option<&str> {
+    let level = heading_level(line)?;
+    Some(line.trim_start()[level..].trim())
+}
+
+fn list_item_text(line: &str) -> Option<&str> {
+    let trimmed = line.trim_start();
+    for bullet in ["- [ ] ", "- ", "* ", "+ "] {
+        if let Some(rest) = trimmed.strip_prefix(bullet) {
+            return Some(rest.trim());
+        }
+    }
+    let digits = trimmed.chars().take_while(char::is_ascii_digit).count();
+    if digits > 0 {
+        let rest = &trimmed[digits..];
+        return rest
+            .strip_prefix(". ")
+            .or_else(|| rest.strip_prefix(") "))
+            .map(str::trim);
+    }
+    None
+}
+
+fn find_markers(line: &str) -> Vec<(String, String)> {
+    let lower = line.to_ascii_lowercase();
+    let mut markers = Vec::new();
+    let mut search_from = 0;
+
+    while let Some(offset) = lower[search_from..].find("[tbd") {
+        let start = search_from + offset;
+        let end = line[start..]
+            .find(']')
+            .map(|close| start + close + 1)
+            .unwrap_or(line.len());
+        search_from = end;
+
+        if line[..start].ends_with('`') {
+            continue;
+        }
+
+        let inner = line[start + "[tbd".len()..end]
+            .trim_end_matches(']')
+            .trim_start_matches(SEPARATORS)
+            .trim()
+            .to_string();
+        let remainder = format!("{}{}", &line[..start], &line[end..]);
+        markers.push((inner, remainder));
+    }
+
+    markers
+}
+
+fn unwrap_markers(text: &str) -> String {
+    let markers = find_markers(text);
+    if markers.is_empty() {
+        return text.to_string();
+    }
+    markers
+        .into_iter()
+        .map(|(inner, remainder)| {
+            if inner.is_empty() {
+                remainder
+            } else {
+                format!("{} {}", remainder.trim(), inner)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn split_recommendation(text: &str) -> (Option<String>, Option<String>) {
+    let Some(index) = text.to_ascii_lowercase().find("recommend") else {
+        return (clean_label(text), None);
+    };
+
+    let tail = text[index..].trim().trim_end_matches(['.', ')', ']', '*']);
+    let tail = match tail.get(.."recommendation".len()) {
+        Some(label) if label.eq_ignore_ascii_case("recommendation") => tail
+            ["recommendation".len()..]
+            .trim_start_matches(SEPARATORS)
+            .trim(),
+        _ => tail,
+    };
+    let recommendation = (!tail.is_empty()).then(|| capitalize(tail));
+
+    (clean_label(&text[..index]), recommendation)
+}
+
+fn context_label(remainder: &str) -> Option<String> {
+    let text = list_item_text(remainder).unwrap_or(remainder);
+    clean_label(text)
+}
+
+fn clean_label(text: &str) -> Option<String> {
+    let cleaned = text
+        .replace("**", "")
+        .trim()
+        .trim_start_matches(['#', '-', '*', '+', '('])
+        .trim_end_matches(SEPARATORS)
+        .trim_end_matches(['(', '"', '['])
+        .trim()
+        .trim_matches('"')
+        .trim()
+        .to_string();
+    if cleaned.is_empty() {
+        None
+    } else {
+        Some(cleaned)
+    }
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn doc(filename: &str, content: &str) -> GeneratedDocument {
+        GeneratedDocument {
+            id: "doc-id".to_string(),
+            session_id: "session-id".to_string(),
+            filename: filename.to_string(),
+            content: content.to_string(),
+            created_at: "2026-02-07 00:00:00".to_string(),
+        }
+    }
+
+    #[test]
+    fn parses_canonical_and_bracketed_variants() {
+        let questions = extract_open_questions(&[doc(
+            "CLAUDE.md",
+            "# Project\n\n## Conventions\n\
+             - Error handling: [TBD — error handling not discussed. Recommend defining before Phase 3.]\n\
+             - Deployment target: [TBD]\n\
+             - Auth: [tbd: unclear from conversation: 'SSO']\n",
+        )]);
+
+        assert_eq!(questions.len(), 3);
+        assert_eq!(questions[0].question, "error handling not discussed");
+        assert_eq!(
+            questions[0].recommendation.as_deref(),
+            Some("Recommend defining before Phase 3")
+        );
+        assert_eq!(questions[1].question, "Deployment target");
+        assert!(questions[1].recommendation.is_none());
+        assert_eq!(questions[2].question, "unclear from conversation: 'SSO'");
+    }
+
+    #[test]
+    fn reads_spec_open_questions_section_without_duplicates() {
+        let spec = "# Spec\n\n## 8. Security\n[TBD — security not discussed]\n\n\
+                    ## 9. Open Questions\n\
+                    - Which database? Recommendation: SQLite for v1.\n\
+                    - [TBD — security not discussed. Recommend a review before launch.]\n\
+                    1. Offline support scope\n\n\
+                    ## 10. Appendix\nNothing here.\n";
+        let questions = extract_open_questions(&[doc("SPEC.md", spec)]);
+
+        let texts = questions
+            .iter()
+            .map(|q| q.question.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            texts,
+            vec![
+                "Which database?",
+                "security not discussed",
+                "Offline support scope"
+            ]
+        );
+        assert_eq!(
+            questions[0].recommendation.as_deref(),
+            Some("SQLite for v1")
+        );
+        assert_eq!(
+            questions[1].recommendation.as_deref(),
+            Some("Recommend a review before launch")
+        );
+    }
+
+    #[test]
+    fn ignores_reports_and_inline_code_mentions() {
+        let questions = extract_open_questions(&[
+            doc("LINT_REPORT.md", "Found 2 `[TBD ...]` marker(s). [TBD]"),
+            doc(
+                "MODEL_HANDOFF.md",
+                "# Handoff\n- [ ] If uncertain, keep it marked as `[TBD]` with missing evidence.\n",
+            ),
+        ]);
+        assert!(questions.is_empty());
+    }
+
+    #[test]
+    fn bare_marker_without_context_uses_heading() {
+        let questions =
+            extract_open_questions(&[doc("README.md", "# Readme\n## Deployment\n[TBD]\n")]);
+        assert_eq!(questions.len(), 1);
+        assert_eq!(questions[0].question, "Deployment");
+    }
+}
@@ -1,76 +1,130 @@
 use std::collections::HashSet;
 
-use crate::types::{CoverageReport, CoverageStatus, CoverageTopic, Message, QualityReport};
+use crate::types::{
+    CoverageReport, CoverageStatus, CoverageTopic, Message, PlanningDepth, QualityReport,
+    ReadinessChecklist, ReadinessItem, TopicPriority,
+};
 
-const MUST_HAVE_TOPICS: &[(&str, &[&str])] = &[
+type TopicSpec = (
+    &'static str,
+    &'static str,
+    &'static [&'static str],
+    &'static [&'static str],
+);
+
+const STRONG_KEYWORD_WEIGHT: usize = 2;
+const RICH_MESSAGE_MIN_WEIGHT: usize = 5;
+const RICH_MESSAGE_MIN_CHARS: usize = 240;
+
+const TOPICS: &[TopicSpec] = &[
     (
+        "problem_statement",
         "Problem statement / why this exists",
-        &["problem", "goal", "why", "build", "need", "pain point"],
+        &["problem", "goal", "why", "build", "need"],
+        &["pain point", "problem statement", "users struggle"],
     ),
     (
+        "core_user_flow",
         "Core user flow (step-by-step)",
-        &["flow", "workflow", "step", "screen", "journey", "user does"],
+        &["flow", "workflow", "step", "screen"],
+        &["journey", "user does", "step-by-step", "user flow"],
     ),
     (
+        "tech_stack",
         "Tech stack with rationale",
-        &[
-            "stack",
-            "react",
-            "rust",
-            "database",
-            "framework",
-            "tauri",
-            "why this",
-        ],
+        &["stack", "react", "rust", "database", "framework", "tauri"],
+        &["why this", "tech stack"],
     ),
     (
+        "data_model",
         "Data model / persistence strategy",
-        &["data", "schema", "entity", "table", "persist", "storage"],
+        &["data", "table", "persist", "storage"],
+        &["schema", "entity", "data model", "foreign key", "migration"],
     ),
     (
+        "scope_boundaries",
         "Scope boundaries (what is out for v1)",
-        &[
-            "scope",
-            "mvp",
-            "v1",
-            "out of scope",
-            "not included",
-            "later",
-        ],
+        &["scope", "mvp", "v1", "later"],
+        &["out of scope", "not included", "non-goal"],
     ),
-];
-
-const SHOULD_HAVE_TOPICS: &[(&str, &[&str])] = &[
     (
+        "error_handling",
         "Error handling approach",
         &["error", "failure", "retry", "fallback", "recover"],
+        &["error handling", "graceful degradation"],
     ),
     (
+        "design_tradeoffs",
         "Design trade-offs / decisions",
-        &["trade-off", "tradeoff", "decision", "chose", "alternative"],
+        &["decision", "chose", "alternative"],
+        &["trade-off", "tradeoff"],
     ),
     (
+        "testing_strategy",
         "Testing strategy",
-        &[
-            "test",
-            "verification",
-            "qa",
-            "integration test",
-            "unit test",
-        ],
+        &["test", "verification", "qa"],
+        &["integration test", "unit test", "test plan"],
     ),
     (
+        "security",
         "Security considerations",
-        &["security", "auth", "permissions", "privacy", "threat"],
+        &["security", "auth", "permissions", "privacy"],
+        &["threat", "encryption", "least privilege"],
     ),
     (
+        "performance",
         "Performance requirements",
-        &["performance", "latency", "throughput", "memory", "optimize"],
+        &["performance", "memory", "optimize"],
+        &["latency", "throughput", "p95"],
     ),
 ];
 
-pub fn analyze_plan_readiness(messages: &[Message]) -> QualityReport {
-    let coverage = analyze_planning_coverage(messages);
+fn depth_topic_ids(depth: PlanningDepth) -> (&'static [&'static str], &'static [&'static str]) {
+    match depth {
+        PlanningDepth::Quick => (
+            &["problem_statement", "core_user_flow", "tech_stack"],
+            &["data_model", "scope_boundaries"],
+        ),
+        PlanningDepth::Standard => (
+            &[
+                "problem_statement",
+                "core_user_flow",
+                "tech_stack",
+                "data_model",
+                "scope_boundaries",
+            ],
+            &[
+                "error_handling",
+                "design_tradeoffs",
+                "testing_strategy",
+                "security",
+                "performance",
+            ],
+        ),
+        PlanningDepth::Thorough => (
+            &[
+                "problem_statement",
+                "core_user_flow",
+                "tech_stack",
+                "data_model",
+                "scope_boundaries",
+                "error_handling",
+                "testing_strategy",
+                "security",
+            ],
+            &["design_tradeoffs", "performance"],
+        ),
+    }
+}
+
+fn topics_by_id(ids: &[&str]) -> Vec<TopicSpec> {
+    ids.iter()
+        .filter_map(|wanted| TOPICS.iter().find(|(id, ..)| id == wanted).copied())
+        .collect()
+}
+
+pub fn analyze_plan_readiness(messages: &[Message], depth: PlanningDepth) -> QualityReport {
+    let coverage = analyze_planning_coverage(messages, depth);
     let missing_must_haves = coverage
         .must_have
         .iter()
@@ -111,14 +165,15 @@ pub fn analyze_plan_readiness(messages: &[Message]) -> QualityReport {
     }
 }
 
-pub fn analyze_planning_coverage(messages: &[Message]) -> CoverageReport {
+pub fn analyze_planning_coverage(messages: &[Message], depth: PlanningDepth) -> CoverageReport {
     let non_system_messages = messages
         .iter()
         .filter(|message| message.role != "system")
         .collect::<Vec<_>>();
 
-    let must_have = evaluate_topics(MUST_HAVE_TOPICS, &non_system_messages);
-    let should_have = evaluate_topics(SHOULD_HAVE_TOPICS, &non_system_messages);
+    let (must_have_ids, should_have_ids) = depth_topic_ids(depth);
+    let must_have = evaluate_topics(&topics_by_id(must_have_ids), &non_system_messages);
+    let should_have = evaluate_topics(&topics_by_id(should_have_ids), &non_system_messages);
     let missing_must_haves = must_have
         .iter()
         .filter(|topic| topic.status == CoverageStatus::Missing)
@@ -151,38 +206,119 @@ pub fn analyze_planning_coverage(messages: &[Message]) -> CoverageReport {
     }
 }
 
-fn evaluate_topics(topics: &[(&str, &[&str])], messages: &[&Message]) -> Vec<CoverageTopic> {
+pub fn build_readiness_checklist(messages: &[Message], depth: PlanningDepth) -> ReadinessChecklist {
+    let coverage = analyze_planning_coverage(messages, depth);
+    let quality = analyze_plan_readiness(messages, depth);
+
+    let mut items = coverage
+        .must_have
+        .iter()
+        .map(|topic| (TopicPriority::MustHave, topic))
+        .chain(
+            coverage
+                .should_have
+                .iter()
+                .map(|topic| (TopicPriority::ShouldHave, topic)),
+        )
+        .map(|(priority, topic)| ReadinessItem {
+            id: topic.id.clone(),
+            topic: topic.topic.clone(),
+            priority,
+            status: topic.status.clone(),
+            evidence_count: topic.evidence_message_ids.len(),
+        })
+        .collect::<Vec<_>>();
+    // Stable sort keeps the curated topic order within each bucket.
+    items.sort_by_key(|item| match (&item.status, item.priority) {
+        (CoverageStatus::Missing, TopicPriority::MustHave) => 0,
+        (CoverageStatus::Partial, TopicPriority::MustHave) => 1,
+        (CoverageStatus::Missing, TopicPriority::ShouldHave) => 2,
+        (CoverageStatus::Partial, TopicPriority::ShouldHave) => 3,
+        (CoverageStatus::Covered, _) => 4,
+    });
+
+    ReadinessChecklist {
+        items,
+        projected_score: quality.score,
+        can_forge_cleanly: quality.missing_must_haves.is_empty(),
+        summary: quality.summary,
+    }
+}
+
+pub fn coverage_evidence_messages(
+    coverage: &CoverageReport,
+    messages: &[Message],
+    topic: &str,
+) -> Option<Vec<Message>> {
+    let wanted = topic.trim();
+    let topic = coverage
+        .must_have
+        .iter()
+        .chain(coverage.should_have.iter())
+        .find(|candidate| {
+            candidate.id.eq_ignore_ascii_case(wanted)
+                || candidate.topic.eq_ignore_ascii_case(wanted)
+        })?;
+
+    Some(
+        messages
+            .iter()
+            .filter(|message| topic.evidence_message_ids.contains(&message.id))
+            .cloned()
+            .collect(),
+    )
+}
+
+fn evaluate_topics(topics: &[TopicSpec], messages: &[&Message]) -> Vec<CoverageTopic> {
     topics
         .iter()
-        .map(|(topic, keywords)| {
+        .map(|(id, topic, keywords, strong_keywords)| {
+            let weighted = keywords
+                .iter()
+                .map(|keyword| (*keyword, 1))
+                .chain(
+                    strong_keywords
+                        .iter()
+                        .map(|keyword| (*keyword, STRONG_KEYWORD_WEIGHT)),
+                )
+                .collect::<Vec<_>>();
             let mut evidence_message_ids = Vec::new();
             let mut matched_keywords = HashSet::new();
+            let mut has_rich_message = false;
 
             for message in messages {
                 let content = message.content.to_ascii_lowercase();
-                let mut matched_this_message = false;
+                let mut message_weight = 0;
 
-                for keyword in *keywords {
+                for (keyword, weight) in &weighted {
                     if content.contains(keyword) {
                         matched_keywords.insert(*keyword);
-                        matched_this_message = true;
+                        message_weight += weight;
                     }
                 }
 
-                if matched_this_message && evidence_message_ids.len() < 4 {
+                if message_weight >= RICH_MESSAGE_MIN_WEIGHT
+                    && message.content.chars().count() >= RICH_MESSAGE_MIN_CHARS
+                {
+                    has_rich_message = true;
+                }
+                if message_weight > 0 && evidence_message_ids.len() < 4 {
                     evidence_message_ids.push(message.id.clone());
                 }
             }
 
             let status = if matched_keywords.is_empty() {
                 CoverageStatus::Missing
-            } else if matched_keywords.len() >= 2 && evidence_message_ids.len() >= 2 {
+            } else if (matched_keywords.len() >= 2 && evidence_message_ids.len() >= 2)
+                || has_rich_message
+            {
                 CoverageStatus::Covered
             } else {
                 CoverageStatus::Partial
             };
 
             CoverageTopic {
+                id: (*id).to_string(),
                 topic: (*topic).to_string(),
                 status,
                 evidence_message_ids,
@@ -206,37 +342,80 @@ mod tests {
         }
     }
 
+    #[test]
+    fn readiness_checklist_puts_missing_must_haves_first() {
+        let messages = [
+            message(
+                "user",
+                "The problem: teams lose track of why decisions were made.",
+            ),
+            message("assistant", "Let's cover security and auth next."),
+        ];
+        let checklist = build_readiness_checklist(&messages, PlanningDepth::Standard);
+
+        assert!(!checklist.can_forge_cleanly);
+        assert_eq!(checklist.items.len(), 10);
+        assert_eq!(checklist.items[0].priority, TopicPriority::MustHave);
+        assert_eq!(checklist.items[0].status, CoverageStatus::Missing);
+
+        let ranks = checklist
+            .items
+            .iter()
+            .map(|item| match (&item.status, item.priority) {
+                (CoverageStatus::Missing, TopicPriority::MustHave) => 0,
+                (CoverageStatus::Partial, TopicPriority::MustHave) => 1,
+                (CoverageStatus::Missing, TopicPriority::ShouldHave) => 2,
+                (CoverageStatus::Partial, TopicPriority::ShouldHave) => 3,
+                (CoverageStatus::Covered, _) => 4,
+            })
+            .collect::<Vec<_>>();
+        assert!(ranks.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert_eq!(
+            checklist.projected_score,
+            analyze_plan_readiness(&messages, PlanningDepth::Standard).score
+        );
+    }
+
     #[test]
     fn reports_missing_must_haves_for_short_conversations() {
-        let report = analyze_plan_readiness(&[
-            message("user", "I want to build an app"),
-            message("assistant", "Tell me more"),
-        ]);
+        let report = analyze_plan_readiness(
+            &[
+                message("user", "I want to build an app"),
+                message("assistant", "Tell me more"),
+            ],
+            PlanningDepth::Standard,
+        );
         assert!(report.score < 90);
         assert!(!report.missing_must_haves.is_empty());
     }
 
     #[test]
     fn scores_higher_for_complete_coverage() {
-        let report = analyze_plan_readiness(&[message(
-            "user",
-            "Our problem is onboarding friction. For v1 scope, out of scope is billing. \
+        let report = analyze_plan_readiness(
+            &[message(
+                "user",
+                "Our problem is onboarding friction. For v1 scope, out of scope is billing. \
                  Core user flow: user signs up, creates project, exports plan. \
                  Tech stack is React + Rust Tauri because of local-first needs. \
                  Data schema stores sessions/messages/documents in sqlite. \
                  Testing strategy includes unit and integration test coverage. \
                  Security and performance constraints are documented with trade-off decisions.",
-        )]);
+            )],
+            PlanningDepth::Standard,
+        );
         assert!(report.score >= 90);
         assert!(report.missing_must_haves.is_empty());
     }
 
     #[test]
     fn planning_coverage_marks_partial_when_single_mention() {
-        let coverage = analyze_planning_coverage(&[message(
-            "user",
-            "The problem is onboarding friction and our goal is to ship quickly.",
-        )]);
+        let coverage = analyze_planning_coverage(
+            &[message(
+                "user",
+                "The problem is onboarding friction and our goal is to ship quickly.",
+            )],
+            PlanningDepth::Standard,
+        );
         let topic = coverage
             .must_have
             .iter()
@@ -248,7 +427,7 @@ mod tests {
 
     #[test]
     fn empty_conversation_has_zero_score_and_all_missing() {
-        let report = analyze_plan_readiness(&[]);
+        let report = analyze_plan_readiness(&[], PlanningDepth::Standard);
         assert_eq!(report.score, 0);
         assert_eq!(report.missing_must_haves.len(), 5);
         assert!(!report.missing_should_haves.is_empty());
@@ -256,16 +435,19 @@ mod tests {
 
     #[test]
     fn planning_coverage_marks_covered_with_multiple_evidence() {
-        let coverage = analyze_planning_coverage(&[
-            message(
-                "user",
-                "The core user flow starts with sign in, then workflow setup.",
-            ),
-            message(
-                "assistant",
-                "Great, this step-by-step user journey is clear with each screen.",
-            ),
-        ]);
+        let coverage = analyze_planning_coverage(
+            &[
+                message(
+                    "user",
+                    "The core user flow starts with sign in, then workflow setup.",
+                ),
+                message(
+                    "assistant",
+                    "Great, this step-by-step user journey is clear with each screen.",
+                ),
+            ],
+            PlanningDepth::Standard,
+        );
         let topic = coverage
             .must_have
             .iter()
@@ -274,4 +456,132 @@ mod tests {
         assert_eq!(topic.status, CoverageStatus::Covered);
         assert!(!topic.evidence_message_ids.is_empty());
     }
+
+    #[test]
+    fn single_rich_message_counts_as_covered() {
+        let coverage = analyze_planning_coverage(
+            &[message(
+                "user",
+                "Data model: the schema has three entity types. Sessions own messages and \
+             documents through a foreign key with cascade deletes. Everything persists in a \
+             local SQLite table per entity, and a schema migration runs on startup so older \
+             storage files upgrade in place without losing history.",
+            )],
+            PlanningDepth::Standard,
+        );
+        let topic = coverage
+            .must_have
+            .iter()
+            .find(|topic| topic.topic == "Data model / persistence strategy")
+            .expect("topic should exist");
+        assert_eq!(topic.status, CoverageStatus::Covered);
+        assert_eq!(topic.evidence_message_ids.len(), 1);
+    }
+
+    #[test]
+    fn short_message_with_strong_keywords_stays_partial() {
+        let coverage = analyze_planning_coverage(
+            &[message(
+                "user",
+                "Schema: one entity per table, with a data model migration later.",
+            )],
+            PlanningDepth::Standard,
+        );
+        let topic = coverage
+            .must_have
+            .iter()
+            .find(|topic| topic.topic == "Data model / persistence strategy")
+            .expect("topic should exist");
+        assert_eq!(topic.status, CoverageStatus::Partial);
+    }
+
+    #[test]
+    fn coverage_evidence_hydrates_cited_messages_in_order() {
+        let mut first = message("user", "Data is stored in a sqlite schema.");
+        first.id = "m1".to_string();
+        let mut unrelated = message("assistant", "Sounds good, tell me more.");
+        unrelated.id = "m2".to_string();
+        let mut second = message("assistant", "Each entity maps to one table.");
+        second.id = "m3".to_string();
+        let messages = vec![first, unrelated, second];
+        let coverage = analyze_planning_coverage(&messages, PlanningDepth::Standard);
+
+        let evidence = coverage_evidence_messages(
+            &coverage,
+            &messages,
+            "  data model / persistence strategy ",
+        )
+        .expect("topic should resolve");
+        let ids = evidence.iter().map(|m| m.id.as_str()).collect::<Vec<_>>();
+        assert_eq!(ids, vec!["m1", "m3"]);
+        assert_eq!(
+            coverage_evidence_messages(&coverage, &messages, "data_model").map(|m| m.len()),
+            Some(2)
+        );
+
+        assert!(coverage_evidence_messages(&coverage, &messages, "Nonexistent topic").is_none());
+    }
+
+    #[test]
+    fn topics_carry_unique_slug_ids_while_readiness_uses_display_names() {
+        let coverage = analyze_planning_coverage(&[], PlanningDepth::Standard);
+        let ids = coverage
+            .must_have
+            .iter()
+            .chain(coverage.should_have.iter())
+            .map(|topic| topic.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(ids.len(), ids.iter().collect::<HashSet<_>>().len());
+        assert!(ids
+            .iter()
+            .all(|id| id.chars().all(|c| c.is_ascii_lowercase() || c == '_')));
+        assert_eq!(coverage.must_have[0].id, "problem_statement");
+
+        let quality = analyze_plan_readiness(&[], PlanningDepth::Standard);
+        assert_eq!(
+            quality.missing_must_haves[0],
+            "Problem statement / why this exists"
+        );
+        let checklist = build_readiness_checklist(&[], PlanningDepth::Standard);
+        assert_eq!(checklist.items[0].id, "problem_statement");
+    }
+
+    #[test]
+    fn planning_depth_changes_what_counts_as_must_have() {
+        let messages = [message(
+            "user",
+            "The problem: freelancers lose invoices. Core user flow: step one upload, \
+             step two review. Tech stack is a React frontend with a Rust backend.",
+        )];
+
+        let quick = analyze_plan_readiness(&messages, PlanningDepth::Quick);
+        let standard = analyze_plan_readiness(&messages, PlanningDepth::Standard);
+        let thorough = analyze_plan_readiness(&messages, PlanningDepth::Thorough);
+
+        assert!(quick.missing_must_haves.is_empty());
+        assert!(standard
+            .missing_must_haves
+            .contains(&"Scope boundaries (what is out for v1)".to_string()));
+        assert!(!standard
+            .missing_must_haves
+            .contains(&"Security considerations".to_string()));
+        assert!(thorough
+            .missing_must_haves
+            .contains(&"Security considerations".to_string()));
+        assert!(quick.score > standard.score);
+        assert!(standard.score > thorough.score);
+    }
+
+    #[test]
+    fn every_depth_references_known_topics() {
+        for depth in [
+            PlanningDepth::Quick,
+            PlanningDepth::Standard,
+            PlanningDepth::Thorough,
+        ] {
+            let (must, should) = depth_topic_ids(depth);
+            assert_eq!(topics_by_id(must).len(), must.len());
+            assert_eq!(topics_by_id(should).len(), should.len());
+        }
+    }
 }
@@ -0,0 +1,274 @@
+use crate::types::{GeneratedDocument, Requirement, RequirementStatus};
+
+use super::open_questions::{heading_level, heading_text};
+
+const CRITERIA_LABELS: &[&str] = &["acceptance criteria", "acceptance criterion", "acceptance"];
+
+const OTHER_FEATURE_LABELS: &[&str] = &[
+    "description",
+    "edge cases",
+    "edge case",
+    "notes",
+    "priority",
+    "user story",
+    "dependencies",
+];
+
+pub fn extract_requirements(docs: &[GeneratedDocument]) -> Vec<Requirement> {
+    let Some(spec) = docs.iter().find(|doc| doc.filename == "SPEC.md") else {
+        return Vec::new();
+    };
+    let lines = spec.content.lines().collect::<Vec<_>>();
+    let Some((start, end)) = features_section(&lines) else {
+        return Vec::new();
+    };
+
+    let mut requirements = Vec::new();
+    let mut feature: Option<String> = None;
+    // Open criteria list: `Some(Some(indent))` under a bulleted label, where
+    // items must be nested deeper; `Some(None)` under a paragraph label.
+    let mut criteria: Option<Option<usize>> = None;
+    let mut in_code = false;
+
+    let mut push = |feature: &Option<String>, criterion: &str, status: RequirementStatus| {
+        let criterion = criterion.replace("**", "").trim().to_string();
+        if criterion.is_empty() {
+            return;
+        }
+        requirements.push(Requirement {
+            feature: feature
+                .clone()
+                .unwrap_or_else(|| "Unnamed feature".to_string()),
+            criterion,
+            status,
+        });
+    };
+
+    for line in &lines[start..end] {
+        if line.trim_start().starts_with("```") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code || line.trim().is_empty() {
+            continue;
+        }
+        if let Some(text) = heading_text(line) {
+            feature = feature_name(text);
+            criteria = None;
+            continue;
+        }
+
+        let indent = indent_of(line);
+        let item = list_item(line);
+        let text = item.map(|(_, text)| text).unwrap_or_else(|| line.trim());
+
+        if let Some((label, value)) = split_label(text) {
+            if CRITERIA_LABELS.contains(&label.as_str()) {
+                criteria = Some(item.map(|_| indent));
+                for criterion in value.split(';') {
+                    push(&feature, criterion, RequirementStatus::Pending);
+                }
+                continue;
+            }
+            if OTHER_FEATURE_LABELS.contains(&label.as_str()) {
+                criteria = None;
+                continue;
+            }
+        }
+
+        if let (Some(label_indent), Some((status, text))) = (criteria, item) {
+            if label_indent.is_none_or(|label_indent| indent > label_indent) {
+                push(&feature, text, status);
+                continue;
+            }
+        }
+        criteria = None;
+
+        if let Some(name) = bold_lead(text) {
+            feature = feature_name(name);
+        }
+    }
+
+    requirements
+}
+
+fn features_section(lines: &[&str]) -> Option<(usize, usize)> {
+    let (heading_index, level) = lines.iter().enumerate().find_map(|(index, line)| {
+        let level = heading_level(line)?;
+        heading_text(line)?
+            .to_ascii_lowercase()
+            .contains("features")
+            .then_some((index, level))
+    })?;
+
+    let end = lines
+        .iter()
+        .enumerate()
+        .skip(heading_index + 1)
+        .find(|(_, line)| heading_level(line).is_some_and(|next| next <= level))
+        .map(|(index, _)| index)
+        .unwrap_or(lines.len());
+
+    Some((heading_index + 1, end))
+}
+
+fn indent_of(line: &str) -> usize {
+    line.chars()
+        .take_while(|c| c.is_whitespace())
+        .map(|c| if c == '\t' { 4 } else { 1 })
+        .sum()
+}
+
+fn list_item(line: &str) -> Option<(RequirementStatus, &str)> {
+    let trimmed = line.trim_start();
+    let rest = ["- ", "* ", "+ "]
+        .iter()
+        .find_map(|bullet| trimmed.strip_prefix(bullet))
+        .or_else(|| {
+            let digits = trimmed.chars().take_while(char::is_ascii_digit).count();
+            if digits == 0 {
+                return None;
+            }
+            let rest = &trimmed[digits..];
+            rest.strip_prefix(". ").or_else(|| rest.strip_prefix(") "))
+        })?
+        .trim();
+
+    for (checkbox, status) in [
+        ("[ ]", RequirementStatus::Pending),
+        ("[x]", RequirementStatus::Done),
+        ("[X]", RequirementStatus::Done),
+    ] {
+        if let Some(text) = rest.strip_prefix(checkbox) {
+            return Some((status, text.trim()));
+        }
+    }
+    Some((RequirementStatus::Pending, rest))
+}
+
+fn split_label(text: &str) -> Option<(String, String)> {
+    let plain = text.replace("**", "");
+    let (label, value) = plain.split_once(':')?;
+    let label = label.trim().to_ascii_lowercase();
+    if label.is_empty() || label.split_whitespace().count() > 3 {
+        return None;
+    }
+    Some((label, value.trim().to_string()))
+}
+
+fn bold_lead(text: &str) -> Option<&str> {
+    let inner = text.strip_prefix("**")?;
+    let close = inner.find("**")?;
+    Some(&inner[..close])
+}
+
+fn feature_name(text: &str) -> Option<String> {
+    let name = text
+        .replace("**", "")
+        .trim()
+        .trim_start_matches(|c: char| c.is_ascii_digit() || c == '.')
+        .trim()
+        .trim_end_matches(':')
+        .trim()
+        .to_string();
+    if name.is_empty() {
+        None
+    } else {
+        Some(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn doc(filename: &str, content: &str) -> GeneratedDocument {
+        GeneratedDocument {
+            id: "doc-id".to_string(),
+            session_id: "session-id".to_string(),
+            filename: filename.to_string(),
+            content: content.to_string(),
+            created_at: "2026-02-07 00:00:00".to_string(),
+        }
+    }
+
+    const SPEC: &str = "# Habit Tracker Spec\n\n\
+        ### 5. Architecture\n\
+        - Acceptance Criteria: not a feature section\n\n\
+        ### 6. Features\n\
+        For each feature discussed:\n\n\
+        **Habit Creation**\n\
+        - Description: Users add a habit with a name and schedule\n\
+        - Acceptance Criteria: A habit can be saved with a name; Empty names are rejected\n\
+        - Edge Cases: Duplicate names show a warning\n\n\
+        **2. Streaks:** counts consecutive days\n\
+        - **Acceptance Criteria:**\n\
+        \x20 - [x] Streak increments when a habit is checked off\n\
+        \x20 - [ ] Streak resets after a missed day\n\
+        - Edge Cases:\n\
+        \x20 - Timezone change mid-streak\n\n\
+        #### Reminders\n\
+        Acceptance Criteria:\n\
+        1. A notification fires at the scheduled time\n\
+        2. Snoozed reminders fire again after 10 minutes\n\n\
+        ```\n\
+        - Acceptance Criteria: inside a code block\n\
+        ```\n\n\
+        ### 7. Error Handling\n\
+        - Acceptance Criteria: belongs to another section\n";
+
+    #[test]
+    fn parses_inline_nested_and_numbered_criteria() {
+        let requirements = extract_requirements(&[doc("SPEC.md", SPEC)]);
+        let rows = requirements
+            .iter()
+            .map(|r| (r.feature.as_str(), r.criterion.as_str(), r.status))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            rows,
+            vec![
+                (
+                    "Habit Creation",
+                    "A habit can be saved with a name",
+                    RequirementStatus::Pending
+                ),
+                (
+                    "Habit Creation",
+                    "Empty names are rejected",
+                    RequirementStatus::Pending
+                ),
+                (
+                    "Streaks",
+                    "Streak increments when a habit is checked off",
+                    RequirementStatus::Done
+                ),
+                (
+                    "Streaks",
+                    "Streak resets after a missed day",
+                    RequirementStatus::Pending
+                ),
+                (
+                    "Reminders",
+                    "A notification fires at the scheduled time",
+                    RequirementStatus::Pending
+                ),
+                (
+                    "Reminders",
+                    "Snoozed reminders fire again after 10 minutes",
+                    RequirementStatus::Pending
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn missing_spec_or_features_section_yields_nothing() {
+        assert!(extract_requirements(&[doc("README.md", SPEC)]).is_empty());
+        assert!(extract_requirements(&[doc(
+            "SPEC.md",
+            "# Spec\n\n## Overview\n- Acceptance Criteria: nothing to see\n"
+        )])
+        .is_empty());
+    }
+}
@@ -0,0 +1,229 @@
+use crate::types::{Message, TechChoice, TechConfidence};
+
+/// Ambiguous everyday words ("go", "node", "express") are only matched by
+/// unambiguous aliases.
+const TECHNOLOGIES: &[(&str, &[&str], &str)] = &[
+    ("Rust", &["rust"], "language"),
+    ("TypeScript", &["typescript"], "language"),
+    ("JavaScript", &["javascript"], "language"),
+    ("Python", &["python"], "language"),
+    ("Go", &["golang"], "language"),
+    ("Java", &["java"], "language"),
+    ("Kotlin", &["kotlin"], "language"),
+    ("Swift", &["swift"], "language"),
+    ("React", &["react"], "frontend"),
+    ("Vue", &["vue", "vue.js"], "frontend"),
+    ("Angular", &["angular"], "frontend"),
+    ("Svelte", &["svelte", "sveltekit"], "frontend"),
+    ("Next.js", &["next.js", "nextjs"], "frontend"),
+    ("Nuxt", &["nuxt"], "frontend"),
+    ("Tailwind CSS", &["tailwind", "tailwindcss"], "frontend"),
+    ("Vite", &["vite"], "frontend"),
+    ("Tauri", &["tauri"], "desktop"),
+    ("Electron", &["electron"], "desktop"),
+    ("Node.js", &["node.js", "nodejs"], "backend"),
+    ("Deno", &["deno"], "backend"),
+    ("Bun", &["bun"], "backend"),
+    ("Express", &["express.js", "expressjs"], "backend"),
+    ("FastAPI", &["fastapi"], "backend"),
+    ("Django", &["django"], "backend"),
+    ("Flask", &["flask"], "backend"),
+    ("Axum", &["axum"], "backend"),
+    ("Actix Web", &["actix-web", "actix"], "backend"),
+    ("Tokio", &["tokio"], "backend"),
+    ("GraphQL", &["graphql"], "api"),
+    ("gRPC", &["grpc"], "api"),
+    ("PostgreSQL", &["postgresql", "postgres"], "database"),
+    ("MySQL", &["mysql"], "database"),
+    ("SQLite", &["sqlite"], "database"),
+    ("MongoDB", &["mongodb"], "database"),
+    ("Redis", &["redis"], "database"),
+    ("Supabase", &["supabase"], "database"),
+    ("Firebase", &["firebase"], "database"),
+    ("Prisma", &["prisma"], "database"),
+    ("Drizzle", &["drizzle"], "database"),
+    ("rusqlite", &["rusqlite"], "database"),
+    ("SQLx", &["sqlx"], "database"),
+    ("Diesel", &["diesel"], "database"),
+    ("Docker", &["docker"], "infrastructure"),
+    ("Kubernetes", &["kubernetes", "k8s"], "infrastructure"),
+    ("AWS", &["aws"], "infrastructure"),
+    ("GCP", &["gcp"], "infrastructure"),
+    ("Azure", &["azure"], "infrastructure"),
+    ("Terraform", &["terraform"], "infrastructure"),
+    ("Vercel", &["vercel"], "infrastructure"),
+];
+
+pub fn extract_tech_stack(messages: &[Message]) -> Vec<TechChoice> {
+    let mut choices: Vec<TechChoice> = Vec::new();
+
+    for message in messages {
+        let confidence = match message.role.as_str() {
+            "user" => TechConfidence::Stated,
+            "assistant" => TechConfidence::Inferred,
+            _ => continue,
+        };
+        for (name, layer, version) in technologies_in(&message.content) {
+            match choices.iter_mut().find(|choice| choice.name == name) {
+                Some(existing) => {
+                    if existing.version.is_none() {
+                        existing.version = version;
+                    }
+                    if confidence == TechConfidence::Stated {
+                        existing.confidence = TechConfidence::Stated;
+                    }
+                }
+                None => choices.push(TechChoice {
+                    name: name.to_string(),
+                    version,
+                    layer: layer.to_string(),
+                    confidence,
+                }),
+            }
+        }
+    }
+
+    choices
+}
+
+pub fn technologies_in(text: &str) -> Vec<(&'static str, &'static str, Option<String>)> {
+    let lower = text.to_lowercase();
+    let mut found = TECHNOLOGIES
+        .iter()
+        .filter_map(|(name, aliases, layer)| {
+            let (position, version) = aliases
+                .iter()
+                .filter_map(|alias| find_mention(&lower, alias))
+                .min_by_key(|(position, _)| *position)?;
+            Some((position, *name, *layer, version))
+        })
+        .collect::<Vec<_>>();
+    found.sort_by_key(|(position, ..)| *position);
+    found
+        .into_iter()
+        .map(|(_, name, layer, version)| (name, layer, version))
+        .collect()
+}
+
+fn find_mention(lower: &str, alias: &str) -> Option<(usize, Option<String>)> {
+    let mut first = None;
+    let mut search_from = 0;
+
+    while let Some(offset) = lower[search_from..].find(alias) {
+        let start = search_from + offset;
+        let end = start + alias.len();
+        search_from = end;
+
+        let before_ok = lower[..start]
+            .chars()
+            .next_back()
+            .is_none_or(|c| !is_word_char(c));
+        let after_ok = lower[end..].chars().next().is_none_or(|c| !is_word_char(c));
+        if !before_ok || !after_ok {
+            continue;
+        }
+
+        let version = version_after(&lower[end..]);
+        if version.is_some() {
+            return Some((first.unwrap_or(start), version));
+        }
+        first.get_or_insert(start);
+    }
+
+    first.map(|position| (position, None))
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '-'
+}
+
+fn version_after(rest: &str) -> Option<String> {
+    let rest = rest
+        .strip_prefix(' ')
+        .or_else(|| rest.strip_prefix('@'))
+        .unwrap_or(rest);
+    let rest = rest.strip_prefix('v').unwrap_or(rest);
+
+    let candidate = rest
+        .char_indices()
+        .take_while(|(_, c)| c.is_ascii_digit() || *c == '.')
+        .map(|(index, c)| index + c.len_utf8())
+        .last()
+        .map(|end| rest[..end].trim_end_matches('.'))?;
+    let followed_by_word = rest[candidate.len()..]
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_alphabetic());
+
+    (candidate.starts_with(|c: char| c.is_ascii_digit()) && !followed_by_word)
+        .then(|| candidate.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn msg(role: &str, content: &str) -> Message {
+        Message {
+            id: "m".to_string(),
+            session_id: "s".to_string(),
+            role: role.to_string(),
+            content: content.to_string(),
+            metadata: None,
+            created_at: "2026-01-01 00:00:00".to_string(),
+        }
+    }
+
+    fn find<'a>(choices: &'a [TechChoice], name: &str) -> &'a TechChoice {
+        choices
+            .iter()
+            .find(|choice| choice.name == name)
+            .unwrap_or_else(|| panic!("{} not extracted", name))
+    }
+
+    #[test]
+    fn extracts_react_with_major_version() {
+        let choices = extract_tech_stack(&[msg("user", "Frontend is React 19 with Vite.")]);
+        let react = find(&choices, "React");
+        assert_eq!(react.version.as_deref(), Some("19"));
+        assert_eq!(react.layer, "frontend");
+        assert_eq!(react.confidence, TechConfidence::Stated);
+        assert!(find(&choices, "Vite").version.is_none());
+    }
+
+    #[test]
+    fn extracts_sqlite_via_rusqlite_version() {
+        let choices = extract_tech_stack(&[msg("user", "Storage: SQLite via rusqlite 0.32.")]);
+        assert_eq!(choices[0].name, "SQLite");
+        assert!(choices[0].version.is_none());
+        assert_eq!(choices[1].name, "rusqlite");
+        assert_eq!(choices[1].version.as_deref(), Some("0.32"));
+        assert_eq!(choices[1].layer, "database");
+    }
+
+    #[test]
+    fn assistant_only_mentions_are_inferred_until_user_confirms() {
+        let choices = extract_tech_stack(&[
+            msg(
+                "assistant",
+                "I'd suggest PostgreSQL 16 and Docker for deployment.",
+            ),
+            msg("user", "Docker is fine."),
+        ]);
+        let postgres = find(&choices, "PostgreSQL");
+        assert_eq!(postgres.confidence, TechConfidence::Inferred);
+        assert_eq!(postgres.version.as_deref(), Some("16"));
+        assert_eq!(find(&choices, "Docker").confidence, TechConfidence::Stated);
+    }
+
+    #[test]
+    fn ignores_partial_words_and_trailing_text() {
+        let choices = extract_tech_stack(&[msg(
+            "user",
+            "Reactive forms, a javascript-free landing page, and Rust 2021-style code on Node.js 22.x.",
+        )]);
+        let names = choices.iter().map(|c| c.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, vec!["Rust", "Node.js"]);
+        assert_eq!(find(&choices, "Node.js").version.as_deref(), Some("22"));
+    }
+}
@@ -1,4 +1,4 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::search::SearchError;
@@ -17,6 +17,49 @@ pub enum ConfigError {
     InvalidValue(String),
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigFieldErrorKind {
+    Missing,
+    Invalid,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ConfigFieldError {
+    pub field_path: String,
+    pub message: String,
+    pub kind: ConfigFieldErrorKind,
+}
+
+impl ConfigFieldError {
+    pub fn missing(field_path: &str) -> Self {
+        Self {
+            field_path: field_path.to_string(),
+            message: "is required".to_string(),
+            kind: ConfigFieldErrorKind::Missing,
+        }
+    }
+
+    pub fn invalid(field_path: &str, message: String) -> Self {
+        Self {
+            field_path: field_path.to_string(),
+            message,
+            kind: ConfigFieldErrorKind::Invalid,
+        }
+    }
+}
+
+impl From<ConfigFieldError> for ConfigError {
+    fn from(err: ConfigFieldError) -> Self {
+        match err.kind {
+            ConfigFieldErrorKind::Missing => ConfigError::MissingField(err.field_path),
+            ConfigFieldErrorKind::Invalid => {
+                ConfigError::InvalidValue(format!("{}: {}", err.field_path, err.message))
+            }
+        }
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum AppError {
     #[error("Cannot connect to Ollama at {url}: {message}")]
@@ -25,36 +68,55 @@ pub enum AppError {
     ModelNotFound { model: String },
     #[error("LLM request failed: {0}")]
     LlmRequest(String),
+    #[error("{}", context_overflow_message(*.limit, *.requested))]
+    ContextOverflow {
+        limit: Option<u64>,
+        requested: Option<u64>,
+    },
     #[error("Response stream interrupted")]
     StreamInterrupted,
     #[error("Response cancelled")]
     StreamCancelled,
-    #[error("Tavily API error: {0}")]
-    TavilyError(String),
+    #[error("Search API key is invalid")]
+    SearchInvalidApiKey,
     #[error("Search rate limited. Daily limit reached.")]
     SearchRateLimit,
-    #[error("Web search unavailable")]
-    SearchUnavailable,
+    #[error("No search results found")]
+    SearchNoResults,
+    #[error("Search request failed: {0}")]
+    SearchNetwork(String),
+    #[error("Web search unavailable: {0}")]
+    SearchUnavailable(String),
     #[error("Database error: {0}")]
     Database(String),
     #[error("Session not found: {0}")]
     SessionNotFound(String),
     #[error("Configuration error: {0}")]
     Config(String),
+    #[error("{}", config_invalid_message(.0))]
+    ConfigInvalid(Vec<ConfigFieldError>),
     #[error("Cannot write to {path}: {message}")]
     FileSystem { path: String, message: String },
     #[error("Folder already exists: {0}")]
     FolderExists(String),
+    #[error("An export to {0} is already in progress")]
+    ExportInProgress(String),
+    #[error("Too many in-flight requests ({0} streams already running)")]
+    TooManyStreams(usize),
     #[error("Invalid request: {0}")]
     Validation(String),
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ErrorResponse {
     pub code: String,
     pub message: String,
     pub recoverable: bool,
     pub action: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<Box<serde_json::Value>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remediation: Option<String>,
 }
 
 impl AppError {
@@ -64,6 +126,8 @@ impl AppError {
             message: self.to_string(),
             recoverable: self.is_recoverable(),
             action: self.suggested_action(),
+            details: self.details(),
+            remediation: self.remediation(),
         }
     }
 
@@ -72,16 +136,22 @@ impl AppError {
             AppError::OllamaConnection { .. } => "ollama_connection",
             AppError::ModelNotFound { .. } => "ollama_model_missing",
             AppError::LlmRequest(_) => "llm_request_failed",
+            AppError::ContextOverflow { .. } => "context_overflow",
             AppError::StreamInterrupted => "stream_interrupted",
             AppError::StreamCancelled => "stream_cancelled",
-            AppError::TavilyError(_) => "tavily_error",
+            AppError::SearchInvalidApiKey => "search_invalid_api_key",
             AppError::SearchRateLimit => "search_rate_limited",
-            AppError::SearchUnavailable => "search_unavailable",
+            AppError::SearchNoResults => "search_no_results",
+            AppError::SearchNetwork(_) => "search_network_error",
+            AppError::SearchUnavailable(_) => "search_unavailable",
             AppError::Database(_) => "database_error",
             AppError::SessionNotFound(_) => "session_not_found",
             AppError::Config(_) => "config_error",
+            AppError::ConfigInvalid(_) => "config_invalid",
             AppError::FileSystem { .. } => "filesystem_error",
             AppError::FolderExists(_) => "folder_exists",
+            AppError::ExportInProgress(_) => "export_in_progress",
+            AppError::TooManyStreams(_) => "too_many_streams",
             AppError::Validation(_) => "validation_error",
         }
     }
@@ -91,16 +161,22 @@ impl AppError {
             AppError::Database(_)
             | AppError::FileSystem { .. }
             | AppError::SearchRateLimit
-            | AppError::SearchUnavailable
+            | AppError::SearchNoResults
+            | AppError::SearchNetwork(_)
+            | AppError::SearchUnavailable(_)
             | AppError::LlmRequest(_)
+            | AppError::ContextOverflow { .. }
             | AppError::StreamInterrupted
-            | AppError::StreamCancelled => true,
+            | AppError::StreamCancelled
+            | AppError::ExportInProgress(_)
+            | AppError::TooManyStreams(_) => true,
             AppError::Config(_)
+            | AppError::ConfigInvalid(_)
             | AppError::OllamaConnection { .. }
             | AppError::ModelNotFound { .. }
             | AppError::SessionNotFound(_)
             | AppError::FolderExists(_)
-            | AppError::TavilyError(_)
+            | AppError::SearchInvalidApiKey
             | AppError::Validation(_) => false,
         }
     }
@@ -109,15 +185,115 @@ impl AppError {
         match self {
             AppError::OllamaConnection { .. } => Some("Start Ollama and retry".to_string()),
             AppError::ModelNotFound { model } => Some(format!("ollama pull {}", model)),
+            AppError::ContextOverflow { .. } => Some(
+                "Start a new session, trim imported context, or switch to a model with a larger context window"
+                    .to_string(),
+            ),
+            AppError::SearchInvalidApiKey => {
+                Some("Update the Tavily API key in Settings".to_string())
+            }
             AppError::SearchRateLimit => Some("Switch to DuckDuckGo or try later".to_string()),
+            AppError::SearchNoResults => Some("Try a broader search query".to_string()),
+            AppError::SearchNetwork(_) => Some("Check your connection and retry".to_string()),
             AppError::FileSystem { .. } => Some("Choose another folder".to_string()),
             AppError::FolderExists(_) => Some("Choose a different folder name".to_string()),
+            AppError::ExportInProgress(_) => {
+                Some("Wait for the current export to finish".to_string())
+            }
+            AppError::TooManyStreams(_) => Some(
+                "Wait for another response to finish, or raise llm.max_concurrent_streams"
+                    .to_string(),
+            ),
             AppError::Validation(_) => Some("Review the request and try again".to_string()),
+            AppError::ConfigInvalid(_) => Some("Fix the highlighted settings".to_string()),
+            _ => None,
+        }
+    }
+
+    fn remediation(&self) -> Option<String> {
+        let text = match self {
+            AppError::OllamaConnection { url, .. } => format!(
+                "Start Ollama with `ollama serve` and verify that llm.base_url ({}) points at it.",
+                url
+            ),
+            AppError::ModelNotFound { model } => format!(
+                "Download the model with `ollama pull {}`, or pick an installed model in Settings.",
+                model
+            ),
+            AppError::ContextOverflow { .. } => "Start a new session or trim imported context; \
+                 otherwise choose a model with a larger context window in Settings."
+                .to_string(),
+            AppError::SearchInvalidApiKey => {
+                "Paste a valid Tavily API key in Settings, or switch search to DuckDuckGo."
+                    .to_string()
+            }
+            AppError::SearchRateLimit => {
+                "The daily search quota is used up. Switch search to DuckDuckGo or wait until tomorrow."
+                    .to_string()
+            }
+            AppError::ConfigInvalid(_) | AppError::Config(_) => {
+                "Fix the listed settings in Settings or in config.yaml, then reload."
+                    .to_string()
+            }
+            AppError::FileSystem { path, .. } => format!(
+                "Check that {} exists and is writable, or choose another folder.",
+                path
+            ),
+            AppError::FolderExists(path) => format!(
+                "Pick a different folder name, or remove {} if it is no longer needed.",
+                path
+            ),
+            AppError::TooManyStreams(_) => {
+                "Wait for a running response to finish, or raise llm.max_concurrent_streams in the config."
+                    .to_string()
+            }
+            _ => return None,
+        };
+        Some(text)
+    }
+
+    fn details(&self) -> Option<Box<serde_json::Value>> {
+        match self {
+            AppError::ConfigInvalid(errors) => {
+                Some(Box::new(serde_json::json!({ "field_errors": errors })))
+            }
             _ => None,
         }
     }
 }
 
+fn config_invalid_message(errors: &[ConfigFieldError]) -> String {
+    let Some(first) = errors.first() else {
+        return "Invalid configuration".to_string();
+    };
+    let more = match errors.len() - 1 {
+        0 => String::new(),
+        n => format!(" (and {} more)", n),
+    };
+    format!(
+        "Invalid configuration: {} {}{}",
+        first.field_path, first.message, more
+    )
+}
+
+fn context_overflow_message(limit: Option<u64>, requested: Option<u64>) -> String {
+    match (limit, requested) {
+        (Some(limit), Some(requested)) => format!(
+            "Prompt exceeds the model context window ({} tokens requested, limit is {})",
+            requested, limit
+        ),
+        (Some(limit), None) => format!(
+            "Prompt exceeds the model context window (limit is {} tokens)",
+            limit
+        ),
+        (None, Some(requested)) => format!(
+            "Prompt exceeds the model context window ({} tokens requested)",
+            requested
+        ),
+        (None, None) => "Prompt exceeds the model context window".to_string(),
+    }
+}
+
 impl From<rusqlite::Error> for AppError {
     fn from(err: rusqlite::Error) -> Self {
         AppError::Database(err.to_string())
@@ -133,12 +309,115 @@ impl From<ConfigError> for AppError {
 impl From<SearchError> for AppError {
     fn from(err: SearchError) -> Self {
         match err {
-            SearchError::InvalidApiKey => AppError::TavilyError("Invalid API key".to_string()),
+            SearchError::InvalidApiKey => AppError::SearchInvalidApiKey,
             SearchError::RateLimited => AppError::SearchRateLimit,
-            SearchError::NoResults => AppError::SearchUnavailable,
-            SearchError::NetworkError(_) | SearchError::ParseError(_) => {
-                AppError::SearchUnavailable
-            }
+            SearchError::NoResults => AppError::SearchNoResults,
+            SearchError::NetworkError(message) => AppError::SearchNetwork(message),
+            SearchError::ParseError(message) => AppError::SearchUnavailable(message),
+            SearchError::Challenged => AppError::SearchUnavailable(
+                "DuckDuckGo is asking for a bot check; searches are paused for a few minutes"
+                    .to_string(),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn search_response(err: SearchError) -> ErrorResponse {
+        AppError::from(err).to_response()
+    }
+
+    #[test]
+    fn invalid_api_key_keeps_its_own_code() {
+        let response = search_response(SearchError::InvalidApiKey);
+        assert_eq!(response.code, "search_invalid_api_key");
+        assert!(!response.recoverable);
+        assert!(response.action.is_some());
+    }
+
+    #[test]
+    fn rate_limit_is_recoverable() {
+        let response = search_response(SearchError::RateLimited);
+        assert_eq!(response.code, "search_rate_limited");
+        assert!(response.recoverable);
+    }
+
+    #[test]
+    fn no_results_is_distinct_from_unavailable() {
+        let response = search_response(SearchError::NoResults);
+        assert_eq!(response.code, "search_no_results");
+        assert!(response.recoverable);
+    }
+
+    #[test]
+    fn network_and_parse_errors_keep_their_detail() {
+        let network = search_response(SearchError::NetworkError("timed out".to_string()));
+        assert_eq!(network.code, "search_network_error");
+        assert!(network.message.contains("timed out"));
+
+        let parse = search_response(SearchError::ParseError("bad html".to_string()));
+        assert_eq!(parse.code, "search_unavailable");
+        assert!(parse.message.contains("bad html"));
+    }
+
+    #[test]
+    fn challenge_pages_surface_as_unavailable() {
+        let response = search_response(SearchError::Challenged);
+        assert_eq!(response.code, "search_unavailable");
+        assert!(response.message.contains("bot check"));
+    }
+
+    #[test]
+    fn connection_and_missing_model_errors_explain_the_fix() {
+        let connection = AppError::OllamaConnection {
+            url: "http://localhost:11434".to_string(),
+            message: "connection refused".to_string(),
+        }
+        .to_response();
+        assert_eq!(
+            connection.remediation.as_deref(),
+            Some("Start Ollama with `ollama serve` and verify that llm.base_url (http://localhost:11434) points at it.")
+        );
+
+        let missing = AppError::ModelNotFound {
+            model: "qwen3-coder:30b".to_string(),
         }
+        .to_response();
+        assert_eq!(
+            missing.remediation.as_deref(),
+            Some("Download the model with `ollama pull qwen3-coder:30b`, or pick an installed model in Settings.")
+        );
+
+        assert!(AppError::StreamCancelled
+            .to_response()
+            .remediation
+            .is_none());
+    }
+
+    #[test]
+    fn config_invalid_carries_field_errors_in_details() {
+        let response = AppError::ConfigInvalid(vec![
+            ConfigFieldError::invalid("llm.temperature", "must be 0.0-2.0 (got 3)".to_string()),
+            ConfigFieldError::missing("search.tavily_api_key"),
+        ])
+        .to_response();
+        assert_eq!(response.code, "config_invalid");
+        assert_eq!(
+            response.message,
+            "Invalid configuration: llm.temperature must be 0.0-2.0 (got 3) (and 1 more)"
+        );
+        let details = response.details.expect("field errors should be attached");
+        assert_eq!(
+            details["field_errors"][1]["field_path"],
+            "search.tavily_api_key"
+        );
+        assert_eq!(details["field_errors"][1]["kind"], "missing");
+        assert!(AppError::Validation("x".to_string())
+            .to_response()
+            .details
+            .is_none());
     }
 }
@@ -2,9 +2,14 @@ use std::collections::HashMap;
 use std::fs;
 use std::io::Read;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
 
 use crate::error::AppError;
-use crate::types::{CodebaseImportSummary, RepoCitation};
+use crate::types::{
+    CodebaseImportSummary, ForgeTarget, ForgeTargetSuggestion, ImportConfig, RepoCitation,
+};
 
 const MAX_FILES_SCANNED: usize = 600;
 const MAX_FILE_BYTES: u64 = 64 * 1024;
@@ -12,6 +17,9 @@ const MAX_TOTAL_BYTES: u64 = 6 * 1024 * 1024;
 const MAX_DEPTH: usize = 8;
 const MAX_SNIPPETS: usize = 20;
 const MAX_SNIPPET_CHARS: usize = 280;
+const MAX_SNIPPET_LINES: usize = 6;
+pub const MAX_READ_WORKERS: usize = 8;
+const STACK_PROBE_DEPTH: usize = 2;
 
 #[derive(Debug, Clone)]
 struct SnippetEvidence {
@@ -21,6 +29,15 @@ struct SnippetEvidence {
     snippet: String,
 }
 
+#[derive(Debug)]
+struct PlannedRead {
+    path: PathBuf,
+    relative: String,
+    file_name: String,
+    ext: String,
+    capped_size: u64,
+}
+
 const SKIP_DIRS: &[&str] = &[
     ".git",
     "node_modules",
@@ -46,9 +63,64 @@ const KEY_FILES: &[&str] = &[
     "Dockerfile",
     "docker-compose.yml",
     "README.md",
+    "tauri.conf.json",
+    "next.config.js",
+    "next.config.mjs",
+    "next.config.ts",
+];
+
+const TAURI_STACK: &str = "Tauri desktop app";
+const NEXTJS_STACK: &str = "Next.js web app";
+const NODE_STACK: &str = "Node.js / JavaScript ecosystem";
+
+/// Frameworks win over the languages they are built on so a Tauri app is not
+/// treated as plain Rust.
+const TARGET_PREFERENCES: &[(&str, ForgeTarget, &str)] = &[
+    (
+        TAURI_STACK,
+        ForgeTarget::Claude,
+        "Tauri projects span a Rust backend and a web frontend; a terminal agent that runs both cargo and npm gates fits best.",
+    ),
+    (
+        NEXTJS_STACK,
+        ForgeTarget::Cursor,
+        "Next.js work is component- and route-centric, which suits editor-integrated agents with project rules.",
+    ),
+    (
+        "Rust",
+        ForgeTarget::Claude,
+        "Rust projects benefit from an agent that iterates on cargo build/clippy/test output in the terminal.",
+    ),
+    (
+        "Python",
+        ForgeTarget::Codex,
+        "Python toolchains are command-driven; AGENTS.md captures the venv, lint and test commands directly.",
+    ),
+    (
+        "Go",
+        ForgeTarget::Codex,
+        "Go projects have a small, standard toolchain that maps cleanly onto AGENTS.md command conventions.",
+    ),
+    (
+        NODE_STACK,
+        ForgeTarget::Cursor,
+        "JavaScript projects are typically driven from the editor, where project rules keep conventions in view.",
+    ),
 ];
 
-pub fn summarize_codebase(root_path: &str) -> Result<CodebaseImportSummary, AppError> {
+pub fn summarize_codebase(
+    root_path: &str,
+    config: &ImportConfig,
+) -> Result<CodebaseImportSummary, AppError> {
+    summarize_codebase_with_workers(root_path, config, read_workers(config))
+}
+
+/// The summary is the same for any worker count.
+pub fn summarize_codebase_with_workers(
+    root_path: &str,
+    config: &ImportConfig,
+    workers: usize,
+) -> Result<CodebaseImportSummary, AppError> {
     let root = PathBuf::from(root_path);
     if !root.exists() {
         return Err(AppError::FileSystem {
@@ -69,17 +141,14 @@ pub fn summarize_codebase(root_path: &str) -> Result<CodebaseImportSummary, AppE
 
     let mut stack = vec![(canonical_root.clone(), 0usize)];
     let mut files_scanned = 0usize;
-    let mut files_included = 0usize;
-    let mut total_bytes_read = 0u64;
     let mut extension_counts: HashMap<String, usize> = HashMap::new();
     let mut key_files = Vec::new();
-    let mut snippets = Vec::<SnippetEvidence>::new();
+    let deadline = (config.read_timeout_secs > 0)
+        .then(|| Instant::now() + Duration::from_secs(config.read_timeout_secs));
+    let mut reads = ReadQueue::new(workers, deadline);
 
     while let Some((dir, depth)) = stack.pop() {
-        if depth > MAX_DEPTH
-            || files_scanned >= MAX_FILES_SCANNED
-            || total_bytes_read >= MAX_TOTAL_BYTES
-        {
+        if depth > MAX_DEPTH || files_scanned >= MAX_FILES_SCANNED || reads.exhausted() {
             break;
         }
 
@@ -89,7 +158,7 @@ pub fn summarize_codebase(root_path: &str) -> Result<CodebaseImportSummary, AppE
         };
 
         for entry in entries.flatten() {
-            if files_scanned >= MAX_FILES_SCANNED || total_bytes_read >= MAX_TOTAL_BYTES {
+            if files_scanned >= MAX_FILES_SCANNED || reads.exhausted() {
                 break;
             }
 
@@ -140,36 +209,41 @@ pub fn summarize_codebase(root_path: &str) -> Result<CodebaseImportSummary, AppE
             if capped_size == 0 {
                 continue;
             }
-            if total_bytes_read + capped_size > MAX_TOTAL_BYTES {
+            if !reads.fits(capped_size) {
                 break;
             }
 
-            let bytes = match read_file_prefix(&path, capped_size as usize) {
-                Ok(bytes) => bytes,
-                Err(_) => continue,
-            };
-            if bytes.iter().take(2048).any(|b| *b == 0) {
-                continue;
-            }
+            reads.push(PlannedRead {
+                file_name: file_name.to_string(),
+                path,
+                relative,
+                ext,
+                capped_size,
+            });
+        }
+    }
 
-            total_bytes_read += bytes.len() as u64;
-            files_included += 1;
-
-            if snippets.len() < MAX_SNIPPETS
-                && (is_key_file(file_name) || is_source_extension(&ext))
-            {
-                let text = String::from_utf8_lossy(&bytes);
-                let lines = text.lines().take(6).collect::<Vec<_>>();
-                let snippet = lines.join(" ");
-                let snippet = snippet.chars().take(MAX_SNIPPET_CHARS).collect::<String>();
-                if !snippet.trim().is_empty() {
-                    snippets.push(SnippetEvidence {
-                        path: relative.clone(),
-                        line_start: Some(1),
-                        line_end: Some(lines.len()),
-                        snippet: snippet.trim().to_string(),
-                    });
-                }
+    let (files_read, total_bytes_read) = reads.finish();
+
+    let mut files_included = 0usize;
+    let mut snippets = Vec::<SnippetEvidence>::new();
+    for (file, bytes) in &files_read {
+        files_included += 1;
+
+        if snippets.len() < MAX_SNIPPETS
+            && (is_key_file(&file.file_name) || is_source_extension(&file.ext))
+        {
+            let text = String::from_utf8_lossy(bytes);
+            if let Some(snippet) = extract_snippet(
+                &file.file_name,
+                &file.ext,
+                &text,
+                config.min_snippet_relevance,
+            ) {
+                snippets.push(SnippetEvidence {
+                    path: file.relative.clone(),
+                    ..snippet
+                });
             }
         }
     }
@@ -216,6 +290,62 @@ pub fn summarize_codebase(root_path: &str) -> Result<CodebaseImportSummary, AppE
     })
 }
 
+pub fn redetect_stacks(root_path: &str) -> Result<Vec<String>, AppError> {
+    let root = PathBuf::from(root_path);
+    if !root.is_dir() {
+        return Err(AppError::FileSystem {
+            path: root_path.to_string(),
+            message: "The imported codebase is no longer at this path. Import it again from its new location.".to_string(),
+        });
+    }
+
+    let mut stack = vec![(root.clone(), 0usize)];
+    let mut entries_seen = 0usize;
+    let mut extension_counts: HashMap<String, usize> = HashMap::new();
+    let mut key_files = Vec::new();
+
+    while let Some((dir, depth)) = stack.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            if entries_seen >= MAX_FILES_SCANNED {
+                break;
+            }
+            let Ok(ft) = entry.file_type() else {
+                continue;
+            };
+            if ft.is_symlink() {
+                continue;
+            }
+            entries_seen += 1;
+
+            let path = entry.path();
+            let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+            if ft.is_dir() {
+                if depth < STACK_PROBE_DEPTH && !should_skip_dir(file_name) {
+                    stack.push((path, depth + 1));
+                }
+                continue;
+            }
+            if !ft.is_file() || is_hidden(path.as_path()) {
+                continue;
+            }
+
+            if is_key_file(file_name) {
+                key_files.push(relative_to_root(&root, &path));
+            }
+            if let Some(ext) = path.extension().and_then(|value| value.to_str()) {
+                *extension_counts
+                    .entry(ext.to_ascii_lowercase())
+                    .or_insert(0) += 1;
+            }
+        }
+    }
+
+    Ok(detect_stacks(&key_files, &extension_counts))
+}
+
 fn should_skip_dir(name: &str) -> bool {
     SKIP_DIRS.contains(&name)
 }
@@ -251,6 +381,233 @@ fn is_source_extension(ext: &str) -> bool {
     )
 }
 
+fn extract_snippet(
+    file_name: &str,
+    ext: &str,
+    text: &str,
+    min_relevance: f64,
+) -> Option<SnippetEvidence> {
+    let lines = text.lines().collect::<Vec<_>>();
+    if lines.is_empty() {
+        return None;
+    }
+    let range = if is_key_file(file_name) && ext != "md" {
+        manifest_dependency_range(file_name, &lines)
+    } else if ext == "md" || ext == "markdown" {
+        markdown_intro_range(&lines)
+    } else if is_source_extension(ext) {
+        first_declaration_range(&lines)
+    } else {
+        None
+    };
+    let (mut start, mut end) = range.unwrap_or((0, lines.len().min(MAX_SNIPPET_LINES) - 1));
+    if is_source_extension(ext) && snippet_relevance(&lines[start..=end]) < min_relevance {
+        (start, end) = (start + 1..lines.len())
+            .filter(|&index| is_substantive_line(lines[index]))
+            .map(|index| (index, (lines.len() - 1).min(index + MAX_SNIPPET_LINES - 1)))
+            .find(|&(from, to)| snippet_relevance(&lines[from..=to]) >= min_relevance)?;
+    }
+
+    let snippet = lines[start..=end]
+        .iter()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+        .chars()
+        .take(MAX_SNIPPET_CHARS)
+        .collect::<String>();
+    if snippet.trim().is_empty() {
+        return None;
+    }
+    Some(SnippetEvidence {
+        path: String::new(),
+        line_start: Some(start + 1),
+        line_end: Some(end + 1),
+        snippet: snippet.trim().to_string(),
+    })
+}
+
+fn manifest_dependency_range(file_name: &str, lines: &[&str]) -> Option<(usize, usize)> {
+    let cap = |start: usize, end: usize| (start, end.min(start + MAX_SNIPPET_LINES - 1));
+    match file_name {
+        "Cargo.toml" | "pyproject.toml" => {
+            let start = lines.iter().position(|line| {
+                let trimmed = line.trim();
+                (trimmed.starts_with('[') && trimmed.contains("dependencies"))
+                    || trimmed.starts_with("dependencies")
+            })?;
+            let end = lines[start + 1..]
+                .iter()
+                .position(|line| line.trim_start().starts_with('['))
+                .map(|offset| start + offset)
+                .unwrap_or(lines.len() - 1);
+            Some(cap(start, end))
+        }
+        "package.json" | "composer.json" => {
+            let key = if file_name == "composer.json" {
+                "\"require\""
+            } else {
+                "\"dependencies\""
+            };
+            let start = lines.iter().position(|line| line.contains(key))?;
+            let end = lines[start..]
+                .iter()
+                .position(|line| line.contains('}'))
+                .map(|offset| start + offset)
+                .unwrap_or(lines.len() - 1);
+            Some(cap(start, end))
+        }
+        "go.mod" => {
+            let start = lines
+                .iter()
+                .position(|line| line.trim_start().starts_with("require"))?;
+            let end = lines[start..]
+                .iter()
+                .position(|line| line.trim() == ")")
+                .map(|offset| start + offset)
+                .unwrap_or(start);
+            Some(cap(start, end))
+        }
+        "Gemfile" => {
+            let start = lines
+                .iter()
+                .position(|line| line.trim_start().starts_with("gem "))?;
+            Some(cap(start, lines.len() - 1))
+        }
+        "requirements.txt" => {
+            let start = lines.iter().position(|line| {
+                let trimmed = line.trim();
+                !trimmed.is_empty() && !trimmed.starts_with('#')
+            })?;
+            Some(cap(start, lines.len() - 1))
+        }
+        _ => None,
+    }
+}
+
+fn markdown_intro_range(lines: &[&str]) -> Option<(usize, usize)> {
+    let heading = lines
+        .iter()
+        .position(|line| line.trim_start().starts_with('#'))?;
+    let Some(para_start) = lines[heading + 1..]
+        .iter()
+        .position(|line| !line.trim().is_empty())
+        .map(|offset| heading + 1 + offset)
+    else {
+        return Some((heading, heading));
+    };
+    if lines[para_start].trim_start().starts_with('#') {
+        return Some((heading, heading));
+    }
+    let para_end = lines[para_start..]
+        .iter()
+        .position(|line| line.trim().is_empty())
+        .map(|offset| para_start + offset - 1)
+        .unwrap_or(lines.len() - 1);
+    Some((heading, para_end.min(heading + MAX_SNIPPET_LINES - 1)))
+}
+
+const DECLARATION_PREFIXES: &[&str] = &[
+    "pub fn ",
+    "pub(crate) fn ",
+    "pub async fn ",
+    "async fn ",
+    "fn ",
+    "pub struct ",
+    "struct ",
+    "pub enum ",
+    "enum ",
+    "pub trait ",
+    "trait ",
+    "impl ",
+    "export default ",
+    "export function ",
+    "export async function ",
+    "export const ",
+    "export class ",
+    "export interface ",
+    "export type ",
+    "function ",
+    "async function ",
+    "class ",
+    "interface ",
+    "def ",
+    "async def ",
+    "func ",
+    "public class ",
+    "public interface ",
+    "public static ",
+    "fun ",
+    "data class ",
+];
+
+fn first_declaration_range(lines: &[&str]) -> Option<(usize, usize)> {
+    let start = lines.iter().position(|line| {
+        DECLARATION_PREFIXES
+            .iter()
+            .any(|prefix| line.starts_with(prefix))
+    })?;
+    Some((start, (lines.len() - 1).min(start + MAX_SNIPPET_LINES - 1)))
+}
+
+const IMPORT_PREFIXES: &[&str] = &[
+    "use ",
+    "pub use ",
+    "extern crate ",
+    "import ",
+    "from ",
+    "export * from ",
+    "#include",
+    "#import",
+    "using ",
+    "package ",
+    "require ",
+];
+
+const BOILERPLATE_MARKERS: &[&str] = &[
+    "copyright",
+    "license",
+    "spdx-license-identifier",
+    "all rights reserved",
+    "auto-generated",
+    "autogenerated",
+    "generated by",
+    "do not edit",
+];
+
+fn snippet_relevance(lines: &[&str]) -> f64 {
+    let non_blank = lines.iter().filter(|line| !line.trim().is_empty()).count();
+    if non_blank == 0 {
+        return 0.0;
+    }
+    let substantive = lines
+        .iter()
+        .filter(|line| is_substantive_line(line))
+        .count();
+    substantive as f64 / non_blank as f64
+}
+
+fn is_substantive_line(line: &str) -> bool {
+    let trimmed = line.trim();
+    if trimmed.is_empty() {
+        return false;
+    }
+    let is_comment = ["//", "/*", "*", "<!--", "--"]
+        .iter()
+        .any(|marker| trimmed.starts_with(marker))
+        || (trimmed.starts_with('#') && !trimmed.starts_with("#[") && !trimmed.starts_with("#!["));
+    let lower = trimmed.to_ascii_lowercase();
+    let is_boilerplate = BOILERPLATE_MARKERS
+        .iter()
+        .any(|marker| lower.contains(marker));
+    let is_import = IMPORT_PREFIXES
+        .iter()
+        .any(|prefix| trimmed.starts_with(prefix))
+        || lower.contains("require(");
+    !(is_comment || is_boilerplate || is_import)
+}
+
 fn relative_to_root(root: &Path, path: &Path) -> String {
     path.strip_prefix(root)
         .map(|value| value.to_string_lossy().to_string())
@@ -260,8 +617,21 @@ fn relative_to_root(root: &Path, path: &Path) -> String {
 fn detect_stacks(key_files: &[String], extension_counts: &HashMap<String, usize>) -> Vec<String> {
     let mut stacks = Vec::new();
 
+    if key_files
+        .iter()
+        .any(|path| path.ends_with("tauri.conf.json"))
+    {
+        stacks.push(TAURI_STACK.to_string());
+    }
+    if key_files.iter().any(|path| {
+        path.ends_with("next.config.js")
+            || path.ends_with("next.config.mjs")
+            || path.ends_with("next.config.ts")
+    }) {
+        stacks.push(NEXTJS_STACK.to_string());
+    }
     if key_files.iter().any(|path| path.ends_with("package.json")) {
-        stacks.push("Node.js / JavaScript ecosystem".to_string());
+        stacks.push(NODE_STACK.to_string());
     }
     if key_files.iter().any(|path| path.ends_with("Cargo.toml"))
         || extension_counts.contains_key("rs")
@@ -293,6 +663,32 @@ fn detect_stacks(key_files: &[String], extension_counts: &HashMap<String, usize>
     stacks
 }
 
+pub fn suggest_forge_target(
+    detected_stacks: &[String],
+    default_target: ForgeTarget,
+) -> ForgeTargetSuggestion {
+    let preferred = TARGET_PREFERENCES.iter().find(|(stack, _, _)| {
+        detected_stacks
+            .iter()
+            .any(|detected| detected.as_str() == *stack)
+    });
+
+    match preferred {
+        Some((stack, target, rationale)) => ForgeTargetSuggestion {
+            target: target.clone(),
+            agent_config_file: target.agent_config_filename().to_string(),
+            dominant_stack: Some((*stack).to_string()),
+            rationale: (*rationale).to_string(),
+        },
+        None => ForgeTargetSuggestion {
+            agent_config_file: default_target.agent_config_filename().to_string(),
+            target: default_target,
+            dominant_stack: None,
+            rationale: "No recognised stack in the imported codebase; keeping the configured default target.".to_string(),
+        },
+    }
+}
+
 fn build_summary_markdown(
     root: &Path,
     files_scanned: usize,
@@ -438,6 +834,133 @@ fn build_verification_plan_markdown(citations: &[RepoCitation]) -> String {
     out
 }
 
+/// `import.read_workers`, where 0 follows available parallelism.
+fn read_workers(config: &ImportConfig) -> usize {
+    match config.read_workers {
+        0 => thread::available_parallelism()
+            .map(|count| count.get())
+            .unwrap_or(1),
+        workers => workers,
+    }
+    .min(MAX_READ_WORKERS)
+}
+
+/// A queued file reserves its capped size until the batch is read. The budget
+/// checks only read the batch when the reservation could change their answer,
+/// so they see the same byte counts as reading each file in walk order.
+struct ReadQueue {
+    workers: usize,
+    deadline: Option<Instant>,
+    pending: Vec<PlannedRead>,
+    reserved_bytes: u64,
+    read_bytes: u64,
+    read: Vec<(PlannedRead, Vec<u8>)>,
+}
+
+impl ReadQueue {
+    fn new(workers: usize, deadline: Option<Instant>) -> Self {
+        Self {
+            workers,
+            deadline,
+            pending: Vec::new(),
+            reserved_bytes: 0,
+            read_bytes: 0,
+            read: Vec::new(),
+        }
+    }
+
+    fn fits(&mut self, size: u64) -> bool {
+        if self.read_bytes + self.reserved_bytes + size <= MAX_TOTAL_BYTES {
+            return true;
+        }
+        self.flush();
+        self.read_bytes + size <= MAX_TOTAL_BYTES
+    }
+
+    fn exhausted(&mut self) -> bool {
+        if self.read_bytes + self.reserved_bytes < MAX_TOTAL_BYTES {
+            return false;
+        }
+        self.flush();
+        self.read_bytes >= MAX_TOTAL_BYTES
+    }
+
+    fn push(&mut self, file: PlannedRead) {
+        self.reserved_bytes += file.capped_size;
+        self.pending.push(file);
+    }
+
+    fn flush(&mut self) {
+        let pending = std::mem::take(&mut self.pending);
+        self.reserved_bytes = 0;
+        let contents = read_planned_files(&pending, self.workers, self.deadline);
+        for (file, bytes) in pending.into_iter().zip(contents) {
+            if let Some(bytes) = bytes {
+                self.read_bytes += bytes.len() as u64;
+                self.read.push((file, bytes));
+            }
+        }
+    }
+
+    fn finish(mut self) -> (Vec<(PlannedRead, Vec<u8>)>, u64) {
+        self.flush();
+        (self.read, self.read_bytes)
+    }
+}
+
+/// Files not started before `deadline` are skipped like unreadable ones.
+fn read_planned_files(
+    planned: &[PlannedRead],
+    workers: usize,
+    deadline: Option<Instant>,
+) -> Vec<Option<Vec<u8>>> {
+    let next = AtomicUsize::new(0);
+    let read_one = |file: &PlannedRead| {
+        let bytes = read_file_prefix(&file.path, file.capped_size as usize).ok()?;
+        if bytes.iter().take(2048).any(|b| *b == 0) {
+            return None;
+        }
+        Some(bytes)
+    };
+
+    let mut contents = planned.iter().map(|_| None).collect::<Vec<_>>();
+    let mut attempted = 0usize;
+    thread::scope(|scope| {
+        let handles = (0..workers.clamp(1, MAX_READ_WORKERS).min(planned.len()))
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut read = Vec::new();
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(file) = planned.get(index) else {
+                            break;
+                        };
+                        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                            break;
+                        }
+                        read.push((index, read_one(file)));
+                    }
+                    read
+                })
+            })
+            .collect::<Vec<_>>();
+        for handle in handles {
+            for (index, bytes) in handle.join().unwrap_or_default() {
+                contents[index] = bytes;
+                attempted += 1;
+            }
+        }
+    });
+    let skipped = planned.len() - attempted;
+    if skipped > 0 {
+        log::warn!(
+            "Import read timeout reached; skipped {} remaining file(s)",
+            skipped
+        );
+    }
+    contents
+}
+
 fn read_file_prefix(path: &Path, max_bytes: usize) -> std::io::Result<Vec<u8>> {
     let file = fs::File::open(path)?;
     let mut buffer = Vec::with_capacity(max_bytes.min(8192));
@@ -451,6 +974,72 @@ mod tests {
     use super::*;
     use tempfile::tempdir;
 
+    #[test]
+    fn detect_stacks_recognises_frameworks_from_key_files() {
+        let key_files = vec![
+            "package.json".to_string(),
+            "src-tauri/Cargo.toml".to_string(),
+            "src-tauri/tauri.conf.json".to_string(),
+        ];
+        let stacks = detect_stacks(&key_files, &HashMap::new());
+        assert_eq!(stacks[0], TAURI_STACK);
+        assert!(stacks.contains(&"Rust".to_string()));
+    }
+
+    #[test]
+    fn redetect_stacks_picks_up_new_key_files() {
+        let dir = tempdir().expect("temp dir should be created");
+        fs::write(dir.path().join("Cargo.toml"), "[package]").expect("manifest written");
+        fs::create_dir_all(dir.path().join("node_modules/pkg")).expect("dir created");
+        fs::write(dir.path().join("node_modules/pkg/package.json"), "{}").expect("file written");
+        let root = dir.path().to_string_lossy().to_string();
+
+        let stacks = redetect_stacks(&root).expect("probe should succeed");
+        assert_eq!(stacks, vec!["Rust".to_string()]);
+
+        fs::create_dir(dir.path().join("deploy")).expect("dir created");
+        fs::write(dir.path().join("deploy/Dockerfile"), "FROM rust").expect("file written");
+        let stacks = redetect_stacks(&root).expect("probe should succeed");
+        assert!(stacks.contains(&"Containerized deployment".to_string()));
+
+        let missing = dir.path().join("moved-away").to_string_lossy().to_string();
+        assert!(matches!(
+            redetect_stacks(&missing),
+            Err(AppError::FileSystem { .. })
+        ));
+    }
+
+    #[test]
+    fn suggest_forge_target_prefers_framework_over_language() {
+        let stacks = vec![
+            NODE_STACK.to_string(),
+            "Rust".to_string(),
+            TAURI_STACK.to_string(),
+        ];
+        let suggestion = suggest_forge_target(&stacks, ForgeTarget::Generic);
+        assert_eq!(suggestion.target, ForgeTarget::Claude);
+        assert_eq!(suggestion.agent_config_file, "CLAUDE.md");
+        assert_eq!(suggestion.dominant_stack.as_deref(), Some(TAURI_STACK));
+
+        let next = suggest_forge_target(
+            &[NEXTJS_STACK.to_string(), NODE_STACK.to_string()],
+            ForgeTarget::Generic,
+        );
+        assert_eq!(next.target, ForgeTarget::Cursor);
+        assert_eq!(next.agent_config_file, ".cursorrules");
+    }
+
+    #[test]
+    fn suggest_forge_target_falls_back_to_default() {
+        let suggestion = suggest_forge_target(
+            &["General source repository".to_string()],
+            ForgeTarget::Gemini,
+        );
+        assert_eq!(suggestion.target, ForgeTarget::Gemini);
+        assert_eq!(suggestion.agent_config_file, "GEMINI.md");
+        assert!(suggestion.dominant_stack.is_none());
+    }
+
     #[test]
     fn read_file_prefix_respects_max_bytes() {
         let dir = tempdir().expect("temp dir should be created");
@@ -472,7 +1061,71 @@ mod tests {
         assert_eq!(bytes, b"hello");
     }
 
+    #[test]
+    fn cargo_toml_snippet_captures_dependencies() {
+        let manifest = "[package]\nname = \"demo\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n\
+                        [dependencies]\nserde = \"1\"\ntokio = { version = \"1\" }\n\n\
+                        [dev-dependencies]\ntempfile = \"3\"\n";
+        let snippet = extract_snippet("Cargo.toml", "toml", manifest, 0.5).unwrap();
+        assert_eq!(
+            snippet.snippet,
+            "[dependencies] serde = \"1\" tokio = { version = \"1\" }"
+        );
+        assert_eq!(snippet.line_start, Some(6));
+        assert_eq!(snippet.line_end, Some(9));
+    }
+
+    #[test]
+    fn package_json_snippet_captures_dependencies_block() {
+        let manifest =
+            "{\n  \"name\": \"demo\",\n  \"scripts\": {\n    \"dev\": \"vite\"\n  },\n  \
+                        \"dependencies\": {\n    \"react\": \"^19\"\n  }\n}\n";
+        let snippet = extract_snippet("package.json", "json", manifest, 0.5).unwrap();
+        assert_eq!(snippet.snippet, "\"dependencies\": { \"react\": \"^19\" }");
+        assert_eq!(snippet.line_start, Some(6));
+    }
+
+    #[test]
+    fn markdown_and_source_snippets_skip_preamble() {
+        let readme = "<!-- badges -->\n\n# Demo\n\nPlans projects.\nLocally.\n\n## Install\n";
+        let snippet = extract_snippet("README.md", "md", readme, 0.5).unwrap();
+        assert_eq!(snippet.snippet, "# Demo Plans projects. Locally.");
+        assert_eq!((snippet.line_start, snippet.line_end), (Some(3), Some(6)));
+
+        let source = "// Copyright\nuse std::fs;\n\npub fn run() -> bool {\n    true\n}\n";
+        let snippet = extract_snippet("main.rs", "rs", source, 0.5).unwrap();
+        assert!(snippet.snippet.starts_with("pub fn run() -> bool {"));
+        assert_eq!(snippet.line_start, Some(4));
+
+        let fallback =
+            extract_snippet("Cargo.toml", "toml", "[package]\nname = \"x\"\n", 0.5).unwrap();
+        assert_eq!(fallback.snippet, "[package] name = \"x\"");
+        assert_eq!(fallback.line_start, Some(1));
+    }
+
     #[cfg(unix)]
+    #[test]
+    fn license_header_gives_way_to_a_deeper_snippet() {
+        let source = "// Copyright (c) 2024 Example Corp.\n\
+                      // Licensed under the Apache License, Version 2.0.\n\
+                      // You may not use this file except in compliance\n\
+                      // with the License.\n\
+                      // SPDX-License-Identifier: Apache-2.0\n\
+                      // All rights reserved.\n\
+                      const MAX_RETRIES: u32 = 3;\n\
+                      static NAME: &str = \"demo\";\n";
+
+        let snippet = extract_snippet("lib.rs", "rs", source, 0.5).unwrap();
+        assert_eq!(snippet.line_start, Some(7));
+        assert!(snippet.snippet.starts_with("const MAX_RETRIES: u32 = 3;"));
+
+        let unfiltered = extract_snippet("lib.rs", "rs", source, 0.0).unwrap();
+        assert_eq!(unfiltered.line_start, Some(1));
+
+        let header_only = "// Copyright (c) 2024 Example Corp.\nuse std::fs;\n";
+        assert!(extract_snippet("lib.rs", "rs", header_only, 0.5).is_none());
+    }
+
     #[test]
     fn summarize_codebase_skips_symlinks() {
         let dir = tempdir().expect("temp dir should be created");
@@ -487,7 +1140,7 @@ mod tests {
         fs::write(&secret, "TOP SECRET DATA").unwrap();
         std::os::unix::fs::symlink(&secret, root.join("link.txt")).unwrap();
 
-        let summary = summarize_codebase(root.to_str().unwrap()).unwrap();
+        let summary = summarize_codebase(root.to_str().unwrap(), &ImportConfig::default()).unwrap();
 
         // The real file should be included but the symlink target should not
         assert!(
@@ -517,8 +1170,11 @@ mod tests {
         )
         .expect("source file should be written");
 
-        let summary = summarize_codebase(root.to_str().expect("path should be valid utf-8"))
-            .expect("summary should succeed");
+        let summary = summarize_codebase(
+            root.to_str().expect("path should be valid utf-8"),
+            &ImportConfig::default(),
+        )
+        .expect("summary should succeed");
         assert!(
             !summary.citations.is_empty(),
             "citations should be present for grounded summaries"
@@ -553,11 +1209,129 @@ mod tests {
         );
     }
 
+    #[test]
+    fn summarize_codebase_is_identical_across_worker_counts() {
+        let dir = tempdir().expect("temp dir should be created");
+        let root = dir.path();
+        fs::write(root.join("Cargo.toml"), "[dependencies]\nserde = \"1\"\n").unwrap();
+        fs::write(
+            root.join("package.json"),
+            r#"{"dependencies":{"react":"19"}}"#,
+        )
+        .unwrap();
+        fs::write(root.join("logo.png"), [0u8, 1, 2, 3]).unwrap();
+        fs::write(root.join(".env"), "SECRET=1").unwrap();
+        for module in 0..6 {
+            let dir = root.join("src").join(format!("module_{}", module));
+            fs::create_dir_all(&dir).unwrap();
+            for file in 0..5 {
+                fs::write(
+                    dir.join(format!("file_{}.rs", file)),
+                    format!(
+                        "pub fn run_{}_{}() -> u32 {{\n    {}\n}}\n",
+                        module, file, file
+                    ),
+                )
+                .unwrap();
+            }
+        }
+        let root_path = root.to_str().unwrap();
+        let config = ImportConfig::default();
+
+        let single = summarize_codebase_with_workers(root_path, &config, 1).unwrap();
+        let single = serde_json::to_string(&single).unwrap();
+        for workers in [2, 4, MAX_READ_WORKERS] {
+            let parallel = summarize_codebase_with_workers(root_path, &config, workers).unwrap();
+            assert_eq!(serde_json::to_string(&parallel).unwrap(), single);
+        }
+
+        let summary = summarize_codebase_with_workers(root_path, &config, 4).unwrap();
+        assert_eq!(summary.files_scanned, 33);
+        assert_eq!(summary.files_included, 32);
+        assert!(!summary.key_files.iter().any(|file| file.contains(".env")));
+    }
+
+    #[test]
+    fn read_workers_follow_config_up_to_the_cap() {
+        let mut config = ImportConfig {
+            read_workers: 3,
+            ..ImportConfig::default()
+        };
+        assert_eq!(read_workers(&config), 3);
+        config.read_workers = MAX_READ_WORKERS + 4;
+        assert_eq!(read_workers(&config), MAX_READ_WORKERS);
+        config.read_workers = 0;
+        assert!((1..=MAX_READ_WORKERS).contains(&read_workers(&config)));
+    }
+
+    #[test]
+    fn read_planned_files_skips_files_after_the_deadline() {
+        let dir = tempdir().expect("temp dir should be created");
+        let planned = (0..4)
+            .map(|file| {
+                let path = dir.path().join(format!("file_{}.txt", file));
+                fs::write(&path, "hello").unwrap();
+                PlannedRead {
+                    relative: format!("file_{}.txt", file),
+                    file_name: format!("file_{}.txt", file),
+                    ext: "txt".to_string(),
+                    capped_size: 5,
+                    path,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let contents = read_planned_files(&planned, 2, None);
+        assert!(contents
+            .iter()
+            .all(|bytes| bytes.as_deref() == Some(b"hello".as_slice())));
+
+        let contents = read_planned_files(&planned, 2, Some(Instant::now()));
+        assert!(contents.iter().all(Option::is_none));
+    }
+
+    #[test]
+    fn summarize_codebase_budgets_bytes_read_and_stops_per_directory() {
+        let dir = tempdir().expect("temp dir should be created");
+        let root = dir.path();
+        let text = "a".repeat(60 * 1024);
+        fs::create_dir_all(root.join("big")).unwrap();
+        for file in 0..110 {
+            fs::write(root.join("big").join(format!("notes_{}.txt", file)), &text).unwrap();
+        }
+        fs::create_dir_all(root.join("assets")).unwrap();
+        for file in 0..50 {
+            fs::write(
+                root.join("assets").join(format!("blob_{}.bin", file)),
+                vec![0u8; MAX_FILE_BYTES as usize],
+            )
+            .unwrap();
+        }
+        fs::create_dir_all(root.join("small")).unwrap();
+        fs::write(root.join("small").join("README.md"), "# Small\n").unwrap();
+
+        let summary =
+            summarize_codebase_with_workers(root.to_str().unwrap(), &ImportConfig::default(), 4)
+                .unwrap();
+
+        // Binary files reserve nothing, and the directory that overflows the
+        // budget stops on its own without ending the walk.
+        let fitting = (MAX_TOTAL_BYTES / text.len() as u64) as usize;
+        assert_eq!(summary.files_included, fitting + 1);
+        assert!(summary
+            .key_files
+            .iter()
+            .any(|file| file == "small/README.md"));
+    }
+
     #[test]
     fn summarize_codebase_marks_tbd_when_evidence_is_sparse() {
         let dir = tempdir().expect("temp dir should be created");
-        let summary = summarize_codebase(dir.path().to_str().expect("path should be valid utf-8"))
-            .expect("summary should succeed");
+        let summary = summarize_codebase(
+            dir.path().to_str().expect("path should be valid utf-8"),
+            &ImportConfig::default(),
+        )
+        .expect("summary should succeed");
         assert!(
             summary.architecture_summary_markdown.contains("[TBD]"),
             "architecture section should mark missing evidence"
@@ -581,7 +1355,8 @@ mod tests {
     fn smoke_import_real_repo_from_env() {
         let repo_path = std::env::var("AURAFORGE_INGEST_SMOKE_REPO")
             .expect("AURAFORGE_INGEST_SMOKE_REPO must be set for smoke tests");
-        let summary = summarize_codebase(&repo_path).expect("smoke import should succeed");
+        let summary = summarize_codebase(&repo_path, &ImportConfig::default())
+            .expect("smoke import should succeed");
 
         assert!(summary.files_scanned > 0, "smoke import should scan files");
         assert!(
@@ -1,15 +1,19 @@
 mod artifact_diff;
 mod commands;
 mod config;
+mod content_search;
 mod db;
 mod docgen;
 mod error;
 mod importer;
 mod lint;
 mod llm;
+mod preferences;
+mod ratelimit;
 mod search;
 mod state;
 mod templates;
+mod timestamp;
 mod types;
 
 use std::sync::Mutex;
@@ -21,9 +25,34 @@ use state::AppState;
 use tauri::menu::{MenuBuilder, PredefinedMenuItem, SubmenuBuilder};
 use tauri::Emitter;
 
+pub(crate) const SAVE_TO_FOLDER_MENU_ID: &str = "save_to_folder";
+
+pub(crate) fn set_menu_item_enabled(
+    app: &tauri::AppHandle,
+    id: &str,
+    enabled: bool,
+) -> tauri::Result<()> {
+    let Some(menu) = app.menu() else {
+        return Ok(());
+    };
+    for item in menu.items()? {
+        let Some(submenu) = item.as_submenu() else {
+            continue;
+        };
+        if let Some(found) = submenu.get(id) {
+            if let Some(menu_item) = found.as_menuitem() {
+                menu_item.set_enabled(enabled)?;
+            }
+            return Ok(());
+        }
+    }
+    Ok(())
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     let (config, config_error) = load_or_create_config();
+    ratelimit::configure(&config.rate_limit);
 
     let db_file = db_path();
     let mut db_error = None;
@@ -49,6 +78,27 @@ pub fn run() {
         }
     };
 
+    let housekeeping_report = if config.housekeeping.enabled {
+        match db.housekeep_sessions(&config.housekeeping) {
+            Ok(report) => {
+                if !report.sessions.is_empty() {
+                    log::info!(
+                        "Housekeeping: {} {} idle session(s)",
+                        report.action,
+                        report.sessions.len()
+                    );
+                }
+                Some(report)
+            }
+            Err(e) => {
+                log::warn!("Session housekeeping failed: {}", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+
     let ollama = OllamaClient::new();
 
     let app_state = AppState {
@@ -58,6 +108,11 @@ pub fn run() {
         config_error: Mutex::new(config_error),
         db_error: Mutex::new(db_error),
         stream_cancel: Mutex::new(std::collections::HashMap::new()),
+        search_cancel: Mutex::new(std::collections::HashMap::new()),
+        generation_cancel: Mutex::new(std::collections::HashMap::new()),
+        export_locks: Default::default(),
+        stream_slots: Default::default(),
+        housekeeping_report: Mutex::new(housekeeping_report),
     };
 
     tauri::Builder::default()
@@ -85,9 +140,9 @@ pub fn run() {
                 )?)
                 .item(&tauri::menu::MenuItem::with_id(
                     app,
-                    "save_to_folder",
+                    SAVE_TO_FOLDER_MENU_ID,
                     "Save to Folder",
-                    true,
+                    false,
                     Some("CmdOrCtrl+S"),
                 )?)
                 .separator()
@@ -170,37 +225,95 @@ pub fn run() {
         .manage(app_state)
         .invoke_handler(tauri::generate_handler![
             commands::check_health,
+            commands::check_all_endpoints_health,
             commands::get_preference,
             commands::set_preference,
+            commands::list_known_preferences,
             commands::list_models,
+            commands::get_token_limit,
             commands::pull_model,
             commands::cancel_pull_model,
+            commands::get_pending_model_pull,
+            commands::clear_pending_model_pull,
             commands::check_disk_space,
             commands::create_session,
             commands::create_session_from_template,
             commands::create_branch_from_message,
             commands::get_sessions,
+            commands::get_sessions_paged,
+            commands::get_sessions_with_status,
             commands::get_session,
             commands::update_session,
+            commands::complete_session,
             commands::delete_session,
             commands::delete_sessions,
+            commands::cleanup_orphans,
+            commands::get_housekeeping_report,
+            commands::get_session_scratchpad,
+            commands::set_session_scratchpad,
+            commands::create_session_snapshot,
+            commands::list_session_snapshots,
+            commands::restore_session_snapshot,
+            commands::can_save_to_folder,
             commands::list_templates,
             commands::get_messages,
+            commands::subscribe_session,
+            commands::get_session_token_usage,
             commands::send_message,
+            commands::preview_chat_request,
             commands::cancel_response,
+            commands::cancel_search,
+            commands::summarize_old_turns,
             commands::import_codebase_context,
+            commands::redetect_stacks,
+            commands::suggest_forge_target,
             commands::get_config,
+            commands::get_effective_config,
+            commands::repair_config,
             commands::update_search_config,
             commands::update_config,
+            commands::resolve_default_save_path,
+            commands::suggest_base_url,
             commands::generate_documents,
+            commands::resume_generation,
+            commands::cancel_generation,
+            commands::get_incomplete_generation,
             commands::get_documents,
+            commands::set_document_order,
+            commands::list_document_names,
+            commands::get_document_chunk,
+            commands::lint_document,
+            commands::lint_conversation,
+            commands::check_document_consistency,
+            commands::lint_all_sessions,
             commands::check_documents_stale,
+            commands::clear_documents,
+            commands::diff_sessions,
+            commands::extract_open_questions,
+            commands::extract_requirements,
+            commands::extract_tech_stack,
+            commands::get_decision_timeline,
+            commands::get_conversation_language,
             commands::analyze_plan_readiness,
             commands::get_planning_coverage,
+            commands::get_coverage_evidence,
+            commands::get_forge_readiness_checklist,
             commands::get_generation_metadata,
+            commands::get_conversation_fingerprint,
             commands::get_generation_confidence,
+            commands::recompute_confidence,
+            commands::regenerate_document,
+            commands::regenerate_handoff,
+            commands::list_document_targets,
+            commands::get_document_catalog,
+            commands::retarget_documents,
+            commands::export_preview,
             commands::save_to_folder,
+            commands::save_to_zip,
+            commands::import_plan_folder,
+            commands::forge_and_export,
             commands::web_search,
+            commands::search_content,
         ])
         .run(tauri::generate_context!())
         .unwrap_or_else(|e| {
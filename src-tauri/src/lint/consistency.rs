@@ -0,0 +1,436 @@
+use crate::docgen::technologies_in;
+use crate::types::{ForgeTarget, GeneratedDocument};
+
+use super::{build_report, LintFinding, LintReport, LintSeverity};
+
+const COMMAND_TOOLS: &[&str] = &[
+    "npm", "npx", "pnpm", "yarn", "bun", "cargo", "pip", "python", "pytest", "go", "make", "uv",
+    "poetry", "deno", "docker",
+];
+
+const SCRIPT_RUNNERS: &[&str] = &["npm", "pnpm", "yarn", "bun"];
+
+pub fn check_document_consistency(docs: &[GeneratedDocument]) -> LintReport {
+    let find = |filename: &str| docs.iter().find(|doc| doc.filename == filename);
+    let claude = docs
+        .iter()
+        .find(|doc| ForgeTarget::AGENT_CONFIG_FILENAMES.contains(&doc.filename.as_str()));
+    let mut findings = Vec::new();
+
+    if let (Some(claude), Some(spec)) = (claude, find("SPEC.md")) {
+        findings.extend(rule_tech_stack_mismatch(claude, spec));
+    }
+    if let (Some(claude), Some(prompts)) = (claude, find("PROMPTS.md")) {
+        findings.extend(rule_command_mismatch(claude, prompts));
+    }
+
+    build_report(findings)
+}
+
+fn rule_tech_stack_mismatch(
+    claude: &GeneratedDocument,
+    spec: &GeneratedDocument,
+) -> Vec<LintFinding> {
+    let claude_stack = stack_technologies(&section_lines(&claude.content, "tech stack"));
+    let spec_stack = stack_technologies(&section_lines(&spec.content, "tech stack"));
+    if claude_stack.is_empty() || spec_stack.is_empty() {
+        return Vec::new();
+    }
+
+    let mut findings = Vec::new();
+    for (name, claude_version) in &claude_stack {
+        let Some((_, spec_version)) = spec_stack.iter().find(|(other, _)| other == name) else {
+            findings.push(LintFinding {
+                rule_id: "tech_stack_drift".to_string(),
+                severity: LintSeverity::Info,
+                filename: claude.filename.clone(),
+                title: format!("{} missing from SPEC.md", name),
+                detail: format!(
+                    "CLAUDE.md lists {} in its tech stack but SPEC.md's stack table does not.",
+                    name
+                ),
+            });
+            continue;
+        };
+        if let (Some(claude_version), Some(spec_version)) = (claude_version, spec_version) {
+            if !versions_compatible(claude_version, spec_version) {
+                findings.push(LintFinding {
+                    rule_id: "tech_version_mismatch".to_string(),
+                    severity: LintSeverity::Warning,
+                    filename: claude.filename.clone(),
+                    title: format!("{} version differs from SPEC.md", name),
+                    detail: format!(
+                        "CLAUDE.md pins {} {} but SPEC.md specifies {}.",
+                        name, claude_version, spec_version
+                    ),
+                });
+            }
+        }
+    }
+    for (name, _) in &spec_stack {
+        if !claude_stack.iter().any(|(other, _)| other == name) {
+            findings.push(LintFinding {
+                rule_id: "tech_stack_drift".to_string(),
+                severity: LintSeverity::Info,
+                filename: claude.filename.clone(),
+                title: format!("{} missing from CLAUDE.md", name),
+                detail: format!(
+                    "SPEC.md's stack table lists {} but CLAUDE.md's tech stack does not.",
+                    name
+                ),
+            });
+        }
+    }
+
+    findings
+}
+
+fn rule_command_mismatch(
+    claude: &GeneratedDocument,
+    prompts: &GeneratedDocument,
+) -> Vec<LintFinding> {
+    let known = commands_in(&section_lines(&claude.content, "commands"));
+    if known.is_empty() {
+        return Vec::new();
+    }
+    let prompt_lines = prompts.content.lines().collect::<Vec<_>>();
+
+    let mut findings = Vec::new();
+    let mut seen = Vec::<String>::new();
+    for command in commands_in(&prompt_lines) {
+        if seen.contains(&command) {
+            continue;
+        }
+        seen.push(command.clone());
+
+        let words = command.split_whitespace().collect::<Vec<_>>();
+        let tool = tool_family(words[0]);
+        if !known
+            .iter()
+            .any(|known| tool_family(known.split_whitespace().next().unwrap_or("")) == tool)
+        {
+            findings.push(LintFinding {
+                rule_id: "command_tool_mismatch".to_string(),
+                severity: LintSeverity::Warning,
+                filename: prompts.filename.clone(),
+                title: format!("`{}` uses a tool CLAUDE.md never runs", command),
+                detail: format!(
+                    "No command in CLAUDE.md's Commands section uses `{}`; check it matches the tech stack.",
+                    words[0]
+                ),
+            });
+            continue;
+        }
+
+        let Some(script) = run_script(&words) else {
+            continue;
+        };
+        let scripts = known
+            .iter()
+            .filter_map(|known| run_script(&known.split_whitespace().collect::<Vec<_>>()))
+            .collect::<Vec<_>>();
+        if !scripts.is_empty() && !scripts.contains(&script) {
+            findings.push(LintFinding {
+                rule_id: "command_script_mismatch".to_string(),
+                severity: LintSeverity::Warning,
+                filename: prompts.filename.clone(),
+                title: format!("`{}` is not a CLAUDE.md command", command),
+                detail: format!(
+                    "CLAUDE.md only runs the scripts {}; `{}` is not among them.",
+                    scripts
+                        .iter()
+                        .map(|script| format!("`{}`", script))
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    script
+                ),
+            });
+        }
+    }
+
+    findings
+}
+
+fn section_lines<'a>(content: &'a str, needle: &str) -> Vec<&'a str> {
+    let mut section = Vec::new();
+    let mut level = None;
+    let mut in_code = false;
+
+    for line in content.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code = !in_code;
+        } else if !in_code {
+            if let Some((heading_level, text)) = heading(line) {
+                match level {
+                    Some(open) if heading_level <= open => break,
+                    Some(_) => {}
+                    None => {
+                        if text.to_ascii_lowercase().contains(needle) {
+                            level = Some(heading_level);
+                        }
+                        continue;
+                    }
+                }
+            }
+        }
+        if level.is_some() {
+            section.push(line);
+        }
+    }
+
+    section
+}
+
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let trimmed = line.trim_start();
+    let level = trimmed.chars().take_while(|c| *c == '#').count();
+    (level > 0 && trimmed[level..].starts_with(' ')).then(|| (level, trimmed[level..].trim()))
+}
+
+fn stack_technologies(lines: &[&str]) -> Vec<(String, Option<String>)> {
+    let mut stack: Vec<(String, Option<String>)> = Vec::new();
+    let mut columns: Option<Vec<usize>> = None;
+
+    for line in lines {
+        let text = if line.trim_start().starts_with('|') {
+            let cells = line
+                .trim()
+                .trim_matches('|')
+                .split('|')
+                .map(str::trim)
+                .collect::<Vec<_>>();
+            if cells
+                .iter()
+                .all(|cell| cell.chars().all(|c| matches!(c, '-' | ':' | ' ')))
+            {
+                continue;
+            }
+            if columns.is_none() {
+                let header = cells
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, cell)| {
+                        matches!(cell.to_ascii_lowercase().as_str(), "technology" | "version")
+                    })
+                    .map(|(index, _)| index)
+                    .collect::<Vec<_>>();
+                if !header.is_empty() {
+                    columns = Some(header);
+                    continue;
+                }
+            }
+            match &columns {
+                Some(columns) => columns
+                    .iter()
+                    .filter_map(|index| cells.get(*index))
+                    .copied()
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                None => cells.join(" "),
+            }
+        } else {
+            line.to_string()
+        };
+
+        for (name, _, version) in technologies_in(&text) {
+            match stack.iter_mut().find(|(existing, _)| existing == name) {
+                Some((_, existing)) => {
+                    if existing.is_none() {
+                        *existing = version;
+                    }
+                }
+                None => stack.push((name.to_string(), version)),
+            }
+        }
+    }
+
+    stack
+}
+
+fn versions_compatible(a: &str, b: &str) -> bool {
+    a.split('.')
+        .zip(b.split('.'))
+        .all(|(left, right)| left == right)
+}
+
+fn commands_in(lines: &[&str]) -> Vec<String> {
+    let mut candidates = Vec::new();
+    let mut in_code = false;
+
+    for line in lines {
+        if line.trim_start().starts_with("```") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code {
+            let code = line.split(" #").next().unwrap_or("");
+            if !code.trim_start().starts_with('#') {
+                candidates.push(code.to_string());
+            }
+            continue;
+        }
+        candidates.extend(line.split('`').skip(1).step_by(2).map(str::to_string));
+    }
+
+    candidates
+        .iter()
+        .flat_map(|candidate| candidate.split("&&"))
+        .map(|part| {
+            let part = part.trim();
+            part.strip_prefix("$ ")
+                .unwrap_or(part)
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .filter(|part| {
+            part.split_whitespace()
+                .next()
+                .is_some_and(|tool| COMMAND_TOOLS.contains(&tool))
+        })
+        .collect()
+}
+
+fn tool_family(tool: &str) -> &str {
+    if tool == "npx" {
+        "npm"
+    } else {
+        tool
+    }
+}
+
+fn run_script(words: &[&str]) -> Option<String> {
+    match words {
+        [tool, "run", script, ..] if SCRIPT_RUNNERS.contains(tool) => Some(script.to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn doc(filename: &str, content: &str) -> GeneratedDocument {
+        GeneratedDocument {
+            id: "doc-id".to_string(),
+            session_id: "session-id".to_string(),
+            filename: filename.to_string(),
+            content: content.to_string(),
+            created_at: "2026-02-07 00:00:00".to_string(),
+        }
+    }
+
+    const CLAUDE: &str = "# Habit Tracker\n\n\
+        ## Tech Stack\n\
+        ```\n\
+        - Frontend: React 19 + TypeScript 5.x\n\
+        - Framework: Tauri 2.0\n\
+        - Database: SQLite via rusqlite 0.32\n\
+        ```\n\n\
+        ## Commands\n\
+        ```bash\n\
+        # Install\n\
+        npm install                      # Frontend dependencies\n\
+        cd src-tauri && cargo build      # Rust dependencies\n\n\
+        # Development\n\
+        npm run tauri dev                # Launch app in dev mode\n\n\
+        # Test\n\
+        cargo test\n\
+        ```\n\n\
+        ## Code Conventions\n\
+        - Run `pytest` never\n";
+
+    const SPEC: &str = "# Habit Tracker Spec\n\n\
+        ### 5. Technical Architecture\n\n\
+        #### Tech Stack\n\
+        | Layer | Technology | Version | Rationale |\n\
+        |-------|------------|---------|-----------|\n\
+        | Frontend | React | 18 | Chosen over Vue |\n\
+        | Desktop | Tauri | 2 | Small binaries |\n\
+        | Database | SQLite via rusqlite | 0.32 | Embedded |\n\
+        | Styling | Tailwind CSS | [latest stable] | Utility classes |\n\n\
+        #### System Design\n\
+        Uses TypeScript everywhere.\n";
+
+    const PROMPTS: &str = "# Habit Tracker Prompts\n\n\
+        ## Phase 1\n\
+        Run `npm install`, then start the app with `npm run dev`.\n\
+        ```bash\n\
+        cd src-tauri && cargo test\n\
+        pip install -r requirements.txt\n\
+        ```\n\
+        - [ ] `npm run tauri dev` opens the window\n";
+
+    fn rows(report: &LintReport) -> Vec<(&str, &str)> {
+        report
+            .findings
+            .iter()
+            .map(|finding| (finding.rule_id.as_str(), finding.title.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn flags_stack_drift_between_claude_md_and_spec() {
+        let report = check_document_consistency(&[doc("CLAUDE.md", CLAUDE), doc("SPEC.md", SPEC)]);
+
+        assert_eq!(
+            rows(&report),
+            vec![
+                (
+                    "tech_version_mismatch",
+                    "React version differs from SPEC.md"
+                ),
+                ("tech_stack_drift", "TypeScript missing from SPEC.md"),
+                ("tech_stack_drift", "Tailwind CSS missing from CLAUDE.md"),
+            ]
+        );
+        assert_eq!(report.summary.warning, 1);
+        assert_eq!(report.summary.info, 2);
+    }
+
+    #[test]
+    fn flags_prompt_commands_missing_from_claude_md() {
+        let report =
+            check_document_consistency(&[doc("CLAUDE.md", CLAUDE), doc("PROMPTS.md", PROMPTS)]);
+
+        assert_eq!(
+            rows(&report),
+            vec![
+                (
+                    "command_script_mismatch",
+                    "`npm run dev` is not a CLAUDE.md command"
+                ),
+                (
+                    "command_tool_mismatch",
+                    "`pip install -r requirements.txt` uses a tool CLAUDE.md never runs"
+                ),
+            ]
+        );
+        assert!(report
+            .findings
+            .iter()
+            .all(|finding| finding.filename == "PROMPTS.md"));
+    }
+
+    #[test]
+    fn consistent_or_partial_sets_pass() {
+        let spec = SPEC
+            .replace("| React | 18 |", "| React | 19.1 |")
+            .replace("| Tailwind CSS | [latest stable] | Utility classes |\n", "");
+        let claude = CLAUDE.replace("React 19 + TypeScript 5.x", "React 19");
+        let prompts = PROMPTS
+            .replace("`npm run dev`", "`npm run tauri dev`")
+            .replace("pip install -r requirements.txt\n", "");
+
+        let report = check_document_consistency(&[
+            doc("CLAUDE.md", &claude),
+            doc("SPEC.md", &spec),
+            doc("PROMPTS.md", &prompts),
+        ]);
+        assert!(report.findings.is_empty(), "{:?}", report.findings);
+
+        assert!(check_document_consistency(&[doc("SPEC.md", SPEC)])
+            .findings
+            .is_empty());
+    }
+}
@@ -0,0 +1,151 @@
+use crate::types::Message;
+
+use super::{build_report, LintFinding, LintReport, LintSeverity};
+
+const MIN_PLANNING_EXCHANGES: usize = 4;
+
+const MULTI_QUESTION_THRESHOLD: usize = 2;
+
+pub fn lint_conversation(session_id: &str, messages: &[Message]) -> LintReport {
+    let turns = messages
+        .iter()
+        .filter(|message| message.role == "user" || message.role == "assistant")
+        .collect::<Vec<_>>();
+
+    let mut findings = Vec::new();
+    findings.extend(rule_short_conversation(session_id, &turns));
+    findings.extend(rule_ignored_questions(session_id, &turns));
+
+    build_report(findings)
+}
+
+fn rule_short_conversation(session_id: &str, turns: &[&Message]) -> Option<LintFinding> {
+    let exchanges = turns.iter().filter(|turn| turn.role == "user").count();
+    if exchanges >= MIN_PLANNING_EXCHANGES {
+        return None;
+    }
+    Some(LintFinding {
+        rule_id: "short_conversation".to_string(),
+        severity: LintSeverity::Warning,
+        filename: session_id.to_string(),
+        title: "Conversation too short to plan from".to_string(),
+        detail: format!(
+            "Only {} exchange(s) so far; at least {} are recommended before generating. \
+             Expect more [TBD] markers in the documents.",
+            exchanges, MIN_PLANNING_EXCHANGES
+        ),
+    })
+}
+
+fn rule_ignored_questions(session_id: &str, turns: &[&Message]) -> Vec<LintFinding> {
+    turns
+        .windows(2)
+        .filter(|pair| pair[0].role == "assistant" && pair[1].role == "user")
+        .filter_map(|pair| {
+            let questions = question_count(&pair[0].content);
+            let reply = pair[1].content.trim();
+            if questions < MULTI_QUESTION_THRESHOLD || reply.split_whitespace().count() > 1 {
+                return None;
+            }
+            Some(LintFinding {
+                rule_id: "ignored_questions".to_string(),
+                severity: LintSeverity::Warning,
+                filename: session_id.to_string(),
+                title: "Questions answered with one word".to_string(),
+                detail: format!(
+                    "The assistant asked {} questions and got \"{}\" back (message {}). \
+                     Answer each question so it doesn't end up as a guess or [TBD].",
+                    questions, reply, pair[1].id
+                ),
+            })
+        })
+        .collect()
+}
+
+fn question_count(content: &str) -> usize {
+    let mut in_code = false;
+    content
+        .lines()
+        .filter(|line| {
+            if line.trim_start().starts_with("```") {
+                in_code = !in_code;
+                return false;
+            }
+            !in_code
+        })
+        .map(|line| line.matches('?').count())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(id: &str, role: &str, content: &str) -> Message {
+        Message {
+            id: id.to_string(),
+            session_id: "s1".to_string(),
+            role: role.to_string(),
+            content: content.to_string(),
+            metadata: None,
+            created_at: "2026-01-01 00:00:00".to_string(),
+        }
+    }
+
+    #[test]
+    fn flags_short_conversations() {
+        let report = lint_conversation(
+            "s1",
+            &[
+                message("1", "system", "Planning seed"),
+                message("2", "user", "I want a habit tracker, generate the plan."),
+                message("3", "assistant", "Sure, forge whenever you're ready."),
+            ],
+        );
+        assert_eq!(report.summary.warning, 1);
+        let finding = &report.findings[0];
+        assert_eq!(finding.rule_id, "short_conversation");
+        assert_eq!(finding.filename, "s1");
+        assert!(finding.detail.contains("Only 1 exchange"));
+    }
+
+    #[test]
+    fn flags_multiple_questions_answered_with_one_word() {
+        let mut messages = vec![
+            message(
+                "a1",
+                "assistant",
+                "Which platforms matter? Do you need offline sync?",
+            ),
+            message("u1", "user", "yes"),
+            message("a2", "assistant", "Should data live in SQLite?"),
+            message("u2", "user", "yes"),
+            message(
+                "a3",
+                "assistant",
+                "How do users sign in?\n```\nlet x = a?;\nlet y = b?;\n```",
+            ),
+            message("u3", "user", "OAuth"),
+            message("a4", "assistant", "Who uses it? How often?"),
+            message("u4", "user", "Busy parents, a few times a day."),
+        ];
+        let report = lint_conversation("s1", &messages);
+        let ignored = report
+            .findings
+            .iter()
+            .filter(|f| f.rule_id == "ignored_questions")
+            .collect::<Vec<_>>();
+        assert_eq!(ignored.len(), 1);
+        assert!(ignored[0].detail.contains("message u1"));
+        assert!(report
+            .findings
+            .iter()
+            .all(|f| f.rule_id != "short_conversation"));
+
+        messages.truncate(2);
+        assert!(lint_conversation("s1", &messages)
+            .findings
+            .iter()
+            .any(|f| f.rule_id == "ignored_questions"));
+    }
+}
@@ -1,7 +1,14 @@
-use chrono::Local;
+mod consistency;
+mod conversation;
+
 use serde::{Deserialize, Serialize};
 
-use crate::types::GeneratedDocument;
+use crate::error::AppError;
+use crate::timestamp;
+use crate::types::{GeneratedDocument, Session};
+
+pub use consistency::check_document_consistency;
+pub use conversation::lint_conversation;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -40,6 +47,68 @@ impl LintReport {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LintMode {
+    FailOnCritical,
+    WarnOnly,
+    Off,
+}
+
+impl LintMode {
+    /// `warn` is accepted as the older spelling of `warn_only`.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "fail_on_critical" => Some(LintMode::FailOnCritical),
+            "warn_only" | "warn" => Some(LintMode::WarnOnly),
+            "off" => Some(LintMode::Off),
+            _ => None,
+        }
+    }
+
+    pub fn from_config(value: &str) -> Self {
+        Self::parse(value).unwrap_or(LintMode::FailOnCritical)
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LintMode::FailOnCritical => "fail_on_critical",
+            LintMode::WarnOnly => "warn_only",
+            LintMode::Off => "off",
+        }
+    }
+
+    pub fn check(self, report: Option<&LintReport>, force: bool) -> Result<(), AppError> {
+        match report {
+            Some(report) if self == LintMode::FailOnCritical && report.has_critical() && !force => {
+                Err(AppError::Validation(format!(
+                    "SpecLint/PromptLint found {} critical issue(s). Review LINT_REPORT.md or continue with force=true.",
+                    report.summary.critical
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocumentLintReport {
+    pub filename: String,
+    #[serde(flatten)]
+    pub report: LintReport,
+    pub skipped_rules: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionLintSummary {
+    pub session_id: String,
+    pub session_name: String,
+    pub summary: LintSummary,
+}
+
+const SET_LEVEL_RULES: &[&str] = &["inconsistent_project_naming"];
+
+const VERIFICATION_DOCS: &[&str] = &["PROMPTS.md", "START_HERE.md", "MODEL_HANDOFF.md"];
+
 pub fn lint_documents(docs: &[GeneratedDocument]) -> LintReport {
     let mut findings = Vec::new();
 
@@ -49,6 +118,50 @@ pub fn lint_documents(docs: &[GeneratedDocument]) -> LintReport {
     findings.extend(rule_vague_requirements(docs));
     findings.extend(rule_missing_verification_steps(docs));
 
+    build_report(findings)
+}
+
+pub fn lint_sessions(sessions: &[(Session, Vec<GeneratedDocument>)]) -> Vec<SessionLintSummary> {
+    let mut summaries = sessions
+        .iter()
+        .map(|(session, docs)| SessionLintSummary {
+            session_id: session.id.clone(),
+            session_name: session.name.clone(),
+            summary: lint_documents(docs).summary,
+        })
+        .collect::<Vec<_>>();
+    summaries.sort_by(|a, b| {
+        b.summary
+            .critical
+            .cmp(&a.summary.critical)
+            .then(b.summary.warning.cmp(&a.summary.warning))
+            .then_with(|| a.session_name.cmp(&b.session_name))
+    });
+    summaries
+}
+
+pub fn lint_document(doc: &GeneratedDocument) -> DocumentLintReport {
+    let docs = std::slice::from_ref(doc);
+    let mut findings = Vec::new();
+
+    findings.extend(rule_tbd_leftovers(docs));
+    findings.extend(rule_missing_acceptance_criteria(docs));
+    findings.extend(rule_vague_requirements(docs));
+    if VERIFICATION_DOCS.contains(&doc.filename.as_str()) {
+        findings.extend(verification_steps_finding(doc));
+    }
+
+    DocumentLintReport {
+        filename: doc.filename.clone(),
+        report: build_report(findings),
+        skipped_rules: SET_LEVEL_RULES
+            .iter()
+            .map(|rule| rule.to_string())
+            .collect(),
+    }
+}
+
+fn build_report(findings: Vec<LintFinding>) -> LintReport {
     let mut summary = LintSummary::default();
     for finding in &findings {
         match finding.severity {
@@ -59,7 +172,7 @@ pub fn lint_documents(docs: &[GeneratedDocument]) -> LintReport {
     }
 
     LintReport {
-        generated_at: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+        generated_at: timestamp::now(),
         summary,
         findings,
     }
@@ -246,9 +359,8 @@ fn rule_vague_requirements(docs: &[GeneratedDocument]) -> Vec<LintFinding> {
 fn rule_missing_verification_steps(docs: &[GeneratedDocument]) -> Vec<LintFinding> {
     let mut findings = Vec::new();
 
-    let required = ["PROMPTS.md", "START_HERE.md", "MODEL_HANDOFF.md"];
-    for filename in required {
-        let Some(doc) = docs.iter().find(|doc| doc.filename == filename) else {
+    for filename in VERIFICATION_DOCS {
+        let Some(doc) = docs.iter().find(|doc| doc.filename == *filename) else {
             findings.push(LintFinding {
                 rule_id: "missing_verification_steps".to_string(),
                 severity: LintSeverity::Critical,
@@ -259,25 +371,29 @@ fn rule_missing_verification_steps(docs: &[GeneratedDocument]) -> Vec<LintFindin
             continue;
         };
 
-        let lower = doc.content.to_ascii_lowercase();
-        let has_verification = lower.contains("verification") || lower.contains("checklist");
-        let has_checkbox = doc.content.contains("- [ ]");
-        if !has_verification || !has_checkbox {
-            findings.push(LintFinding {
-                rule_id: "missing_verification_steps".to_string(),
-                severity: LintSeverity::Critical,
-                filename: doc.filename.clone(),
-                title: "Missing concrete verification steps".to_string(),
-                detail:
-                    "Document should include explicit verification/checklist steps with checkboxes."
-                        .to_string(),
-            });
-        }
+        findings.extend(verification_steps_finding(doc));
     }
 
     findings
 }
 
+fn verification_steps_finding(doc: &GeneratedDocument) -> Option<LintFinding> {
+    let lower = doc.content.to_ascii_lowercase();
+    let has_verification = lower.contains("verification") || lower.contains("checklist");
+    let has_checkbox = doc.content.contains("- [ ]");
+    if has_verification && has_checkbox {
+        return None;
+    }
+    Some(LintFinding {
+        rule_id: "missing_verification_steps".to_string(),
+        severity: LintSeverity::Critical,
+        filename: doc.filename.clone(),
+        title: "Missing concrete verification steps".to_string(),
+        detail: "Document should include explicit verification/checklist steps with checkboxes."
+            .to_string(),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -292,6 +408,23 @@ mod tests {
         }
     }
 
+    #[test]
+    fn lint_modes_parse_and_only_fail_on_critical_blocks() {
+        assert_eq!(LintMode::parse("warn"), Some(LintMode::WarnOnly));
+        assert_eq!(LintMode::parse(" Warn_Only "), Some(LintMode::WarnOnly));
+        assert_eq!(LintMode::parse("strict"), None);
+        assert_eq!(LintMode::from_config("strict"), LintMode::FailOnCritical);
+
+        let report = lint_documents(&[doc("SPEC.md", "# Spec\n[TBD - fill later]")]);
+        assert!(LintMode::FailOnCritical
+            .check(Some(&report), false)
+            .is_err());
+        assert!(LintMode::FailOnCritical.check(Some(&report), true).is_ok());
+        assert!(LintMode::FailOnCritical.check(None, false).is_ok());
+        assert!(LintMode::WarnOnly.check(Some(&report), false).is_ok());
+        assert!(LintMode::Off.check(Some(&report), false).is_ok());
+    }
+
     #[test]
     fn lint_flags_tbd_leftovers() {
         let report = lint_documents(&[doc("SPEC.md", "# Spec\n[TBD - fill later]")]);
@@ -341,4 +474,52 @@ mod tests {
         ]);
         assert_eq!(report.summary.critical, 0);
     }
+
+    #[test]
+    fn lint_document_runs_single_file_rules_only() {
+        let spec = doc(
+            "SPEC.md",
+            "# Other Name\n## Features\nA fast, robust login.\n[TBD - auth provider]",
+        );
+        let report = lint_document(&spec);
+        let rules = report
+            .report
+            .findings
+            .iter()
+            .map(|f| f.rule_id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rules,
+            vec![
+                "tbd_leftover",
+                "missing_acceptance_criteria",
+                "vague_requirements"
+            ]
+        );
+        // A lone SPEC must not be blamed for the other documents being absent.
+        assert_eq!(report.report.summary.critical, 2);
+        assert_eq!(report.skipped_rules, vec!["inconsistent_project_naming"]);
+    }
+
+    #[test]
+    fn lint_document_checks_verification_steps_in_execution_docs() {
+        let prompts = doc("PROMPTS.md", "# Project\n## Phase 1\nBuild it.");
+        let report = lint_document(&prompts);
+        assert_eq!(report.filename, "PROMPTS.md");
+        assert_eq!(report.report.findings.len(), 1);
+        assert_eq!(
+            report.report.findings[0].rule_id,
+            "missing_verification_steps"
+        );
+
+        let fixed = doc(
+            "PROMPTS.md",
+            "# Project\n## Verification Checklist\n- [ ] run tests",
+        );
+        assert!(lint_document(&fixed).report.findings.is_empty());
+        assert!(lint_document(&doc("README.md", "# Project"))
+            .report
+            .findings
+            .is_empty());
+    }
 }
@@ -1,5 +1,6 @@
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 use futures::StreamExt;
 use reqwest::Client;
@@ -8,8 +9,66 @@ use tauri::Emitter;
 use tokio::time::{timeout, Duration};
 
 use crate::error::AppError;
+use crate::ratelimit;
 use crate::search::SearchResult;
-use crate::types::{AppConfig, LLMConfig};
+use crate::types::{AppConfig, EndpointHealth, LLMConfig, LlmProfile, TokenLimitStatus};
+
+const MAX_OUTPUT_CONTEXT_DIVISOR: u64 = 2;
+
+const ENDPOINT_PROBE_TIMEOUT: Duration = Duration::from_secs(6);
+
+const ACTIVE_ENDPOINT_NAME: &str = "active";
+
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+/// Anthropic caps sampling temperature at 1.0; the config allows up to 2.0.
+const ANTHROPIC_MAX_TEMPERATURE: f64 = 1.0;
+
+const LLM_RETRY_BACKOFF_MS: &[u64] = &[250, 500, 1000];
+
+#[derive(Debug, Clone, Copy)]
+struct StreamTimeouts {
+    first_chunk: Duration,
+    idle: Duration,
+}
+
+impl StreamTimeouts {
+    fn from_config(config: &LLMConfig) -> Self {
+        Self {
+            first_chunk: Duration::from_secs(config.first_token_timeout_secs.max(1)),
+            idle: Duration::from_secs(config.stream_idle_timeout_secs.max(1)),
+        }
+    }
+}
+
+fn request_timeout(config: &LLMConfig) -> Duration {
+    Duration::from_secs(config.request_timeout_secs.max(1))
+}
+
+async fn next_chunk<S>(
+    stream: &mut S,
+    received_any: &mut bool,
+    timeouts: StreamTimeouts,
+) -> Result<Option<S::Item>, AppError>
+where
+    S: futures::Stream + Unpin,
+{
+    let limit = if *received_any {
+        timeouts.idle
+    } else {
+        timeouts.first_chunk
+    };
+    let item = timeout(limit, stream.next()).await.map_err(|_| {
+        log::warn!(
+            "Stream produced no data for {:?} ({} chunk)",
+            limit,
+            if *received_any { "next" } else { "first" }
+        );
+        AppError::StreamInterrupted
+    })?;
+    *received_any = true;
+    Ok(item)
+}
 
 #[derive(Debug, Deserialize)]
 struct OllamaTagsResponse {
@@ -34,6 +93,21 @@ struct OllamaOptions {
     temperature: f64,
     #[serde(skip_serializing_if = "Option::is_none")]
     num_predict: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    seed: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+struct OllamaShowRequest {
+    model: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct OllamaShowResponse {
+    #[serde(default)]
+    parameters: Option<String>,
+    #[serde(default)]
+    model_info: Option<serde_json::Map<String, serde_json::Value>>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -54,11 +128,22 @@ struct OpenAiChatRequest {
     temperature: f64,
     #[serde(skip_serializing_if = "Option::is_none")]
     max_tokens: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    seed: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream_options: Option<OpenAiStreamOptions>,
+}
+
+#[derive(Debug, Serialize)]
+struct OpenAiStreamOptions {
+    include_usage: bool,
 }
 
 #[derive(Debug, Deserialize)]
 struct OpenAiChatResponse {
     choices: Vec<OpenAiChoice>,
+    #[serde(default)]
+    usage: Option<UsageStats>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -73,7 +158,36 @@ struct OpenAiChatMessage {
 
 #[derive(Debug, Deserialize)]
 struct OpenAiStreamResponse {
+    #[serde(default)]
     choices: Vec<OpenAiStreamChoice>,
+    #[serde(default)]
+    usage: Option<UsageStats>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct UsageStats {
+    pub prompt_tokens: Option<u64>,
+    pub completion_tokens: Option<u64>,
+    pub total_tokens: Option<u64>,
+}
+
+impl UsageStats {
+    fn from_counts(prompt: Option<u64>, completion: Option<u64>) -> Option<Self> {
+        if prompt.is_none() && completion.is_none() {
+            return None;
+        }
+        Some(Self {
+            prompt_tokens: prompt,
+            completion_tokens: completion,
+            total_tokens: prompt.zip(completion).map(|(p, c)| p + c),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct LlmReply {
+    pub content: String,
+    pub usage: Option<UsageStats>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -87,6 +201,86 @@ struct OpenAiStreamDelta {
     content: Option<String>,
 }
 
+#[derive(Debug, Serialize)]
+struct AnthropicMessagesRequest {
+    model: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system: Option<String>,
+    messages: Vec<ChatMessage>,
+    max_tokens: u64,
+    temperature: f64,
+    stream: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicMessagesResponse {
+    content: Vec<AnthropicContentBlock>,
+    #[serde(default)]
+    usage: Option<AnthropicUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicContentBlock {
+    #[serde(default)]
+    text: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicModelsResponse {
+    data: Vec<AnthropicModel>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicModel {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum AnthropicStreamEvent {
+    MessageStart {
+        message: AnthropicStreamMessage,
+    },
+    ContentBlockDelta {
+        delta: AnthropicStreamDelta,
+    },
+    MessageDelta {
+        #[serde(default)]
+        usage: Option<AnthropicUsage>,
+    },
+    MessageStop,
+    Error {
+        error: AnthropicStreamError,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicStreamMessage {
+    #[serde(default)]
+    usage: Option<AnthropicUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicStreamDelta {
+    #[serde(default)]
+    text: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicUsage {
+    #[serde(default)]
+    input_tokens: Option<u64>,
+    #[serde(default)]
+    output_tokens: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicStreamError {
+    message: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChatMessage {
     pub role: String,
@@ -97,6 +291,19 @@ pub struct ChatMessage {
 struct OllamaStreamResponse {
     message: OllamaStreamMessage,
     done: bool,
+    #[serde(default)]
+    prompt_eval_count: Option<u64>,
+    #[serde(default)]
+    eval_count: Option<u64>,
+}
+
+/// Ollama reports failures after streaming has started as a bare
+/// `{"error": "..."}` object.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum OllamaStreamLine {
+    Error { error: String },
+    Chunk(OllamaStreamResponse),
 }
 
 #[derive(Debug, Deserialize)]
@@ -107,6 +314,10 @@ struct OllamaStreamMessage {
 #[derive(Debug, Deserialize)]
 struct OllamaChatResponse {
     message: OllamaChatResponseMessage,
+    #[serde(default)]
+    prompt_eval_count: Option<u64>,
+    #[serde(default)]
+    eval_count: Option<u64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -122,6 +333,7 @@ pub struct StreamChunk {
     pub search_query: Option<String>,
     pub search_results: Option<Vec<SearchResult>>,
     pub session_id: Option<String>,
+    pub usage: Option<UsageStats>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -148,12 +360,14 @@ struct OllamaPullResponse {
 pub struct OllamaClient {
     client: Client,
     pull_cancelled: Arc<AtomicBool>,
+    context_lengths: Mutex<HashMap<String, u64>>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum ProviderKind {
     Ollama,
     OpenAiCompatible,
+    Anthropic,
 }
 
 impl ProviderKind {
@@ -161,8 +375,9 @@ impl ProviderKind {
         match provider.trim().to_ascii_lowercase().as_str() {
             "ollama" => Ok(Self::Ollama),
             "openai_compatible" | "openai-compatible" | "lmstudio" => Ok(Self::OpenAiCompatible),
+            "anthropic" => Ok(Self::Anthropic),
             other => Err(AppError::Validation(format!(
-                "Unsupported local provider '{}'",
+                "Unsupported provider '{}'",
                 other
             ))),
         }
@@ -182,6 +397,7 @@ impl OllamaClient {
         Self {
             client,
             pull_cancelled: Arc::new(AtomicBool::new(false)),
+            context_lengths: Mutex::new(HashMap::new()),
         }
     }
 
@@ -193,27 +409,92 @@ impl OllamaClient {
         )
     }
 
+    /// Retries happen before any body is read, so streamed output is never replayed.
+    async fn send_with_retry(
+        &self,
+        config: &LLMConfig,
+        request: reqwest::RequestBuilder,
+    ) -> Result<reqwest::Response, reqwest::Error> {
+        let retries = (config.max_retries as usize).min(LLM_RETRY_BACKOFF_MS.len());
+        for delay_ms in &LLM_RETRY_BACKOFF_MS[..retries] {
+            let Some(attempt) = request.try_clone() else {
+                break;
+            };
+            match attempt.send().await {
+                Ok(response) if response.status().is_server_error() => {
+                    log::warn!(
+                        "{} returned {}; retrying in {}ms",
+                        config.base_url,
+                        response.status(),
+                        delay_ms
+                    );
+                }
+                Err(e) if e.is_connect() => {
+                    log::warn!(
+                        "Could not reach {} ({}); retrying in {}ms",
+                        config.base_url,
+                        e,
+                        delay_ms
+                    );
+                }
+                result => return result,
+            }
+            tokio::time::sleep(Duration::from_millis(*delay_ms)).await;
+            ratelimit::acquire(&config.base_url).await;
+        }
+        request.send().await
+    }
+
+    fn with_headers(
+        &self,
+        request: reqwest::RequestBuilder,
+        config: &LLMConfig,
+    ) -> reqwest::RequestBuilder {
+        config
+            .headers
+            .iter()
+            .fold(request, |request, (name, value)| {
+                request.header(name.trim(), value.trim())
+            })
+    }
+
     fn with_auth(
         &self,
         request: reqwest::RequestBuilder,
-        api_key: Option<&str>,
+        config: &LLMConfig,
     ) -> reqwest::RequestBuilder {
-        if let Some(key) = api_key.filter(|value| !value.trim().is_empty()) {
-            request.bearer_auth(key.trim())
-        } else {
-            request
+        let request = self.with_headers(request, config);
+        let key = config
+            .api_key
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty());
+        if ProviderKind::from_config(config).ok() == Some(ProviderKind::Anthropic) {
+            let request = request.header("anthropic-version", ANTHROPIC_VERSION);
+            return match key {
+                Some(key) => request.header("x-api-key", key),
+                None => request,
+            };
+        }
+        match key {
+            Some(key) => request.bearer_auth(key),
+            None => request,
         }
     }
 
     pub async fn list_models(&self, config: &LLMConfig) -> Result<Vec<String>, AppError> {
         match ProviderKind::from_config(config)? {
             ProviderKind::OpenAiCompatible => self.list_models_openai(config).await,
+            ProviderKind::Anthropic => self.list_models_anthropic(config).await,
             ProviderKind::Ollama => {
                 let base_url = &config.base_url;
-                let resp = self
+                ratelimit::acquire(base_url).await;
+                let request = self
                     .client
                     .get(Self::endpoint(base_url, "/api/tags"))
-                    .timeout(std::time::Duration::from_secs(5))
+                    .timeout(std::time::Duration::from_secs(5));
+                let resp = self
+                    .with_headers(request, config)
                     .send()
                     .await
                     .map_err(|e| AppError::OllamaConnection {
@@ -238,18 +519,17 @@ impl OllamaClient {
     }
 
     async fn list_models_openai(&self, config: &LLMConfig) -> Result<Vec<String>, AppError> {
+        ratelimit::acquire(&config.base_url).await;
         let request = self
             .client
             .get(Self::endpoint(&config.base_url, "/v1/models"))
             .timeout(Duration::from_secs(5));
-        let resp = self
-            .with_auth(request, config.api_key.as_deref())
-            .send()
-            .await
-            .map_err(|e| AppError::OllamaConnection {
+        let resp = self.with_auth(request, config).send().await.map_err(|e| {
+            AppError::OllamaConnection {
                 url: config.base_url.to_string(),
                 message: e.to_string(),
-            })?;
+            }
+        })?;
 
         if !resp.status().is_success() {
             return Err(AppError::LlmRequest(format!(
@@ -268,6 +548,32 @@ impl OllamaClient {
         Ok(body.data.into_iter().map(|model| model.id).collect())
     }
 
+    async fn list_models_anthropic(&self, config: &LLMConfig) -> Result<Vec<String>, AppError> {
+        ratelimit::acquire(&config.base_url).await;
+        let request = self
+            .client
+            .get(Self::endpoint(&config.base_url, "/v1/models?limit=1000"))
+            .timeout(Duration::from_secs(10));
+        let resp = self.with_auth(request, config).send().await.map_err(|e| {
+            AppError::OllamaConnection {
+                url: config.base_url.to_string(),
+                message: e.to_string(),
+            }
+        })?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            return Err(request_error("Anthropic API", status, &body));
+        }
+
+        let body: AnthropicModelsResponse = resp.json().await.map_err(|e| {
+            AppError::LlmRequest(format!("Failed to parse Anthropic models response: {}", e))
+        })?;
+
+        Ok(body.data.into_iter().map(|model| model.id).collect())
+    }
+
     pub async fn pull_model(
         &self,
         app: &tauri::AppHandle,
@@ -275,7 +581,7 @@ impl OllamaClient {
         model_name: &str,
     ) -> Result<(), AppError> {
         match ProviderKind::from_config(config)? {
-            ProviderKind::OpenAiCompatible => {
+            ProviderKind::OpenAiCompatible | ProviderKind::Anthropic => {
                 let _ = app.emit(
                     "model:pull_progress",
                     ModelPullProgress {
@@ -285,7 +591,7 @@ impl OllamaClient {
                     },
                 );
                 return Err(AppError::Validation(
-                    "Model pull is only supported for Ollama. Load models directly in your local runtime."
+                    "Model pull is only supported for Ollama. Load models directly in your local runtime, or pick a hosted model."
                         .to_string(),
                 ));
             }
@@ -295,14 +601,17 @@ impl OllamaClient {
         let base_url = &config.base_url;
         self.pull_cancelled.store(false, Ordering::SeqCst);
 
-        let response = self
+        ratelimit::acquire(base_url).await;
+        let request = self
             .client
             .post(Self::endpoint(base_url, "/api/pull"))
             .json(&OllamaPullRequest {
                 name: model_name.to_string(),
                 stream: true,
             })
-            .timeout(Duration::from_secs(300))
+            .timeout(request_timeout(config));
+        let response = self
+            .with_headers(request, config)
             .send()
             .await
             .map_err(|e| AppError::OllamaConnection {
@@ -318,15 +627,13 @@ impl OllamaClient {
                     model: model_name.to_string(),
                 });
             }
-            return Err(AppError::LlmRequest(format!(
-                "Ollama returned {}: {}",
-                status, body
-            )));
+            return Err(request_error("Ollama", status, &body));
         }
 
         let mut stream = response.bytes_stream();
         let mut buffer = String::new();
         let mut completed = false;
+        let mut resumed_layers: HashMap<String, bool> = HashMap::new();
 
         while let Some(chunk) = timeout(Duration::from_secs(120), stream.next())
             .await
@@ -373,7 +680,12 @@ impl OllamaClient {
                         let _ = app.emit(
                             "model:pull_progress",
                             ModelPullProgress {
-                                status: status.clone(),
+                                status: pull_progress_status(
+                                    &status,
+                                    parsed.total,
+                                    parsed.completed,
+                                    &mut resumed_layers,
+                                ),
                                 total: parsed.total,
                                 completed: parsed.completed,
                             },
@@ -412,7 +724,12 @@ impl OllamaClient {
                         let _ = app.emit(
                             "model:pull_progress",
                             ModelPullProgress {
-                                status: status.clone(),
+                                status: pull_progress_status(
+                                    &status,
+                                    parsed.total,
+                                    parsed.completed,
+                                    &mut resumed_layers,
+                                ),
                                 total: parsed.total,
                                 completed: parsed.completed,
                             },
@@ -458,26 +775,33 @@ impl OllamaClient {
 
     pub async fn check_connection(&self, config: &LLMConfig) -> Result<bool, AppError> {
         match ProviderKind::from_config(config)? {
-            ProviderKind::OpenAiCompatible => {
+            kind @ (ProviderKind::OpenAiCompatible | ProviderKind::Anthropic) => {
+                let path = if kind == ProviderKind::Anthropic {
+                    "/v1/models?limit=1"
+                } else {
+                    "/v1/models"
+                };
+                ratelimit::acquire(&config.base_url).await;
                 let request = self
                     .client
-                    .get(Self::endpoint(&config.base_url, "/v1/models"))
+                    .get(Self::endpoint(&config.base_url, path))
                     .timeout(std::time::Duration::from_secs(5));
-                let resp = self
-                    .with_auth(request, config.api_key.as_deref())
-                    .send()
-                    .await
-                    .map_err(|e| AppError::OllamaConnection {
+                let resp = self.with_auth(request, config).send().await.map_err(|e| {
+                    AppError::OllamaConnection {
                         url: config.base_url.to_string(),
                         message: e.to_string(),
-                    })?;
+                    }
+                })?;
                 Ok(resp.status().is_success())
             }
             ProviderKind::Ollama => {
-                let resp = self
+                ratelimit::acquire(&config.base_url).await;
+                let request = self
                     .client
                     .get(Self::endpoint(&config.base_url, "/api/tags"))
-                    .timeout(std::time::Duration::from_secs(5))
+                    .timeout(std::time::Duration::from_secs(5));
+                let resp = self
+                    .with_headers(request, config)
                     .send()
                     .await
                     .map_err(|e| AppError::OllamaConnection {
@@ -493,6 +817,10 @@ impl OllamaClient {
         let models = self.list_models(config).await?;
         match ProviderKind::from_config(config)? {
             ProviderKind::OpenAiCompatible => Ok(models.iter().any(|candidate| candidate == model)),
+            // Aliases such as `claude-sonnet-4-5` resolve to dated ids in the list.
+            ProviderKind::Anthropic => Ok(models.iter().any(|candidate| {
+                candidate == model || candidate.starts_with(&format!("{}-", model))
+            })),
             ProviderKind::Ollama => {
                 let model_base = model.split(':').next().unwrap_or(model);
                 Ok(models.iter().any(|candidate| {
@@ -518,6 +846,137 @@ impl OllamaClient {
         (connected, model_available)
     }
 
+    pub async fn check_all_endpoints(&self, llm: &LLMConfig) -> Vec<EndpointHealth> {
+        futures::future::join_all(
+            endpoint_configs(llm)
+                .into_iter()
+                .map(|(name, config)| self.probe_endpoint(name, config)),
+        )
+        .await
+    }
+
+    async fn probe_endpoint(&self, profile: String, config: LLMConfig) -> EndpointHealth {
+        let deadline = tokio::time::Instant::now() + ENDPOINT_PROBE_TIMEOUT;
+        let timed_out = || {
+            format!(
+                "No response from {} within {}s",
+                config.base_url,
+                ENDPOINT_PROBE_TIMEOUT.as_secs()
+            )
+        };
+        let mut health = EndpointHealth {
+            profile,
+            provider: config.provider.clone(),
+            base_url: config.base_url.clone(),
+            model: config.model.clone(),
+            connected: false,
+            model_available: false,
+            error: None,
+        };
+
+        match tokio::time::timeout_at(deadline, self.check_connection(&config)).await {
+            Ok(Ok(true)) => health.connected = true,
+            Ok(Ok(false)) => {
+                health.error = Some(format!("{} returned an error status", config.base_url))
+            }
+            Ok(Err(err)) => health.error = Some(err.to_string()),
+            Err(_) => health.error = Some(timed_out()),
+        }
+        if !health.connected {
+            return health;
+        }
+
+        match tokio::time::timeout_at(deadline, self.check_model(&config, &config.model)).await {
+            Ok(Ok(true)) => health.model_available = true,
+            Ok(Ok(false)) => {
+                health.error = Some(format!("Model '{}' is not available", config.model))
+            }
+            Ok(Err(err)) => health.error = Some(err.to_string()),
+            Err(_) => health.error = Some(timed_out()),
+        }
+        health
+    }
+
+    pub async fn context_length(&self, config: &LLMConfig) -> Option<u64> {
+        if ProviderKind::from_config(config).ok()? != ProviderKind::Ollama {
+            return None;
+        }
+
+        let cache_key = format!(
+            "{}::{}",
+            config.base_url.trim_end_matches('/'),
+            config.model
+        );
+        let cached = self
+            .context_lengths
+            .lock()
+            .ok()
+            .and_then(|cache| cache.get(&cache_key).copied());
+        if cached.is_some() {
+            return cached;
+        }
+
+        ratelimit::acquire(&config.base_url).await;
+        let request = self
+            .client
+            .post(Self::endpoint(&config.base_url, "/api/show"))
+            .json(&OllamaShowRequest {
+                model: config.model.clone(),
+            })
+            .timeout(Duration::from_secs(5));
+        let resp = self.with_headers(request, config).send().await.ok()?;
+        if !resp.status().is_success() {
+            log::debug!(
+                "Ollama /api/show returned {} for model '{}'",
+                resp.status(),
+                config.model
+            );
+            return None;
+        }
+
+        let show: OllamaShowResponse = resp.json().await.ok()?;
+        let length = parse_context_length(&show)?;
+        if let Ok(mut cache) = self.context_lengths.lock() {
+            cache.insert(cache_key, length);
+        }
+        Some(length)
+    }
+
+    pub async fn token_limit(&self, config: &LLMConfig) -> TokenLimitStatus {
+        let context_length = if config.clamp_to_context {
+            self.context_length(config).await
+        } else {
+            None
+        };
+        let effective = clamp_to_context(config.max_tokens, context_length);
+        TokenLimitStatus {
+            configured: config.max_tokens,
+            context_length,
+            effective,
+            clamped: effective < config.max_tokens,
+        }
+    }
+
+    async fn resolve_num_predict(&self, config: &LLMConfig, requested: Option<u64>) -> Option<u64> {
+        let requested = requested?;
+        if !config.clamp_to_context {
+            return Some(requested);
+        }
+
+        let context_length = self.context_length(config).await;
+        let effective = clamp_to_context(requested, context_length);
+        if effective < requested {
+            log::info!(
+                "Clamped max_tokens from {} to {} for model '{}' (context length {})",
+                requested,
+                effective,
+                config.model,
+                context_length.unwrap_or_default()
+            );
+        }
+        Some(effective)
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub async fn stream_chat(
         &self,
@@ -528,26 +987,44 @@ impl OllamaClient {
         num_predict: Option<u64>,
         session_id: &str,
         cancel: Option<Arc<AtomicBool>>,
-    ) -> Result<String, AppError> {
-        if ProviderKind::from_config(config)? == ProviderKind::OpenAiCompatible {
-            return self
-                .stream_chat_openai(
-                    app,
-                    config,
-                    messages,
-                    temperature,
-                    num_predict,
-                    session_id,
-                    cancel,
-                )
-                .await;
+    ) -> Result<LlmReply, AppError> {
+        let num_predict = self.resolve_num_predict(config, num_predict).await;
+        match ProviderKind::from_config(config)? {
+            ProviderKind::OpenAiCompatible => {
+                return self
+                    .stream_chat_openai(
+                        app,
+                        config,
+                        messages,
+                        temperature,
+                        num_predict,
+                        session_id,
+                        cancel,
+                    )
+                    .await;
+            }
+            ProviderKind::Anthropic => {
+                return self
+                    .stream_chat_anthropic(
+                        app,
+                        config,
+                        messages,
+                        temperature,
+                        num_predict,
+                        session_id,
+                        cancel,
+                    )
+                    .await;
+            }
+            ProviderKind::Ollama => {}
         }
 
         let base_url = &config.base_url;
         let model = &config.model;
         let url = Self::endpoint(base_url, "/api/chat");
 
-        let response = self
+        ratelimit::acquire(base_url).await;
+        let request = self
             .client
             .post(&url)
             .json(&OllamaChatRequest {
@@ -557,10 +1034,12 @@ impl OllamaClient {
                 options: OllamaOptions {
                     temperature,
                     num_predict: num_predict.map(|n| n as i64),
+                    seed: config.seed,
                 },
             })
-            .timeout(std::time::Duration::from_secs(300))
-            .send()
+            .timeout(request_timeout(config));
+        let response = self
+            .send_with_retry(config, self.with_headers(request, config))
             .await
             .map_err(|e| AppError::OllamaConnection {
                 url: base_url.to_string(),
@@ -575,10 +1054,7 @@ impl OllamaClient {
                     model: model.to_string(),
                 });
             }
-            return Err(AppError::LlmRequest(format!(
-                "Ollama returned {}: {}",
-                status, body
-            )));
+            return Err(request_error("Ollama", status, &body));
         }
 
         let mut stream = response.bytes_stream();
@@ -586,10 +1062,10 @@ impl OllamaClient {
         let mut buffer = String::new();
 
         let mut done = false;
-        while let Some(chunk) = timeout(Duration::from_secs(60), stream.next())
-            .await
-            .map_err(|_| AppError::StreamInterrupted)?
-        {
+        let mut usage = None;
+        let timeouts = StreamTimeouts::from_config(config);
+        let mut received_any = false;
+        while let Some(chunk) = next_chunk(&mut stream, &mut received_any, timeouts).await? {
             if let Some(flag) = &cancel {
                 if flag.load(Ordering::SeqCst) {
                     let _ = app.emit(
@@ -615,8 +1091,8 @@ impl OllamaClient {
                     continue;
                 }
 
-                match serde_json::from_str::<OllamaStreamResponse>(&line) {
-                    Ok(parsed) => {
+                match parse_ollama_stream_line(&line)? {
+                    Some(parsed) => {
                         if !parsed.message.content.is_empty() {
                             full_response.push_str(&parsed.message.content);
 
@@ -632,11 +1108,16 @@ impl OllamaClient {
                         }
 
                         if parsed.done {
+                            usage = UsageStats::from_counts(
+                                parsed.prompt_eval_count,
+                                parsed.eval_count,
+                            );
                             let _ = app.emit(
                                 "stream:done",
                                 StreamChunk {
                                     r#type: "done".to_string(),
                                     session_id: Some(session_id.to_string()),
+                                    usage: usage.clone(),
                                     ..Default::default()
                                 },
                             );
@@ -644,7 +1125,7 @@ impl OllamaClient {
                             break;
                         }
                     }
-                    Err(_) => continue,
+                    None => continue,
                 }
             }
 
@@ -656,7 +1137,7 @@ impl OllamaClient {
         // Process any remaining data in the buffer
         let remaining = buffer.trim();
         if !remaining.is_empty() {
-            if let Ok(parsed) = serde_json::from_str::<OllamaStreamResponse>(remaining) {
+            if let Some(parsed) = parse_ollama_stream_line(remaining)? {
                 if !parsed.message.content.is_empty() {
                     full_response.push_str(&parsed.message.content);
                     let _ = app.emit(
@@ -670,11 +1151,13 @@ impl OllamaClient {
                     );
                 }
                 if parsed.done {
+                    usage = UsageStats::from_counts(parsed.prompt_eval_count, parsed.eval_count);
                     let _ = app.emit(
                         "stream:done",
                         StreamChunk {
                             r#type: "done".to_string(),
                             session_id: Some(session_id.to_string()),
+                            usage: usage.clone(),
                             ..Default::default()
                         },
                     );
@@ -700,7 +1183,10 @@ impl OllamaClient {
             return Err(AppError::StreamInterrupted);
         }
 
-        Ok(full_response)
+        Ok(LlmReply {
+            content: full_response,
+            usage,
+        })
     }
 
     /// Non-streaming generation for document creation
@@ -709,16 +1195,23 @@ impl OllamaClient {
         config: &LLMConfig,
         messages: Vec<ChatMessage>,
         temperature: f64,
-    ) -> Result<String, AppError> {
-        if ProviderKind::from_config(config)? == ProviderKind::OpenAiCompatible {
-            return self.generate_openai(config, messages, temperature).await;
+    ) -> Result<LlmReply, AppError> {
+        match ProviderKind::from_config(config)? {
+            ProviderKind::OpenAiCompatible => {
+                return self.generate_openai(config, messages, temperature).await;
+            }
+            ProviderKind::Anthropic => {
+                return self.generate_anthropic(config, messages, temperature).await;
+            }
+            ProviderKind::Ollama => {}
         }
 
         let base_url = &config.base_url;
         let model = &config.model;
         let url = Self::endpoint(base_url, "/api/chat");
 
-        let response = self
+        ratelimit::acquire(base_url).await;
+        let request = self
             .client
             .post(&url)
             .json(&OllamaChatRequest {
@@ -728,10 +1221,12 @@ impl OllamaClient {
                 options: OllamaOptions {
                     temperature,
                     num_predict: None, // Use Ollama's default for doc generation
+                    seed: config.seed,
                 },
             })
-            .timeout(std::time::Duration::from_secs(300))
-            .send()
+            .timeout(request_timeout(config));
+        let response = self
+            .send_with_retry(config, self.with_headers(request, config))
             .await
             .map_err(|e| AppError::OllamaConnection {
                 url: base_url.to_string(),
@@ -746,10 +1241,7 @@ impl OllamaClient {
                     model: model.to_string(),
                 });
             }
-            return Err(AppError::LlmRequest(format!(
-                "Ollama returned {}: {}",
-                status, body
-            )));
+            return Err(request_error("Ollama", status, &body));
         }
 
         let body: OllamaChatResponse = response
@@ -757,7 +1249,10 @@ impl OllamaClient {
             .await
             .map_err(|e| AppError::LlmRequest(format!("Failed to parse Ollama response: {}", e)))?;
 
-        Ok(body.message.content)
+        Ok(LlmReply {
+            content: body.message.content,
+            usage: UsageStats::from_counts(body.prompt_eval_count, body.eval_count),
+        })
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -770,7 +1265,8 @@ impl OllamaClient {
         max_tokens: Option<u64>,
         session_id: &str,
         cancel: Option<Arc<AtomicBool>>,
-    ) -> Result<String, AppError> {
+    ) -> Result<LlmReply, AppError> {
+        ratelimit::acquire(&config.base_url).await;
         let request = self
             .client
             .post(Self::endpoint(&config.base_url, "/v1/chat/completions"))
@@ -780,11 +1276,14 @@ impl OllamaClient {
                 stream: true,
                 temperature,
                 max_tokens,
+                seed: config.seed,
+                stream_options: config.stream_usage.then_some(OpenAiStreamOptions {
+                    include_usage: true,
+                }),
             })
-            .timeout(Duration::from_secs(300));
+            .timeout(request_timeout(config));
         let response = self
-            .with_auth(request, config.api_key.as_deref())
-            .send()
+            .send_with_retry(config, self.with_auth(request, config))
             .await
             .map_err(|e| AppError::OllamaConnection {
                 url: config.base_url.to_string(),
@@ -799,21 +1298,21 @@ impl OllamaClient {
                     model: config.model.clone(),
                 });
             }
-            return Err(AppError::LlmRequest(format!(
-                "OpenAI-compatible endpoint returned {}: {}",
-                status, body
-            )));
+            return Err(request_error("OpenAI-compatible endpoint", status, &body));
         }
 
         let mut stream = response.bytes_stream();
         let mut full_response = String::new();
         let mut buffer = String::new();
         let mut done = false;
-
-        while let Some(chunk) = timeout(Duration::from_secs(60), stream.next())
-            .await
-            .map_err(|_| AppError::StreamInterrupted)?
-        {
+        // With `stream_usage` the usage chunk arrives after the one carrying
+        // `finish_reason`, so that alone no longer ends the stream.
+        let mut finished = false;
+        let mut usage = None;
+        let timeouts = StreamTimeouts::from_config(config);
+        let mut received_any = false;
+
+        while let Some(chunk) = next_chunk(&mut stream, &mut received_any, timeouts).await? {
             if let Some(flag) = &cancel {
                 if flag.load(Ordering::SeqCst) {
                     let _ = app.emit(
@@ -844,20 +1343,15 @@ impl OllamaClient {
 
                 let data = line.trim_start_matches("data:").trim();
                 if data == "[DONE]" {
-                    let _ = app.emit(
-                        "stream:done",
-                        StreamChunk {
-                            r#type: "done".to_string(),
-                            session_id: Some(session_id.to_string()),
-                            ..Default::default()
-                        },
-                    );
                     done = true;
                     break;
                 }
 
                 match serde_json::from_str::<OpenAiStreamResponse>(data) {
                     Ok(parsed) => {
+                        if parsed.usage.is_some() {
+                            usage = parsed.usage;
+                        }
                         for choice in parsed.choices {
                             if let Some(content) = choice.delta.content {
                                 if !content.is_empty() {
@@ -874,18 +1368,13 @@ impl OllamaClient {
                                 }
                             }
                             if choice.finish_reason.is_some() {
-                                let _ = app.emit(
-                                    "stream:done",
-                                    StreamChunk {
-                                        r#type: "done".to_string(),
-                                        session_id: Some(session_id.to_string()),
-                                        ..Default::default()
-                                    },
-                                );
-                                done = true;
-                                break;
+                                finished = true;
                             }
                         }
+                        if finished && (!config.stream_usage || usage.is_some()) {
+                            done = true;
+                            break;
+                        }
                     }
                     Err(_) => continue,
                 }
@@ -896,7 +1385,7 @@ impl OllamaClient {
             }
         }
 
-        if !done {
+        if !done && !finished {
             if let Some(flag) = &cancel {
                 if flag.load(Ordering::SeqCst) {
                     let _ = app.emit(
@@ -913,7 +1402,20 @@ impl OllamaClient {
             return Err(AppError::StreamInterrupted);
         }
 
-        Ok(full_response)
+        let _ = app.emit(
+            "stream:done",
+            StreamChunk {
+                r#type: "done".to_string(),
+                session_id: Some(session_id.to_string()),
+                usage: usage.clone(),
+                ..Default::default()
+            },
+        );
+
+        Ok(LlmReply {
+            content: full_response,
+            usage,
+        })
     }
 
     async fn generate_openai(
@@ -921,7 +1423,8 @@ impl OllamaClient {
         config: &LLMConfig,
         messages: Vec<ChatMessage>,
         temperature: f64,
-    ) -> Result<String, AppError> {
+    ) -> Result<LlmReply, AppError> {
+        ratelimit::acquire(&config.base_url).await;
         let request = self
             .client
             .post(Self::endpoint(&config.base_url, "/v1/chat/completions"))
@@ -931,11 +1434,12 @@ impl OllamaClient {
                 stream: false,
                 temperature,
                 max_tokens: None,
+                seed: config.seed,
+                stream_options: None,
             })
-            .timeout(Duration::from_secs(300));
+            .timeout(request_timeout(config));
         let response = self
-            .with_auth(request, config.api_key.as_deref())
-            .send()
+            .send_with_retry(config, self.with_auth(request, config))
             .await
             .map_err(|e| AppError::OllamaConnection {
                 url: config.base_url.to_string(),
@@ -950,15 +1454,13 @@ impl OllamaClient {
                     model: config.model.clone(),
                 });
             }
-            return Err(AppError::LlmRequest(format!(
-                "OpenAI-compatible endpoint returned {}: {}",
-                status, body
-            )));
+            return Err(request_error("OpenAI-compatible endpoint", status, &body));
         }
 
         let body: OpenAiChatResponse = response.json().await.map_err(|e| {
             AppError::LlmRequest(format!("Failed to parse OpenAI-compatible response: {}", e))
         })?;
+        let usage = body.usage;
         let content = body
             .choices
             .into_iter()
@@ -972,20 +1474,608 @@ impl OllamaClient {
             ));
         }
 
-        Ok(content)
+        Ok(LlmReply { content, usage })
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    fn anthropic_request(
+        config: &LLMConfig,
+        messages: Vec<ChatMessage>,
+        temperature: f64,
+        max_tokens: Option<u64>,
+        stream: bool,
+    ) -> AnthropicMessagesRequest {
+        let (system, messages) = anthropic_messages(messages);
+        AnthropicMessagesRequest {
+            model: config.model.clone(),
+            system,
+            messages,
+            // Anthropic requires `max_tokens` on every request.
+            max_tokens: max_tokens.unwrap_or(config.max_tokens),
+            temperature: temperature.min(ANTHROPIC_MAX_TEMPERATURE),
+            stream,
+        }
+    }
 
-    #[test]
-    fn provider_kind_accepts_supported_aliases() {
-        assert_eq!(
-            ProviderKind::from_provider("ollama").expect("ollama should parse"),
-            ProviderKind::Ollama
-        );
+    #[allow(clippy::too_many_arguments)]
+    async fn stream_chat_anthropic(
+        &self,
+        app: &tauri::AppHandle,
+        config: &LLMConfig,
+        messages: Vec<ChatMessage>,
+        temperature: f64,
+        max_tokens: Option<u64>,
+        session_id: &str,
+        cancel: Option<Arc<AtomicBool>>,
+    ) -> Result<LlmReply, AppError> {
+        ratelimit::acquire(&config.base_url).await;
+        let request = self
+            .client
+            .post(Self::endpoint(&config.base_url, "/v1/messages"))
+            .json(&Self::anthropic_request(
+                config,
+                messages,
+                temperature,
+                max_tokens,
+                true,
+            ))
+            .timeout(request_timeout(config));
+        let response = self
+            .send_with_retry(config, self.with_auth(request, config))
+            .await
+            .map_err(|e| AppError::OllamaConnection {
+                url: config.base_url.to_string(),
+                message: e.to_string(),
+            })?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            if status == reqwest::StatusCode::NOT_FOUND {
+                return Err(AppError::ModelNotFound {
+                    model: config.model.clone(),
+                });
+            }
+            return Err(request_error("Anthropic API", status, &body));
+        }
+
+        let mut stream = response.bytes_stream();
+        let mut full_response = String::new();
+        let mut buffer = String::new();
+        let mut done = false;
+        let mut usage = UsageStats::default();
+        let timeouts = StreamTimeouts::from_config(config);
+        let mut received_any = false;
+
+        while let Some(chunk) = next_chunk(&mut stream, &mut received_any, timeouts).await? {
+            if let Some(flag) = &cancel {
+                if flag.load(Ordering::SeqCst) {
+                    let _ = app.emit(
+                        "stream:done",
+                        StreamChunk {
+                            r#type: "done".to_string(),
+                            session_id: Some(session_id.to_string()),
+                            ..Default::default()
+                        },
+                    );
+                    return Err(AppError::StreamCancelled);
+                }
+            }
+
+            let chunk = chunk.map_err(|_| AppError::StreamInterrupted)?;
+            buffer.push_str(&String::from_utf8_lossy(&chunk));
+
+            while let Some(newline_pos) = buffer.find('\n') {
+                let line = buffer[..newline_pos].trim().to_string();
+                buffer = buffer[newline_pos + 1..].to_string();
+
+                // `event:` lines repeat the `type` already inside each payload.
+                if !line.starts_with("data:") {
+                    continue;
+                }
+
+                let data = line.trim_start_matches("data:").trim();
+                match serde_json::from_str::<AnthropicStreamEvent>(data) {
+                    Ok(AnthropicStreamEvent::MessageStart { message }) => {
+                        if let Some(reported) = message.usage {
+                            usage.prompt_tokens = reported.input_tokens;
+                        }
+                    }
+                    Ok(AnthropicStreamEvent::ContentBlockDelta { delta }) => {
+                        if let Some(content) = delta.text.filter(|text| !text.is_empty()) {
+                            full_response.push_str(&content);
+                            let _ = app.emit(
+                                "stream:chunk",
+                                StreamChunk {
+                                    r#type: "content".to_string(),
+                                    content: Some(content),
+                                    session_id: Some(session_id.to_string()),
+                                    ..Default::default()
+                                },
+                            );
+                        }
+                    }
+                    Ok(AnthropicStreamEvent::MessageDelta { usage: reported }) => {
+                        if let Some(reported) = reported {
+                            usage.completion_tokens = reported.output_tokens;
+                        }
+                    }
+                    Ok(AnthropicStreamEvent::MessageStop) => {
+                        done = true;
+                        break;
+                    }
+                    Ok(AnthropicStreamEvent::Error { error }) => {
+                        return Err(AppError::LlmRequest(format!(
+                            "Anthropic API error: {}",
+                            error.message
+                        )));
+                    }
+                    Ok(AnthropicStreamEvent::Other) | Err(_) => continue,
+                }
+            }
+
+            if done {
+                break;
+            }
+        }
+
+        if !done {
+            if let Some(flag) = &cancel {
+                if flag.load(Ordering::SeqCst) {
+                    let _ = app.emit(
+                        "stream:done",
+                        StreamChunk {
+                            r#type: "done".to_string(),
+                            session_id: Some(session_id.to_string()),
+                            ..Default::default()
+                        },
+                    );
+                    return Err(AppError::StreamCancelled);
+                }
+            }
+            return Err(AppError::StreamInterrupted);
+        }
+
+        let usage = UsageStats::from_counts(usage.prompt_tokens, usage.completion_tokens);
+        let _ = app.emit(
+            "stream:done",
+            StreamChunk {
+                r#type: "done".to_string(),
+                session_id: Some(session_id.to_string()),
+                usage: usage.clone(),
+                ..Default::default()
+            },
+        );
+
+        Ok(LlmReply {
+            content: full_response,
+            usage,
+        })
+    }
+
+    async fn generate_anthropic(
+        &self,
+        config: &LLMConfig,
+        messages: Vec<ChatMessage>,
+        temperature: f64,
+    ) -> Result<LlmReply, AppError> {
+        ratelimit::acquire(&config.base_url).await;
+        let request = self
+            .client
+            .post(Self::endpoint(&config.base_url, "/v1/messages"))
+            .json(&Self::anthropic_request(
+                config,
+                messages,
+                temperature,
+                None,
+                false,
+            ))
+            .timeout(request_timeout(config));
+        let response = self
+            .send_with_retry(config, self.with_auth(request, config))
+            .await
+            .map_err(|e| AppError::OllamaConnection {
+                url: config.base_url.to_string(),
+                message: e.to_string(),
+            })?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            if status == reqwest::StatusCode::NOT_FOUND {
+                return Err(AppError::ModelNotFound {
+                    model: config.model.clone(),
+                });
+            }
+            return Err(request_error("Anthropic API", status, &body));
+        }
+
+        let body: AnthropicMessagesResponse = response.json().await.map_err(|e| {
+            AppError::LlmRequest(format!("Failed to parse Anthropic response: {}", e))
+        })?;
+        let usage = body.usage.and_then(|reported| {
+            UsageStats::from_counts(reported.input_tokens, reported.output_tokens)
+        });
+        let content: String = body
+            .content
+            .into_iter()
+            .filter_map(|block| block.text)
+            .collect();
+
+        if content.is_empty() {
+            return Err(AppError::LlmRequest(
+                "Anthropic API returned an empty response".to_string(),
+            ));
+        }
+
+        Ok(LlmReply { content, usage })
+    }
+}
+
+fn anthropic_messages(messages: Vec<ChatMessage>) -> (Option<String>, Vec<ChatMessage>) {
+    let mut system_parts = Vec::new();
+    let mut conversation: Vec<ChatMessage> = Vec::new();
+    for message in messages {
+        if message.role == "system" {
+            system_parts.push(message.content);
+            continue;
+        }
+        match conversation.last_mut() {
+            Some(last) if last.role == message.role => {
+                last.content.push_str("\n\n");
+                last.content.push_str(&message.content);
+            }
+            _ => conversation.push(message),
+        }
+    }
+    let system = (!system_parts.is_empty()).then(|| system_parts.join("\n\n"));
+    (system, conversation)
+}
+
+/// A profile that points at a different `base_url` only uses its own `api_key`,
+/// so the active key is never sent to another host.
+fn endpoint_configs(llm: &LLMConfig) -> Vec<(String, LLMConfig)> {
+    let mut active = llm.clone();
+    active.profiles = Vec::new();
+
+    let mut configs = vec![(ACTIVE_ENDPOINT_NAME.to_string(), active.clone())];
+    configs.extend(llm.profiles.iter().map(|profile| {
+        (
+            profile.name.trim().to_string(),
+            resolve_profile(&active, profile),
+        )
+    }));
+    configs
+}
+
+fn resolve_profile(active: &LLMConfig, profile: &LlmProfile) -> LLMConfig {
+    let set = |value: &Option<String>| {
+        value
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    };
+    let mut config = active.clone();
+    if let Some(provider) = set(&profile.provider) {
+        config.provider = provider;
+    }
+    if let Some(model) = set(&profile.model) {
+        config.model = model;
+    }
+    if let Some(base_url) = set(&profile.base_url) {
+        config.base_url = base_url;
+        config.api_key = None;
+    }
+    if let Some(api_key) = set(&profile.api_key) {
+        config.api_key = Some(api_key);
+    }
+    config
+}
+
+/// Ollama keeps partially downloaded layers, so a layer whose first progress
+/// event already has bytes completed is reported as resuming.
+fn pull_progress_status(
+    status: &str,
+    total: Option<u64>,
+    completed: Option<u64>,
+    resumed_layers: &mut HashMap<String, bool>,
+) -> String {
+    let Some(digest) = status.strip_prefix("pulling ") else {
+        return status.to_string();
+    };
+    let resumed =
+        *resumed_layers
+            .entry(digest.to_string())
+            .or_insert_with(|| match (completed, total) {
+                (Some(done), Some(total)) => done > 0 && done < total,
+                _ => false,
+            });
+    if resumed {
+        format!("resuming {}", digest)
+    } else {
+        status.to_string()
+    }
+}
+
+fn parse_context_length(show: &OllamaShowResponse) -> Option<u64> {
+    // An explicit `num_ctx` in the Modelfile is what the runtime actually
+    // allocates, so it wins over the architecture's trained maximum.
+    let num_ctx = show.parameters.as_deref().and_then(|params| {
+        params.lines().find_map(|line| {
+            let mut parts = line.split_whitespace();
+            match (parts.next(), parts.next()) {
+                (Some("num_ctx"), Some(value)) => value.parse::<u64>().ok(),
+                _ => None,
+            }
+        })
+    });
+    if num_ctx.is_some() {
+        return num_ctx;
+    }
+
+    show.model_info.as_ref()?.iter().find_map(|(key, value)| {
+        if key.ends_with(".context_length") {
+            value.as_u64()
+        } else {
+            None
+        }
+    })
+}
+
+const CONTEXT_OVERFLOW_MARKERS: &[&str] = &[
+    "maximum context length",
+    "context length exceeded",
+    "context_length_exceeded",
+    "exceeds the available context size",
+    "exceed context window",
+    "exceeds the context window",
+    "input length exceeds",
+    "prompt is too long",
+    "context the overflows",
+];
+
+const CONTEXT_LIMIT_PREFIXES: &[&str] = &[
+    "maximum context length is",
+    "context length of only",
+    "context window of",
+    "\"n_ctx\":",
+    "n_ctx",
+];
+
+const CONTEXT_REQUESTED_PREFIXES: &[&str] = &[
+    "you requested",
+    "resulted in",
+    "requested tokens (",
+    "prompt is too long:",
+    "keep the first",
+];
+
+fn parse_ollama_stream_line(line: &str) -> Result<Option<OllamaStreamResponse>, AppError> {
+    match serde_json::from_str::<OllamaStreamLine>(line) {
+        Ok(OllamaStreamLine::Chunk(parsed)) => Ok(Some(parsed)),
+        Ok(OllamaStreamLine::Error { error }) => Err(parse_context_overflow(&error)
+            .unwrap_or_else(|| AppError::LlmRequest(format!("Ollama stream error: {}", error)))),
+        Err(_) => Ok(None),
+    }
+}
+
+fn request_error(provider: &str, status: reqwest::StatusCode, body: &str) -> AppError {
+    parse_context_overflow(body).unwrap_or_else(|| {
+        AppError::LlmRequest(format!("{} returned {}: {}", provider, status, body))
+    })
+}
+
+fn parse_context_overflow(body: &str) -> Option<AppError> {
+    let lower = body.to_ascii_lowercase();
+    if !CONTEXT_OVERFLOW_MARKERS
+        .iter()
+        .any(|marker| lower.contains(marker))
+    {
+        return None;
+    }
+
+    Some(AppError::ContextOverflow {
+        limit: number_after_any(&lower, CONTEXT_LIMIT_PREFIXES),
+        requested: number_after_any(&lower, CONTEXT_REQUESTED_PREFIXES),
+    })
+}
+
+fn number_after_any(text: &str, prefixes: &[&str]) -> Option<u64> {
+    prefixes.iter().find_map(|prefix| {
+        let start = text.find(prefix)? + prefix.len();
+        let rest = text[start..].trim_start_matches([' ', '=', ':', '(']);
+        let digits = rest
+            .chars()
+            .take_while(|c| c.is_ascii_digit() || *c == ',')
+            .filter(char::is_ascii_digit)
+            .collect::<String>();
+        digits.parse::<u64>().ok()
+    })
+}
+
+fn clamp_to_context(requested: u64, context_length: Option<u64>) -> u64 {
+    match context_length {
+        Some(length) if length > 0 => requested.min((length / MAX_OUTPUT_CONTEXT_DIVISOR).max(1)),
+        _ => requested,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn delayed_stream(delays_ms: &[u64]) -> impl futures::Stream<Item = u64> + Unpin {
+        Box::pin(
+            futures::stream::iter(delays_ms.to_vec()).then(|delay| async move {
+                tokio::time::sleep(Duration::from_millis(delay)).await;
+                delay
+            }),
+        )
+    }
+
+    async fn drain(delays_ms: &[u64], timeouts: StreamTimeouts) -> Result<Vec<u64>, AppError> {
+        let mut stream = delayed_stream(delays_ms);
+        let mut received_any = false;
+        let mut items = Vec::new();
+        while let Some(item) = next_chunk(&mut stream, &mut received_any, timeouts).await? {
+            items.push(item);
+        }
+        Ok(items)
+    }
+
+    #[tokio::test]
+    async fn slow_first_chunk_uses_the_longer_deadline() {
+        let timeouts = StreamTimeouts {
+            first_chunk: Duration::from_millis(400),
+            idle: Duration::from_millis(100),
+        };
+        let items = drain(&[200, 10, 10], timeouts)
+            .await
+            .expect("slow first chunk within first-token timeout");
+        assert_eq!(items, vec![200, 10, 10]);
+    }
+
+    #[tokio::test]
+    async fn idle_deadline_applies_after_first_chunk() {
+        let timeouts = StreamTimeouts {
+            first_chunk: Duration::from_millis(400),
+            idle: Duration::from_millis(50),
+        };
+        let err = drain(&[10, 200], timeouts)
+            .await
+            .expect_err("gap after first chunk should time out");
+        assert!(matches!(err, AppError::StreamInterrupted));
+
+        let err = drain(&[600], timeouts)
+            .await
+            .expect_err("first chunk past its deadline should time out");
+        assert!(matches!(err, AppError::StreamInterrupted));
+    }
+
+    fn profile(name: &str, base_url: Option<&str>, model: Option<&str>) -> LlmProfile {
+        LlmProfile {
+            name: name.to_string(),
+            provider: None,
+            base_url: base_url.map(str::to_string),
+            model: model.map(str::to_string),
+            api_key: None,
+        }
+    }
+
+    #[test]
+    fn endpoint_configs_resolve_profiles_against_active_settings() {
+        let mut llm = AppConfig::default().llm;
+        llm.api_key = Some("active-key".to_string());
+        llm.profiles = vec![
+            profile(" lmstudio ", Some("http://localhost:1234"), None),
+            profile("bigger", None, Some("qwen3-coder:30b")),
+        ];
+
+        let configs = endpoint_configs(&llm);
+        let names = configs
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["active", "lmstudio", "bigger"]);
+        assert!(configs.iter().all(|(_, config)| config.profiles.is_empty()));
+
+        let lmstudio = &configs[1].1;
+        assert_eq!(lmstudio.base_url, "http://localhost:1234");
+        assert_eq!(lmstudio.model, llm.model);
+        assert_eq!(lmstudio.api_key, None);
+
+        let bigger = &configs[2].1;
+        assert_eq!(bigger.base_url, llm.base_url);
+        assert_eq!(bigger.model, "qwen3-coder:30b");
+        assert_eq!(bigger.api_key.as_deref(), Some("active-key"));
+    }
+
+    #[tokio::test]
+    async fn unreachable_profile_reports_an_error_without_failing_the_batch() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind probe port");
+        let dead_url = format!("http://{}", listener.local_addr().expect("local addr"));
+        drop(listener);
+
+        let mut llm = AppConfig::default().llm;
+        llm.base_url = dead_url.clone();
+        llm.profiles = vec![profile("also-dead", Some(&dead_url), None)];
+
+        let results = OllamaClient::new().check_all_endpoints(&llm).await;
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[1].profile, "also-dead");
+        for health in results {
+            assert!(!health.connected);
+            assert!(!health.model_available);
+            assert!(health.error.is_some());
+        }
+    }
+
+    fn scripted_server(statuses: &'static [u16]) -> (String, std::thread::JoinHandle<()>) {
+        use std::io::{Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind test port");
+        let url = format!("http://{}", listener.local_addr().expect("local addr"));
+        let handle = std::thread::spawn(move || {
+            for status in statuses {
+                let (mut socket, _) = listener.accept().expect("accept request");
+                let mut request = Vec::new();
+                let mut buf = [0u8; 1024];
+                while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                    let n = socket.read(&mut buf).expect("read request");
+                    if n == 0 {
+                        break;
+                    }
+                    request.extend_from_slice(&buf[..n]);
+                }
+                let response = format!(
+                    "HTTP/1.1 {} Scripted\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                    status
+                );
+                socket
+                    .write_all(response.as_bytes())
+                    .expect("write response");
+            }
+        });
+        (url, handle)
+    }
+
+    #[tokio::test]
+    async fn server_errors_are_retried_until_success() {
+        let (url, server) = scripted_server(&[503, 502, 200]);
+        let client = OllamaClient::new();
+        let mut config = AppConfig::default().llm;
+        config.max_retries = 2;
+
+        let response = client
+            .send_with_retry(&config, client.client.get(&url))
+            .await
+            .expect("third attempt should succeed");
+        assert_eq!(response.status(), reqwest::StatusCode::OK);
+        server.join().expect("server thread");
+    }
+
+    #[tokio::test]
+    async fn client_errors_are_not_retried() {
+        // A retry would find the listener closed and end in a connect error.
+        let (url, server) = scripted_server(&[404]);
+        let client = OllamaClient::new();
+        let mut config = AppConfig::default().llm;
+        config.max_retries = 2;
+
+        let response = client
+            .send_with_retry(&config, client.client.get(&url))
+            .await
+            .expect("404 should be returned as is");
+        assert_eq!(response.status(), reqwest::StatusCode::NOT_FOUND);
+        server.join().expect("server thread");
+    }
+
+    #[test]
+    fn provider_kind_accepts_supported_aliases() {
+        assert_eq!(
+            ProviderKind::from_provider("ollama").expect("ollama should parse"),
+            ProviderKind::Ollama
+        );
         assert_eq!(
             ProviderKind::from_provider("openai_compatible")
                 .expect("openai_compatible should parse"),
@@ -1002,10 +2092,311 @@ mod tests {
         );
     }
 
+    #[test]
+    fn configured_headers_are_added_to_outgoing_requests() {
+        let client = OllamaClient::new();
+        let mut config = AppConfig::default().llm;
+        config.api_key = Some("secret".to_string());
+        config
+            .headers
+            .insert("X-Org-Id".to_string(), " acme ".to_string());
+
+        let request = client
+            .with_auth(
+                client.client.get("http://localhost:1234/v1/models"),
+                &config,
+            )
+            .build()
+            .expect("request should build");
+        assert_eq!(request.headers()["x-org-id"], "acme");
+        assert_eq!(request.headers()["authorization"], "Bearer secret");
+
+        let request = client
+            .with_headers(
+                client.client.get("http://localhost:11434/api/tags"),
+                &config,
+            )
+            .build()
+            .expect("request should build");
+        assert_eq!(request.headers()["x-org-id"], "acme");
+        assert!(request.headers().get("authorization").is_none());
+    }
+
+    #[test]
+    fn anthropic_requests_use_api_key_header() {
+        let client = OllamaClient::new();
+        let mut config = AppConfig::default().llm;
+        config.provider = "anthropic".to_string();
+        config.api_key = Some(" sk-ant ".to_string());
+
+        let request = client
+            .with_auth(
+                client.client.get("https://api.anthropic.com/v1/models"),
+                &config,
+            )
+            .build()
+            .expect("request should build");
+        assert_eq!(request.headers()["x-api-key"], "sk-ant");
+        assert_eq!(request.headers()["anthropic-version"], ANTHROPIC_VERSION);
+        assert!(request.headers().get("authorization").is_none());
+    }
+
+    #[test]
+    fn anthropic_messages_lift_system_prompts_and_merge_turns() {
+        let message = |role: &str, content: &str| ChatMessage {
+            role: role.to_string(),
+            content: content.to_string(),
+        };
+        let (system, conversation) = anthropic_messages(vec![
+            message("system", "Be terse."),
+            message("user", "Hi"),
+            message("system", "Context: notes"),
+            message("user", "Plan it"),
+            message("assistant", "Sure"),
+        ]);
+        assert_eq!(system.as_deref(), Some("Be terse.\n\nContext: notes"));
+        assert_eq!(conversation.len(), 2);
+        assert_eq!(conversation[0].content, "Hi\n\nPlan it");
+        assert_eq!(conversation[1].role, "assistant");
+
+        let request = serde_json::to_value(AnthropicMessagesRequest {
+            model: "claude".to_string(),
+            system: None,
+            messages: conversation,
+            max_tokens: 128,
+            temperature: 1.0,
+            stream: true,
+        })
+        .expect("request should serialize");
+        assert!(request.get("system").is_none());
+    }
+
+    #[test]
+    fn anthropic_stream_events_parse() {
+        let delta: AnthropicStreamEvent = serde_json::from_str(
+            r#"{"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"Hi"}}"#,
+        )
+        .expect("delta should parse");
+        assert!(matches!(
+            delta,
+            AnthropicStreamEvent::ContentBlockDelta { delta } if delta.text.as_deref() == Some("Hi")
+        ));
+
+        let usage: AnthropicStreamEvent = serde_json::from_str(
+            r#"{"type":"message_delta","delta":{"stop_reason":"end_turn"},"usage":{"output_tokens":12}}"#,
+        )
+        .expect("message delta should parse");
+        assert!(matches!(
+            usage,
+            AnthropicStreamEvent::MessageDelta {
+                usage: Some(AnthropicUsage {
+                    output_tokens: Some(12),
+                    ..
+                })
+            }
+        ));
+
+        let ping: AnthropicStreamEvent =
+            serde_json::from_str(r#"{"type":"ping"}"#).expect("ping should parse");
+        assert!(matches!(ping, AnthropicStreamEvent::Other));
+    }
+
+    #[test]
+    fn seed_is_sent_only_when_configured() {
+        let ollama = serde_json::to_value(OllamaOptions {
+            temperature: 0.4,
+            num_predict: None,
+            seed: Some(7),
+        })
+        .expect("options should serialize");
+        assert_eq!(ollama["seed"], 7);
+
+        let openai = serde_json::to_value(OpenAiChatRequest {
+            model: "local".to_string(),
+            messages: Vec::new(),
+            stream: false,
+            temperature: 0.4,
+            max_tokens: None,
+            seed: None,
+            stream_options: None,
+        })
+        .expect("request should serialize");
+        assert!(openai.get("seed").is_none());
+        assert!(openai.get("stream_options").is_none());
+    }
+
+    #[test]
+    fn trailing_usage_chunk_is_parsed() {
+        let request = serde_json::to_value(OpenAiChatRequest {
+            model: "local".to_string(),
+            messages: Vec::new(),
+            stream: true,
+            temperature: 0.4,
+            max_tokens: None,
+            seed: None,
+            stream_options: Some(OpenAiStreamOptions {
+                include_usage: true,
+            }),
+        })
+        .expect("request should serialize");
+        assert_eq!(request["stream_options"]["include_usage"], true);
+
+        let content: OpenAiStreamResponse = serde_json::from_str(
+            r#"{"choices":[{"delta":{"content":"Hi"},"finish_reason":"stop"}],"usage":null}"#,
+        )
+        .expect("content chunk should parse");
+        assert!(content.usage.is_none());
+        assert_eq!(content.choices.len(), 1);
+
+        let usage: OpenAiStreamResponse = serde_json::from_str(
+            r#"{"id":"x","choices":[],"usage":{"prompt_tokens":12,"completion_tokens":5,"total_tokens":17}}"#,
+        )
+        .expect("usage chunk should parse");
+        assert!(usage.choices.is_empty());
+        assert_eq!(
+            usage.usage,
+            Some(UsageStats {
+                prompt_tokens: Some(12),
+                completion_tokens: Some(5),
+                total_tokens: Some(17),
+            })
+        );
+    }
+
     #[test]
     fn provider_kind_rejects_unknown_provider() {
         let err = ProviderKind::from_provider("remote_cloud")
             .expect_err("unknown provider should return validation error");
         assert!(matches!(err, AppError::Validation(_)));
     }
+
+    #[test]
+    fn parse_context_length_prefers_modelfile_num_ctx() {
+        let show: OllamaShowResponse = serde_json::from_value(serde_json::json!({
+            "parameters": "stop \"<|im_end|>\"\nnum_ctx 4096",
+            "model_info": { "general.architecture": "qwen2", "qwen2.context_length": 32768 }
+        }))
+        .expect("show response should parse");
+        assert_eq!(parse_context_length(&show), Some(4096));
+    }
+
+    #[test]
+    fn parse_context_length_falls_back_to_model_info() {
+        let show: OllamaShowResponse = serde_json::from_value(serde_json::json!({
+            "model_info": { "general.architecture": "llama", "llama.context_length": 8192 }
+        }))
+        .expect("show response should parse");
+        assert_eq!(parse_context_length(&show), Some(8192));
+
+        let empty: OllamaShowResponse =
+            serde_json::from_value(serde_json::json!({})).expect("empty response should parse");
+        assert_eq!(parse_context_length(&empty), None);
+    }
+
+    #[test]
+    fn clamp_to_context_caps_at_half_the_window() {
+        assert_eq!(clamp_to_context(65536, Some(4096)), 2048);
+        assert_eq!(clamp_to_context(1024, Some(4096)), 1024);
+        assert_eq!(clamp_to_context(65536, None), 65536);
+        assert_eq!(clamp_to_context(65536, Some(0)), 65536);
+    }
+
+    fn overflow_parts(body: &str) -> Option<(Option<u64>, Option<u64>)> {
+        match parse_context_overflow(body)? {
+            AppError::ContextOverflow { limit, requested } => Some((limit, requested)),
+            other => panic!("unexpected error: {other}"),
+        }
+    }
+
+    #[test]
+    fn parses_openai_style_context_overflow() {
+        let body = r#"{"error":{"message":"This model's maximum context length is 8192 tokens. However, you requested 9,500 tokens (8500 in the messages, 1000 in the completion).","type":"invalid_request_error","code":"context_length_exceeded"}}"#;
+        assert_eq!(overflow_parts(body), Some((Some(8192), Some(9500))));
+    }
+
+    #[test]
+    fn parses_llama_cpp_and_lm_studio_overflow() {
+        let llama = r#"{"error":{"code":400,"message":"the request exceeds the available context size, try increasing it","type":"exceed_context_size_error","n_prompt_tokens":5120,"n_ctx":4096}}"#;
+        assert_eq!(overflow_parts(llama), Some((Some(4096), None)));
+
+        let lm_studio = "Trying to keep the first 6000 tokens when context the overflows. However, the model is loaded with context length of only 4096 tokens";
+        assert_eq!(overflow_parts(lm_studio), Some((Some(4096), Some(6000))));
+    }
+
+    #[test]
+    fn detects_unparseable_overflow_and_ignores_other_errors() {
+        assert_eq!(
+            overflow_parts(r#"{"error":"input length exceeds maximum context length"}"#),
+            Some((None, None))
+        );
+        assert!(parse_context_overflow(r#"{"error":"model failed to load"}"#).is_none());
+
+        let generic = request_error("Ollama", reqwest::StatusCode::INTERNAL_SERVER_ERROR, "boom");
+        assert!(matches!(generic, AppError::LlmRequest(message) if message.contains("boom")));
+    }
+
+    #[test]
+    fn ollama_final_stream_line_carries_token_counts() {
+        let last = parse_ollama_stream_line(
+            r#"{"message":{"content":""},"done":true,"prompt_eval_count":26,"eval_count":290}"#,
+        )
+        .expect("final line should parse")
+        .expect("final line should be a chunk");
+        assert_eq!(
+            UsageStats::from_counts(last.prompt_eval_count, last.eval_count),
+            Some(UsageStats {
+                prompt_tokens: Some(26),
+                completion_tokens: Some(290),
+                total_tokens: Some(316),
+            })
+        );
+        assert_eq!(UsageStats::from_counts(None, None), None);
+    }
+
+    #[test]
+    fn ollama_stream_error_line_is_surfaced() {
+        let chunk = parse_ollama_stream_line(r#"{"message":{"content":"Hi"},"done":false}"#)
+            .unwrap()
+            .unwrap();
+        assert_eq!(chunk.message.content, "Hi");
+        assert!(!chunk.done);
+
+        let err = parse_ollama_stream_line(r#"{"error":"model runner has unexpectedly stopped"}"#)
+            .unwrap_err();
+        match err {
+            AppError::LlmRequest(message) => {
+                assert!(message.contains("model runner has unexpectedly stopped"))
+            }
+            other => panic!("expected LlmRequest, got {:?}", other),
+        }
+
+        assert!(parse_ollama_stream_line("not json").unwrap().is_none());
+    }
+
+    #[test]
+    fn pull_progress_reports_resumed_layers() {
+        let mut layers = HashMap::new();
+        assert_eq!(
+            pull_progress_status("pulling aaa", Some(100), Some(0), &mut layers),
+            "pulling aaa"
+        );
+        assert_eq!(
+            pull_progress_status("pulling bbb", Some(100), Some(40), &mut layers),
+            "resuming bbb"
+        );
+        // Later events for a resumed layer keep the resuming label.
+        assert_eq!(
+            pull_progress_status("pulling bbb", Some(100), Some(90), &mut layers),
+            "resuming bbb"
+        );
+        // Fresh layers stay "pulling" as they progress.
+        assert_eq!(
+            pull_progress_status("pulling aaa", Some(100), Some(50), &mut layers),
+            "pulling aaa"
+        );
+        assert_eq!(
+            pull_progress_status("verifying sha256 digest", None, None, &mut layers),
+            "verifying sha256 digest"
+        );
+    }
 }
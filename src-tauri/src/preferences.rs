@@ -0,0 +1,173 @@
+use serde::{Deserialize, Serialize};
+
+use crate::db::Database;
+use crate::error::AppError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KnownPreference {
+    WizardCompleted,
+    FirstSessionCompleted,
+    PendingModelPull,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PreferenceKind {
+    Flag,
+    Text,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreferenceEntry {
+    pub key: String,
+    pub kind: PreferenceKind,
+    pub default_value: Option<String>,
+    pub description: String,
+    pub value: Option<String>,
+}
+
+impl KnownPreference {
+    pub const ALL: &'static [KnownPreference] = &[
+        KnownPreference::WizardCompleted,
+        KnownPreference::FirstSessionCompleted,
+        KnownPreference::PendingModelPull,
+    ];
+
+    pub fn key(self) -> &'static str {
+        match self {
+            KnownPreference::WizardCompleted => "wizard_completed",
+            KnownPreference::FirstSessionCompleted => "first_session_completed",
+            KnownPreference::PendingModelPull => "pending_model_pull",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|pref| pref.key() == key)
+    }
+
+    pub fn kind(self) -> PreferenceKind {
+        match self {
+            KnownPreference::WizardCompleted | KnownPreference::FirstSessionCompleted => {
+                PreferenceKind::Flag
+            }
+            KnownPreference::PendingModelPull => PreferenceKind::Text,
+        }
+    }
+
+    pub fn default_value(self) -> Option<&'static str> {
+        match self.kind() {
+            PreferenceKind::Flag => Some("false"),
+            PreferenceKind::Text => None,
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            KnownPreference::WizardCompleted => "Setup wizard finished or dismissed",
+            KnownPreference::FirstSessionCompleted => {
+                "First planning session finished; hides first-run hints"
+            }
+            KnownPreference::PendingModelPull => {
+                "Model whose download was interrupted, offered for resumption on launch"
+            }
+        }
+    }
+
+    pub fn validate(self, value: &str) -> Result<(), AppError> {
+        if self.kind() == PreferenceKind::Flag && !matches!(value, "true" | "false") {
+            return Err(AppError::Validation(format!(
+                "Preference '{}' must be 'true' or 'false' (got '{}')",
+                self.key(),
+                value
+            )));
+        }
+        Ok(())
+    }
+}
+
+pub fn get_text(db: &Database, pref: KnownPreference) -> Result<Option<String>, rusqlite::Error> {
+    Ok(db
+        .get_preference(pref.key())?
+        .or_else(|| pref.default_value().map(str::to_string)))
+}
+
+pub fn set_text(db: &Database, pref: KnownPreference, value: &str) -> Result<(), rusqlite::Error> {
+    db.set_preference(pref.key(), value)
+}
+
+pub fn clear(db: &Database, pref: KnownPreference) -> Result<(), rusqlite::Error> {
+    db.delete_preference(pref.key())
+}
+
+pub fn list_known(db: &Database) -> Result<Vec<PreferenceEntry>, rusqlite::Error> {
+    KnownPreference::ALL
+        .iter()
+        .map(|pref| {
+            Ok(PreferenceEntry {
+                key: pref.key().to_string(),
+                kind: pref.kind(),
+                default_value: pref.default_value().map(str::to_string),
+                description: pref.description().to_string(),
+                value: db.get_preference(pref.key())?,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_round_trip_and_are_unique() {
+        for pref in KnownPreference::ALL {
+            assert_eq!(KnownPreference::from_key(pref.key()), Some(*pref));
+        }
+        assert_eq!(KnownPreference::from_key("wizard_complete"), None);
+    }
+
+    #[test]
+    fn typed_accessors_use_defaults_until_stored() {
+        let db = Database::new_in_memory().unwrap();
+        assert_eq!(
+            get_text(&db, KnownPreference::WizardCompleted)
+                .unwrap()
+                .as_deref(),
+            Some("false")
+        );
+        db.set_preference("wizard_completed", "true").unwrap();
+        assert_eq!(
+            get_text(&db, KnownPreference::WizardCompleted)
+                .unwrap()
+                .as_deref(),
+            Some("true")
+        );
+
+        assert_eq!(
+            get_text(&db, KnownPreference::PendingModelPull).unwrap(),
+            None
+        );
+        set_text(&db, KnownPreference::PendingModelPull, "qwen3-coder").unwrap();
+        let entries = list_known(&db).unwrap();
+        let pending = entries
+            .iter()
+            .find(|entry| entry.key == "pending_model_pull")
+            .unwrap();
+        assert_eq!(pending.value.as_deref(), Some("qwen3-coder"));
+        assert_eq!(entries.len(), KnownPreference::ALL.len());
+
+        clear(&db, KnownPreference::PendingModelPull).unwrap();
+        assert_eq!(
+            get_text(&db, KnownPreference::PendingModelPull).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn flags_reject_non_boolean_values() {
+        assert!(KnownPreference::WizardCompleted.validate("true").is_ok());
+        assert!(KnownPreference::WizardCompleted.validate("yes").is_err());
+        assert!(KnownPreference::PendingModelPull.validate("yes").is_ok());
+    }
+}
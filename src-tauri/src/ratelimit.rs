@@ -0,0 +1,142 @@
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use crate::types::RateLimitConfig;
+
+const MAX_BURST: u32 = 10;
+
+#[derive(Debug, Default)]
+struct Limiter {
+    config: RateLimitConfig,
+    buckets: HashMap<String, Bucket>,
+}
+
+#[derive(Debug, Clone)]
+struct Bucket {
+    capacity: f64,
+    tokens: f64,
+    refill_per_sec: f64,
+    last_refill: Instant,
+}
+
+impl Bucket {
+    fn new(per_minute: u32, now: Instant) -> Self {
+        let capacity = f64::from(per_minute.clamp(1, MAX_BURST));
+        Self {
+            capacity,
+            tokens: capacity,
+            refill_per_sec: f64::from(per_minute) / 60.0,
+            last_refill: now,
+        }
+    }
+
+    fn try_take(&mut self, now: Instant) -> Result<(), Duration> {
+        let elapsed = now
+            .saturating_duration_since(self.last_refill)
+            .as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        self.last_refill = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64(
+                (1.0 - self.tokens) / self.refill_per_sec,
+            ))
+        }
+    }
+}
+
+fn limiter() -> &'static Mutex<Limiter> {
+    static LIMITER: OnceLock<Mutex<Limiter>> = OnceLock::new();
+    LIMITER.get_or_init(|| Mutex::new(Limiter::default()))
+}
+
+pub fn configure(config: &RateLimitConfig) {
+    if let Ok(mut guard) = limiter().lock() {
+        guard.config = config.clone();
+        guard.buckets.clear();
+    }
+}
+
+pub async fn acquire(url: &str) {
+    let Some(host) = host_key(url) else {
+        return;
+    };
+
+    loop {
+        let wait = {
+            let Ok(mut guard) = limiter().lock() else {
+                return;
+            };
+            let per_minute = limit_for_host(&guard.config, &host);
+            if per_minute == 0 {
+                return;
+            }
+            let now = Instant::now();
+            let bucket = guard
+                .buckets
+                .entry(host.clone())
+                .or_insert_with(|| Bucket::new(per_minute, now));
+            match bucket.try_take(now) {
+                Ok(()) => return,
+                Err(wait) => wait,
+            }
+        };
+        log::debug!("Rate limit reached for {}; waiting {:?}", host, wait);
+        tokio::time::sleep(wait).await;
+    }
+}
+
+fn host_key(url: &str) -> Option<String> {
+    url::Url::parse(url)
+        .ok()?
+        .host_str()
+        .map(|host| host.trim_matches(['[', ']']).to_ascii_lowercase())
+}
+
+fn limit_for_host(config: &RateLimitConfig, host: &str) -> u32 {
+    config
+        .per_host
+        .iter()
+        .find(|(configured, _)| configured.trim().eq_ignore_ascii_case(host))
+        .map(|(_, limit)| *limit)
+        .unwrap_or(config.default_per_minute)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bucket_allows_burst_then_paces() {
+        let start = Instant::now();
+        let mut bucket = Bucket::new(60, start);
+        for _ in 0..MAX_BURST {
+            assert!(bucket.try_take(start).is_ok());
+        }
+        let wait = bucket
+            .try_take(start)
+            .expect_err("burst should be exhausted");
+        assert!(wait <= Duration::from_secs(1));
+
+        assert!(bucket.try_take(start + Duration::from_secs(1)).is_ok());
+    }
+
+    #[test]
+    fn per_host_limit_overrides_default() {
+        let config = RateLimitConfig {
+            default_per_minute: 30,
+            per_host: HashMap::from([("LocalHost".to_string(), 0)]),
+        };
+        assert_eq!(limit_for_host(&config, "localhost"), 0);
+        assert_eq!(limit_for_host(&config, "html.duckduckgo.com"), 30);
+        assert_eq!(
+            host_key("http://LOCALHOST:11434/api/chat").as_deref(),
+            Some("localhost")
+        );
+        assert!(host_key("not a url").is_none());
+    }
+}
@@ -1,19 +1,42 @@
 use scraper::{Html, Selector};
 
-use super::{SearchError, SearchResult};
+use super::{SearchError, SearchResult, PROBE_TIMEOUT};
+use crate::ratelimit;
+
+const DDG_HTML_URL: &str = "https://html.duckduckgo.com/html/";
+
+pub async fn probe(client: &reqwest::Client) -> Result<(), SearchError> {
+    ratelimit::acquire(DDG_HTML_URL).await;
+    let response = client
+        .head(DDG_HTML_URL)
+        .timeout(PROBE_TIMEOUT)
+        .send()
+        .await
+        .map_err(|e| SearchError::NetworkError(e.to_string()))?;
+    if response.status().is_server_error() {
+        return Err(SearchError::NetworkError(format!(
+            "DuckDuckGo returned status {}",
+            response.status()
+        )));
+    }
+    Ok(())
+}
 
 pub async fn search(
     client: &reqwest::Client,
     query: &str,
+    region: Option<&str>,
+    safe_search: Option<&str>,
 ) -> Result<Vec<SearchResult>, SearchError> {
+    ratelimit::acquire(DDG_HTML_URL).await;
     let response = client
-        .post("https://html.duckduckgo.com/html/")
+        .post(DDG_HTML_URL)
         .header("Content-Type", "application/x-www-form-urlencoded")
         .header(
             "User-Agent",
             "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36",
         )
-        .body(format!("q={}", urlencoding(query)))
+        .body(build_form_body(query, region, safe_search))
         .send()
         .await
         .map_err(|e| SearchError::NetworkError(e.to_string()))?;
@@ -33,6 +56,23 @@ pub async fn search(
     parse_results(&html)
 }
 
+fn build_form_body(query: &str, region: Option<&str>, safe_search: Option<&str>) -> String {
+    let mut body = format!("q={}", urlencoding(query));
+    if let Some(region) = region {
+        body.push_str(&format!("&kl={}", urlencoding(region)));
+    }
+    let safe_search = match safe_search {
+        Some("strict") => Some("1"),
+        Some("moderate") => Some("-1"),
+        Some("off") => Some("-2"),
+        _ => None,
+    };
+    if let Some(level) = safe_search {
+        body.push_str(&format!("&kp={}", level));
+    }
+    body
+}
+
 fn urlencoding(s: &str) -> String {
     url::form_urlencoded::byte_serialize(s.as_bytes()).collect()
 }
@@ -47,7 +87,31 @@ const SELECTOR_SETS: &[(&str, &str, &str)] = &[
     (".result", "a.result__url", ".result__snippet"),
 ];
 
+/// Strings only found on DuckDuckGo's bot-check ("anomaly") page, which it
+/// serves with a 200 status in place of results.
+const CHALLENGE_MARKERS: &[&str] = &[
+    "anomaly-modal",
+    "challenge-form",
+    "bots use duckduckgo too",
+    "please complete the following challenge",
+];
+
+fn is_challenge_page(html: &str) -> bool {
+    let lower = html.to_ascii_lowercase();
+    CHALLENGE_MARKERS
+        .iter()
+        .any(|marker| lower.contains(marker))
+}
+
 fn parse_results(html: &str) -> Result<Vec<SearchResult>, SearchError> {
+    if is_challenge_page(html) {
+        log::warn!(
+            "DuckDuckGo served a bot challenge page ({} bytes) instead of results",
+            html.len()
+        );
+        return Err(SearchError::Challenged);
+    }
+
     let document = Html::parse_document(html);
 
     // Try each selector set until one produces results
@@ -200,6 +264,21 @@ fn extract_ddg_url(href: &str) -> Option<String> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn form_body_defaults_to_query_only() {
+        assert_eq!(build_form_body("rust tauri", None, None), "q=rust+tauri");
+    }
+
+    #[test]
+    fn form_body_includes_region_and_safe_search() {
+        let body = build_form_body("sqlite wal", Some("de-de"), Some("strict"));
+        assert_eq!(body, "q=sqlite+wal&kl=de-de&kp=1");
+
+        let body = build_form_body("q", None, Some("off"));
+        assert!(body.ends_with("&kp=-2"));
+        assert!(!body.contains("kl="));
+    }
+
     #[test]
     fn parse_results_with_result_class() {
         let html = r#"
@@ -223,6 +302,28 @@ mod tests {
         assert_eq!(results[1].url, "https://other.com");
     }
 
+    #[test]
+    fn parse_results_reports_challenge_pages_distinctly() {
+        let html = r#"
+        <html><body>
+        <div class="anomaly-modal__mask">
+            <div class="anomaly-modal__modal" data-testid="anomaly-modal">
+                <div class="anomaly-modal__title">Unfortunately, bots use DuckDuckGo too.</div>
+                <div class="anomaly-modal__description">
+                    Please complete the following challenge to confirm this search was made by a human.
+                </div>
+                <form id="challenge-form" action="//duckduckgo.com/anomaly.js" method="POST"></form>
+            </div>
+        </div>
+        </body></html>
+        "#;
+        assert!(matches!(parse_results(html), Err(SearchError::Challenged)));
+        assert!(matches!(
+            parse_results("<html><body><p>No results.</p></body></html>"),
+            Err(SearchError::NoResults)
+        ));
+    }
+
     #[test]
     fn parse_results_fallback_with_uddg_links() {
         // No .result containers, but there are <a> tags with uddg params
@@ -24,7 +24,12 @@ fn search_client() -> &'static Client {
     })
 }
 
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
 const SEARCH_CACHE_TTL_SECS: u64 = 45;
+/// DuckDuckGo keeps challenging a client for a while once it has flagged it,
+/// so it is left alone for this long after serving a challenge page.
+const CHALLENGE_BACKOFF: Duration = Duration::from_secs(300);
 const SEARCH_CACHE_MAX_ENTRIES: usize = 64;
 
 #[derive(Debug, Clone)]
@@ -93,6 +98,8 @@ pub enum SearchError {
     NetworkError(String),
     #[error("Parse error: {0}")]
     ParseError(String),
+    #[error("Search provider served a bot challenge instead of results")]
+    Challenged,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -103,6 +110,25 @@ pub struct SearchResult {
     pub score: f64,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Citation {
+    pub id: String,
+    pub title: String,
+    pub url: String,
+}
+
+pub fn assign_citations(results: &[SearchResult]) -> Vec<Citation> {
+    results
+        .iter()
+        .enumerate()
+        .map(|(index, result)| Citation {
+            id: (index + 1).to_string(),
+            title: result.title.clone(),
+            url: result.url.clone(),
+        })
+        .collect()
+}
+
 pub async fn execute_search(
     config: &SearchConfig,
     query: &str,
@@ -113,7 +139,17 @@ pub async fn execute_search(
     }
 
     let provider = config.provider.trim().to_ascii_lowercase();
-    let key = cache_key(&provider, query);
+    // Region and safe-search change DuckDuckGo results, so they scope the cache.
+    let cache_scope = match (&config.region, &config.safe_search) {
+        (None, None) => provider.clone(),
+        (region, safe_search) => format!(
+            "{}[{}|{}]",
+            provider,
+            region.as_deref().unwrap_or_default(),
+            safe_search.as_deref().unwrap_or_default()
+        ),
+    };
+    let key = cache_key(&cache_scope, query);
     if let Some(cached) = get_cached_results(&key) {
         return Ok(cached);
     }
@@ -127,16 +163,20 @@ pub async fn execute_search(
                 | SearchError::RateLimited
                 | SearchError::NetworkError(_)
                 | SearchError::ParseError(_)
-                | SearchError::NoResults,
+                | SearchError::NoResults
+                | SearchError::Challenged,
             ) => {
                 log::warn!(
                     "Tavily search failed, falling back to DuckDuckGo for query '{}'",
                     query
                 );
-                duckduckgo::search(client, query).await?
+                search_duckduckgo(client, config, query).await?
             }
         },
-        "duckduckgo" => duckduckgo::search(client, query).await?,
+        "duckduckgo" => match search_duckduckgo(client, config, query).await {
+            Err(SearchError::Challenged) => search_after_challenge(client, config, query).await?,
+            other => other?,
+        },
         "searxng" => match searxng::search(client, &config.searxng_url, query).await {
             Ok(results) => results,
             Err(err) => {
@@ -145,7 +185,7 @@ pub async fn execute_search(
                     err,
                     query
                 );
-                duckduckgo::search(client, query).await?
+                search_duckduckgo(client, config, query).await?
             }
         },
         other => {
@@ -158,6 +198,98 @@ pub async fn execute_search(
     Ok(results)
 }
 
+fn challenge_backoff() -> &'static Mutex<Option<Instant>> {
+    static UNTIL: OnceLock<Mutex<Option<Instant>>> = OnceLock::new();
+    UNTIL.get_or_init(|| Mutex::new(None))
+}
+
+async fn search_duckduckgo(
+    client: &Client,
+    config: &SearchConfig,
+    query: &str,
+) -> Result<Vec<SearchResult>, SearchError> {
+    let backing_off = challenge_backoff()
+        .lock()
+        .ok()
+        .and_then(|until| *until)
+        .is_some_and(|until| Instant::now() < until);
+    if backing_off {
+        return Err(SearchError::Challenged);
+    }
+
+    let result = duckduckgo::search(
+        client,
+        query,
+        config.region.as_deref(),
+        config.safe_search.as_deref(),
+    )
+    .await;
+    if matches!(result, Err(SearchError::Challenged)) {
+        log::warn!(
+            "Pausing DuckDuckGo searches for {}s after a challenge page",
+            CHALLENGE_BACKOFF.as_secs()
+        );
+        if let Ok(mut until) = challenge_backoff().lock() {
+            *until = Some(Instant::now() + CHALLENGE_BACKOFF);
+        }
+    }
+    result
+}
+
+async fn search_after_challenge(
+    client: &Client,
+    config: &SearchConfig,
+    query: &str,
+) -> Result<Vec<SearchResult>, SearchError> {
+    if !config.tavily_api_key.trim().is_empty() {
+        log::warn!("DuckDuckGo challenged; trying Tavily for query '{}'", query);
+        return tavily::search(client, &config.tavily_api_key, query).await;
+    }
+    if !config.searxng_url.trim().is_empty() {
+        log::warn!(
+            "DuckDuckGo challenged; trying SearXNG for query '{}'",
+            query
+        );
+        return searxng::search(client, &config.searxng_url, query).await;
+    }
+    Err(SearchError::Challenged)
+}
+
+pub async fn probe_provider(config: &SearchConfig) -> Option<Result<(), SearchError>> {
+    let provider = active_provider(config)?;
+    let client = search_client();
+    Some(match provider.as_str() {
+        "tavily" => tavily::probe(client, &config.tavily_api_key).await,
+        "searxng" => searxng::probe(client, &config.searxng_url).await,
+        "duckduckgo" => duckduckgo::probe(client).await,
+        other => Err(SearchError::NetworkError(format!(
+            "Unknown search provider '{}'",
+            other
+        ))),
+    })
+}
+
+fn active_provider(config: &SearchConfig) -> Option<String> {
+    let provider = config.provider.trim().to_ascii_lowercase();
+    (config.enabled && provider != "none").then_some(provider)
+}
+
+pub fn probe_remediation(provider: &str, err: &SearchError) -> String {
+    match (provider.trim().to_ascii_lowercase().as_str(), err) {
+        ("tavily", SearchError::InvalidApiKey) => {
+            "Tavily API key is missing. Add search.tavily_api_key or switch search.provider to duckduckgo.".to_string()
+        }
+        ("searxng", _) => format!(
+            "SearXNG is unreachable ({}). Check search.searxng_url and that the instance is running.",
+            err
+        ),
+        (provider, _) => format!(
+            "Search provider '{}' is unreachable ({}). Check your network connection or switch search.provider.",
+            provider, err
+        ),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -169,4 +301,35 @@ mod tests {
         assert_eq!(a, b);
         assert_eq!(a, "tavily::how to build");
     }
+
+    #[test]
+    fn probe_is_skipped_when_search_disabled() {
+        let mut config = crate::types::AppConfig::default().search;
+        config.provider = " None ".to_string();
+        assert!(active_provider(&config).is_none());
+
+        config.provider = "DuckDuckGo".to_string();
+        assert_eq!(active_provider(&config).as_deref(), Some("duckduckgo"));
+        config.enabled = false;
+        assert!(active_provider(&config).is_none());
+    }
+
+    #[test]
+    fn probe_remediation_points_at_the_relevant_setting() {
+        let message = probe_remediation("tavily", &SearchError::InvalidApiKey);
+        assert!(message.contains("search.tavily_api_key"));
+
+        let message = probe_remediation(
+            "SearXNG",
+            &SearchError::NetworkError("connection refused".to_string()),
+        );
+        assert!(message.contains("search.searxng_url"));
+        assert!(message.contains("connection refused"));
+
+        let message = probe_remediation(
+            "duckduckgo",
+            &SearchError::NetworkError("timed out".to_string()),
+        );
+        assert!(message.contains("'duckduckgo' is unreachable"));
+    }
 }
@@ -1,6 +1,7 @@
 use serde::Deserialize;
 
-use super::{SearchError, SearchResult};
+use super::{SearchError, SearchResult, PROBE_TIMEOUT};
+use crate::ratelimit;
 
 #[derive(Debug, Deserialize)]
 struct SearxResponse {
@@ -15,6 +16,30 @@ struct SearxResult {
     score: Option<f64>,
 }
 
+pub async fn probe(client: &reqwest::Client, base_url: &str) -> Result<(), SearchError> {
+    if base_url.trim().is_empty() {
+        return Err(SearchError::NetworkError(
+            "SearXNG URL is empty".to_string(),
+        ));
+    }
+
+    let url = format!("{}/", base_url.trim_end_matches('/'));
+    ratelimit::acquire(&url).await;
+    let response = client
+        .get(url)
+        .timeout(PROBE_TIMEOUT)
+        .send()
+        .await
+        .map_err(|e| SearchError::NetworkError(e.to_string()))?;
+    if !response.status().is_success() {
+        return Err(SearchError::NetworkError(format!(
+            "SearXNG returned status {}",
+            response.status()
+        )));
+    }
+    Ok(())
+}
+
 pub async fn search(
     client: &reqwest::Client,
     base_url: &str,
@@ -27,6 +52,7 @@ pub async fn search(
     }
 
     let url = format!("{}/search", base_url.trim_end_matches('/'));
+    ratelimit::acquire(&url).await;
     let response = client
         .get(url)
         .query(&[("q", query), ("format", "json")])
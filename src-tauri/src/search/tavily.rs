@@ -1,6 +1,9 @@
 use serde::{Deserialize, Serialize};
 
-use super::{SearchError, SearchResult};
+use super::{SearchError, SearchResult, PROBE_TIMEOUT};
+use crate::ratelimit;
+
+const TAVILY_SEARCH_URL: &str = "https://api.tavily.com/search";
 
 #[derive(Serialize)]
 struct TavilyRequest<'a> {
@@ -23,6 +26,22 @@ struct TavilyResult {
     score: f64,
 }
 
+/// Tavily has no free ping endpoint, so the key itself is only checked for
+/// presence to avoid spending a search credit.
+pub async fn probe(client: &reqwest::Client, api_key: &str) -> Result<(), SearchError> {
+    if api_key.trim().is_empty() {
+        return Err(SearchError::InvalidApiKey);
+    }
+    ratelimit::acquire(TAVILY_SEARCH_URL).await;
+    client
+        .head(TAVILY_SEARCH_URL)
+        .timeout(PROBE_TIMEOUT)
+        .send()
+        .await
+        .map_err(|e| SearchError::NetworkError(e.to_string()))?;
+    Ok(())
+}
+
 pub async fn search(
     client: &reqwest::Client,
     api_key: &str,
@@ -32,8 +51,9 @@ pub async fn search(
         return Err(SearchError::InvalidApiKey);
     }
 
+    ratelimit::acquire(TAVILY_SEARCH_URL).await;
     let response = client
-        .post("https://api.tavily.com/search")
+        .post(TAVILY_SEARCH_URL)
         .json(&TavilyRequest {
             api_key,
             query,
@@ -1,3 +1,5 @@
+use crate::types::TriggerOverrides;
+
 const TECH_KEYWORDS: &[&str] = &[
     "react",
     "vue",
@@ -74,29 +76,49 @@ const TRIGGER_PATTERNS: &[&str] = &[
     "which is better",
 ];
 
-pub fn should_search(message: &str) -> Option<String> {
+pub fn should_search(message: &str, overrides: &TriggerOverrides) -> Option<String> {
     let lower = message.to_lowercase();
 
-    let has_tech = TECH_KEYWORDS.iter().any(|k| lower.contains(k));
+    if overrides
+        .never
+        .iter()
+        .any(|pattern| pattern_matches(&lower, pattern))
+    {
+        return None;
+    }
+    if overrides
+        .always
+        .iter()
+        .any(|pattern| pattern_matches(&lower, pattern))
+    {
+        return Some(build_search_query(message));
+    }
 
-    for pattern in TRIGGER_PATTERNS {
-        if pattern.contains('*') {
-            let parts: Vec<&str> = pattern.split('*').collect();
-            if parts.len() == 2 {
-                if let Some(start) = lower.find(parts[0]) {
-                    if lower[start..].contains(parts[1]) && has_tech {
-                        return Some(build_search_query(message));
-                    }
-                }
-            }
-        } else if lower.contains(pattern) && has_tech {
-            return Some(build_search_query(message));
-        }
+    let has_tech = TECH_KEYWORDS.iter().any(|k| lower.contains(k));
+    if has_tech
+        && TRIGGER_PATTERNS
+            .iter()
+            .any(|pattern| pattern_matches(&lower, pattern))
+    {
+        return Some(build_search_query(message));
     }
 
     None
 }
 
+fn pattern_matches(lower: &str, pattern: &str) -> bool {
+    let pattern = pattern.to_lowercase();
+    if pattern.trim().is_empty() {
+        return false;
+    }
+    match pattern.split_once('*') {
+        Some((head, tail)) => lower
+            .find(head)
+            .is_some_and(|start| lower[start + head.len()..].contains(tail)),
+        None => lower.contains(&pattern),
+    }
+}
+
 fn build_search_query(message: &str) -> String {
     let lower = message.to_lowercase();
 
@@ -155,78 +177,82 @@ fn extract_comparison_query(lower: &str) -> Option<String> {
 mod tests {
     use super::*;
 
+    fn check(message: &str) -> Option<String> {
+        should_search(message, &TriggerOverrides::default())
+    }
+
     // ---- Trigger Detection ----
 
     #[test]
     fn triggers_on_comparison_with_tech() {
-        let result = should_search("Should I use React vs Vue for my dashboard?");
+        let result = check("Should I use React vs Vue for my dashboard?");
         assert!(result.is_some());
     }
 
     #[test]
     fn triggers_on_best_practice() {
-        let result = should_search("What are best practices for using PostgreSQL?");
+        let result = check("What are best practices for using PostgreSQL?");
         assert!(result.is_some());
     }
 
     #[test]
     fn triggers_on_recommendation() {
-        let result = should_search("Can you recommend an alternative to Firebase?");
+        let result = check("Can you recommend an alternative to Firebase?");
         assert!(result.is_some());
     }
 
     #[test]
     fn triggers_on_how_to_implement() {
-        let result = should_search("How to implement authentication with Next.js?");
+        let result = check("How to implement authentication with Next.js?");
         assert!(result.is_some());
     }
 
     #[test]
     fn triggers_on_maintenance_wildcard() {
         // Wildcard patterns now also require a tech keyword
-        let result = should_search("Is the React router still maintained?");
+        let result = check("Is the React router still maintained?");
         assert!(result.is_some());
     }
 
     #[test]
     fn no_trigger_wildcard_without_tech() {
         // "moment.js" is not in TECH_KEYWORDS, so wildcard alone shouldn't trigger
-        let result = should_search("Is my car still maintained?");
+        let result = check("Is my car still maintained?");
         assert!(result.is_none());
     }
 
     #[test]
     fn triggers_on_compatibility_wildcard() {
-        let result = should_search("Does React work with Electron?");
+        let result = check("Does React work with Electron?");
         assert!(result.is_some());
     }
 
     #[test]
     fn triggers_on_year_freshness() {
-        let result = should_search("Best practices for Docker in 2026");
+        let result = check("Best practices for Docker in 2026");
         assert!(result.is_some());
     }
 
     #[test]
     fn no_trigger_without_tech_keyword() {
-        let result = should_search("What are best practices for cooking pasta?");
+        let result = check("What are best practices for cooking pasta?");
         assert!(result.is_none());
     }
 
     #[test]
     fn no_trigger_without_pattern() {
-        let result = should_search("I like using React for my projects");
+        let result = check("I like using React for my projects");
         assert!(result.is_none());
     }
 
     #[test]
     fn no_trigger_on_empty() {
-        assert!(should_search("").is_none());
+        assert!(check("").is_none());
     }
 
     #[test]
     fn case_insensitive() {
-        let result = should_search("SHOULD I USE REACT VS VUE?");
+        let result = check("SHOULD I USE REACT VS VUE?");
         assert!(result.is_some());
     }
 
@@ -234,14 +260,14 @@ mod tests {
 
     #[test]
     fn comparison_query_extracted() {
-        let result = should_search("Should I use React vs Vue?").unwrap();
+        let result = check("Should I use React vs Vue?").unwrap();
         assert!(result.contains("vs"));
         assert!(result.contains("comparison"));
     }
 
     #[test]
     fn non_comparison_query_uses_message() {
-        let result = should_search("What are best practices for Docker?").unwrap();
+        let result = check("What are best practices for Docker?").unwrap();
         assert!(result.contains("Docker"));
     }
 
@@ -251,7 +277,7 @@ mod tests {
             "What are the best practices for using {} in a large-scale enterprise production environment with complex microservices architecture?",
             "Kubernetes"
         );
-        let result = should_search(&long_msg).unwrap();
+        let result = check(&long_msg).unwrap();
         assert!(result.len() <= 80);
     }
 
@@ -264,7 +290,7 @@ mod tests {
         ];
         for tech in techs {
             let msg = format!("What are best practices for {}?", tech);
-            assert!(should_search(&msg).is_some(), "Failed for: {}", tech);
+            assert!(check(&msg).is_some(), "Failed for: {}", tech);
         }
     }
 
@@ -272,7 +298,7 @@ mod tests {
     fn utf8_multibyte_no_panic() {
         // This previously panicked by slicing mid-character
         let msg = "What are the best practices for using Kubernetes в крупномасштабной корпоративной production среде с микросервисной архитектурой?";
-        let result = should_search(msg);
+        let result = check(msg);
         assert!(result.is_some());
         // Should not exceed 80 chars
         assert!(result.unwrap().chars().count() <= 80);
@@ -293,7 +319,43 @@ mod tests {
             "recommended database in 2026",
         ];
         for p in patterns {
-            assert!(should_search(p).is_some(), "Failed for: {}", p);
+            assert!(check(p).is_some(), "Failed for: {}", p);
         }
     }
+
+    // ---- User Overrides ----
+
+    #[test]
+    fn never_phrase_suppresses_search_that_would_fire() {
+        let message = "Should I use Rust vs Go for the CLI?";
+        assert!(check(message).is_some());
+
+        let overrides = TriggerOverrides {
+            always: Vec::new(),
+            never: vec!["rust".to_string()],
+        };
+        assert!(should_search(message, &overrides).is_none());
+        assert!(should_search("Should I use React vs Vue?", &overrides).is_some());
+    }
+
+    #[test]
+    fn never_wins_over_always() {
+        let overrides = TriggerOverrides {
+            always: vec!["tauri".to_string()],
+            never: vec!["don't * search".to_string()],
+        };
+        assert!(should_search("Tauri updater setup, don't bother to search", &overrides).is_none());
+    }
+
+    #[test]
+    fn always_phrase_fires_without_tech_keyword() {
+        assert!(check("What's the newest espresso grinder?").is_none());
+
+        let overrides = TriggerOverrides {
+            always: vec!["newest * grinder".to_string()],
+            never: Vec::new(),
+        };
+        let query = should_search("What's the newest espresso grinder?", &overrides);
+        assert!(query.is_some_and(|q| q.contains("espresso")));
+    }
 }
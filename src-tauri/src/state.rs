@@ -1,10 +1,13 @@
-use std::collections::HashMap;
-use std::sync::atomic::AtomicBool;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 
+use tokio::sync::Notify;
+
 use crate::db::Database;
 use crate::llm::OllamaClient;
-use crate::types::AppConfig;
+use crate::types::{AppConfig, HousekeepingReport};
 
 pub struct AppState {
     pub db: Database,
@@ -13,4 +16,97 @@ pub struct AppState {
     pub config_error: Mutex<Option<String>>,
     pub db_error: Mutex<Option<String>>,
     pub stream_cancel: Mutex<HashMap<String, Arc<AtomicBool>>>,
+    pub search_cancel: Mutex<HashMap<String, Arc<Notify>>>,
+    pub generation_cancel: Mutex<HashMap<String, Arc<AtomicBool>>>,
+    pub export_locks: ExportLocks,
+    pub stream_slots: StreamSlots,
+    pub housekeeping_report: Mutex<Option<HousekeepingReport>>,
+}
+
+#[derive(Default)]
+pub struct ExportLocks {
+    paths: Mutex<HashSet<PathBuf>>,
+}
+
+impl ExportLocks {
+    pub fn try_acquire(&self, path: &Path) -> Option<ExportLockGuard<'_>> {
+        let mut paths = self.paths.lock().unwrap_or_else(|e| e.into_inner());
+        if !paths.insert(path.to_path_buf()) {
+            return None;
+        }
+        Some(ExportLockGuard {
+            locks: self,
+            path: path.to_path_buf(),
+        })
+    }
+}
+
+pub struct ExportLockGuard<'a> {
+    locks: &'a ExportLocks,
+    path: PathBuf,
+}
+
+impl Drop for ExportLockGuard<'_> {
+    fn drop(&mut self) {
+        let mut paths = self.locks.paths.lock().unwrap_or_else(|e| e.into_inner());
+        paths.remove(&self.path);
+    }
+}
+
+#[derive(Default)]
+pub struct StreamSlots {
+    active: AtomicUsize,
+}
+
+impl StreamSlots {
+    pub fn try_acquire(&self, limit: usize) -> Option<StreamSlotGuard<'_>> {
+        self.active
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |active| {
+                (active < limit).then_some(active + 1)
+            })
+            .ok()?;
+        Some(StreamSlotGuard { slots: self })
+    }
+}
+
+pub struct StreamSlotGuard<'a> {
+    slots: &'a StreamSlots,
+}
+
+impl Drop for StreamSlotGuard<'_> {
+    fn drop(&mut self) {
+        self.slots.active.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overlapping_exports_to_same_path_are_rejected() {
+        let locks = ExportLocks::default();
+        let target = Path::new("/tmp/exports/demo-plan");
+
+        let first = locks.try_acquire(target).expect("first export should lock");
+        assert!(locks.try_acquire(target).is_none());
+        assert!(locks
+            .try_acquire(Path::new("/tmp/exports/other-plan"))
+            .is_some());
+
+        drop(first);
+        assert!(locks.try_acquire(target).is_some());
+    }
+
+    #[test]
+    fn third_concurrent_stream_is_rejected() {
+        let slots = StreamSlots::default();
+
+        let first = slots.try_acquire(2).expect("first stream should start");
+        let _second = slots.try_acquire(2).expect("second stream should start");
+        assert!(slots.try_acquire(2).is_none());
+
+        drop(first);
+        assert!(slots.try_acquire(2).is_some());
+    }
 }
@@ -0,0 +1,87 @@
+//! SQLite's `CURRENT_TIMESTAMP` has no offset, so everything leaving the backend
+//! is normalized to ISO-8601 UTC with an explicit `+00:00` offset.
+
+use chrono::{DateTime, NaiveDateTime, SecondsFormat, Utc};
+
+const NAIVE_FORMATS: &[&str] = &[
+    "%Y-%m-%d %H:%M:%S",
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%Y-%m-%dT%H:%M:%S",
+    "%Y-%m-%dT%H:%M:%S%.f",
+];
+
+pub fn now() -> String {
+    format(Utc::now())
+}
+
+pub fn format(value: DateTime<Utc>) -> String {
+    value.to_rfc3339_opts(SecondsFormat::Secs, false)
+}
+
+pub fn parse(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    if let Ok(parsed) = DateTime::parse_from_rfc3339(value) {
+        return Some(parsed.with_timezone(&Utc));
+    }
+    NAIVE_FORMATS
+        .iter()
+        .find_map(|layout| NaiveDateTime::parse_from_str(value, layout).ok())
+        .map(|naive| naive.and_utc())
+}
+
+pub fn normalize(value: String) -> String {
+    parse(&value).map(format).unwrap_or(value)
+}
+
+pub fn is_after(later: &str, earlier: &str) -> Option<bool> {
+    Some(parse(later)? > parse(earlier)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_sqlite_and_rfc3339_forms_as_the_same_instant() {
+        let expected = parse("2026-03-01T10:15:30+00:00").unwrap();
+        assert_eq!(parse("2026-03-01 10:15:30"), Some(expected));
+        assert_eq!(parse("2026-03-01T10:15:30Z"), Some(expected));
+        assert_eq!(parse("2026-03-01T12:15:30+02:00"), Some(expected));
+        assert_eq!(parse(" 2026-03-01T10:15:30 "), Some(expected));
+        assert!(parse("2026-03-01 10:15:30.250").unwrap() > expected);
+    }
+
+    #[test]
+    fn rejects_unrecognized_values() {
+        assert!(parse("").is_none());
+        assert!(parse("yesterday").is_none());
+        assert!(parse("2026-13-01 00:00:00").is_none());
+    }
+
+    #[test]
+    fn normalize_adds_explicit_utc_offset() {
+        assert_eq!(
+            normalize("2026-03-01 10:15:30".to_string()),
+            "2026-03-01T10:15:30+00:00"
+        );
+        assert_eq!(
+            normalize("2026-03-01T12:15:30+02:00".to_string()),
+            "2026-03-01T10:15:30+00:00"
+        );
+        assert_eq!(normalize("not a time".to_string()), "not a time");
+        assert!(parse(&now()).is_some());
+    }
+
+    #[test]
+    fn is_after_compares_across_offsets() {
+        assert_eq!(
+            is_after("2026-03-01T11:00:00+02:00", "2026-03-01 08:30:00"),
+            Some(true)
+        );
+        assert_eq!(
+            is_after("2026-03-01 08:30:00", "2026-03-01T08:30:00Z"),
+            Some(false)
+        );
+        assert_eq!(is_after("bad", "2026-03-01 08:30:00"), None);
+    }
+}
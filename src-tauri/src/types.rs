@@ -1,4 +1,6 @@
+use crate::error::ErrorResponse;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fmt;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -9,6 +11,8 @@ pub struct Session {
     pub status: String,
     pub created_at: String,
     pub updated_at: String,
+    #[serde(default)]
+    pub completed_at: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,6 +25,20 @@ pub struct Message {
     pub created_at: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionListEntry {
+    #[serde(flatten)]
+    pub session: Session,
+    pub has_documents: bool,
+    pub docs_stale: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionPage {
+    pub sessions: Vec<Session>,
+    pub total: usize,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateSessionRequest {
     pub name: Option<String>,
@@ -39,6 +57,7 @@ pub struct HealthStatus {
     pub ollama_model_available: bool,
     pub database_ok: bool,
     pub config_valid: bool,
+    pub search_ok: Option<bool>,
     pub errors: Vec<String>,
 }
 
@@ -48,6 +67,12 @@ pub struct AppConfig {
     pub search: SearchConfig,
     pub ui: UIConfig,
     pub output: OutputConfig,
+    #[serde(default)]
+    pub rate_limit: RateLimitConfig,
+    #[serde(default)]
+    pub housekeeping: HousekeepingConfig,
+    #[serde(default)]
+    pub import: ImportConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -58,6 +83,63 @@ pub struct LLMConfig {
     pub api_key: Option<String>,
     pub temperature: f64,
     pub max_tokens: u64,
+    #[serde(default = "default_clamp_to_context")]
+    pub clamp_to_context: bool,
+    #[serde(default = "default_request_timeout_secs")]
+    pub request_timeout_secs: u64,
+    #[serde(default = "default_first_token_timeout_secs")]
+    pub first_token_timeout_secs: u64,
+    #[serde(default = "default_stream_idle_timeout_secs")]
+    pub stream_idle_timeout_secs: u64,
+    #[serde(default = "default_context_tokens")]
+    pub context_tokens: u64,
+    #[serde(default = "default_compact_keep_recent_messages")]
+    pub compact_keep_recent_messages: usize,
+    #[serde(default = "default_max_concurrent_streams")]
+    pub max_concurrent_streams: usize,
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+    #[serde(default)]
+    pub seed: Option<i64>,
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    /// Off by default because some servers reject `stream_options.include_usage`.
+    #[serde(default)]
+    pub stream_usage: bool,
+    #[serde(default)]
+    pub profiles: Vec<LlmProfile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LlmProfile {
+    pub name: String,
+    #[serde(default)]
+    pub provider: Option<String>,
+    #[serde(default)]
+    pub base_url: Option<String>,
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub api_key: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EndpointHealth {
+    pub profile: String,
+    pub provider: String,
+    pub base_url: String,
+    pub model: String,
+    pub connected: bool,
+    pub model_available: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenLimitStatus {
+    pub configured: u64,
+    pub context_length: Option<u64>,
+    pub effective: u64,
+    pub clamped: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -67,6 +149,98 @@ pub struct SearchConfig {
     pub tavily_api_key: String,
     pub searxng_url: String,
     pub proactive: bool,
+    #[serde(default)]
+    pub region: Option<String>,
+    #[serde(default)]
+    pub safe_search: Option<String>,
+    #[serde(default)]
+    pub trigger_overrides: TriggerOverrides,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TriggerOverrides {
+    #[serde(default)]
+    pub always: Vec<String>,
+    #[serde(default)]
+    pub never: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RateLimitConfig {
+    #[serde(default)]
+    pub default_per_minute: u32,
+    #[serde(default)]
+    pub per_host: std::collections::HashMap<String, u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HousekeepingConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_housekeeping_max_idle_days")]
+    pub max_idle_days: u32,
+    #[serde(default = "default_housekeeping_action")]
+    pub action: String,
+    #[serde(default)]
+    pub include_with_documents: bool,
+    #[serde(default)]
+    pub include_inactive: bool,
+}
+
+impl Default for HousekeepingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_idle_days: default_housekeeping_max_idle_days(),
+            action: default_housekeeping_action(),
+            include_with_documents: false,
+            include_inactive: false,
+        }
+    }
+}
+
+fn default_housekeeping_max_idle_days() -> u32 {
+    90
+}
+
+fn default_housekeeping_action() -> String {
+    "archive".to_string()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportConfig {
+    #[serde(default = "default_min_snippet_relevance")]
+    pub min_snippet_relevance: f64,
+    #[serde(default)]
+    pub read_workers: usize,
+    #[serde(default = "default_import_read_timeout_secs")]
+    pub read_timeout_secs: u64,
+}
+
+impl Default for ImportConfig {
+    fn default() -> Self {
+        Self {
+            min_snippet_relevance: default_min_snippet_relevance(),
+            read_workers: 0,
+            read_timeout_secs: default_import_read_timeout_secs(),
+        }
+    }
+}
+
+fn default_min_snippet_relevance() -> f64 {
+    0.5
+}
+
+fn default_import_read_timeout_secs() -> u64 {
+    60
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HousekeepingReport {
+    pub action: String,
+    pub max_idle_days: u32,
+    pub sessions: Vec<Session>,
+    pub ran_at: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -81,6 +255,22 @@ pub struct OutputConfig {
     pub default_target: String,
     #[serde(default = "default_lint_mode")]
     pub lint_mode: String,
+    #[serde(default = "default_generation_retries")]
+    pub generation_retries: u32,
+    #[serde(default = "default_doc_validation")]
+    pub doc_validation: String,
+    #[serde(default = "default_export_folder_template")]
+    pub export_folder_template: String,
+    #[serde(default)]
+    pub generation_context_limit: u64,
+    #[serde(default)]
+    pub conversation_generation_details: bool,
+    #[serde(default)]
+    pub frontmatter: bool,
+    #[serde(default = "default_completion_checks")]
+    pub completion_checks: String,
+    #[serde(default = "default_planning_depth")]
+    pub planning_depth: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -92,23 +282,168 @@ pub struct GeneratedDocument {
     pub created_at: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocumentSummary {
+    pub filename: String,
+    pub bytes: usize,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocumentChunk {
+    pub filename: String,
+    pub offset: usize,
+    pub content: String,
+    pub total_chars: usize,
+    pub next_offset: Option<usize>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GenerateDocumentsRequest {
     pub session_id: String,
     pub target: Option<String>,
     pub force: Option<bool>,
+    #[serde(default)]
+    pub quiet: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SaveToFolderRequest {
     pub session_id: String,
     pub folder_path: String,
+    #[serde(default)]
+    pub include_provenance: Option<bool>,
+    #[serde(default)]
+    pub incremental: Option<bool>,
+    #[serde(default)]
+    pub file_order: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForgeAndExportRequest {
+    pub session_id: String,
+    pub folder_path: String,
+    pub target: Option<String>,
+    pub force: Option<bool>,
+    #[serde(default)]
+    pub include_provenance: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForgeAndExportResult {
+    pub documents: Vec<GeneratedDocument>,
+    pub output_path: Option<String>,
+    pub export_error: Option<ErrorResponse>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportManifestFile {
+    pub filename: String,
+    pub bytes: usize,
+    pub lines: usize,
+    pub sha256: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportPreview {
+    pub folder_name: String,
+    pub files: Vec<ExportManifestFile>,
+    pub total_bytes: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IncrementalExportReport {
+    pub session_id: String,
+    pub output_path: String,
+    pub written: Vec<String>,
+    pub skipped: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigSource {
+    Default,
+    File,
+    // Reserved for environment and profile overrides.
+    #[allow(dead_code)]
+    Env,
+    #[allow(dead_code)]
+    Profile,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ConfigSectionSources {
+    pub llm: ConfigSource,
+    pub search: ConfigSource,
+    pub ui: ConfigSource,
+    pub output: ConfigSource,
+    pub rate_limit: ConfigSource,
+    pub housekeeping: ConfigSource,
+    pub import: ConfigSource,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EffectiveConfig {
+    pub config: AppConfig,
+    pub sources: ConfigSectionSources,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ConfigResetField {
+    pub field_path: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigRepairReport {
+    pub source_path: String,
+    pub backup_path: Option<String>,
+    pub recovered: Vec<String>,
+    pub reset: Vec<ConfigResetField>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionCompletion {
+    pub session: Session,
+    pub completed: bool,
+    pub unmet_checks: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BaseUrlSuggestion {
+    pub base_url: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrphanTableCount {
+    pub table: String,
+    pub deleted: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrphanCleanupReport {
+    pub tables: Vec<OrphanTableCount>,
+    pub total: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionSnapshot {
+    pub id: String,
+    pub session_id: String,
+    pub label: String,
+    pub message_count: usize,
+    pub document_count: usize,
+    pub created_at: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ImportCodebaseRequest {
     pub session_id: String,
     pub root_path: String,
+    #[serde(default)]
+    pub update_existing: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -124,6 +459,28 @@ pub struct CreateBranchRequest {
     pub name: Option<String>,
 }
 
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PlanningDepth {
+    Quick,
+    #[default]
+    Standard,
+    Thorough,
+}
+
+impl std::str::FromStr for PlanningDepth {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "quick" => Ok(PlanningDepth::Quick),
+            "standard" => Ok(PlanningDepth::Standard),
+            "thorough" => Ok(PlanningDepth::Thorough),
+            other => Err(format!("Unsupported planning depth: {}", other)),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum ForgeTarget {
@@ -135,6 +492,17 @@ pub enum ForgeTarget {
 }
 
 impl ForgeTarget {
+    pub const ALL: [ForgeTarget; 5] = [
+        ForgeTarget::Claude,
+        ForgeTarget::Codex,
+        ForgeTarget::Cursor,
+        ForgeTarget::Gemini,
+        ForgeTarget::Generic,
+    ];
+
+    pub const AGENT_CONFIG_FILENAMES: [&'static str; 4] =
+        ["CLAUDE.md", "AGENTS.md", ".cursorrules", "GEMINI.md"];
+
     pub fn as_str(&self) -> &'static str {
         match self {
             ForgeTarget::Claude => "claude",
@@ -144,6 +512,15 @@ impl ForgeTarget {
             ForgeTarget::Generic => "generic",
         }
     }
+
+    pub fn agent_config_filename(&self) -> &'static str {
+        match self {
+            ForgeTarget::Claude => "CLAUDE.md",
+            ForgeTarget::Codex | ForgeTarget::Generic => "AGENTS.md",
+            ForgeTarget::Cursor => ".cursorrules",
+            ForgeTarget::Gemini => "GEMINI.md",
+        }
+    }
 }
 
 impl fmt::Display for ForgeTarget {
@@ -167,18 +544,126 @@ impl std::str::FromStr for ForgeTarget {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocumentTargetOption {
+    pub target: ForgeTarget,
+    pub agent_config_file: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocumentTargets {
+    pub current_target: Option<String>,
+    pub agent_config_file: Option<String>,
+    pub targets: Vec<DocumentTargetOption>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DocumentSource {
+    Llm,
+    Template,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocumentCatalogEntry {
+    pub filename: String,
+    pub purpose: String,
+    pub source: DocumentSource,
+    pub required: bool,
+    pub export_rank: usize,
+    pub targets: Vec<ForgeTarget>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForgeTargetSuggestion {
+    pub target: ForgeTarget,
+    pub agent_config_file: String,
+    pub dominant_stack: Option<String>,
+    pub rationale: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PlanFolderImport {
+    pub session: Session,
+    pub documents: Vec<GeneratedDocument>,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, PartialEq, Eq)]
+pub struct SessionTokenUsage {
+    pub session_id: String,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub total_tokens: u64,
+    pub messages_counted: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionMessagesSnapshot {
+    pub session_id: String,
+    pub messages: Vec<Message>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchContextInjected {
+    pub session_id: String,
+    pub query: String,
+    pub result_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HistoryTruncated {
+    pub session_id: String,
+    pub dropped_messages: usize,
+    pub context_tokens: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct GenerationDrafts {
+    pub run_id: String,
+    pub documents: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IncompleteGeneration {
+    pub session_id: String,
+    pub completed: Vec<String>,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct GenerateProgress {
     pub current: usize,
     pub total: usize,
     pub filename: String,
     pub session_id: String,
+    pub elapsed_ms: u64,
+    pub previous: Option<DocumentTiming>,
 }
 
 #[derive(Debug, Clone, Serialize)]
 pub struct GenerateComplete {
     pub session_id: String,
     pub count: usize,
+    pub timing: GenerationTiming,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GenerateCancelled {
+    pub session_id: String,
+    pub documents: Vec<GeneratedDocument>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocumentTiming {
+    pub filename: String,
+    pub duration_ms: u64,
+    pub attempts: u32,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GenerationTiming {
+    pub total_ms: u64,
+    pub documents: Vec<DocumentTiming>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -199,6 +684,7 @@ pub enum CoverageStatus {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CoverageTopic {
+    pub id: String,
     pub topic: String,
     pub status: CoverageStatus,
     pub evidence_message_ids: Vec<String>,
@@ -213,6 +699,109 @@ pub struct CoverageReport {
     pub summary: String,
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TopicPriority {
+    MustHave,
+    ShouldHave,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadinessItem {
+    pub id: String,
+    pub topic: String,
+    pub priority: TopicPriority,
+    pub status: CoverageStatus,
+    pub evidence_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadinessChecklist {
+    pub items: Vec<ReadinessItem>,
+    pub projected_score: u8,
+    pub can_forge_cleanly: bool,
+    pub summary: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct ContentSearchRow {
+    pub session_id: String,
+    pub session_name: String,
+    pub is_document: bool,
+    pub source_id: String,
+    pub label: String,
+    pub content: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TechConfidence {
+    Stated,
+    Inferred,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TechChoice {
+    pub name: String,
+    pub version: Option<String>,
+    pub layer: String,
+    pub confidence: TechConfidence,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum DecisionConfidence {
+    Low,
+    Medium,
+    High,
+}
+
+impl DecisionConfidence {
+    pub fn downgraded(self) -> Self {
+        match self {
+            DecisionConfidence::High => DecisionConfidence::Medium,
+            DecisionConfidence::Medium | DecisionConfidence::Low => DecisionConfidence::Low,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ConversationLanguage {
+    pub code: String,
+    pub name: String,
+    pub is_english: bool,
+    pub reliable: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecisionEntry {
+    pub message_id: String,
+    pub created_at: String,
+    pub decision_text: String,
+    pub confidence: DecisionConfidence,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RequirementStatus {
+    Pending,
+    Done,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Requirement {
+    pub feature: String,
+    pub criterion: String,
+    pub status: RequirementStatus,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpenQuestion {
+    pub filename: String,
+    pub question: String,
+    pub recommendation: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConfidenceFactor {
     pub name: String,
@@ -238,9 +827,22 @@ pub struct GenerationMetadata {
     pub run_id: Option<String>,
     pub quality_json: Option<String>,
     pub confidence_json: Option<String>,
+    #[serde(default)]
+    pub warnings_json: Option<String>,
+    #[serde(default)]
+    pub timing_json: Option<String>,
+    #[serde(default)]
+    pub seed: Option<i64>,
     pub created_at: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GenerationWarning {
+    pub filename: String,
+    pub reason: String,
+    pub attempts: u32,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GenerationRunRecord {
     pub run_id: String,
@@ -271,6 +873,8 @@ pub struct PlanningTemplate {
     pub target_stack: String,
     pub version: u8,
     pub recommended_target: Option<String>,
+    #[serde(default)]
+    pub recommended_depth: Option<PlanningDepth>,
     pub required_sections: Option<Vec<String>>,
     pub verification_focus: Option<Vec<String>>,
     pub seed_prompt: String,
@@ -305,10 +909,62 @@ pub struct CodebaseImportSummary {
     pub citations: Vec<RepoCitation>,
 }
 
+fn default_clamp_to_context() -> bool {
+    true
+}
+
 fn default_lint_mode() -> String {
     "fail_on_critical".to_string()
 }
 
+fn default_request_timeout_secs() -> u64 {
+    300
+}
+
+fn default_first_token_timeout_secs() -> u64 {
+    300
+}
+
+fn default_stream_idle_timeout_secs() -> u64 {
+    60
+}
+
+fn default_context_tokens() -> u64 {
+    32768
+}
+
+fn default_compact_keep_recent_messages() -> usize {
+    20
+}
+
+fn default_max_concurrent_streams() -> usize {
+    2
+}
+
+fn default_max_retries() -> u32 {
+    2
+}
+
+fn default_generation_retries() -> u32 {
+    1
+}
+
+fn default_doc_validation() -> String {
+    "strict".to_string()
+}
+
+fn default_completion_checks() -> String {
+    "lenient".to_string()
+}
+
+fn default_planning_depth() -> String {
+    "standard".to_string()
+}
+
+pub fn default_export_folder_template() -> String {
+    "{name}-plan".to_string()
+}
+
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
@@ -319,6 +975,18 @@ impl Default for AppConfig {
                 api_key: None,
                 temperature: 0.7,
                 max_tokens: 65536,
+                clamp_to_context: true,
+                request_timeout_secs: default_request_timeout_secs(),
+                first_token_timeout_secs: default_first_token_timeout_secs(),
+                stream_idle_timeout_secs: default_stream_idle_timeout_secs(),
+                context_tokens: default_context_tokens(),
+                compact_keep_recent_messages: default_compact_keep_recent_messages(),
+                max_concurrent_streams: default_max_concurrent_streams(),
+                max_retries: default_max_retries(),
+                seed: None,
+                headers: HashMap::new(),
+                stream_usage: false,
+                profiles: Vec::new(),
             },
             search: SearchConfig {
                 enabled: true,
@@ -326,6 +994,9 @@ impl Default for AppConfig {
                 tavily_api_key: String::new(),
                 searxng_url: String::new(),
                 proactive: true,
+                region: None,
+                safe_search: None,
+                trigger_overrides: TriggerOverrides::default(),
             },
             ui: UIConfig {
                 theme: "dark".to_string(),
@@ -335,7 +1006,18 @@ impl Default for AppConfig {
                 default_save_path: "~/Projects".to_string(),
                 default_target: "generic".to_string(),
                 lint_mode: "fail_on_critical".to_string(),
+                generation_retries: 1,
+                doc_validation: "strict".to_string(),
+                export_folder_template: default_export_folder_template(),
+                generation_context_limit: 0,
+                conversation_generation_details: false,
+                frontmatter: false,
+                completion_checks: default_completion_checks(),
+                planning_depth: default_planning_depth(),
             },
+            rate_limit: RateLimitConfig::default(),
+            housekeeping: HousekeepingConfig::default(),
+            import: ImportConfig::default(),
         }
     }
 }
@@ -3,7 +3,7 @@ import { clsx } from "clsx";
 import ReactMarkdown from "react-markdown";
 import remarkGfm from "remark-gfm";
 import { openUrl as openExternal } from "@tauri-apps/plugin-opener";
-import type { Message } from "../types";
+import type { Message, MessageMetadata } from "../types";
 
 interface ChatMessageProps {
   message: Message;
@@ -34,6 +34,28 @@ const markdownComponents = {
   },
 };
 
+/** Appends footnote definitions for the citation markers a reply uses, from
+ *  the citations stored in its metadata, so they render as source links. */
+function withCitationFootnotes(message: Message): string {
+  const raw: unknown = message.metadata;
+  let meta: MessageMetadata | null = null;
+  if (typeof raw === "string") {
+    try {
+      meta = JSON.parse(raw) as MessageMetadata;
+    } catch {
+      meta = null;
+    }
+  } else {
+    meta = raw as MessageMetadata | null;
+  }
+  const definitions = (meta?.citations ?? [])
+    .filter((citation) => message.content.includes(`[^${citation.id}]`))
+    .map((citation) => `[^${citation.id}]: [${citation.title}](${citation.url})`);
+  return definitions.length > 0
+    ? `${message.content}\n\n${definitions.join("\n")}`
+    : message.content;
+}
+
 export const ChatMessage = memo(
   function ChatMessage({ message, onBranch }: ChatMessageProps) {
   const isUser = message.role === "user";
@@ -71,7 +93,7 @@ export const ChatMessage = memo(
             remarkPlugins={[remarkGfm]}
             components={markdownComponents}
           >
-            {message.content}
+            {withCitationFootnotes(message)}
           </ReactMarkdown>
         )}
       </div>
@@ -15,6 +15,9 @@ const TAB_ORDER = [
   "README.md",
   "SPEC.md",
   "CLAUDE.md",
+  "AGENTS.md",
+  "GEMINI.md",
+  ".cursorrules",
   "PROMPTS.md",
   "MODEL_HANDOFF.md",
   "CONVERSATION.md",
@@ -204,6 +204,7 @@ export function SettingsPanel({ open, onClose }: SettingsPanelProps) {
                 >
                   <option value="ollama">Ollama</option>
                   <option value="openai_compatible">OpenAI-compatible (local)</option>
+                  <option value="anthropic">Anthropic (cloud)</option>
                 </select>
               </div>
 
@@ -246,22 +247,28 @@ export function SettingsPanel({ open, onClose }: SettingsPanelProps) {
                   placeholder={
                     provider === "ollama"
                       ? "http://localhost:11434"
-                      : "http://localhost:1234"
+                      : provider === "anthropic"
+                        ? "https://api.anthropic.com"
+                        : "http://localhost:1234"
                   }
                   className="w-full px-3 py-2 bg-surface border border-border-default rounded-lg text-sm text-text-primary placeholder:text-text-muted focus:outline-none focus:border-accent-glow focus:shadow-[0_0_0_3px_rgba(232,160,69,0.15)] transition-colors font-mono text-[13px]"
                 />
               </div>
 
-              {provider === "openai_compatible" && (
+              {provider !== "ollama" && (
                 <div>
                   <label className="block text-sm text-text-secondary mb-1.5">
-                    API Key (optional)
+                    {provider === "anthropic" ? "API Key" : "API Key (optional)"}
                   </label>
                   <input
                     type="password"
                     value={apiKey}
                     onChange={(e) => setApiKey(e.target.value)}
-                    placeholder="Leave empty for keyless local endpoints"
+                    placeholder={
+                      provider === "anthropic"
+                        ? "sk-ant-..."
+                        : "Leave empty for keyless local endpoints"
+                    }
                     className="w-full px-3 py-2 bg-surface border border-border-default rounded-lg text-sm text-text-primary placeholder:text-text-muted focus:outline-none focus:border-accent-glow focus:shadow-[0_0_0_3px_rgba(232,160,69,0.15)] transition-colors font-mono text-[13px]"
                   />
                 </div>
@@ -347,7 +354,8 @@ export function SettingsPanel({ open, onClose }: SettingsPanelProps) {
                   className="w-full px-3 py-2 bg-surface border border-border-default rounded-lg text-sm text-text-primary focus:outline-none focus:border-accent-glow focus:shadow-[0_0_0_3px_rgba(232,160,69,0.15)] transition-colors"
                 >
                   <option value="fail_on_critical">Fail on critical issues</option>
-                  <option value="warn">Warn only</option>
+                  <option value="warn_only">Warn only</option>
+                  <option value="off">Off</option>
                 </select>
               </div>
 
@@ -416,6 +424,7 @@ export function SettingsPanel({ open, onClose }: SettingsPanelProps) {
                     >
                       <option value="ollama">Ollama</option>
                       <option value="openai_compatible">OpenAI-compatible (local)</option>
+                      <option value="anthropic">Anthropic (cloud)</option>
                     </select>
                   </div>
 
@@ -443,22 +452,28 @@ export function SettingsPanel({ open, onClose }: SettingsPanelProps) {
                       placeholder={
                         provider === "ollama"
                           ? "http://localhost:11434"
-                          : "http://localhost:1234"
+                          : provider === "anthropic"
+                            ? "https://api.anthropic.com"
+                            : "http://localhost:1234"
                       }
                       className="w-full px-3 py-2 bg-surface border border-border-default rounded-lg text-sm text-text-primary placeholder:text-text-muted focus:outline-none focus:border-accent-glow focus:shadow-[0_0_0_3px_rgba(232,160,69,0.15)] transition-colors font-mono text-[13px]"
                     />
                   </div>
 
-                  {provider === "openai_compatible" && (
+                  {provider !== "ollama" && (
                     <div>
                       <label className="block text-sm text-text-secondary mb-1.5">
-                        API Key (optional)
+                        {provider === "anthropic" ? "API Key" : "API Key (optional)"}
                       </label>
                       <input
                         type="password"
                         value={apiKey}
                         onChange={(e) => setApiKey(e.target.value)}
-                        placeholder="Leave empty for keyless local endpoints"
+                        placeholder={
+                          provider === "anthropic"
+                            ? "sk-ant-..."
+                            : "Leave empty for keyless local endpoints"
+                        }
                         className="w-full px-3 py-2 bg-surface border border-border-default rounded-lg text-sm text-text-primary placeholder:text-text-muted focus:outline-none focus:border-accent-glow focus:shadow-[0_0_0_3px_rgba(232,160,69,0.15)] transition-colors font-mono text-[13px]"
                       />
                     </div>
@@ -648,7 +663,8 @@ export function SettingsPanel({ open, onClose }: SettingsPanelProps) {
                       className="w-full px-3 py-2 bg-surface border border-border-default rounded-lg text-sm text-text-primary focus:outline-none focus:border-accent-glow focus:shadow-[0_0_0_3px_rgba(232,160,69,0.15)] transition-colors"
                     >
                       <option value="fail_on_critical">Fail on critical issues</option>
-                      <option value="warn">Warn only</option>
+                      <option value="warn_only">Warn only</option>
+                      <option value="off">Off</option>
                     </select>
                   </div>
                 </div>
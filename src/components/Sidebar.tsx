@@ -167,7 +167,7 @@ export function Sidebar() {
 
   const formatTimestamp = (ts: string) => {
     try {
-      const date = new Date(ts + "Z");
+      const date = new Date(ts);
       const now = new Date();
       const diffMs = now.getTime() - date.getTime();
       const diffMins = Math.floor(diffMs / 60000);
@@ -314,7 +314,7 @@ export function Sidebar() {
               <div className="px-3 pb-3 space-y-2">
                 <div className="space-y-1">
                   {planningCoverage.must_have.map((topic) => (
-                    <div key={topic.topic} className="flex items-start gap-2">
+                    <div key={topic.id} className="flex items-start gap-2">
                       <span
                         className={clsx(
                           "mt-0.5 px-1.5 py-0.5 rounded border text-[10px] font-semibold uppercase tracking-wide",
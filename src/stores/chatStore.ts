@@ -1242,6 +1242,19 @@ export const useChatStore = create<ChatState>((set, get) => {
     });
     unlisteners.push(unlMenu);
 
+    // Keep File > Save to Folder enabled only while the session has documents.
+    let menuStateKey: string | null = null;
+    const syncMenuState = (state: ChatState) => {
+      const key = `${state.currentSessionId ?? ""}:${state.documents.length}`;
+      if (key === menuStateKey) return;
+      menuStateKey = key;
+      invoke<boolean>("can_save_to_folder", {
+        session_id: state.currentSessionId,
+      }).catch(() => {});
+    };
+    syncMenuState(get());
+    unlisteners.push(useChatStore.subscribe(syncMenuState));
+
     set({ _unlisteners: unlisteners });
   },
 
@@ -6,6 +6,17 @@ export interface Session {
   status: 'active' | 'completed' | 'archived';
   created_at: string;
   updated_at: string;
+  completed_at?: string | null;
+}
+
+export interface SessionListEntry extends Session {
+  has_documents: boolean;
+  docs_stale: boolean;
+}
+
+export interface SessionPage {
+  sessions: Session[];
+  total: number;
 }
 
 export interface CreateSessionRequest {
@@ -19,6 +30,7 @@ export interface PlanningTemplate {
   target_stack: string;
   version: number;
   recommended_target?: ForgeTarget | string;
+  recommended_depth?: PlanningDepth | null;
   required_sections?: string[];
   verification_focus?: string[];
   seed_prompt: string;
@@ -70,7 +82,11 @@ export interface Message {
 export interface MessageMetadata {
   search_query?: string;
   search_results?: SearchResult[];
+  citations?: Citation[];
   model_used?: string;
+  provider?: string;
+  temperature?: number;
+  search_used?: boolean;
   tokens_used?: number;
 }
 
@@ -81,6 +97,13 @@ export interface SearchResult {
   score: number;
 }
 
+/** Footnote id a reply uses to cite a search result, e.g. `1` for `[^1]`. */
+export interface Citation {
+  id: string;
+  title: string;
+  url: string;
+}
+
 // Chat types
 export interface SendMessageRequest {
   session_id: string;
@@ -88,6 +111,12 @@ export interface SendMessageRequest {
   retry?: boolean;
 }
 
+/** One entry of the request sent to the model, as returned by `preview_chat_request`. */
+export interface ChatRequestMessage {
+  role: 'system' | 'user' | 'assistant';
+  content: string;
+}
+
 export interface StreamChunk {
   type: 'content' | 'search_start' | 'search_result' | 'error' | 'done';
   content?: string;
@@ -95,6 +124,13 @@ export interface StreamChunk {
   search_results?: SearchResult[];
   error?: string;
   session_id?: string;
+  usage?: UsageStats | null;
+}
+
+export interface UsageStats {
+  prompt_tokens: number | null;
+  completion_tokens: number | null;
+  total_tokens: number | null;
 }
 
 // Document types
@@ -106,49 +142,319 @@ export interface GeneratedDocument {
   created_at: string;
 }
 
+export interface DocumentSummary {
+  filename: string;
+  bytes: number;
+  created_at: string;
+}
+
+export interface DocumentChunk {
+  filename: string;
+  offset: number;
+  content: string;
+  total_chars: number;
+  next_offset: number | null;
+}
+
+export interface SearchContextInjected {
+  session_id: string;
+  query: string;
+  result_count: number;
+}
+
+export interface PlanFolderImport {
+  session: Session;
+  documents: GeneratedDocument[];
+  warnings: string[];
+}
+
+export interface SessionTokenUsage {
+  session_id: string;
+  prompt_tokens: number;
+  completion_tokens: number;
+  total_tokens: number;
+  messages_counted: number;
+}
+
+export interface HistoryTruncated {
+  session_id: string;
+  dropped_messages: number;
+  context_tokens: number;
+}
+
 export interface GenerateProgress {
   current: number;
   total: number;
   filename: string;
   session_id: string;
+  elapsed_ms: number;
+  previous: DocumentTiming | null;
 }
 
 export interface GenerateComplete {
   session_id: string;
   count: number;
+  timing: GenerationTiming;
+}
+
+export type ArtifactDiffStatus = 'added' | 'removed' | 'changed' | 'unchanged';
+
+export interface ArtifactDiffEntry {
+  filename: string;
+  status: ArtifactDiffStatus;
+  lines_added: number;
+  lines_removed: number;
+}
+
+export interface ArtifactDiffReport {
+  added: number;
+  removed: number;
+  changed: number;
+  unchanged: number;
+  entries: ArtifactDiffEntry[];
+}
+
+export interface IncompleteGeneration {
+  session_id: string;
+  completed: string[];
+}
+
+export interface DocumentTiming {
+  filename: string;
+  duration_ms: number;
+  attempts: number;
+}
+
+export interface GenerationTiming {
+  total_ms: number;
+  documents: DocumentTiming[];
 }
 
 export interface GenerateDocumentsRequest {
   session_id: string;
   target?: ForgeTarget;
   force?: boolean;
+  quiet?: boolean;
 }
 
 export interface SaveToFolderRequest {
   session_id: string;
   folder_path: string;
+  include_provenance?: boolean;
+  incremental?: boolean;
+  file_order?: string[];
+}
+
+export interface IncrementalExportReport {
+  session_id: string;
+  output_path: string;
+  written: string[];
+  skipped: string[];
+  removed: string[];
+}
+
+export interface SessionMessagesSnapshot {
+  session_id: string;
+  messages: Message[];
+}
+
+export interface DocumentTargetOption {
+  target: ForgeTarget;
+  agent_config_file: string;
+}
+
+export type DocumentSource = 'llm' | 'template';
+
+export interface DocumentCatalogEntry {
+  filename: string;
+  purpose: string;
+  source: DocumentSource;
+  required: boolean;
+  export_rank: number;
+  targets: ForgeTarget[];
+}
+
+export interface DocumentTargets {
+  current_target: ForgeTarget | string | null;
+  agent_config_file: string | null;
+  targets: DocumentTargetOption[];
+}
+
+export interface ForgeTargetSuggestion {
+  target: ForgeTarget;
+  agent_config_file: string;
+  dominant_stack: string | null;
+  rationale: string;
+}
+
+export interface ForgeAndExportRequest {
+  session_id: string;
+  folder_path: string;
+  target?: ForgeTarget;
+  force?: boolean;
+  include_provenance?: boolean;
+}
+
+export interface ForgeAndExportResult {
+  documents: GeneratedDocument[];
+  output_path: string | null;
+  export_error: ErrorResponse | null;
+}
+
+export interface ExportManifestFile {
+  filename: string;
+  bytes: number;
+  lines: number;
+  sha256: string;
+}
+
+export interface ExportPreview {
+  folder_name: string;
+  files: ExportManifestFile[];
+  total_bytes: number;
+}
+
+export type ConfigSource = 'default' | 'file' | 'env' | 'profile';
+
+export interface ConfigSectionSources {
+  llm: ConfigSource;
+  search: ConfigSource;
+  ui: ConfigSource;
+  output: ConfigSource;
+  rate_limit: ConfigSource;
+  housekeeping: ConfigSource;
+  import: ConfigSource;
+}
+
+export interface EffectiveConfig {
+  config: AppConfig;
+  sources: ConfigSectionSources;
+}
+
+export interface ConfigResetField {
+  field_path: string;
+  reason: string;
+}
+
+export interface ConfigRepairReport {
+  source_path: string;
+  backup_path: string | null;
+  recovered: string[];
+  reset: ConfigResetField[];
+}
+
+export interface SessionCompletion {
+  session: Session;
+  completed: boolean;
+  unmet_checks: string[];
+}
+
+export interface BaseUrlSuggestion {
+  base_url: string;
+  reason: string;
+}
+
+export interface OrphanTableCount {
+  table: string;
+  deleted: number;
+}
+
+export interface OrphanCleanupReport {
+  tables: OrphanTableCount[];
+  total: number;
+}
+
+export interface SessionSnapshot {
+  id: string;
+  session_id: string;
+  label: string;
+  message_count: number;
+  document_count: number;
+  created_at: string;
 }
 
 export interface ImportCodebaseRequest {
   session_id: string;
   root_path: string;
+  update_existing?: boolean;
 }
 
 // Config types
+export interface RateLimitConfig {
+  default_per_minute: number;
+  per_host: Record<string, number>;
+}
+
+export interface HousekeepingConfig {
+  enabled: boolean;
+  max_idle_days: number;
+  action: 'archive' | 'delete';
+  include_with_documents: boolean;
+  include_inactive: boolean;
+}
+
+export interface ImportConfig {
+  min_snippet_relevance: number;
+  read_workers?: number;
+  read_timeout_secs?: number;
+}
+
+export interface HousekeepingReport {
+  action: 'archive' | 'delete';
+  max_idle_days: number;
+  sessions: Session[];
+  ran_at: string;
+}
+
 export interface AppConfig {
   llm: LLMConfig;
   search: SearchConfig;
   ui: UIConfig;
   output: OutputConfig;
+  rate_limit?: RateLimitConfig;
+  housekeeping?: HousekeepingConfig;
+  import?: ImportConfig;
 }
 
 export interface LLMConfig {
-  provider: 'ollama' | 'openai_compatible';
+  provider: 'ollama' | 'openai_compatible' | 'anthropic';
   model: string;
   base_url: string;
   api_key?: string | null;
   temperature: number;
   max_tokens: number;
+  clamp_to_context?: boolean;
+  request_timeout_secs?: number;
+  first_token_timeout_secs?: number;
+  stream_idle_timeout_secs?: number;
+  context_tokens?: number;
+  compact_keep_recent_messages?: number;
+  max_concurrent_streams?: number;
+  max_retries?: number;
+  seed?: number | null;
+  headers?: Record<string, string>;
+  stream_usage?: boolean;
+  profiles?: LlmProfile[];
+}
+
+export interface LlmProfile {
+  name: string;
+  provider?: 'ollama' | 'openai_compatible' | 'anthropic' | null;
+  base_url?: string | null;
+  model?: string | null;
+  api_key?: string | null;
+}
+
+export interface TokenLimitStatus {
+  configured: number;
+  context_length: number | null;
+  effective: number;
+  clamped: boolean;
+}
+
+export interface TriggerOverrides {
+  always: string[];
+  never: string[];
 }
 
 export interface SearchConfig {
@@ -157,6 +463,9 @@ export interface SearchConfig {
   tavily_api_key: string;
   searxng_url: string;
   proactive: boolean;
+  region?: string | null;
+  safe_search?: "strict" | "moderate" | "off" | null;
+  trigger_overrides?: TriggerOverrides;
 }
 
 export interface UIConfig {
@@ -167,9 +476,19 @@ export interface OutputConfig {
   include_conversation: boolean;
   default_save_path: string;
   default_target: ForgeTarget;
-  lint_mode: "fail_on_critical" | "warn";
+  lint_mode: "fail_on_critical" | "warn_only" | "off";
+  generation_retries?: number;
+  doc_validation?: "strict" | "heading" | "off";
+  export_folder_template?: string;
+  generation_context_limit?: number;
+  conversation_generation_details?: boolean;
+  frontmatter?: boolean;
+  completion_checks?: 'lenient' | 'strict';
+  planning_depth?: PlanningDepth;
 }
 
+export type PlanningDepth = 'quick' | 'standard' | 'thorough';
+
 export type ForgeTarget = 'claude' | 'codex' | 'cursor' | 'gemini' | 'generic';
 
 export interface QualityReport {
@@ -182,6 +501,7 @@ export interface QualityReport {
 export type CoverageStatus = 'missing' | 'partial' | 'covered';
 
 export interface CoverageTopic {
+  id: string;
   topic: string;
   status: CoverageStatus;
   evidence_message_ids: string[];
@@ -195,6 +515,76 @@ export interface CoverageReport {
   summary: string;
 }
 
+export type TechConfidence = 'stated' | 'inferred';
+
+export interface TechChoice {
+  name: string;
+  version: string | null;
+  layer: string;
+  confidence: TechConfidence;
+}
+
+export type TopicPriority = 'must_have' | 'should_have';
+
+export interface ReadinessItem {
+  id: string;
+  topic: string;
+  priority: TopicPriority;
+  status: CoverageStatus;
+  evidence_count: number;
+}
+
+export interface ReadinessChecklist {
+  items: ReadinessItem[];
+  projected_score: number;
+  can_forge_cleanly: boolean;
+  summary: string;
+}
+
+export type DecisionConfidence = 'low' | 'medium' | 'high';
+
+export interface ConversationLanguage {
+  code: string;
+  name: string;
+  is_english: boolean;
+  reliable: boolean;
+}
+
+export interface DecisionEntry {
+  message_id: string;
+  created_at: string;
+  decision_text: string;
+  confidence: DecisionConfidence;
+}
+
+export interface OpenQuestion {
+  filename: string;
+  question: string;
+  recommendation: string | null;
+}
+
+export type RequirementStatus = 'pending' | 'done';
+
+export interface Requirement {
+  feature: string;
+  criterion: string;
+  status: RequirementStatus;
+}
+
+export type ContentSource = 'message' | 'document';
+
+export interface ContentSearchHit {
+  session_id: string;
+  session_name: string;
+  source: ContentSource;
+  source_id: string;
+  label: string;
+  snippet: string;
+  match_offset: number;
+  match_len: number;
+  line: number | null;
+}
+
 export interface ConfidenceFactor {
   name: string;
   max_points: number;
@@ -217,23 +607,66 @@ export interface GenerationMetadata {
   run_id?: string | null;
   quality_json: string | null;
   confidence_json: string | null;
+  warnings_json?: string | null;
+  timing_json?: string | null;
+  seed?: number | null;
   created_at: string;
 }
 
+export interface GenerationWarning {
+  filename: string;
+  reason: string;
+  attempts: number;
+}
+
 // Health check
+export type KnownPreferenceKey =
+  | 'wizard_completed'
+  | 'first_session_completed'
+  | 'pending_model_pull';
+
+export interface PreferenceEntry {
+  key: KnownPreferenceKey;
+  kind: 'flag' | 'text';
+  default_value: string | null;
+  description: string;
+  value: string | null;
+}
+
 export interface HealthStatus {
   ollama_connected: boolean;
   ollama_model_available: boolean;
   database_ok: boolean;
   config_valid: boolean;
+  search_ok: boolean | null;
   errors: string[];
 }
 
+export interface EndpointHealth {
+  profile: string;
+  provider: string;
+  base_url: string;
+  model: string;
+  connected: boolean;
+  model_available: boolean;
+  error: string | null;
+}
+
+export interface ConfigFieldError {
+  field_path: string;
+  message: string;
+  kind: 'missing' | 'invalid';
+}
+
 export interface ErrorResponse {
   code: string;
   message: string;
   recoverable: boolean;
   action?: string;
+  details?: {
+    field_errors?: ConfigFieldError[];
+  };
+  remediation?: string;
 }
 
 // Model management